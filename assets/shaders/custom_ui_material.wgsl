@@ -0,0 +1,22 @@
+struct ScanlineMaterial {
+    color: vec4<f32>,
+    offset: f32,
+};
+
+@group(1) @binding(0)
+var<uniform> material: ScanlineMaterial;
+
+struct FragmentInput {
+    #import bevy_ui::ui_vertex_output
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    // A bright line every 4 logical pixels, scrolling down the node
+    let y = in.uv.y * in.size.y + material.offset;
+    let line = step(0.5, fract(y / 4.0));
+    // Fade out towards the edges of the node
+    let edge = min(min(in.uv.x, 1.0 - in.uv.x), min(in.uv.y, 1.0 - in.uv.y));
+    let alpha = clamp(edge * 10.0, 0.0, 1.0);
+    return vec4<f32>(material.color.rgb * (0.5 + 0.5 * line), alpha);
+}
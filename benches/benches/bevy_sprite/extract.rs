@@ -0,0 +1,101 @@
+use bevy_app::{App, PostUpdate};
+use bevy_asset::{AddAsset, AssetPlugin, Assets};
+use bevy_core::TaskPoolPlugin;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_render::{
+    camera::Camera,
+    mesh::Mesh,
+    primitives::Frustum,
+    texture::{FailedImages, Image},
+    view::{VisibilityPlugin, VisibleEntities},
+    MainWorld,
+};
+use bevy_sprite::{
+    extract_sprites, ExtractedSprites, SpriteBundle, SpriteSheetBundle, TextureAtlas,
+    TextureAtlasSprite,
+};
+use bevy_transform::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// A render world holding a main world with `count` visible sprites, half of them from a texture
+/// atlas, and a schedule extracting them.
+fn setup(count: usize) -> (World, Schedule) {
+    let mut app = App::new();
+    app.add_plugin(TaskPoolPlugin::default())
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Image>()
+        .add_asset::<Mesh>()
+        .add_asset::<TextureAtlas>()
+        .add_plugin(VisibilityPlugin)
+        .init_resource::<FailedImages>();
+    let texture_atlas =
+        app.world
+            .resource_mut::<Assets<TextureAtlas>>()
+            .add(TextureAtlas::from_grid(
+                Default::default(),
+                Vec2::splat(16.0),
+                4,
+                4,
+                None,
+                None,
+            ));
+
+    // The sprites are only visible to a camera
+    app.world.spawn((
+        Camera::default(),
+        VisibleEntities::default(),
+        Frustum::default(),
+    ));
+    for i in 0..count {
+        let transform = Transform::from_xyz(i as f32, 0.0, (i % 10) as f32);
+        if i % 2 == 0 {
+            app.world.spawn(SpriteBundle {
+                transform,
+                ..Default::default()
+            });
+        } else {
+            app.world.spawn(SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(i % 16),
+                texture_atlas: texture_atlas.clone(),
+                transform,
+                ..Default::default()
+            });
+        }
+    }
+    app.world.run_schedule(PostUpdate);
+
+    let mut main_world = MainWorld::default();
+    *main_world = std::mem::take(&mut app.world);
+    let mut render_world = World::new();
+    render_world.insert_resource(main_world);
+    render_world.init_resource::<ExtractedSprites>();
+
+    let mut schedule = Schedule::new();
+    schedule.add_systems(extract_sprites);
+    (render_world, schedule)
+}
+
+fn extract(c: &mut Criterion) {
+    let mut group = c.benchmark_group("extract_sprites");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for count in [10_000, 100_000] {
+        let (mut render_world, mut schedule) = setup(count);
+        schedule.run(&mut render_world);
+        assert_eq!(
+            render_world.resource::<ExtractedSprites>().sprites.len(),
+            count
+        );
+
+        group.bench_function(BenchmarkId::from_parameter(count), |bencher| {
+            bencher.iter(|| schedule.run(&mut render_world));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, extract);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+use bevy_app::App;
+use bevy_asset::{AddAsset, AssetPlugin, Assets};
+use bevy_core::TaskPoolPlugin;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_sprite::{SpriteSheetBundle, TextureAtlas, TextureAtlasSprite};
+use bevy_transform::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+const MAP_SIZE: usize = 200;
+
+fn tiles() -> impl Iterator<Item = (usize, Transform)> {
+    (0..MAP_SIZE * MAP_SIZE).map(|i| {
+        let (x, y) = (i % MAP_SIZE, i / MAP_SIZE);
+        (i % 16, Transform::from_xyz(x as f32, y as f32, 0.0))
+    })
+}
+
+fn spawn_tile_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("spawn_tile_map");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    let mut app = App::new();
+    app.add_plugin(TaskPoolPlugin::default())
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<TextureAtlas>();
+    let texture_atlas =
+        app.world
+            .resource_mut::<Assets<TextureAtlas>>()
+            .add(TextureAtlas::new_empty(
+                Default::default(),
+                Vec2::splat(64.0),
+            ));
+
+    group.bench_function("commands_spawn", |bencher| {
+        bencher.iter(|| {
+            let mut world = World::new();
+            let mut command_queue = bevy_ecs::system::CommandQueue::default();
+            let mut commands = Commands::new(&mut command_queue, &world);
+            for (index, transform) in tiles() {
+                commands.spawn(SpriteSheetBundle {
+                    sprite: TextureAtlasSprite::new(index),
+                    texture_atlas: texture_atlas.clone(),
+                    transform,
+                    ..Default::default()
+                });
+            }
+            command_queue.apply(&mut world);
+        });
+    });
+
+    group.bench_function("spawn_batch_strong_handles", |bencher| {
+        bencher.iter(|| {
+            let mut world = World::new();
+            world.spawn_batch(tiles().map(|(index, transform)| SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(index),
+                texture_atlas: texture_atlas.clone(),
+                transform,
+                ..Default::default()
+            }));
+        });
+    });
+
+    group.bench_function("spawn_batch_sprite_sheet_batch", |bencher| {
+        bencher.iter(|| {
+            let mut world = World::new();
+            world.spawn_batch(SpriteSheetBundle::batch(
+                texture_atlas.clone_weak(),
+                tiles(),
+            ));
+        });
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, spawn_tile_map);
+criterion_main!(benches);
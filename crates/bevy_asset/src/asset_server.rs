@@ -1,14 +1,14 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
     Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    AssetLoader, AssetMeta, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext,
+    LoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut, Resource};
 use bevy_log::warn;
 use bevy_tasks::IoTaskPool;
-use bevy_utils::{Entry, HashMap, Uuid};
+use bevy_utils::{get_short_name, Entry, HashMap, HashSet, Uuid};
 use crossbeam_channel::TryRecvError;
 use parking_lot::{Mutex, RwLock};
 use std::{path::Path, sync::Arc};
@@ -55,11 +55,159 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+/// The asset sources and dependencies drawn by [`AssetServer::dependency_graph_dot`]
+#[derive(Default)]
+struct DependencyGraph {
+    /// The path, asset types and load state of each source
+    sources: Vec<(SourcePathId, String, Vec<Uuid>, LoadState)>,
+    /// The paths of the sources depended on
+    dependency_paths: HashMap<SourcePathId, String>,
+    dependencies: Vec<(SourcePathId, SourcePathId)>,
+    /// The number of sources left out of the graph
+    omitted: usize,
+}
+
+impl DependencyGraph {
+    /// Adds a source, returning the sources it depends on.
+    fn add_source(
+        &mut self,
+        asset_sources: &HashMap<SourcePathId, SourceInfo>,
+        source_path_id: SourcePathId,
+    ) -> Vec<SourcePathId> {
+        let Some(source_info) = asset_sources.get(&source_path_id) else {
+            if let Some(path) = self.dependency_paths.get(&source_path_id).cloned() {
+                self.sources
+                    .push((source_path_id, path, Vec::new(), LoadState::NotLoaded));
+            }
+            return Vec::new();
+        };
+        let mut types: Vec<_> = source_info.asset_types.values().copied().collect();
+        types.sort();
+        types.dedup();
+        self.sources.push((
+            source_path_id,
+            source_info.path.to_string_lossy().into_owned(),
+            types,
+            source_info.load_state,
+        ));
+
+        let mut dependencies = Vec::new();
+        let meta_dependencies = source_info
+            .meta
+            .iter()
+            .flat_map(|meta| &meta.assets)
+            .flat_map(|asset| &asset.dependencies);
+        for path in meta_dependencies {
+            let dependency = path.get_id().source_path_id();
+            if !dependencies.contains(&dependency) {
+                dependencies.push(dependency);
+                self.dependencies.push((source_path_id, dependency));
+                self.dependency_paths
+                    .insert(dependency, path.path().to_string_lossy().into_owned());
+            }
+        }
+        dependencies
+    }
+
+    fn to_dot(&self, asset_lifecycles: &HashMap<Uuid, Box<dyn AssetLifecycle>>) -> String {
+        let quote = |value: &str| {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            format!("\"{}\"", value.replace('\n', "\\n"))
+        };
+        let mut paths: HashMap<_, _> = self
+            .dependency_paths
+            .iter()
+            .map(|(id, path)| (*id, path.as_str()))
+            .collect();
+        let mut dot = String::from("digraph assets {\n    node [shape=box, style=filled];\n");
+        for (id, path, types, load_state) in &self.sources {
+            paths.insert(*id, path);
+            let mut label = vec![path.clone()];
+            label.extend(types.iter().map(|type_uuid| {
+                asset_lifecycles.get(type_uuid).map_or_else(
+                    || type_uuid.to_string(),
+                    |lifecycle| get_short_name(lifecycle.type_name()),
+                )
+            }));
+            label.push(format!("{load_state:?}"));
+            let color = match load_state {
+                LoadState::Loaded => "green",
+                LoadState::Loading => "yellow",
+                LoadState::Failed => "red",
+                LoadState::NotLoaded | LoadState::Unloaded => "lightgray",
+            };
+            dot.push_str(&format!(
+                "    {} [label={}, fillcolor={color}];\n",
+                quote(path),
+                quote(&label.join("\n")),
+            ));
+        }
+        for (source, dependency) in &self.dependencies {
+            dot.push_str(&format!(
+                "    {} -> {};\n",
+                quote(paths[source]),
+                quote(paths[dependency])
+            ));
+        }
+        if self.omitted > 0 {
+            dot.push_str(&format!("    // {} more sources omitted\n", self.omitted));
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// The assets of a folder loaded with [`AssetServer::load_folder_filtered`], sorted by path.
+#[derive(Debug, Clone, Default)]
+pub struct LoadedFolder {
+    handles: Vec<(AssetPath<'static>, HandleUntyped)>,
+}
+
+impl LoadedFolder {
+    /// Returns the assets of the folder with their paths.
+    pub fn iter(&self) -> impl Iterator<Item = (&AssetPath<'static>, &HandleUntyped)> {
+        self.handles.iter().map(|(path, handle)| (path, handle))
+    }
+
+    /// Returns the assets of the folder of type `T` with their paths.
+    ///
+    /// Only the assets that are loaded are returned, see [`LoadedFolder::load_state`].
+    pub fn iter_typed<'a, T: Asset>(
+        &'a self,
+        asset_server: &'a AssetServer,
+    ) -> impl Iterator<Item = (&'a AssetPath<'static>, Handle<T>)> + 'a {
+        self.handles
+            .iter()
+            .filter(|(_, handle)| asset_server.get_handle_type(handle) == Some(T::TYPE_UUID))
+            .map(|(path, handle)| (path, handle.clone().typed()))
+    }
+
+    /// Returns the number of assets in the folder.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Returns `true` if the folder has no assets.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Gets the overall load state of the assets of the folder.
+    ///
+    /// See [`AssetServer::get_group_load_state`].
+    pub fn load_state(&self, asset_server: &AssetServer) -> LoadState {
+        asset_server.get_group_load_state(self.handles.iter().map(|(_, handle)| handle.id()))
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
     pub(crate) ref_counts: Arc<RwLock<HashMap<HandleId, usize>>>,
     pub(crate) mark_unused_assets: Arc<Mutex<Vec<HandleId>>>,
+    /// Assets that were already reported as unused, and are freed on the next pass if they still
+    /// have no strong handles
+    pub(crate) unused_assets: Arc<Mutex<Vec<HandleId>>>,
 }
 
 /// Internal data for the asset server.
@@ -73,6 +221,10 @@ pub struct AssetServerInternal {
     loaders: RwLock<Vec<Arc<dyn AssetLoader>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    /// Strong handles keeping the assets registered with
+    /// [`AddAsset::register_builtin_asset`](crate::AddAsset::register_builtin_asset) alive, by
+    /// the source they are registered under
+    builtin_assets: RwLock<HashMap<SourcePathId, Vec<HandleUntyped>>>,
 }
 
 /// Loads assets from the filesystem in the background.
@@ -133,6 +285,7 @@ impl AssetServer {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
+                builtin_assets: Default::default(),
                 asset_io,
             }),
         }
@@ -154,7 +307,7 @@ impl AssetServer {
             panic!("Error while registering new asset type: {:?} with UUID: {:?}. Another type with the same UUID is already registered. Can not register new asset type with the same UUID",
                 std::any::type_name::<T>(), T::TYPE_UUID);
         }
-        Assets::new(self.server.asset_ref_counter.channel.sender.clone())
+        Assets::new(&self.server.asset_ref_counter)
     }
 
     /// Adds the provided asset loader to the server.
@@ -240,6 +393,24 @@ impl AssetServer {
             .cloned()
     }
 
+    /// Gets the labels of the secondary assets loaded from the source at `path`, in alphabetical
+    /// order.
+    ///
+    /// This is empty while the source hasn't been loaded.
+    pub fn get_labels<'a, P: Into<AssetPath<'a>>>(&self, path: P) -> Vec<String> {
+        let source_path_id = path.into().get_id().source_path_id();
+        let asset_sources = self.server.asset_sources.read();
+        let mut labels: Vec<_> = asset_sources
+            .get(&source_path_id)
+            .and_then(|info| info.meta.as_ref())
+            .into_iter()
+            .flat_map(|meta| &meta.assets)
+            .filter_map(|asset| asset.label.clone())
+            .collect();
+        labels.sort();
+        labels
+    }
+
     /// Gets the load state of an asset from the provided handle.
     pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
         match handle.into() {
@@ -312,6 +483,16 @@ impl AssetServer {
     ) -> Result<AssetPathId, AssetServerError> {
         let asset_path_id: AssetPathId = asset_path.get_id();
 
+        // builtin assets are not read from the asset I/O, even when reloaded
+        if self
+            .server
+            .builtin_assets
+            .read()
+            .contains_key(&asset_path_id.source_path_id())
+        {
+            return Ok(asset_path_id);
+        }
+
         // load metadata and update source info. this is done in a scope to ensure we release the
         // locks before loading
         let version = {
@@ -429,6 +610,55 @@ impl AssetServer {
         Ok(asset_path_id)
     }
 
+    /// Marks the asset of type `T` at `asset_path` as loaded without reading it from the asset
+    /// I/O, and returns a strong handle to it which the asset server keeps forever.
+    ///
+    /// The asset itself must be added to [`Assets<T>`] with the returned handle, see
+    /// [`AddAsset::register_builtin_asset`](crate::AddAsset::register_builtin_asset).
+    pub(crate) fn register_builtin_asset<T: Asset>(&self, asset_path: AssetPath<'_>) -> Handle<T> {
+        let asset_path_id = asset_path.get_id();
+        let label_id = asset_path_id.label_id();
+        {
+            let mut asset_sources = self.server.asset_sources.write();
+            let source_info = asset_sources
+                .entry(asset_path_id.source_path_id())
+                .or_insert_with(|| SourceInfo {
+                    asset_types: Default::default(),
+                    committed_assets: Default::default(),
+                    load_state: LoadState::Loaded,
+                    meta: Some(SourceMeta { assets: Vec::new() }),
+                    path: asset_path.path().to_owned(),
+                    version: 0,
+                });
+            source_info.asset_types.insert(label_id, T::TYPE_UUID);
+            source_info.committed_assets.insert(label_id);
+            source_info.load_state = LoadState::Loaded;
+            if let Some(meta) = &mut source_info.meta {
+                let label = asset_path.label().map(ToString::to_string);
+                if !meta.assets.iter().any(|asset| asset.label == label) {
+                    meta.assets.push(AssetMeta {
+                        label,
+                        dependencies: Vec::new(),
+                        type_uuid: T::TYPE_UUID,
+                    });
+                }
+            }
+        }
+        self.server
+            .handle_to_path
+            .write()
+            .insert(asset_path_id.into(), asset_path.to_owned());
+
+        let handle = self.get_handle_untyped(asset_path_id);
+        self.server
+            .builtin_assets
+            .write()
+            .entry(asset_path_id.source_path_id())
+            .or_default()
+            .push(handle.clone());
+        handle.typed()
+    }
+
     /// Queues the [`Asset`] at the provided path for loading and returns an untyped handle.
     ///
     /// See [`load`](AssetServer::load).
@@ -469,6 +699,9 @@ impl AssetServer {
 
     /// Loads assets from the specified folder recursively.
     ///
+    /// The handles are sorted by asset path. See [`AssetServer::load_folder_filtered`] to only
+    /// load some of the assets, and to get their paths.
+    ///
     /// # Errors
     ///
     /// - If the provided path is not a directory, it will fail with
@@ -480,33 +713,156 @@ impl AssetServer {
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
+        let folder = self.load_folder_filtered(path, |_| true)?;
+        Ok(folder
+            .handles
+            .into_iter()
+            .map(|(_, handle)| handle)
+            .collect())
+    }
+
+    /// Loads the assets from the specified folder recursively whose path matches `filter`.
+    ///
+    /// The assets can be iterated by type with [`LoadedFolder::iter_typed`] once they are
+    /// loaded.
+    ///
+    /// ```rust,no_run
+    /// # use bevy_asset::AssetServer;
+    /// # fn load(asset_server: &AssetServer) {
+    /// let folder = asset_server
+    ///     .load_folder_filtered("textures", |path| {
+    ///         path.extension().is_some_and(|extension| extension == "png")
+    ///     })
+    ///     .unwrap();
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// See [`AssetServer::load_folder`].
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_folder_filtered<P: AsRef<Path>>(
+        &self,
+        path: P,
+        filter: impl Fn(&Path) -> bool,
+    ) -> Result<LoadedFolder, AssetServerError> {
+        let mut handles = Vec::new();
+        self.load_folder_recursive(path.as_ref(), &filter, &mut handles)?;
+        // Directories are listed in a platform-dependent order
+        handles.sort_by(|(a, _), (b, _)| a.path().cmp(b.path()));
+        Ok(LoadedFolder { handles })
+    }
+
+    fn load_folder_recursive(
+        &self,
+        path: &Path,
+        filter: &dyn Fn(&Path) -> bool,
+        handles: &mut Vec<(AssetPath<'static>, HandleUntyped)>,
+    ) -> Result<(), AssetServerError> {
         if !self.asset_io().is_dir(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let mut handles = Vec::new();
-        for child_path in self.asset_io().read_directory(path.as_ref())? {
+        for child_path in self.asset_io().read_directory(path)? {
             if self.asset_io().is_dir(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
+                self.load_folder_recursive(&child_path, filter, handles)?;
             } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
+                if !filter(&child_path) || self.get_path_asset_loader(&child_path).is_err() {
                     continue;
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
-                handles.push(handle);
+                let asset_path = AssetPath::new(child_path, None);
+                let handle = self.load_untyped(asset_path.clone());
+                handles.push((asset_path, handle));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the [`TypeUuid`](bevy_reflect::TypeUuid) of the asset of a handle, or `None` if it
+    /// is not loaded yet.
+    pub fn get_handle_type<H: Into<HandleId>>(&self, handle: H) -> Option<Uuid> {
+        match handle.into() {
+            HandleId::AssetPathId(id) => self
+                .server
+                .asset_sources
+                .read()
+                .get(&id.source_path_id())?
+                .get_asset_type(id.label_id()),
+            HandleId::Id(type_uuid, _) => Some(type_uuid),
+        }
+    }
+
+    /// Gets the paths of the assets that the source of a handle depends on, which are loaded
+    /// with it. Empty until the source is loaded.
+    pub fn get_dependencies<H: Into<HandleId>>(&self, handle: H) -> Vec<AssetPath<'static>> {
+        let HandleId::AssetPathId(id) = handle.into() else {
+            return Vec::new();
+        };
+        self.server
+            .asset_sources
+            .read()
+            .get(&id.source_path_id())
+            .and_then(|source_info| source_info.meta.as_ref())
+            .map(|meta| {
+                meta.assets
+                    .iter()
+                    .flat_map(|asset| asset.dependencies.iter().cloned())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns a [DOT](https://graphviz.org/doc/info/lang.html) graph of the asset sources that
+    /// the source of a handle depends on, directly or not, to debug assets that never load.
+    ///
+    /// Each node is labeled with the path, asset types and [`LoadState`] of a source, and is
+    /// colored by its load state: green when loaded, yellow while loading and red if it failed.
+    /// The dependencies of a source are only known once it is loaded.
+    pub fn dependency_graph_dot<H: Into<HandleId>>(&self, root: H) -> String {
+        let asset_sources = self.server.asset_sources.read();
+        let mut graph = DependencyGraph::default();
+        if let HandleId::AssetPathId(id) = root.into() {
+            let mut queue = vec![id.source_path_id()];
+            let mut visited = HashSet::default();
+            while let Some(source_path_id) = queue.pop() {
+                if visited.insert(source_path_id) {
+                    queue.extend(graph.add_source(&asset_sources, source_path_id));
+                }
             }
         }
+        graph.to_dot(&self.server.asset_lifecycles.read())
+    }
 
-        Ok(handles)
+    /// Returns a [DOT](https://graphviz.org/doc/info/lang.html) graph of all the asset sources
+    /// known to the server and their dependencies, like [`AssetServer::dependency_graph_dot`].
+    ///
+    /// Only the first `max_sources` sources by path are included, the dependencies outside of
+    /// them are still drawn.
+    pub fn all_dependencies_graph_dot(&self, max_sources: usize) -> String {
+        let asset_sources = self.server.asset_sources.read();
+        let mut sources: Vec<_> = asset_sources.iter().collect();
+        sources.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+        let mut graph = DependencyGraph::default();
+        for (&source_path_id, _) in sources.iter().take(max_sources) {
+            graph.add_source(&asset_sources, source_path_id);
+        }
+        graph.omitted = sources.len().saturating_sub(max_sources);
+        graph.to_dot(&self.server.asset_lifecycles.read())
     }
 
     /// Frees unused assets, unloading them from memory.
+    ///
+    /// Assets are freed on the second call after they were marked as unused by
+    /// [`mark_unused_assets`](AssetServer::mark_unused_assets), so an [`AssetEvent::Unused`]
+    /// is received a frame before the asset is removed.
+    ///
+    /// [`AssetEvent::Unused`]: crate::AssetEvent::Unused
     pub fn free_unused_assets(&self) {
-        let mut potential_frees = self.server.asset_ref_counter.mark_unused_assets.lock();
+        let mut potential_frees =
+            std::mem::take(&mut *self.server.asset_ref_counter.unused_assets.lock());
 
         if !potential_frees.is_empty() {
             let ref_counts = self.server.asset_ref_counter.ref_counts.read();
@@ -514,28 +870,46 @@ impl AssetServer {
             let asset_lifecycles = self.server.asset_lifecycles.read();
             for potential_free in potential_frees.drain(..) {
                 if let Some(&0) = ref_counts.get(&potential_free) {
-                    let type_uuid = match potential_free {
-                        HandleId::Id(type_uuid, _) => Some(type_uuid),
-                        HandleId::AssetPathId(id) => asset_sources
-                            .get(&id.source_path_id())
-                            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
-                    };
-
-                    if let Some(type_uuid) = type_uuid {
-                        if let Some(asset_lifecycle) = asset_lifecycles.get(&type_uuid) {
-                            asset_lifecycle.free_asset(potential_free);
-                        }
+                    if let Some(asset_lifecycle) =
+                        asset_lifecycle_of(&asset_sources, &asset_lifecycles, potential_free)
+                    {
+                        asset_lifecycle.free_asset(potential_free);
                     }
                 }
             }
         }
+        // The assets marked since the last call are freed on the next one
+        let mut newly_unused = self.server.asset_ref_counter.mark_unused_assets.lock();
+        potential_frees.append(&mut newly_unused);
+        *self.server.asset_ref_counter.unused_assets.lock() = potential_frees;
+    }
+
+    /// Marks an asset removed from its [`Assets`] collection without being freed, like by an
+    /// [`AssetBudget`](crate::AssetBudget), as unloaded so it is loaded again the next time.
+    pub(crate) fn asset_removed(&self, id: HandleId) {
+        unload_source_asset(&mut self.server.asset_sources.write(), id);
     }
 
     /// Iterates through asset references and marks assets with no active handles as unused.
+    ///
+    /// An [`AssetEvent::Unused`](crate::AssetEvent::Unused) is sent for each of them.
     pub fn mark_unused_assets(&self) {
+        let unused = self.apply_ref_changes();
+        if !unused.is_empty() {
+            self.report_unused_assets(
+                &self.server.asset_sources.read(),
+                &self.server.asset_lifecycles.read(),
+                unused,
+            );
+        }
+    }
+
+    /// Updates the reference counts with the handles made and dropped since the last call,
+    /// returning the assets that have no strong handles left.
+    fn apply_ref_changes(&self) -> Vec<HandleId> {
         let receiver = &self.server.asset_ref_counter.channel.receiver;
         let mut ref_counts = self.server.asset_ref_counter.ref_counts.write();
-        let mut potential_frees = None;
+        let mut unused = Vec::new();
         loop {
             let ref_change = match receiver.try_recv() {
                 Ok(ref_change) => ref_change,
@@ -548,15 +922,32 @@ impl AssetServer {
                     let entry = ref_counts.entry(handle_id).or_insert(0);
                     *entry -= 1;
                     if *entry == 0 {
-                        potential_frees
-                            .get_or_insert_with(|| {
-                                self.server.asset_ref_counter.mark_unused_assets.lock()
-                            })
-                            .push(handle_id);
+                        unused.push(handle_id);
                     }
                 }
             }
         }
+        unused
+    }
+
+    fn report_unused_assets(
+        &self,
+        asset_sources: &HashMap<SourcePathId, SourceInfo>,
+        asset_lifecycles: &HashMap<Uuid, Box<dyn AssetLifecycle>>,
+        mut unused: Vec<HandleId>,
+    ) {
+        for &handle_id in &unused {
+            if let Some(asset_lifecycle) =
+                asset_lifecycle_of(asset_sources, asset_lifecycles, handle_id)
+            {
+                asset_lifecycle.unused_asset(handle_id);
+            }
+        }
+        self.server
+            .asset_ref_counter
+            .mark_unused_assets
+            .lock()
+            .append(&mut unused);
     }
 
     fn create_assets_in_load_context(&self, load_context: &mut LoadContext) {
@@ -611,15 +1002,20 @@ impl AssetServer {
 
                     assets.set_untracked(result.id, *result.asset);
                 }
+                Ok(AssetLifecycleEvent::Unused(handle_id)) => assets.mark_unused(handle_id),
                 Ok(AssetLifecycleEvent::Free(handle_id)) => {
-                    if let HandleId::AssetPathId(id) = handle_id {
-                        let asset_sources = asset_sources_guard
-                            .get_or_insert_with(|| self.server.asset_sources.write());
-                        if let Some(source_info) = asset_sources.get_mut(&id.source_path_id()) {
-                            source_info.committed_assets.remove(&id.label_id());
-                            source_info.load_state = LoadState::Unloaded;
-                        }
+                    let asset_sources = asset_sources_guard
+                        .get_or_insert_with(|| self.server.asset_sources.write());
+                    // Keep the asset if a strong handle was made again since it was freed
+                    let unused = self.apply_ref_changes();
+                    if !unused.is_empty() {
+                        self.report_unused_assets(asset_sources, &asset_lifecycles, unused);
                     }
+                    let ref_counts = self.server.asset_ref_counter.ref_counts.read();
+                    if ref_counts.get(&handle_id).is_some_and(|&count| count > 0) {
+                        continue;
+                    }
+                    unload_source_asset(asset_sources, handle_id);
                     assets.remove(handle_id);
                 }
                 Err(TryRecvError::Empty) => {
@@ -631,6 +1027,33 @@ impl AssetServer {
     }
 }
 
+/// Marks the asset with the given id as unloaded in the info of its source, if it has one.
+fn unload_source_asset(asset_sources: &mut HashMap<SourcePathId, SourceInfo>, id: HandleId) {
+    if let HandleId::AssetPathId(id) = id {
+        if let Some(source_info) = asset_sources.get_mut(&id.source_path_id()) {
+            source_info.committed_assets.remove(&id.label_id());
+            source_info.load_state = LoadState::Unloaded;
+        }
+    }
+}
+
+/// Finds the lifecycle of the type of the asset with the given id.
+fn asset_lifecycle_of<'a>(
+    asset_sources: &HashMap<SourcePathId, SourceInfo>,
+    asset_lifecycles: &'a HashMap<Uuid, Box<dyn AssetLifecycle>>,
+    id: HandleId,
+) -> Option<&'a dyn AssetLifecycle> {
+    let type_uuid = match id {
+        HandleId::Id(type_uuid, _) => Some(type_uuid),
+        HandleId::AssetPathId(id) => asset_sources
+            .get(&id.source_path_id())
+            .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+    }?;
+    asset_lifecycles
+        .get(&type_uuid)
+        .map(|lifecycle| &**lifecycle)
+}
+
 fn free_unused_assets_system_impl(asset_server: &AssetServer) {
     asset_server.free_unused_assets();
     asset_server.mark_unused_assets();
@@ -644,7 +1067,10 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use crate::{
+        load_group_system, loader::LoadedAsset, update_asset_storage_system, AddAsset,
+        LabeledAssetError, LoadGroup, LoadGroupComplete, LoadGroupProgress,
+    };
     use bevy_app::{App, Update};
     use bevy_ecs::prelude::*;
     use bevy_reflect::{TypePath, TypeUuid};
@@ -670,6 +1096,53 @@ mod test {
         }
     }
 
+    struct FakeDependentLoader;
+    impl AssetLoader for FakeDependentLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            ctx.set_default_asset(LoadedAsset::new(PngAsset).with_dependency("fake.png".into()));
+            Box::pin(async move { Ok(()) })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["dep"]
+        }
+    }
+
+    struct FakeSheetLoader;
+    impl AssetLoader for FakeSheetLoader {
+        fn load<'a>(
+            &'a self,
+            _: &'a [u8],
+            ctx: &'a mut LoadContext,
+        ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+            Box::pin(async move {
+                let idle = ctx.try_set_labeled_asset("goblin_idle", LoadedAsset::new(PngAsset))?;
+                assert_eq!(idle.label(), "goblin_idle");
+                ctx.try_set_labeled_asset("goblin_run", LoadedAsset::new(PngAsset))?;
+                assert_eq!(
+                    ctx.try_set_labeled_asset("goblin_idle", LoadedAsset::new(PngAsset))
+                        .unwrap_err(),
+                    LabeledAssetError::DuplicateLabel("goblin_idle".to_string())
+                );
+                assert_eq!(
+                    ctx.try_set_labeled_asset("", LoadedAsset::new(PngAsset))
+                        .unwrap_err(),
+                    LabeledAssetError::EmptyLabel
+                );
+                ctx.set_default_asset(LoadedAsset::new(PngAsset));
+                Ok(())
+            })
+        }
+
+        fn extensions(&self) -> &[&str] {
+            &["sheet"]
+        }
+    }
+
     struct FailingLoader;
     impl AssetLoader for FailingLoader {
         fn load<'a>(
@@ -841,6 +1314,21 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_get_labels() {
+        let dir = create_dir_and_file("fake.sheet");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeSheetLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+        assert!(asset_server.get_labels("fake.sheet").is_empty());
+
+        futures_lite::future::block_on(asset_server.load_async("fake.sheet".into(), true)).unwrap();
+        assert_eq!(
+            asset_server.get_labels("fake.sheet#goblin_run"),
+            vec!["goblin_idle", "goblin_run"]
+        );
+    }
+
     #[test]
     fn test_asset_lifecycle() {
         let dir = create_dir_and_file("fake.png");
@@ -907,7 +1395,12 @@ mod test {
         assert_eq!(LoadState::Loaded, get_load_state(&weak_handle, &app.world));
         assert!(get_asset(&weak_handle, &app.world).is_some());
 
-        // second call to tick will actually remove the asset.
+        // the asset is kept for a frame after it was reported as unused.
+        app.update();
+        assert_eq!(LoadState::Loaded, get_load_state(&weak_handle, &app.world));
+        assert!(get_asset(&weak_handle, &app.world).is_some());
+
+        // third call to tick will actually remove the asset.
         app.update();
         assert_eq!(
             LoadState::Unloaded,
@@ -923,6 +1416,191 @@ mod test {
         assert!(get_asset(&handle, &app.world).is_some());
     }
 
+    #[test]
+    fn test_load_folder_filtered() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("sprites/enemies")).unwrap();
+        for file in ["b.png", "a.png", "skip.png", "notes.txt", "enemies/orc.png"] {
+            std::fs::write(dir.path().join("sprites").join(file), []).unwrap();
+        }
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+
+        let folder = asset_server
+            .load_folder_filtered("sprites", |path| !path.ends_with("skip.png"))
+            .unwrap();
+        let paths: Vec<_> = folder.iter().map(|(path, _)| path.path()).collect();
+        assert_eq!(
+            paths,
+            ["sprites/a.png", "sprites/b.png", "sprites/enemies/orc.png"].map(Path::new)
+        );
+    }
+
+    #[test]
+    fn test_get_handle_type() {
+        let dir = create_dir_and_file("fake.png");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let _assets = asset_server.register_asset_type::<PngAsset>();
+
+        let path: AssetPath = "fake.png".into();
+        assert_eq!(asset_server.get_handle_type(path.get_id()), None);
+        futures_lite::future::block_on(asset_server.load_async(path.clone(), true)).unwrap();
+        assert_eq!(
+            asset_server.get_handle_type(path.get_id()),
+            Some(PngAsset::TYPE_UUID)
+        );
+    }
+
+    #[test]
+    fn test_builtin_asset() {
+        let dir = tempfile::tempdir().unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_systems(
+                Update,
+                (
+                    free_unused_assets_system,
+                    update_asset_storage_system::<PngAsset>,
+                )
+                    .chain(),
+            )
+            .register_builtin_asset("embedded://test/white.png", PngAsset);
+
+        let handle: Handle<PngAsset> = asset_server.load("embedded://test/white.png");
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+        assert!(app.world.resource::<Assets<PngAsset>>().contains(&handle));
+
+        // The asset is not read from the asset folder, which doesn't contain it
+        let path = AssetPath::from("embedded://test/white.png");
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+        assert_eq!(asset_server.get_load_state(&handle), LoadState::Loaded);
+
+        // The asset stays loaded without handles
+        let weak = handle.clone_weak();
+        drop(handle);
+        app.update();
+        app.update();
+        assert!(app.world.resource::<Assets<PngAsset>>().contains(&weak));
+    }
+
+    #[test]
+    fn test_load_group_progress() {
+        let dir = create_dir_and_file("fake.png");
+        std::fs::write(dir.path().join("level.dep"), []).unwrap();
+        std::fs::write(dir.path().join("broken.fail"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FakeDependentLoader);
+        asset_server.add_loader(FailingLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_event::<LoadGroupComplete>()
+            .add_systems(
+                Update,
+                (update_asset_storage_system::<PngAsset>, load_group_system).chain(),
+            );
+
+        let mut group = LoadGroup::new(&asset_server);
+        let level = group.load_untyped("level.dep");
+        let _broken = group.load_untyped("broken.fail");
+        let entity = app.world.spawn(group).id();
+
+        // Loading happens on the IO task pool, and the dependency is only found once the level
+        // is loaded
+        let mut progress = LoadGroupProgress::default();
+        for _ in 0..1000 {
+            app.update();
+            progress = app.world.get::<LoadGroup>(entity).unwrap().progress();
+            if progress.is_finished() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        let expected = LoadGroupProgress {
+            loaded: 2,
+            failed: 1,
+            total: 3,
+        };
+        assert_eq!(progress, expected);
+        let events = app.world.resource::<Events<LoadGroupComplete>>();
+        assert_eq!(
+            events.get_reader().iter(events).collect::<Vec<_>>(),
+            [&LoadGroupComplete {
+                entity,
+                progress: expected
+            }]
+        );
+
+        // Assets that are already loaded count right away
+        let mut group = LoadGroup::new(&asset_server);
+        group.add(level);
+        assert_eq!(
+            group.progress(),
+            LoadGroupProgress {
+                loaded: 2,
+                failed: 0,
+                total: 2,
+            }
+        );
+    }
+
+    #[test]
+    fn test_dependency_graph_dot() {
+        // The dependency of the level is missing
+        let dir = create_dir_and_file("level.dep");
+        std::fs::write(dir.path().join("other.dep"), []).unwrap();
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakeDependentLoader);
+        let assets = asset_server.register_asset_type::<PngAsset>();
+
+        let mut app = App::new();
+        app.insert_resource(assets)
+            .insert_resource(asset_server.clone())
+            .add_systems(Update, update_asset_storage_system::<PngAsset>);
+
+        let level = asset_server.load_untyped("level.dep");
+        let other = asset_server.load_untyped("other.dep");
+        for _ in 0..1000 {
+            app.update();
+            if asset_server.get_load_state(level.id()) == LoadState::Loaded
+                && asset_server.get_load_state(other.id()) == LoadState::Loaded
+                && asset_server.get_load_state("fake.png") == LoadState::Failed
+            {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+
+        assert_eq!(
+            asset_server.dependency_graph_dot(level.id()),
+            r#"digraph assets {
+    node [shape=box, style=filled];
+    "level.dep" [label="level.dep\nPngAsset\nLoaded", fillcolor=green];
+    "fake.png" [label="fake.png\nFailed", fillcolor=red];
+    "level.dep" -> "fake.png";
+}
+"#
+        );
+        assert_eq!(
+            asset_server.all_dependencies_graph_dot(1),
+            r#"digraph assets {
+    node [shape=box, style=filled];
+    "fake.png" [label="fake.png\nFailed", fillcolor=red];
+    // 2 more sources omitted
+}
+"#
+        );
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
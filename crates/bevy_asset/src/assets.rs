@@ -1,13 +1,23 @@
 use crate::{
-    update_asset_storage_system, Asset, AssetEvents, AssetLoader, AssetServer, Handle, HandleId,
+    asset_server::AssetRefCounter, budget, derived, update_asset_storage_system, Asset,
+    AssetBudget, AssetByteSize, AssetEvents, AssetLoader, AssetPath, AssetServer, Handle, HandleId,
     LoadAssets, RefChange, ReflectAsset, ReflectHandle,
 };
 use bevy_app::{App, AppTypeRegistry};
-use bevy_ecs::prelude::*;
+use bevy_ecs::{
+    change_detection::CHECK_TICK_THRESHOLD, component::Tick, prelude::*, system::SystemChangeTick,
+};
 use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect};
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, Uuid};
 use crossbeam_channel::Sender;
-use std::fmt::Debug;
+use parking_lot::RwLock;
+use std::{
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
 
 /// Events that involve assets of type `T`.
 ///
@@ -21,6 +31,15 @@ pub enum AssetEvent<T: Asset> {
     Modified { handle: Handle<T> },
     #[allow(missing_docs)]
     Removed { handle: Handle<T> },
+    /// The last strong handle to the asset was dropped.
+    ///
+    /// The asset is removed a frame later, unless a strong handle is made again in the meantime,
+    /// for example with [`Assets::strong_handle`]. Caches holding weak handles can use this to
+    /// either keep the asset alive or clean up.
+    Unused {
+        /// A _Weak_ handle to the asset
+        handle: Handle<T>,
+    },
 }
 
 impl<T: Asset> Debug for AssetEvent<T> {
@@ -47,6 +66,13 @@ impl<T: Asset> Debug for AssetEvent<T> {
                 ))
                 .field("handle", &handle.id())
                 .finish(),
+            AssetEvent::Unused { handle } => f
+                .debug_struct(&format!(
+                    "AssetEvent<{}>::Unused",
+                    std::any::type_name::<T>()
+                ))
+                .field("handle", &handle.id())
+                .finish(),
         }
     }
 }
@@ -69,14 +95,30 @@ pub struct Assets<T: Asset> {
     assets: HashMap<HandleId, T>,
     events: Events<AssetEvent<T>>,
     pub(crate) ref_change_sender: Sender<RefChange>,
+    ref_counts: Arc<RwLock<HashMap<HandleId, usize>>>,
+    /// The frame each asset was last accessed in, only tracked once an [`AssetBudget`] is added
+    ///
+    /// [`AssetBudget`]: crate::AssetBudget
+    last_access: Option<HashMap<HandleId, AtomicU32>>,
+    access_frame: u32,
+    /// The tick of the [`Assets::asset_event_system`] run that sent the last
+    /// [`AssetEvent::Created`] or [`AssetEvent::Modified`] of each asset
+    change_ticks: HashMap<HandleId, Tick>,
+    /// The tick the `change_ticks` were last clamped at, see [`Assets::check_change_ticks`]
+    last_check_tick: Tick,
 }
 
 impl<T: Asset> Assets<T> {
-    pub(crate) fn new(ref_change_sender: Sender<RefChange>) -> Self {
+    pub(crate) fn new(ref_counter: &AssetRefCounter) -> Self {
         Assets {
             assets: HashMap::default(),
             events: Events::default(),
-            ref_change_sender,
+            ref_change_sender: ref_counter.channel.sender.clone(),
+            ref_counts: ref_counter.ref_counts.clone(),
+            last_access: None,
+            access_frame: 0,
+            change_ticks: HashMap::default(),
+            last_check_tick: Tick::new(0),
         }
     }
 
@@ -88,6 +130,7 @@ impl<T: Asset> Assets<T> {
     pub fn add(&mut self, asset: T) -> Handle<T> {
         let id = HandleId::random::<T>();
         self.assets.insert(id, asset);
+        self.insert_access(id);
         self.events.send(AssetEvent::Created {
             handle: Handle::weak(id),
         });
@@ -107,6 +150,26 @@ impl<T: Asset> Assets<T> {
         self.get_handle(id)
     }
 
+    /// Adds an asset with a stable id derived from `uuid`, so deterministic procedural assets keep
+    /// the same handle across app runs without being loaded.
+    ///
+    /// Inserting again with the same `uuid` replaces the asset and sends an
+    /// [`AssetEvent::Modified`]. The handle can also be created without the asset with
+    /// [`Handle::weak_from_uuid`], for example in a `const`.
+    ///
+    /// # Collisions
+    ///
+    /// Loaded assets are identified by their path, so they never collide with these ids. The ids
+    /// are a 64 bit hash of the whole `uuid`, so like the random ids of [`Assets::add`] and the
+    /// numbers given to [`HandleId::new`] or
+    /// [`HandleUntyped::weak_from_u64`](crate::HandleUntyped::weak_from_u64), they are all
+    /// 64 bit numbers for the same asset type: they can collide with each other, but only by
+    /// chance, however the uuids are structured.
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset"]
+    pub fn insert_with_uuid(&mut self, uuid: Uuid, asset: T) -> Handle<T> {
+        self.set(HandleId::from_uuid::<T>(uuid), asset)
+    }
+
     /// Add/modify the asset pointed to by the given handle.
     ///
     /// If an asset already exists with the given [`HandleId`], it will be modified. Otherwise the
@@ -118,6 +181,7 @@ impl<T: Asset> Assets<T> {
     /// * [`AssetEvent::Modified`]: Sent if the asset with given handle already existed.
     pub fn set_untracked<H: Into<HandleId>>(&mut self, handle: H, asset: T) {
         let id: HandleId = handle.into();
+        self.insert_access(id);
         if self.assets.insert(id, asset).is_some() {
             self.events.send(AssetEvent::Modified {
                 handle: Handle::weak(id),
@@ -134,7 +198,9 @@ impl<T: Asset> Assets<T> {
     /// This is the main method for accessing asset data from an [Assets] collection. If you need
     /// mutable access to the asset, use [`get_mut`](Assets::get_mut).
     pub fn get(&self, handle: &Handle<T>) -> Option<&T> {
-        self.assets.get::<HandleId>(&handle.into())
+        let id: HandleId = handle.into();
+        self.record_access(id);
+        self.assets.get(&id)
     }
 
     /// Checks if an asset exists for the given handle
@@ -148,17 +214,86 @@ impl<T: Asset> Assets<T> {
     /// do not need mutable access to the asset, you may also use [get](Assets::get).
     pub fn get_mut(&mut self, handle: &Handle<T>) -> Option<&mut T> {
         let id: HandleId = handle.into();
+        self.record_access(id);
         self.events.send(AssetEvent::Modified {
             handle: Handle::weak(id),
         });
         self.assets.get_mut(&id)
     }
 
+    /// Returns the frame the asset with the given id was last added, modified with
+    /// [`get_mut`](Assets::get_mut) or accessed with [`get`](Assets::get) or
+    /// [`iter`](Assets::iter) in, including by the extract systems of the renderer.
+    ///
+    /// Accesses are only tracked for the asset types with an [`AssetBudget`], and the frames are
+    /// counted from when the budget was added. Returns `None` for other types, or if the asset
+    /// doesn't exist.
+    ///
+    /// [`AssetBudget`]: crate::AssetBudget
+    pub fn last_access<H: Into<HandleId>>(&self, handle: H) -> Option<u32> {
+        let last_access = self.last_access.as_ref()?;
+        last_access
+            .get(&handle.into())
+            .map(|frame| frame.load(Ordering::Relaxed))
+    }
+
+    /// Starts tracking the last access of every asset.
+    pub(crate) fn track_access(&mut self) {
+        if self.last_access.is_none() {
+            let frame = self.access_frame;
+            self.last_access = Some(
+                self.assets
+                    .keys()
+                    .map(|&id| (id, AtomicU32::new(frame)))
+                    .collect(),
+            );
+        }
+    }
+
+    pub(crate) fn advance_access_frame(&mut self) {
+        self.access_frame = self.access_frame.wrapping_add(1);
+    }
+
+    fn record_access(&self, id: HandleId) {
+        if let Some(frame) = self.last_access.as_ref().and_then(|map| map.get(&id)) {
+            frame.store(self.access_frame, Ordering::Relaxed);
+        }
+    }
+
+    fn insert_access(&mut self, id: HandleId) {
+        if let Some(last_access) = &mut self.last_access {
+            last_access.insert(id, AtomicU32::new(self.access_frame));
+        }
+    }
+
     /// Gets a _Strong_ handle pointing to the same asset as the given one.
     pub fn get_handle<H: Into<HandleId>>(&self, handle: H) -> Handle<T> {
         Handle::strong(handle.into(), self.ref_change_sender.clone())
     }
 
+    /// Gets a _Strong_ handle to the asset with the given id, or `None` if it doesn't exist.
+    ///
+    /// Unlike [`get_handle`](Assets::get_handle), this never makes a handle to a removed asset, so
+    /// caches can hold weak handles and upgrade them on use, or when they receive an
+    /// [`AssetEvent::Unused`].
+    pub fn strong_handle<H: Into<HandleId>>(&self, handle: H) -> Option<Handle<T>> {
+        let id: HandleId = handle.into();
+        self.assets.contains_key(&id).then(|| self.get_handle(id))
+    }
+
+    /// Gets the number of strong handles to the asset with the given id, or `None` if it doesn't
+    /// exist.
+    ///
+    /// The counts are updated once per frame by
+    /// [`free_unused_assets_system`](crate::free_unused_assets_system), so handles made or dropped
+    /// since then aren't counted yet. This is meant for diagnostics.
+    pub fn handle_count<H: Into<HandleId>>(&self, handle: H) -> Option<usize> {
+        let id: HandleId = handle.into();
+        self.assets
+            .contains_key(&id)
+            .then(|| self.ref_counts.read().get(&id).copied().unwrap_or(0))
+    }
+
     /// Gets mutable access to an asset for the given handle, inserting a new value if none exists.
     ///
     /// # Events
@@ -177,6 +312,10 @@ impl<T: Asset> Assets<T> {
             });
             insert_fn()
         });
+        // The asset was either added or accessed
+        if let Some(last_access) = &mut self.last_access {
+            last_access.insert(id, AtomicU32::new(self.access_frame));
+        }
 
         if let Some(event) = event {
             self.events.send(event);
@@ -185,10 +324,37 @@ impl<T: Asset> Assets<T> {
     }
 
     /// Gets an iterator over all assets in the collection.
+    ///
+    /// Every asset iterated over counts as accessed, see [`last_access`](Assets::last_access).
     pub fn iter(&self) -> impl Iterator<Item = (HandleId, &T)> {
+        self.assets.iter().map(|(k, v)| {
+            self.record_access(*k);
+            (*k, v)
+        })
+    }
+
+    /// Gets an iterator over all assets in the collection, without counting them as accessed.
+    pub(crate) fn iter_untracked(&self) -> impl Iterator<Item = (HandleId, &T)> {
         self.assets.iter().map(|(k, v)| (*k, v))
     }
 
+    /// Gets an iterator over the assets created or modified since `last_run`, with the ticks of a
+    /// [`SystemChangeTick`].
+    ///
+    /// Like their [`AssetEvent`]s, changes are only seen once [`Assets::asset_event_system`] ran,
+    /// so systems can cheaply update what they derive from the assets that actually changed.
+    /// Assets changed with [`Assets::set_untracked`] are never seen.
+    pub fn iter_changed(
+        &self,
+        last_run: Tick,
+        this_run: Tick,
+    ) -> impl Iterator<Item = (HandleId, &T)> {
+        self.change_ticks
+            .iter()
+            .filter(move |(_, tick)| tick.is_newer_than(last_run, this_run))
+            .filter_map(|(id, _)| Some((*id, self.assets.get(id)?)))
+    }
+
     /// Gets a mutable iterator over all assets in the collection.
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (HandleId, &mut T)> {
         self.assets.iter_mut().map(|(k, v)| {
@@ -214,6 +380,10 @@ impl<T: Asset> Assets<T> {
     pub fn remove<H: Into<HandleId>>(&mut self, handle: H) -> Option<T> {
         let id: HandleId = handle.into();
         let asset = self.assets.remove(&id);
+        self.change_ticks.remove(&id);
+        if let Some(last_access) = &mut self.last_access {
+            last_access.remove(&id);
+        }
         if asset.is_some() {
             self.events.send(AssetEvent::Removed {
                 handle: Handle::weak(id),
@@ -222,11 +392,24 @@ impl<T: Asset> Assets<T> {
         asset
     }
 
+    /// Sends an [`AssetEvent::Unused`] for the given handle if the asset exists.
+    pub(crate) fn mark_unused(&mut self, id: HandleId) {
+        if self.assets.contains_key(&id) {
+            self.events.send(AssetEvent::Unused {
+                handle: Handle::weak(id),
+            });
+        }
+    }
+
     /// Clears the inner asset map, removing all key-value pairs.
     ///
     /// Keeps the allocated memory for reuse.
     pub fn clear(&mut self) {
         self.assets.clear();
+        self.change_ticks.clear();
+        if let Some(last_access) = &mut self.last_access {
+            last_access.clear();
+        }
     }
 
     /// Reserves capacity for at least additional more elements to be inserted into the assets.
@@ -249,14 +432,45 @@ impl<T: Asset> Assets<T> {
     pub fn asset_event_system(
         mut events: EventWriter<AssetEvent<T>>,
         mut assets: ResMut<Assets<T>>,
+        change_tick: SystemChangeTick,
     ) {
+        assets
+            .bypass_change_detection()
+            .check_change_ticks(change_tick.this_run());
+
         // Check if the events are empty before calling `drain`.
         // As `drain` triggers change detection.
         if !assets.events.is_empty() {
-            events.send_batch(assets.events.drain());
+            let assets = &mut *assets;
+            let change_ticks = &mut assets.change_ticks;
+            events.send_batch(assets.events.drain().inspect(|event| match event {
+                AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                    change_ticks.insert(handle.id(), change_tick.this_run());
+                }
+                AssetEvent::Removed { handle } => {
+                    change_ticks.remove(&handle.id());
+                }
+                AssetEvent::Unused { .. } => {}
+            }));
         }
     }
 
+    /// Clamps the change ticks of the assets that are too old, like
+    /// [`World::check_change_ticks`] does for components, so that [`Assets::iter_changed`]
+    /// doesn't see old changes as new once the tick wraps around.
+    ///
+    /// Does nothing unless the tick advanced by at least [`CHECK_TICK_THRESHOLD`] since the
+    /// previous pass.
+    fn check_change_ticks(&mut self, this_run: Tick) {
+        if this_run.get().wrapping_sub(self.last_check_tick.get()) < CHECK_TICK_THRESHOLD {
+            return;
+        }
+        for tick in self.change_ticks.values_mut() {
+            tick.check_tick(this_run);
+        }
+        self.last_check_tick = this_run;
+    }
+
     /// Gets the number of assets in the collection.
     pub fn len(&self) -> usize {
         self.assets.len()
@@ -316,6 +530,29 @@ pub trait AddAsset {
     fn add_asset_loader<T>(&mut self, loader: T) -> &mut Self
     where
         T: AssetLoader;
+
+    /// Registers an asset constructed in code under `path`, so it can be loaded with
+    /// [`AssetServer::load`] from anywhere, without depending on the order plugins are built or
+    /// their systems run in.
+    ///
+    /// The asset is available in [`Assets<T>`] immediately, is never unloaded and is not read from
+    /// the asset I/O, even when reloaded. By convention, the paths of builtin assets start with
+    /// `embedded://` followed by the name of the crate registering them, like
+    /// `embedded://bevy_sprite/white.png`, so they don't collide with files in the asset folder.
+    ///
+    /// `T` must already be added with [`AddAsset::add_asset`].
+    fn register_builtin_asset<'a, T, P>(&mut self, path: P, asset: T) -> &mut Self
+    where
+        T: Asset,
+        P: Into<AssetPath<'a>>;
+
+    /// Keeps the assets of type `T` within `budget`, evicting them when they use too much memory.
+    ///
+    /// Adding a budget again replaces the previous one. `T` must already be added with
+    /// [`AddAsset::add_asset`].
+    fn add_asset_budget<T>(&mut self, budget: AssetBudget<T>) -> &mut Self
+    where
+        T: Asset + AssetByteSize;
 }
 
 impl AddAsset for App {
@@ -333,7 +570,14 @@ impl AddAsset for App {
 
         self.insert_resource(assets)
             .add_systems(LoadAssets, update_asset_storage_system::<T>)
-            .add_systems(AssetEvents, Assets::<T>::asset_event_system)
+            .add_systems(
+                AssetEvents,
+                (
+                    Assets::<T>::asset_event_system,
+                    derived::collect_modified_sources::<T>.in_set(derived::CollectModifiedSources),
+                )
+                    .chain(),
+            )
             .register_type::<Handle<T>>()
             .add_event::<AssetEvent<T>>()
     }
@@ -403,6 +647,35 @@ impl AddAsset for App {
         self.world.resource_mut::<AssetServer>().add_loader(loader);
         self
     }
+
+    fn register_builtin_asset<'a, T, P>(&mut self, path: P, asset: T) -> &mut Self
+    where
+        T: Asset,
+        P: Into<AssetPath<'a>>,
+    {
+        let handle = self
+            .world
+            .resource::<AssetServer>()
+            .register_builtin_asset::<T>(path.into());
+        self.world
+            .resource_mut::<Assets<T>>()
+            .set_untracked(handle, asset);
+        self
+    }
+
+    fn add_asset_budget<T>(&mut self, budget: AssetBudget<T>) -> &mut Self
+    where
+        T: Asset + AssetByteSize,
+    {
+        if !self.world.contains_resource::<AssetBudget<T>>() {
+            self.world.resource_mut::<Assets<T>>().track_access();
+            self.add_systems(
+                AssetEvents,
+                budget::enforce_asset_budget_system::<T>.before(Assets::<T>::asset_event_system),
+            );
+        }
+        self.insert_resource(budget)
+    }
 }
 
 /// Loads an internal asset.
@@ -485,7 +758,11 @@ macro_rules! load_internal_binary_asset {
 mod tests {
     use bevy_app::App;
 
-    use crate::{AddAsset, Assets};
+    use bevy_utils::Uuid;
+
+    use bevy_ecs::{component::Tick, event::Events};
+
+    use crate::{AddAsset, AssetEvent, Assets, Handle, HandleId};
 
     #[test]
     fn asset_overwriting() {
@@ -503,4 +780,177 @@ mod tests {
         let assets_after = app.world.resource_mut::<Assets<MyAsset>>();
         assert!(assets_after.get(&handle).is_some());
     }
+
+    #[test]
+    fn insert_with_uuid_replaces_asset() {
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath, Debug, PartialEq)]
+        #[uuid = "0b6f2c1e-5d3a-4e8b-9c7f-1a2b3c4d5e6f"]
+        struct MyAsset(u32);
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<MyAsset>();
+        let uuid = Uuid::from_u128(0x2f8e_4c6a_91d0_4b3e_a5f7_6c1d_8e9b_0a42);
+        const WEAK: Handle<MyAsset> =
+            Handle::weak_from_uuid(Uuid::from_u128(0x2f8e_4c6a_91d0_4b3e_a5f7_6c1d_8e9b_0a42));
+
+        let mut assets = app.world.resource_mut::<Assets<MyAsset>>();
+        let handle = assets.insert_with_uuid(uuid, MyAsset(1));
+        assert_eq!(handle.id(), WEAK.id());
+        let _ = assets.insert_with_uuid(uuid, MyAsset(2));
+        assert_eq!(assets.get(&WEAK), Some(&MyAsset(2)));
+        assert_eq!(assets.len(), 1);
+
+        let events: Vec<_> = assets.events.drain().collect();
+        assert!(matches!(
+            events.as_slice(),
+            [AssetEvent::Created { .. }, AssetEvent::Modified { handle }] if *handle == WEAK
+        ));
+    }
+
+    #[test]
+    fn old_change_ticks_are_clamped() {
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "9c4e2a7d-1b6f-4d3a-8e5c-0f7b2d9a6c13"]
+        struct MyAsset;
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<MyAsset>();
+
+        let mut assets = app.world.resource_mut::<Assets<MyAsset>>();
+        let handle = assets.add(MyAsset);
+        assets.change_ticks.insert(handle.id(), Tick::new(0));
+        // Long after the change, the ticks are checked
+        assets.check_change_ticks(Tick::new(3_500_000_000));
+        // Once the tick wrapped around, the change isn't seen as new
+        let (last_run, this_run) = (Tick::new(u32::MAX - 4), Tick::new(5));
+        assert_eq!(assets.iter_changed(last_run, this_run).count(), 0);
+
+        assets.remove(&handle);
+        assert!(assets.change_ticks.is_empty());
+    }
+
+    #[test]
+    fn uuid_ids_hash_the_whole_uuid() {
+        use bevy_reflect::TypeUuid;
+
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "3e9a1c7b-8f2d-4b6e-a0c5-7d1f9b3e2a84"]
+        struct MyAsset;
+
+        for n in [0, 1, 42, u64::MAX] {
+            assert_ne!(
+                HandleId::from_uuid::<MyAsset>(Uuid::from_u128(n as u128)),
+                HandleId::new(MyAsset::TYPE_UUID, n)
+            );
+        }
+        // Equal halves don't cancel out
+        let equal_halves = |n: u128| HandleId::from_uuid::<MyAsset>(Uuid::from_u128((n << 64) | n));
+        assert_ne!(equal_halves(5), equal_halves(7));
+        assert_ne!(equal_halves(5), HandleId::new(MyAsset::TYPE_UUID, 0));
+    }
+
+    #[test]
+    fn unused_assets_can_be_upgraded() {
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "6d3c8a1f-2b4e-4f7a-9e1d-5c0b7a3f8e92"]
+        struct MyAsset;
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<MyAsset>();
+
+        let handle = app.world.resource_mut::<Assets<MyAsset>>().add(MyAsset);
+        let weak = handle.clone_weak();
+        app.update();
+        assert_eq!(
+            app.world.resource::<Assets<MyAsset>>().handle_count(&weak),
+            Some(1)
+        );
+
+        // The asset is reported as unused a frame after its last handle is dropped
+        drop(handle);
+        app.update();
+        app.update();
+        let events = app.world.resource::<Events<AssetEvent<MyAsset>>>();
+        assert!(events
+            .iter_current_update_events()
+            .any(|event| matches!(event, AssetEvent::Unused { handle } if *handle == weak)));
+
+        // and upgrading it then keeps it alive
+        let handle = app.world.resource::<Assets<MyAsset>>().strong_handle(&weak);
+        assert!(handle.is_some());
+        for _ in 0..3 {
+            app.update();
+        }
+        let assets = app.world.resource::<Assets<MyAsset>>();
+        assert!(assets.contains(&weak));
+        assert_eq!(assets.handle_count(&weak), Some(1));
+
+        drop(handle);
+        for _ in 0..4 {
+            app.update();
+        }
+        let assets = app.world.resource::<Assets<MyAsset>>();
+        assert!(!assets.contains(&weak));
+        assert!(assets.strong_handle(&weak).is_none());
+        assert_eq!(assets.handle_count(&weak), None);
+    }
+
+    #[test]
+    fn iter_changed_only_returns_changed_assets() {
+        use bevy_ecs::system::{Res, ResMut, SystemChangeTick};
+
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "c8d7a3f2-6b1e-4f09-8a5d-2e7c9b4f1d36"]
+        struct MyAsset;
+
+        #[derive(bevy_ecs::system::Resource, Default)]
+        struct Changed(Vec<crate::HandleId>);
+
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<MyAsset>()
+            .init_resource::<Changed>()
+            .add_systems(
+                bevy_app::Update,
+                |assets: Res<Assets<MyAsset>>,
+                 mut changed: ResMut<Changed>,
+                 tick: SystemChangeTick| {
+                    changed.0 = assets
+                        .iter_changed(tick.last_run(), tick.this_run())
+                        .map(|(id, _)| id)
+                        .collect();
+                },
+            );
+
+        let mut assets = app.world.resource_mut::<Assets<MyAsset>>();
+        let first = assets.add(MyAsset);
+        let second = assets.add(MyAsset);
+        // Changes are seen once their events are sent at the end of the frame
+        app.update();
+        app.update();
+        let mut changed = app.world.resource::<Changed>().0.clone();
+        changed.sort();
+        let mut expected = vec![first.id(), second.id()];
+        expected.sort();
+        assert_eq!(changed, expected);
+
+        app.world
+            .resource_mut::<Assets<MyAsset>>()
+            .get_mut(&second)
+            .unwrap();
+        app.update();
+        app.update();
+        assert_eq!(app.world.resource::<Changed>().0, vec![second.id()]);
+
+        app.update();
+        assert!(app.world.resource::<Changed>().0.is_empty());
+    }
 }
@@ -0,0 +1,204 @@
+use crate::{Asset, AssetServer, Assets, HandleId};
+use bevy_ecs::{
+    change_detection::DetectChangesMut,
+    system::{Res, ResMut, Resource},
+};
+use std::marker::PhantomData;
+
+/// The memory used by an asset, to keep the assets of its type within an [`AssetBudget`].
+pub trait AssetByteSize {
+    /// Returns an estimate of the memory used by the asset, in bytes.
+    fn byte_size(&self) -> usize;
+}
+
+/// The eviction policy of an [`AssetBudget`], see [`AssetBudget::with_eviction_policy`].
+pub type EvictionPolicy = Box<dyn FnMut(&mut Vec<EvictionCandidate>) + Send + Sync + 'static>;
+
+/// An asset that can be evicted to get back within an [`AssetBudget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EvictionCandidate {
+    /// The id of the asset
+    pub id: HandleId,
+    /// The frame the asset was last accessed in, see [`Assets::last_access`]
+    pub last_access: u32,
+    /// The size of the asset, as returned by [`AssetByteSize::byte_size`]
+    pub byte_size: usize,
+    /// The number of strong handles to the asset, see [`Assets::handle_count`]
+    pub handle_count: usize,
+}
+
+/// A memory budget for the assets of type `T`.
+///
+/// Once added with [`AddAsset::add_asset_budget`](crate::AddAsset::add_asset_budget), assets are
+/// removed from [`Assets<T>`] at the end of every frame where they use more than `max_bytes`,
+/// least recently accessed first, until they fit in the budget again. Evicted assets send an
+/// [`AssetEvent::Removed`](crate::AssetEvent::Removed) like any removed asset, and assets loaded
+/// by the [`AssetServer`] are loaded again the next time they are loaded.
+///
+/// By default, assets with strong handles are never evicted, so only the assets that are kept
+/// around without being used, like those held by weak handles, are candidates. Among them,
+/// [`Assets::get`], [`Assets::get_mut`] and [`Assets::iter`] count as accesses, including from
+/// the extract systems of the renderer. Set [`evict_held`](AssetBudget::evict_held) to evict
+/// assets with strong handles too, which frees them while they may still be in use.
+///
+/// Removing this resource disables the budget.
+#[derive(Resource)]
+pub struct AssetBudget<T: Asset> {
+    /// The number of bytes the assets may use before they are evicted
+    pub max_bytes: usize,
+    /// Whether assets with strong handles can be evicted, `false` by default
+    pub evict_held: bool,
+    eviction_policy: EvictionPolicy,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Asset> AssetBudget<T> {
+    /// Creates a budget of `max_bytes`, which evicts the least recently accessed assets first.
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            max_bytes,
+            evict_held: false,
+            eviction_policy: Box::new(|_| {}),
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the eviction policy, which is called when the assets are over budget with every
+    /// asset that can be evicted, least recently accessed first.
+    ///
+    /// The assets are evicted from the front of the list until they fit in the budget, so the
+    /// policy can veto evictions by removing assets from the list, for example pinned ones, or
+    /// change the eviction order by sorting it.
+    pub fn with_eviction_policy(
+        mut self,
+        policy: impl FnMut(&mut Vec<EvictionCandidate>) + Send + Sync + 'static,
+    ) -> Self {
+        self.eviction_policy = Box::new(policy);
+        self
+    }
+}
+
+/// Evicts assets from [`Assets<T>`] until they fit in their [`AssetBudget`].
+pub(crate) fn enforce_asset_budget_system<T: Asset + AssetByteSize>(
+    budget: Option<ResMut<AssetBudget<T>>>,
+    mut assets: ResMut<Assets<T>>,
+    asset_server: Res<AssetServer>,
+) {
+    if let Some(mut budget) = budget {
+        let mut used_bytes: usize = assets
+            .iter_untracked()
+            .map(|(_, asset)| asset.byte_size())
+            .sum();
+        if used_bytes > budget.max_bytes {
+            let mut candidates: Vec<_> = assets
+                .iter_untracked()
+                .map(|(id, asset)| EvictionCandidate {
+                    id,
+                    last_access: assets.last_access(id).unwrap_or(0),
+                    byte_size: asset.byte_size(),
+                    handle_count: assets.handle_count(id).unwrap_or(0),
+                })
+                .filter(|candidate| budget.evict_held || candidate.handle_count == 0)
+                .collect();
+            candidates.sort_by_key(|candidate| candidate.last_access);
+            (budget.eviction_policy)(&mut candidates);
+
+            for candidate in candidates {
+                if used_bytes <= budget.max_bytes {
+                    break;
+                }
+                if assets.remove(candidate.id).is_some() {
+                    asset_server.asset_removed(candidate.id);
+                    used_bytes = used_bytes.saturating_sub(candidate.byte_size);
+                }
+            }
+        }
+    }
+    assets.bypass_change_detection().advance_access_frame();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+
+    use crate::{AddAsset, AssetBudget, AssetByteSize, Assets, Handle, HandleId};
+
+    #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+    #[uuid = "8b1f6e3a-4c2d-4a7e-9f05-3d6c2b1a0e74"]
+    struct Blob(usize);
+
+    impl AssetByteSize for Blob {
+        fn byte_size(&self) -> usize {
+            self.0
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<Blob>();
+        app
+    }
+
+    /// Adds `asset` without a strong handle, so that it can be evicted.
+    fn add_unheld(assets: &mut Assets<Blob>, asset: Blob) -> Handle<Blob> {
+        let handle = Handle::weak(HandleId::random::<Blob>());
+        assets.set_untracked(&handle, asset);
+        handle
+    }
+
+    #[test]
+    fn evicts_least_recently_accessed_assets() {
+        let mut app = app();
+
+        let mut assets = app.world.resource_mut::<Assets<Blob>>();
+        let pinned = add_unheld(&mut assets, Blob(4));
+        let old = add_unheld(&mut assets, Blob(4));
+        let recent = add_unheld(&mut assets, Blob(4));
+        let pinned_id = pinned.id();
+        app.add_asset_budget(AssetBudget::<Blob>::new(12).with_eviction_policy(
+            move |candidates| candidates.retain(|candidate| candidate.id != pinned_id),
+        ));
+
+        // Within budget, nothing is evicted
+        app.update();
+        let assets = app.world.resource::<Assets<Blob>>();
+        assert_eq!(assets.len(), 3);
+        assert!(assets.get(&recent).is_some());
+
+        // Over budget, the oldest asset that isn't pinned is evicted
+        app.world.resource_mut::<AssetBudget<Blob>>().max_bytes = 8;
+        app.update();
+        let assets = app.world.resource::<Assets<Blob>>();
+        assert!(assets.contains(&pinned));
+        assert!(!assets.contains(&old));
+        assert!(assets.contains(&recent));
+    }
+
+    #[test]
+    fn keeps_assets_with_strong_handles() {
+        let mut app = app();
+
+        let mut assets = app.world.resource_mut::<Assets<Blob>>();
+        let held = assets.add(Blob(4));
+        let unheld = add_unheld(&mut assets, Blob(4));
+        app.add_asset_budget(AssetBudget::<Blob>::new(4));
+
+        // Both were accessed last in the same frame, but only the unheld one can be evicted
+        app.update();
+        let assets = app.world.resource::<Assets<Blob>>();
+        assert!(assets.contains(&held));
+        assert!(!assets.contains(&unheld));
+
+        // Still over budget, but nothing else can be evicted
+        app.world.resource_mut::<AssetBudget<Blob>>().max_bytes = 0;
+        app.update();
+        assert!(app.world.resource::<Assets<Blob>>().contains(&held));
+
+        app.world.resource_mut::<AssetBudget<Blob>>().evict_held = true;
+        app.update();
+        assert!(!app.world.resource::<Assets<Blob>>().contains(&held));
+    }
+}
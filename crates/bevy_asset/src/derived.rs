@@ -0,0 +1,242 @@
+use crate::{Asset, AssetEvent, HandleId};
+use bevy_ecs::{
+    event::EventReader,
+    schedule::SystemSet,
+    system::{ResMut, Resource},
+    world::{Mut, World},
+};
+use bevy_log::warn;
+use bevy_utils::HashSet;
+use thiserror::Error;
+
+/// The rebuild function of a derivation, see [`DerivedAssets::register`].
+pub type RebuildDerivedAssets =
+    Box<dyn Fn(&mut World) -> Result<(), anyhow::Error> + Send + Sync + 'static>;
+
+/// Assets built from other assets, which are rebuilt when one of their sources is modified.
+///
+/// For example, a texture atlas packed from images at startup goes stale when one of the images
+/// is hot reloaded. Registering the atlas as derived from the images repacks it in place, so
+/// the existing handles to the atlas show the new images.
+///
+/// Sources are watched for [`AssetEvent::Modified`] for every asset type added with
+/// [`AddAsset::add_asset`](crate::AddAsset::add_asset), and the derived assets are rebuilt during
+/// [`AssetEvents`](crate::AssetEvents).
+#[derive(Resource, Default)]
+pub struct DerivedAssets {
+    derivations: Vec<Derivation>,
+    /// The sources modified since the derived assets were last rebuilt
+    modified: HashSet<HandleId>,
+}
+
+struct Derivation {
+    sources: HashSet<HandleId>,
+    targets: HashSet<HandleId>,
+    rebuild: RebuildDerivedAssets,
+}
+
+/// An error that occurs when registering [`DerivedAssets`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum DerivedAssetError {
+    /// The asset would be rebuilt whenever it is rebuilt.
+    #[error("the asset {0:?} would be derived from itself")]
+    Cycle(HandleId),
+}
+
+impl DerivedAssets {
+    /// Registers `targets` as derived from `sources`, so `rebuild` is run whenever one of the
+    /// sources is modified.
+    ///
+    /// `rebuild` should update the targets in place, like with [`Assets::get_mut`](crate::Assets::get_mut),
+    /// so their handles stay valid. Errors it returns, like for sources that are not loaded, are
+    /// logged.
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`DerivedAssetError::Cycle`] if a target is one of the sources, or if a source is
+    /// already derived from one of the targets.
+    pub fn register(
+        &mut self,
+        sources: impl IntoIterator<Item = HandleId>,
+        targets: impl IntoIterator<Item = HandleId>,
+        rebuild: impl Fn(&mut World) -> Result<(), anyhow::Error> + Send + Sync + 'static,
+    ) -> Result<(), DerivedAssetError> {
+        let sources: HashSet<HandleId> = sources.into_iter().collect();
+        let targets: HashSet<HandleId> = targets.into_iter().collect();
+
+        // Follow the assets rebuilt after the targets, looking for the new sources
+        let mut reached: HashSet<HandleId> = HashSet::default();
+        let mut pending: Vec<HandleId> = targets.iter().copied().collect();
+        while let Some(id) = pending.pop() {
+            if sources.contains(&id) {
+                return Err(DerivedAssetError::Cycle(id));
+            }
+            if !reached.insert(id) {
+                continue;
+            }
+            for derivation in &self.derivations {
+                if derivation.sources.contains(&id) {
+                    pending.extend(derivation.targets.iter().copied());
+                }
+            }
+        }
+
+        self.derivations.push(Derivation {
+            sources,
+            targets,
+            rebuild: Box::new(rebuild),
+        });
+        Ok(())
+    }
+
+    /// Stops rebuilding the derivations that have `target` as one of their targets.
+    pub fn remove(&mut self, target: impl Into<HandleId>) {
+        let target = target.into();
+        self.derivations
+            .retain(|derivation| !derivation.targets.contains(&target));
+    }
+
+    /// Returns `true` if `id` is the source of a derived asset.
+    pub fn is_source(&self, id: impl Into<HandleId>) -> bool {
+        let id = id.into();
+        self.derivations
+            .iter()
+            .any(|derivation| derivation.sources.contains(&id))
+    }
+}
+
+/// Collects the modified sources of [`DerivedAssets`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub(crate) struct CollectModifiedSources;
+
+pub(crate) fn collect_modified_sources<T: Asset>(
+    mut events: EventReader<AssetEvent<T>>,
+    derived_assets: Option<ResMut<DerivedAssets>>,
+) {
+    let Some(mut derived_assets) = derived_assets.filter(|derived| !derived.derivations.is_empty())
+    else {
+        events.clear();
+        return;
+    };
+    for event in events.iter() {
+        if let AssetEvent::Modified { handle } = event {
+            if derived_assets.is_source(handle) {
+                derived_assets.modified.insert(handle.id());
+            }
+        }
+    }
+}
+
+/// Rebuilds the [`DerivedAssets`] with modified sources.
+pub(crate) fn rebuild_derived_assets(world: &mut World) {
+    match world.get_resource::<DerivedAssets>() {
+        Some(derived_assets) if !derived_assets.modified.is_empty() => {}
+        _ => return,
+    }
+    world.resource_scope(|world, mut derived_assets: Mut<DerivedAssets>| {
+        let modified = std::mem::take(&mut derived_assets.modified);
+        for derivation in &derived_assets.derivations {
+            if derivation.sources.is_disjoint(&modified) {
+                continue;
+            }
+            if let Err(err) = (derivation.rebuild)(world) {
+                warn!(
+                    "could not rebuild the assets derived from {:?}: {}",
+                    derivation.sources, err
+                );
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_utils::Uuid;
+
+    fn id(id: u64) -> HandleId {
+        HandleId::new(Uuid::nil(), id)
+    }
+
+    #[test]
+    fn register_rejects_cycles() {
+        let mut derived_assets = DerivedAssets::default();
+        derived_assets
+            .register([id(0), id(1)], [id(2)], |_| Ok(()))
+            .unwrap();
+        derived_assets
+            .register([id(2)], [id(3)], |_| Ok(()))
+            .unwrap();
+
+        assert_eq!(
+            derived_assets
+                .register([id(3)], [id(3)], |_| Ok(()))
+                .unwrap_err(),
+            DerivedAssetError::Cycle(id(3))
+        );
+        assert_eq!(
+            derived_assets
+                .register([id(3)], [id(1)], |_| Ok(()))
+                .unwrap_err(),
+            DerivedAssetError::Cycle(id(3))
+        );
+        assert!(derived_assets.is_source(id(0)));
+        assert!(!derived_assets.is_source(id(3)));
+
+        derived_assets.remove(id(2));
+        assert!(!derived_assets.is_source(id(0)));
+        derived_assets
+            .register([id(3)], [id(1)], |_| Ok(()))
+            .unwrap();
+    }
+
+    #[test]
+    fn modified_sources_rebuild_derived_assets() {
+        use crate::{AddAsset, Assets};
+        use bevy_app::App;
+
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "5d8e1f3a-7c2b-4e9d-a6f0-3b1c8d2e4f57"]
+        struct Source(u32);
+        #[derive(bevy_reflect::TypeUuid, bevy_reflect::TypePath)]
+        #[uuid = "b2a4c6e8-1d3f-4a5b-9c7e-0f2d4b6a8c13"]
+        struct Doubled(u32);
+
+        let mut app = App::new();
+        app.add_plugin(bevy_core::TaskPoolPlugin::default())
+            .add_plugin(bevy_core::TypeRegistrationPlugin)
+            .add_plugin(crate::AssetPlugin::default());
+        app.add_asset::<Source>().add_asset::<Doubled>();
+
+        let source = app.world.resource_mut::<Assets<Source>>().add(Source(1));
+        let doubled = app.world.resource_mut::<Assets<Doubled>>().add(Doubled(2));
+        let (weak_source, weak_doubled) = (source.clone_weak(), doubled.clone_weak());
+        app.world
+            .resource_mut::<DerivedAssets>()
+            .register([source.id()], [doubled.id()], move |world| {
+                let value = world
+                    .resource::<Assets<Source>>()
+                    .get(&weak_source)
+                    .unwrap()
+                    .0;
+                let mut doubled = world.resource_mut::<Assets<Doubled>>();
+                doubled.get_mut(&weak_doubled).unwrap().0 = value * 2;
+                Ok(())
+            })
+            .unwrap();
+        let doubled_value = |app: &App| {
+            app.world
+                .resource::<Assets<Doubled>>()
+                .get(&doubled)
+                .unwrap()
+                .0
+        };
+        app.update();
+        assert_eq!(doubled_value(&app), 2);
+
+        let mut sources = app.world.resource_mut::<Assets<Source>>();
+        sources.get_mut(&source).unwrap().0 = 5;
+        app.update();
+        assert_eq!(doubled_value(&app), 10);
+    }
+}
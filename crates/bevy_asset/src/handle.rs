@@ -72,6 +72,32 @@ impl HandleId {
     pub const fn new(type_uuid: Uuid, id: u64) -> Self {
         HandleId::Id(type_uuid, id)
     }
+
+    /// Creates a stable id for an asset of type `T` from `uuid`, which stays the same across
+    /// app runs.
+    ///
+    /// All 128 bits of `uuid` are hashed into the 64 bit id, see [`Assets::insert_with_uuid`] for
+    /// how it can collide with other ids.
+    #[inline]
+    pub const fn from_uuid<T: Asset>(uuid: Uuid) -> Self {
+        HandleId::Id(T::TYPE_UUID, hash_uuid(uuid))
+    }
+}
+
+/// Hashes `uuid` with 64 bit FNV-1a, which unlike the hasher of [`AssetPathId`] can run in a
+/// `const` context, so that [`Handle::weak_from_uuid`] can be used in a `const`.
+const fn hash_uuid(uuid: Uuid) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+    let bytes = uuid.as_bytes();
+    let mut hash = OFFSET_BASIS;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(PRIME);
+        i += 1;
+    }
+    hash
 }
 
 /// A handle into a specific [`Asset`] of type `T`.
@@ -150,6 +176,16 @@ impl<T: Asset> Handle<T> {
         }
     }
 
+    /// Creates a weak handle to the asset added with [`Assets::insert_with_uuid`] for `uuid`.
+    #[inline]
+    pub const fn weak_from_uuid(uuid: Uuid) -> Self {
+        Self {
+            id: HandleId::from_uuid::<T>(uuid),
+            handle_type: HandleType::Weak,
+            marker: PhantomData,
+        }
+    }
+
     /// The ID of the asset as contained within its respective [`Assets`] collection.
     #[inline]
     pub fn id(&self) -> HandleId {
@@ -13,8 +13,10 @@
 
 mod asset_server;
 mod assets;
+mod budget;
 #[cfg(feature = "debug_asset_server")]
 pub mod debug_asset_server;
+mod derived;
 pub mod diagnostic;
 #[cfg(all(
     feature = "filesystem_watcher",
@@ -24,6 +26,7 @@ mod filesystem_watcher;
 mod handle;
 mod info;
 mod io;
+mod load_group;
 mod loader;
 mod path;
 mod reflect;
@@ -40,15 +43,18 @@ pub use anyhow::Error;
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use budget::*;
+pub use derived::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
+pub use load_group::*;
 pub use loader::*;
 pub use path::*;
 pub use reflect::*;
 
 use bevy_app::{prelude::*, MainScheduleOrder};
-use bevy_ecs::schedule::ScheduleLabel;
+use bevy_ecs::schedule::{IntoSystemConfigs, ScheduleLabel};
 use bevy_utils::Duration;
 
 /// Asset storages are updated.
@@ -133,9 +139,16 @@ impl Plugin for AssetPlugin {
         app.register_type::<AssetPath>();
 
         app.add_systems(PreUpdate, asset_server::free_unused_assets_system);
+        app.add_event::<LoadGroupComplete>()
+            .add_systems(PreUpdate, load_group_system);
         app.init_schedule(LoadAssets);
         app.init_schedule(AssetEvents);
 
+        app.init_resource::<DerivedAssets>().add_systems(
+            AssetEvents,
+            derived::rebuild_derived_assets.after(derived::CollectModifiedSources),
+        );
+
         #[cfg(all(
             feature = "filesystem_watcher",
             all(not(target_arch = "wasm32"), not(target_os = "android"))
@@ -0,0 +1,172 @@
+use crate::{Asset, AssetPath, AssetServer, Handle, HandleId, HandleUntyped, LoadState};
+use bevy_ecs::{
+    prelude::{Component, Entity, Event, EventWriter},
+    system::Query,
+};
+use bevy_utils::HashSet;
+
+/// A group of assets loaded together, like the textures of a level, which tracks their load
+/// progress including the assets they depend on.
+///
+/// The group keeps strong handles to its assets, which are dropped with it unless they are taken
+/// with [`LoadGroup::into_handles`]. Add it to an entity to get a [`LoadGroupComplete`] event
+/// once all its assets are loaded or failed.
+///
+/// ```
+/// # use bevy_asset::{AssetServer, LoadGroup};
+/// # use bevy_ecs::prelude::*;
+/// fn load_level(mut commands: Commands, asset_server: Res<AssetServer>) {
+///     let mut group = LoadGroup::new(&asset_server);
+///     let _ = group.load_untyped("level/tiles.png");
+///     let _ = group.load_untyped("level/music.ogg");
+///     commands.spawn(group);
+/// }
+///
+/// fn loading_screen(groups: Query<&LoadGroup>) {
+///     for group in &groups {
+///         let progress = group.progress();
+///         println!(
+///             "loaded {}/{} assets, {} failed",
+///             progress.loaded, progress.total, progress.failed
+///         );
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(load_level);
+/// # bevy_ecs::system::assert_is_system(loading_screen);
+/// ```
+#[derive(Component)]
+pub struct LoadGroup {
+    asset_server: AssetServer,
+    handles: Vec<HandleUntyped>,
+    /// Whether [`LoadGroupComplete`] was sent for the group
+    completed: bool,
+}
+
+/// The number of assets of a [`LoadGroup`] in each state, see [`LoadGroup::progress`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadGroupProgress {
+    /// The number of assets that are loaded
+    pub loaded: usize,
+    /// The number of assets that failed to load
+    pub failed: usize,
+    /// The number of assets in the group and the assets they depend on found so far
+    pub total: usize,
+}
+
+impl LoadGroupProgress {
+    /// Returns `true` if every asset finished loading, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.loaded + self.failed == self.total
+    }
+}
+
+/// An event sent when all the assets of a [`LoadGroup`] on an entity are loaded or failed.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadGroupComplete {
+    /// The entity of the [`LoadGroup`]
+    pub entity: Entity,
+    /// The final progress of the group
+    pub progress: LoadGroupProgress,
+}
+
+impl LoadGroup {
+    /// Creates an empty [`LoadGroup`] loading assets with `asset_server`.
+    pub fn new(asset_server: &AssetServer) -> Self {
+        Self {
+            asset_server: asset_server.clone(),
+            handles: Vec::new(),
+            completed: false,
+        }
+    }
+
+    /// Loads the asset at `path` as part of the group, see [`AssetServer::load`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset when the group is dropped"]
+    pub fn load<'a, T: Asset, P: Into<AssetPath<'a>>>(&mut self, path: P) -> Handle<T> {
+        let handle = self.asset_server.load(path);
+        self.add(handle.clone_untyped());
+        handle
+    }
+
+    /// Loads the asset at `path` as part of the group, see [`AssetServer::load_untyped`].
+    #[must_use = "not using the returned strong handle may result in the unexpected release of the asset when the group is dropped"]
+    pub fn load_untyped<'a, P: Into<AssetPath<'a>>>(&mut self, path: P) -> HandleUntyped {
+        let handle = self.asset_server.load_untyped(path);
+        self.add(handle.clone());
+        handle
+    }
+
+    /// Adds an asset to the group, which may already be loaded.
+    ///
+    /// Assets added to [`Assets`](crate::Assets) directly instead of loaded from a path are
+    /// counted as loaded.
+    pub fn add(&mut self, handle: impl Into<HandleUntyped>) {
+        self.handles.push(handle.into());
+        self.completed = false;
+    }
+
+    /// The handles of the assets of the group, without their dependencies
+    pub fn handles(&self) -> &[HandleUntyped] {
+        &self.handles
+    }
+
+    /// Consumes the group and returns the handles of its assets, to keep them loaded.
+    pub fn into_handles(self) -> Vec<HandleUntyped> {
+        self.handles
+    }
+
+    /// Counts the assets of the group in each state, including the assets they depend on.
+    ///
+    /// The dependencies of an asset are only known once it is loaded, so the total can grow
+    /// while the group loads.
+    pub fn progress(&self) -> LoadGroupProgress {
+        let mut progress = LoadGroupProgress::default();
+        let mut visited: HashSet<HandleId> = HashSet::default();
+        let mut pending: Vec<HandleId> = self.handles.iter().map(|handle| handle.id()).collect();
+        while let Some(id) = pending.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            progress.total += 1;
+            let HandleId::AssetPathId(_) = id else {
+                progress.loaded += 1;
+                continue;
+            };
+            match self.asset_server.get_load_state(id) {
+                LoadState::Loaded => {
+                    progress.loaded += 1;
+                    pending.extend(
+                        self.asset_server
+                            .get_dependencies(id)
+                            .into_iter()
+                            .map(HandleId::from),
+                    );
+                }
+                LoadState::Failed => progress.failed += 1,
+                LoadState::NotLoaded | LoadState::Loading | LoadState::Unloaded => {}
+            }
+        }
+        progress
+    }
+
+    /// Returns `true` if every asset of the group finished loading, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.progress().is_finished()
+    }
+}
+
+/// Sends a [`LoadGroupComplete`] event for each [`LoadGroup`] that finished loading.
+pub fn load_group_system(
+    mut load_groups: Query<(Entity, &mut LoadGroup)>,
+    mut events: EventWriter<LoadGroupComplete>,
+) {
+    for (entity, mut load_group) in &mut load_groups {
+        if load_group.completed {
+            continue;
+        }
+        let progress = load_group.progress();
+        if progress.is_finished() {
+            load_group.completed = true;
+            events.send(LoadGroupComplete { entity, progress });
+        }
+    }
+}
@@ -5,12 +5,14 @@ use crate::{
 use anyhow::Error;
 use anyhow::Result;
 use bevy_ecs::system::{Res, ResMut};
+use bevy_log::warn;
 use bevy_reflect::TypePath;
 use bevy_reflect::{TypeUuid, TypeUuidDynamic};
 use bevy_utils::{BoxedFuture, HashMap};
 use crossbeam_channel::{Receiver, Sender};
 use downcast_rs::{impl_downcast, Downcast};
 use std::path::Path;
+use thiserror::Error;
 
 /// A loader for an asset source.
 ///
@@ -119,7 +121,7 @@ impl<T: Asset> From<LoadedAsset<T>> for BoxedLoadedAsset {
 ///
 /// An asset source can define one or more assets from a single source path. The main asset is set
 /// using [`LoadContext::set_default_asset`] and sub-assets are defined with
-/// [`LoadContext::set_labeled_asset`].
+/// [`LoadContext::set_labeled_asset`] or [`LoadContext::try_set_labeled_asset`].
 pub struct LoadContext<'a> {
     pub(crate) ref_change_channel: &'a RefChangeChannel,
     pub(crate) asset_io: &'a dyn AssetIo,
@@ -160,11 +162,50 @@ impl<'a> LoadContext<'a> {
     }
 
     /// Sets a secondary asset loaded from the asset source.
+    ///
+    /// An asset already set with the same label is replaced, use
+    /// [`try_set_labeled_asset`](Self::try_set_labeled_asset) to fail instead.
     pub fn set_labeled_asset<T: Asset>(&mut self, label: &str, asset: LoadedAsset<T>) -> Handle<T> {
         assert!(!label.is_empty());
+        if self
+            .labeled_assets
+            .insert(Some(label.to_string()), asset.into())
+            .is_some()
+        {
+            warn!(
+                "{:?} replaced the asset already labeled {label:?}",
+                self.path()
+            );
+        }
+        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+    }
+
+    /// Sets a secondary asset loaded from the asset source, failing if the label is empty or
+    /// already used by another asset of the source.
+    pub fn try_set_labeled_asset<T: Asset>(
+        &mut self,
+        label: &str,
+        asset: LoadedAsset<T>,
+    ) -> Result<LabeledHandle<T>, LabeledAssetError> {
+        if label.is_empty() {
+            return Err(LabeledAssetError::EmptyLabel);
+        }
+        if self.has_labeled_asset(label) {
+            return Err(LabeledAssetError::DuplicateLabel(label.to_string()));
+        }
         self.labeled_assets
             .insert(Some(label.to_string()), asset.into());
-        self.get_handle(AssetPath::new_ref(self.path(), Some(label)))
+        Ok(LabeledHandle {
+            label: label.to_string(),
+            handle: self.get_handle(AssetPath::new_ref(self.path(), Some(label))),
+        })
+    }
+
+    /// Returns the labels of the secondary assets set so far.
+    pub fn labels(&self) -> impl Iterator<Item = &str> {
+        self.labeled_assets
+            .keys()
+            .filter_map(|label| label.as_deref())
     }
 
     /// Gets a strong handle to an asset of type `T` from its id.
@@ -204,6 +245,43 @@ impl<'a> LoadContext<'a> {
     }
 }
 
+/// A strong handle to a secondary asset of a [`LoadContext`], along with its label.
+#[derive(Debug)]
+pub struct LabeledHandle<T: Asset> {
+    label: String,
+    handle: Handle<T>,
+}
+
+impl<T: Asset> LabeledHandle<T> {
+    /// Gets the label of the asset.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Gets the handle of the asset.
+    pub fn handle(&self) -> &Handle<T> {
+        &self.handle
+    }
+}
+
+impl<T: Asset> From<LabeledHandle<T>> for Handle<T> {
+    fn from(labeled: LabeledHandle<T>) -> Self {
+        labeled.handle
+    }
+}
+
+/// An error that occurs when setting a secondary asset with
+/// [`LoadContext::try_set_labeled_asset`].
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum LabeledAssetError {
+    /// The label is empty, which is the label of the primary asset.
+    #[error("labeled assets can't have an empty label")]
+    EmptyLabel,
+    /// Another asset of the source already has this label.
+    #[error("an asset labeled {0:?} was already set")]
+    DuplicateLabel(String),
+}
+
 /// The result of loading an asset of type `T`.
 #[derive(Debug)]
 pub struct AssetResult<T> {
@@ -228,6 +306,8 @@ pub struct AssetLifecycleChannel<T> {
 pub enum AssetLifecycleEvent<T> {
     /// An asset was created.
     Create(AssetResult<T>),
+    /// An asset has no strong handles left and will be freed unless one is made again.
+    Unused(HandleId),
     /// An asset was freed.
     Free(HandleId),
 }
@@ -236,8 +316,12 @@ pub enum AssetLifecycleEvent<T> {
 pub trait AssetLifecycle: Downcast + Send + Sync + 'static {
     /// Notifies the asset server that a new asset was created.
     fn create_asset(&self, id: HandleId, asset: Box<dyn AssetDynamic>, version: usize);
+    /// Notifies the asset server that an asset has no strong handles left.
+    fn unused_asset(&self, id: HandleId);
     /// Notifies the asset server that an asset was freed.
     fn free_asset(&self, id: HandleId);
+    /// The name of the type of the assets.
+    fn type_name(&self) -> &'static str;
 }
 impl_downcast!(AssetLifecycle);
 
@@ -259,9 +343,17 @@ impl<T: AssetDynamic> AssetLifecycle for AssetLifecycleChannel<T> {
         }
     }
 
+    fn unused_asset(&self, id: HandleId) {
+        self.sender.send(AssetLifecycleEvent::Unused(id)).unwrap();
+    }
+
     fn free_asset(&self, id: HandleId) {
         self.sender.send(AssetLifecycleEvent::Free(id)).unwrap();
     }
+
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<T>()
+    }
 }
 
 impl<T> Default for AssetLifecycleChannel<T> {
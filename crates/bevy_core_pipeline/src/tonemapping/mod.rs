@@ -8,7 +8,9 @@ use bevy_render::extract_component::{ExtractComponent, ExtractComponentPlugin};
 use bevy_render::extract_resource::{ExtractResource, ExtractResourcePlugin};
 use bevy_render::render_asset::RenderAssets;
 use bevy_render::renderer::RenderDevice;
-use bevy_render::texture::{CompressedImageFormats, Image, ImageSampler, ImageType};
+use bevy_render::texture::{
+    CompressedImageFormats, Image, ImageMemoryTag, ImageSampler, ImageType,
+};
 use bevy_render::view::{ViewTarget, ViewUniform};
 use bevy_render::{render_resource::*, Render, RenderApp, RenderSet};
 
@@ -413,5 +415,6 @@ pub fn lut_placeholder() -> Image {
         },
         sampler_descriptor: ImageSampler::Default,
         texture_view_descriptor: None,
+        memory_tag: ImageMemoryTag::Other,
     }
 }
@@ -700,9 +700,14 @@ impl Tick {
 
     /// Wraps this change tick's value if it exceeds [`Tick::MAX`].
     ///
+    /// Storages keeping their own ticks should call this on them at least every
+    /// [`CHECK_TICK_THRESHOLD`](crate::change_detection::CHECK_TICK_THRESHOLD) ticks, like
+    /// [`World::check_change_ticks`](crate::world::World::check_change_ticks) does for
+    /// components.
+    ///
     /// Returns `true` if wrapping was performed. Otherwise, returns `false`.
     #[inline]
-    pub(crate) fn check_tick(&mut self, tick: Tick) -> bool {
+    pub fn check_tick(&mut self, tick: Tick) -> bool {
         let age = tick.relative_to(*self);
         // This comparison assumes that `age` has not overflowed `u32::MAX` before, which will be true
         // so long as this check always runs before that can happen.
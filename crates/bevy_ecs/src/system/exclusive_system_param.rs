@@ -9,6 +9,30 @@ use bevy_utils::synccell::SyncCell;
 
 /// A parameter that can be used in an exclusive system (a system with an `&mut World` parameter).
 /// Any parameters implementing this trait must come after the `&mut World` parameter.
+///
+/// Regular [`SystemParam`]s such as [`Query`](crate::system::Query) can't be mixed with an
+/// `&mut World` in the same signature, since the world reference could be used to alias the
+/// data they borrow. Instead, cache them in a [`SystemState`] and fetch them from the world
+/// whenever they're needed:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::system::SystemState;
+/// # #[derive(Component)]
+/// # struct Health(u32);
+/// fn despawn_dead(world: &mut World, query: &mut SystemState<Query<(Entity, &Health)>>) {
+///     let dead: Vec<Entity> = query
+///         .get(world)
+///         .iter()
+///         .filter(|(_, health)| health.0 == 0)
+///         .map(|(entity, _)| entity)
+///         .collect();
+///     for entity in dead {
+///         world.despawn(entity);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(despawn_dead);
+/// ```
 pub trait ExclusiveSystemParam: Sized {
     /// Used to store data which persists across invocations of a system.
     type State: Send + Sync + 'static;
@@ -1,8 +1,9 @@
 use crate::components::{Children, Parent};
 use bevy_ecs::{
+    component::Component,
     entity::Entity,
-    system::{Command, EntityCommands},
-    world::{EntityMut, World},
+    system::{Command, EntityCommands, Resource},
+    world::{EntityMut, Mut, World},
 };
 use bevy_utils::tracing::debug;
 
@@ -20,6 +21,74 @@ pub struct DespawnChildrenRecursive {
     pub entity: Entity,
 }
 
+/// Functions that run for entities with a given component right before they are despawned by
+/// [`despawn_with_children_recursive`], [`DespawnRecursive`] or [`DespawnChildrenRecursive`].
+///
+/// The hooks run synchronously, in the same command application as the despawn, so they can
+/// remove despawned entities from resources indexed by [`Entity`] before any system sees them,
+/// unlike [`RemovedComponents`](bevy_ecs::removal_detection::RemovedComponents) which is only
+/// read the next time a system runs. An entity's descendants are despawned before it.
+///
+/// Entities despawned with [`World::despawn`] or
+/// [`EntityCommands::despawn`](bevy_ecs::system::EntityCommands::despawn) don't run the hooks.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::{DespawnHooks, DespawnRecursiveExt};
+/// # use bevy_utils::HashMap;
+/// #[derive(Component)]
+/// struct Widget;
+///
+/// #[derive(Resource, Default)]
+/// struct WidgetRegistry(HashMap<Entity, String>);
+///
+/// let mut world = World::new();
+/// world.init_resource::<WidgetRegistry>();
+/// world.init_resource::<DespawnHooks>();
+/// world.resource_mut::<DespawnHooks>().add::<Widget>(|world, entity| {
+///     world.resource_mut::<WidgetRegistry>().0.remove(&entity);
+/// });
+///
+/// let widget = world.spawn(Widget).id();
+/// world.resource_mut::<WidgetRegistry>().0.insert(widget, "ok button".to_string());
+/// world.entity_mut(widget).despawn_recursive();
+/// assert!(world.resource::<WidgetRegistry>().0.is_empty());
+/// ```
+#[derive(Resource, Default)]
+pub struct DespawnHooks {
+    hooks: Vec<DespawnHook>,
+}
+
+struct DespawnHook {
+    has_component: fn(&World, Entity) -> bool,
+    hook: fn(&mut World, Entity),
+}
+
+impl DespawnHooks {
+    /// Adds a `hook` that runs right before entities with the component `C` are despawned.
+    pub fn add<C: Component>(&mut self, hook: fn(&mut World, Entity)) {
+        self.hooks.push(DespawnHook {
+            has_component: |world, entity| world.get::<C>(entity).is_some(),
+            hook,
+        });
+    }
+
+    fn run(world: &mut World, entity: Entity) {
+        if !world.contains_resource::<DespawnHooks>() {
+            return;
+        }
+        world.resource_scope(|world, despawn_hooks: Mut<DespawnHooks>| {
+            for despawn_hook in &despawn_hooks.hooks {
+                if (despawn_hook.has_component)(world, entity) {
+                    (despawn_hook.hook)(world, entity);
+                }
+            }
+        });
+    }
+}
+
 /// Function for despawning an entity and all its children
 pub fn despawn_with_children_recursive(world: &mut World, entity: Entity) {
     // first, make the entity's own parent forget about it
@@ -41,6 +110,7 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
         }
     }
 
+    DespawnHooks::run(world, entity);
     if !world.despawn(entity) {
         debug!("Failed to despawn entity {:?}", entity);
     }
@@ -23,12 +23,15 @@ pub use valid_parent_check_plugin::*;
 mod query_extension;
 pub use query_extension::*;
 
+mod state_scoped;
+pub use state_scoped::*;
+
 #[doc(hidden)]
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        child_builder::*, components::*, hierarchy::*, query_extension::*, HierarchyPlugin,
-        ValidParentCheckPlugin,
+        child_builder::*, components::*, hierarchy::*, query_extension::*, state_scoped::*,
+        HierarchyPlugin, ValidParentCheckPlugin,
     };
 }
 
@@ -43,6 +46,7 @@ impl Plugin for HierarchyPlugin {
         app.register_type::<Children>()
             .register_type::<Parent>()
             .register_type::<smallvec::SmallVec<[bevy_ecs::entity::Entity; 8]>>()
+            .init_resource::<DespawnHooks>()
             .add_event::<HierarchyEvent>();
     }
 }
@@ -34,6 +34,47 @@ pub trait HierarchyQueryExt<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> {
     where
         Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>;
 
+    /// Returns an [`Iterator`] of [`Entity`]s over all of `entity`s descendants, in child order.
+    ///
+    /// Can only be called on a [`Query`] of [`Children`] (i.e. `Query<&Children>`).
+    ///
+    /// Traverses the hierarchy depth-first: every child is followed by its own descendants
+    /// before its next sibling, in the order of [`Children`]. This is also the order `bevy_ui`
+    /// lays out the children of a node in.
+    ///
+    /// To mutate the descendants, pass the iterator to [`Query::iter_many_mut`] of another query.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # use bevy_hierarchy::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Marker;
+    /// # #[derive(Component)]
+    /// # struct Position(usize);
+    /// fn system(
+    ///     query: Query<Entity, With<Marker>>,
+    ///     children_query: Query<&Children>,
+    ///     mut positions: Query<&mut Position>,
+    /// ) {
+    ///     let entity = query.single();
+    ///     let mut descendants =
+    ///         positions.iter_many_mut(children_query.iter_descendants_depth_first(entity));
+    ///     let mut index = 0;
+    ///     while let Some(mut position) = descendants.fetch_next() {
+    ///         position.0 = index;
+    ///         index += 1;
+    ///     }
+    /// }
+    /// # bevy_ecs::system::assert_is_system(system);
+    /// ```
+    fn iter_descendants_depth_first(
+        &'w self,
+        entity: Entity,
+    ) -> DescendantDepthFirstIter<'w, 's, Q, F>
+    where
+        Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>;
+
     /// Returns an [`Iterator`] of [`Entity`]s over all of `entity`s ancestors.
     ///
     /// Can only be called on a [`Query`] of [`Parent`] (i.e. `Query<&Parent>`).
@@ -67,6 +108,16 @@ impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> HierarchyQueryExt<'w, 's, Q,
         DescendantIter::new(self, entity)
     }
 
+    fn iter_descendants_depth_first(
+        &'w self,
+        entity: Entity,
+    ) -> DescendantDepthFirstIter<'w, 's, Q, F>
+    where
+        Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
+    {
+        DescendantDepthFirstIter::new(self, entity)
+    }
+
     fn iter_ancestors(&'w self, entity: Entity) -> AncestorIter<'w, 's, Q, F>
     where
         Q::ReadOnly: WorldQuery<Item<'w> = &'w Parent>,
@@ -121,6 +172,54 @@ where
     }
 }
 
+/// An [`Iterator`] of [`Entity`]s over the descendants of an [`Entity`], in child order.
+///
+/// Traverses the hierarchy depth-first.
+pub struct DescendantDepthFirstIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery>
+where
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
+{
+    children_query: &'w Query<'w, 's, Q, F>,
+    // The entities left to visit, the next one last
+    stack: Vec<Entity>,
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> DescendantDepthFirstIter<'w, 's, Q, F>
+where
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
+{
+    /// Returns a new [`DescendantDepthFirstIter`].
+    pub fn new(children_query: &'w Query<'w, 's, Q, F>, entity: Entity) -> Self {
+        DescendantDepthFirstIter {
+            children_query,
+            stack: children_query
+                .get(entity)
+                .into_iter()
+                .flat_map(|children| children.iter().rev())
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+impl<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery> Iterator
+    for DescendantDepthFirstIter<'w, 's, Q, F>
+where
+    Q::ReadOnly: WorldQuery<Item<'w> = &'w Children>,
+{
+    type Item = Entity;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entity = self.stack.pop()?;
+
+        if let Ok(children) = self.children_query.get(entity) {
+            self.stack.extend(children.iter().rev());
+        }
+
+        Some(entity)
+    }
+}
+
 /// An [`Iterator`] of [`Entity`]s over the ancestors of an [`Entity`].
 pub struct AncestorIter<'w, 's, Q: WorldQuery, F: ReadOnlyWorldQuery>
 where
@@ -187,6 +286,30 @@ mod tests {
         assert_eq!([&A(1), &A(2), &A(3)], result.as_slice());
     }
 
+    #[test]
+    fn descendant_depth_first_iter() {
+        let world = &mut World::new();
+
+        let [a, b, c, d, e] = std::array::from_fn(|i| world.spawn(A(i)).id());
+
+        world.entity_mut(a).push_children(&[b, c]);
+        world.entity_mut(b).push_children(&[d]);
+        world.entity_mut(c).push_children(&[e]);
+
+        let mut system_state = SystemState::<(Query<&Children>, Query<&mut A>)>::new(world);
+        let (children_query, mut a_query) = system_state.get_mut(world);
+
+        let mut result = Vec::new();
+        let mut descendants = a_query.iter_many_mut(children_query.iter_descendants_depth_first(a));
+        while let Some(mut descendant) = descendants.fetch_next() {
+            result.push(descendant.0);
+            descendant.0 += 10;
+        }
+
+        assert_eq!([1, 3, 2, 4], result.as_slice());
+        assert_eq!(world.get::<A>(d), Some(&A(13)));
+    }
+
     #[test]
     fn ancestor_iter() {
         let world = &mut World::new();
@@ -0,0 +1,168 @@
+use crate::DespawnRecursiveExt;
+use bevy_app::{App, StateTransition};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    schedule::{
+        apply_state_transition, common_conditions::state_changed, IntoSystemConfigs, State, States,
+    },
+    system::{Commands, EntityCommands, Query, Res},
+};
+
+/// Despawns the entity and its descendants when the app leaves the state `S`.
+///
+/// The state type must be set up with [`StateScopedAppExt::add_state_scoped`]. Entities are
+/// despawned recursively in [`StateTransition`], after the [`OnExit`](bevy_ecs::schedule::OnExit)
+/// and [`OnEnter`](bevy_ecs::schedule::OnEnter) schedules of the transition have run, so
+/// entities spawned in their systems, or entities the component was added to after they were
+/// spawned, are despawned too if their state isn't the new one.
+///
+/// The entities of different state types are independent: an entity with both a
+/// `StateScoped<Menu>` and a `StateScoped<Settings>` is despawned when either state is left.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_hierarchy::{StateScoped, StateScopedAppExt, StateScopedCommandsExt};
+/// #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+/// enum GameState {
+///     #[default]
+///     MainMenu,
+///     InGame,
+/// }
+///
+/// fn spawn_menu(mut commands: Commands) {
+///     // Despawned when the state changes to `GameState::InGame`
+///     commands.spawn_state_scoped(GameState::MainMenu, ());
+///     // Same as
+///     commands.spawn(StateScoped(GameState::MainMenu));
+/// }
+///
+/// App::new()
+///     .add_state::<GameState>()
+///     .add_state_scoped::<GameState>()
+///     .add_systems(OnEnter(GameState::MainMenu), spawn_menu);
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct StateScoped<S: States>(pub S);
+
+/// Despawns the entities with a [`StateScoped<S>`] of another state than the current one,
+/// recursively.
+pub fn despawn_state_scoped_entities<S: States>(
+    mut commands: Commands,
+    state: Res<State<S>>,
+    query: Query<(Entity, &StateScoped<S>)>,
+) {
+    for (entity, scope) in &query {
+        if scope.0 != *state.get() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Extension trait for [`App`] to despawn [`StateScoped`] entities.
+pub trait StateScopedAppExt {
+    /// Despawns the entities with a [`StateScoped<S>`] when their state is left, with
+    /// [`despawn_state_scoped_entities`].
+    ///
+    /// The state must be added with [`App::add_state`].
+    fn add_state_scoped<S: States>(&mut self) -> &mut Self;
+}
+
+impl StateScopedAppExt for App {
+    fn add_state_scoped<S: States>(&mut self) -> &mut Self {
+        self.add_systems(
+            StateTransition,
+            despawn_state_scoped_entities::<S>
+                .after(apply_state_transition::<S>)
+                .run_if(state_changed::<S>()),
+        )
+    }
+}
+
+/// Extension trait for [`Commands`] to spawn [`StateScoped`] entities.
+pub trait StateScopedCommandsExt<'w, 's> {
+    /// Spawns an entity with the given bundle and a [`StateScoped`] of `state`, so that it is
+    /// despawned when the app leaves `state`.
+    fn spawn_state_scoped<'a, S: States>(
+        &'a mut self,
+        state: S,
+        bundle: impl Bundle,
+    ) -> EntityCommands<'w, 's, 'a>;
+}
+
+impl<'w, 's> StateScopedCommandsExt<'w, 's> for Commands<'w, 's> {
+    fn spawn_state_scoped<'a, S: States>(
+        &'a mut self,
+        state: S,
+        bundle: impl Bundle,
+    ) -> EntityCommands<'w, 's, 'a> {
+        self.spawn((bundle, StateScoped(state)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::App;
+    use bevy_ecs::prelude::*;
+
+    use crate::{BuildWorldChildren, StateScoped, StateScopedAppExt, StateScopedCommandsExt};
+
+    #[derive(States, Default, Clone, PartialEq, Eq, Hash, Debug)]
+    enum AppState {
+        #[default]
+        Menu,
+        Game,
+    }
+
+    #[derive(Resource, Default)]
+    struct Spawned(Vec<Entity>);
+
+    #[test]
+    fn despawns_entities_when_their_state_is_left() {
+        let mut app = App::new();
+        app.add_state::<AppState>()
+            .add_state_scoped::<AppState>()
+            .init_resource::<Spawned>()
+            .add_systems(
+                OnExit(AppState::Menu),
+                |mut commands: Commands, mut spawned: ResMut<Spawned>| {
+                    // Spawned during the exit transition itself
+                    let entity = commands.spawn_state_scoped(AppState::Menu, ()).id();
+                    spawned.0.push(entity);
+                },
+            );
+        app.update();
+
+        let menu = app.world.spawn(StateScoped(AppState::Menu)).id();
+        let child = app.world.spawn_empty().id();
+        app.world.entity_mut(menu).add_child(child);
+        let game = app.world.spawn(StateScoped(AppState::Game)).id();
+        // The component is added after the entity is spawned
+        let late = app.world.spawn_empty().id();
+        app.world
+            .entity_mut(late)
+            .insert(StateScoped(AppState::Menu));
+        let unscoped = app.world.spawn_empty().id();
+
+        app.update();
+        assert!(app.world.get_entity(menu).is_some());
+
+        app.world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::Game);
+        app.update();
+        assert!(app.world.get_entity(menu).is_none());
+        assert!(app.world.get_entity(child).is_none());
+        assert!(app.world.get_entity(late).is_none());
+        assert!(app
+            .world
+            .get_entity(app.world.resource::<Spawned>().0[0])
+            .is_none());
+        assert!(app.world.get_entity(game).is_some());
+        assert!(app.world.get_entity(unscoped).is_some());
+    }
+}
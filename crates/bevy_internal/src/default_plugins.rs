@@ -20,6 +20,7 @@ use bevy_app::{PluginGroup, PluginGroupBuilder};
 /// * [`PipelinedRenderingPlugin`](crate::render::pipelined_rendering::PipelinedRenderingPlugin) - with feature `bevy_render` when not targeting `wasm32`
 /// * [`CorePipelinePlugin`](crate::core_pipeline::CorePipelinePlugin) - with feature `bevy_core_pipeline`
 /// * [`SpritePlugin`](crate::sprite::SpritePlugin) - with feature `bevy_sprite`
+/// * [`TileMapPlugin`](crate::tilemap::TileMapPlugin) - with feature `bevy_tilemap`
 /// * [`TextPlugin`](crate::text::TextPlugin) - with feature `bevy_text`
 /// * [`UiPlugin`](crate::ui::UiPlugin) - with feature `bevy_ui`
 /// * [`PbrPlugin`](crate::pbr::PbrPlugin) - with feature `bevy_pbr`
@@ -98,6 +99,11 @@ impl PluginGroup for DefaultPlugins {
             group = group.add(bevy_sprite::SpritePlugin::default());
         }
 
+        #[cfg(feature = "bevy_tilemap")]
+        {
+            group = group.add(bevy_tilemap::TileMapPlugin);
+        }
+
         #[cfg(feature = "bevy_text")]
         {
             group = group.add(bevy_text::TextPlugin::default());
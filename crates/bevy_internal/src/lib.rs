@@ -155,6 +155,12 @@ pub mod sprite {
     pub use bevy_sprite::*;
 }
 
+#[cfg(feature = "bevy_tilemap")]
+pub mod tilemap {
+    //! Tile maps and tilesets.
+    pub use bevy_tilemap::*;
+}
+
 #[cfg(feature = "bevy_text")]
 pub mod text {
     //! Text drawing, styling, and font assets.
@@ -39,6 +39,10 @@ pub use crate::scene::prelude::*;
 #[cfg(feature = "bevy_sprite")]
 pub use crate::sprite::prelude::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_tilemap")]
+pub use crate::tilemap::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_text")]
 pub use crate::text::prelude::*;
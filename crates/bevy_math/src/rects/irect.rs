@@ -321,6 +321,71 @@ impl IRect {
         r
     }
 
+    /// Create a new rectangle grown by `amount.x` on its left and right sides, and by `amount.y`
+    /// on its bottom and top sides.
+    ///
+    /// Unlike [`IRect::inset()`], the amount can differ between the axes. A negative amount
+    /// shrinks the rectangle like [`IRect::deflate()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{IRect, IVec2};
+    /// let r = IRect::new(0, 0, 5, 1); // w=5 h=1
+    /// let r2 = r.inflate(IVec2::new(1, 2)); // w=7 h=5
+    /// assert_eq!(r2.min, IVec2::new(-1, -2));
+    /// assert_eq!(r2.max, IVec2::new(6, 3));
+    /// ```
+    #[inline]
+    pub fn inflate(&self, amount: IVec2) -> Self {
+        let mut r = Self {
+            min: self.min.saturating_sub(amount),
+            max: self.max.saturating_add(amount),
+        };
+        // Collapse min over max to enforce invariants and ensure e.g. width() or
+        // height() never return a negative value.
+        r.min = r.min.min(r.max);
+        r
+    }
+
+    /// Create a new rectangle shrunk by `amount.x` on its left and right sides, and by
+    /// `amount.y` on its bottom and top sides.
+    ///
+    /// This is the inverse of [`IRect::inflate()`]. If the amount is larger than the rectangle
+    /// half-size on an axis, the created rectangle is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{IRect, IVec2};
+    /// let r = IRect::new(0, 0, 6, 4); // w=6 h=4
+    /// let r2 = r.deflate(IVec2::new(1, 2)); // w=4 h=0
+    /// assert_eq!(r2.min, IVec2::new(1, 2));
+    /// assert_eq!(r2.max, IVec2::new(5, 2));
+    /// assert!(r2.is_empty());
+    /// ```
+    #[inline]
+    pub fn deflate(&self, amount: IVec2) -> Self {
+        self.inflate(-amount)
+    }
+
+    /// Check if a rectangle is entirely within this rectangle, edges included.
+    ///
+    /// An empty rectangle is contained when its corners are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::IRect;
+    /// let r = IRect::new(0, 0, 5, 1); // w=5 h=1
+    /// assert!(r.contains_rect(IRect::new(1, 0, 5, 1)));
+    /// assert!(!r.contains_rect(IRect::new(-1, 0, 5, 1)));
+    /// ```
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+    }
+
     /// Returns self as [`Rect`] (f32)
     #[inline]
     pub fn as_rect(&self) -> Rect {
@@ -455,4 +520,52 @@ mod tests {
         assert_eq!(r2.min, IVec2::new(-4, -4));
         assert_eq!(r2.max, IVec2::new(4, 4));
     }
+
+    fn samples() -> Vec<IRect> {
+        let mut rects = vec![
+            IRect::default(),
+            IRect::new(-1, -1, 1, 1),
+            IRect::new(2, 3, 2, 8),
+            IRect::new(-10, 4, 1, 4),
+        ];
+        for x in [-3, 0, 5] {
+            for y in [-1, 2, 4] {
+                rects.push(IRect::from_center_size(IVec2::new(x, y), IVec2::new(4, 2)));
+            }
+        }
+        rects
+    }
+
+    #[test]
+    fn rect_union_contains_both() {
+        for a in samples() {
+            for b in samples() {
+                let u = a.union(b);
+                assert!(u.contains_rect(a) && u.contains_rect(b), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rect_intersect_inside_both() {
+        for a in samples() {
+            for b in samples() {
+                let i = a.intersect(b);
+                if !i.is_empty() {
+                    assert!(a.contains_rect(i) && b.contains_rect(i), "{a:?} {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rect_inflate_deflate_round_trip() {
+        let amount = IVec2::new(1, 2);
+        for r in samples() {
+            assert_eq!(r.inflate(amount).deflate(amount), r);
+            assert!(r.inflate(amount).contains_rect(r));
+        }
+        // Deflating past the half-size collapses the rectangle
+        assert!(IRect::new(0, 0, 2, 2).deflate(IVec2::splat(2)).is_empty());
+    }
 }
@@ -309,17 +309,287 @@ impl Rect {
         r
     }
 
-    /// Returns self as [`IRect`] (i32)
+    /// Create a new rectangle grown by `amount.x` on its left and right sides, and by `amount.y`
+    /// on its bottom and top sides.
+    ///
+    /// Unlike [`Rect::inset()`], the amount can differ between the axes. A negative amount
+    /// shrinks the rectangle like [`Rect::deflate()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 5., 1.); // w=5 h=1
+    /// let r2 = r.inflate(Vec2::new(1., 2.)); // w=7 h=5
+    /// assert!(r2.min.abs_diff_eq(Vec2::new(-1., -2.), 1e-5));
+    /// assert!(r2.max.abs_diff_eq(Vec2::new(6., 3.), 1e-5));
+    /// ```
     #[inline]
-    pub fn as_urect(&self) -> IRect {
+    pub fn inflate(&self, amount: Vec2) -> Self {
+        let mut r = Self {
+            min: self.min - amount,
+            max: self.max + amount,
+        };
+        // Collapse min over max to enforce invariants and ensure e.g. width() or
+        // height() never return a negative value.
+        r.min = r.min.min(r.max);
+        r
+    }
+
+    /// Create a new rectangle shrunk by `amount.x` on its left and right sides, and by
+    /// `amount.y` on its bottom and top sides.
+    ///
+    /// This is the inverse of [`Rect::inflate()`]. If the amount is larger than the rectangle
+    /// half-size on an axis, the created rectangle is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Rect, Vec2};
+    /// let r = Rect::new(0., 0., 6., 4.); // w=6 h=4
+    /// let r2 = r.deflate(Vec2::new(1., 2.)); // w=4 h=0
+    /// assert!(r2.min.abs_diff_eq(Vec2::new(1., 2.), 1e-5));
+    /// assert!(r2.max.abs_diff_eq(Vec2::new(5., 2.), 1e-5));
+    /// assert!(r2.is_empty());
+    /// ```
+    #[inline]
+    pub fn deflate(&self, amount: Vec2) -> Self {
+        self.inflate(-amount)
+    }
+
+    /// Check if a rectangle is entirely within this rectangle, edges included.
+    ///
+    /// An empty rectangle is contained when its corners are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 5., 1.); // w=5 h=1
+    /// assert!(r.contains_rect(Rect::new(1., 0., 5., 1.)));
+    /// assert!(!r.contains_rect(Rect::new(1., 0., 6., 1.)));
+    /// ```
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+    }
+
+    /// Splits the rectangle into columns, from left to right, with widths proportional to
+    /// `weights`.
+    ///
+    /// Negative and NaN weights count as zero. If no weight is positive, every column is empty
+    /// and placed on the left edge of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 8., 2.); // w=8 h=2
+    /// let columns: Vec<_> = r.split_cols(&[1., 3.]).collect();
+    /// assert_eq!(columns, [Rect::new(0., 0., 2., 2.), Rect::new(2., 0., 8., 2.)]);
+    /// ```
+    pub fn split_cols<'a>(&self, weights: &'a [f32]) -> impl Iterator<Item = Rect> + 'a {
+        let rect = *self;
+        split_weights(self.min.x, self.max.x, weights).map(move |(min_x, max_x)| Rect {
+            min: Vec2::new(min_x, rect.min.y),
+            max: Vec2::new(max_x, rect.max.y),
+        })
+    }
+
+    /// Splits the rectangle into rows, from [`Rect::min`] to [`Rect::max`] on the y axis, with
+    /// heights proportional to `weights`.
+    ///
+    /// Negative and NaN weights count as zero. If no weight is positive, every row is empty and
+    /// placed on the `min.y` edge of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 2., 8.); // w=2 h=8
+    /// let rows: Vec<_> = r.split_rows(&[1., 1., 2.]).collect();
+    /// assert_eq!(
+    ///     rows,
+    ///     [Rect::new(0., 0., 2., 2.), Rect::new(0., 2., 2., 4.), Rect::new(0., 4., 2., 8.)]
+    /// );
+    /// ```
+    pub fn split_rows<'a>(&self, weights: &'a [f32]) -> impl Iterator<Item = Rect> + 'a {
+        let rect = *self;
+        split_weights(self.min.y, self.max.y, weights).map(move |(min_y, max_y)| Rect {
+            min: Vec2::new(rect.min.x, min_y),
+            max: Vec2::new(rect.max.x, max_y),
+        })
+    }
+
+    /// Cuts a column of width `amount` off the left side of the rectangle, returning it and the
+    /// rest of the rectangle.
+    ///
+    /// The amount is clamped between zero and the width of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 8., 2.); // w=8 h=2
+    /// let (left, rest) = r.cut_left(3.);
+    /// assert_eq!(left, Rect::new(0., 0., 3., 2.));
+    /// assert_eq!(rest, Rect::new(3., 0., 8., 2.));
+    /// ```
+    pub fn cut_left(&self, amount: f32) -> (Rect, Rect) {
+        let x = self.min.x + amount.clamp(0., self.width());
+        (
+            Rect::from_corners(self.min, Vec2::new(x, self.max.y)),
+            Rect::from_corners(Vec2::new(x, self.min.y), self.max),
+        )
+    }
+
+    /// Cuts a column of width `amount` off the right side of the rectangle, returning it and the
+    /// rest of the rectangle.
+    ///
+    /// The amount is clamped between zero and the width of the rectangle.
+    pub fn cut_right(&self, amount: f32) -> (Rect, Rect) {
+        let x = self.max.x - amount.clamp(0., self.width());
+        (
+            Rect::from_corners(Vec2::new(x, self.min.y), self.max),
+            Rect::from_corners(self.min, Vec2::new(x, self.max.y)),
+        )
+    }
+
+    /// Cuts a row of height `amount` off the top side of the rectangle, returning it and the
+    /// rest of the rectangle.
+    ///
+    /// The top side is the `min.y` side, like in UI and image coordinates where the y axis
+    /// points down. The amount is clamped between zero and the height of the rectangle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::Rect;
+    /// let r = Rect::new(0., 0., 8., 2.); // w=8 h=2
+    /// let (top, rest) = r.cut_top(0.5);
+    /// assert_eq!(top, Rect::new(0., 0., 8., 0.5));
+    /// assert_eq!(rest, Rect::new(0., 0.5, 8., 2.));
+    /// ```
+    pub fn cut_top(&self, amount: f32) -> (Rect, Rect) {
+        let y = self.min.y + amount.clamp(0., self.height());
+        (
+            Rect::from_corners(self.min, Vec2::new(self.max.x, y)),
+            Rect::from_corners(Vec2::new(self.min.x, y), self.max),
+        )
+    }
+
+    /// Cuts a row of height `amount` off the bottom side of the rectangle, returning it and the
+    /// rest of the rectangle.
+    ///
+    /// The bottom side is the `max.y` side, see [`Rect::cut_top()`]. The amount is clamped
+    /// between zero and the height of the rectangle.
+    pub fn cut_bottom(&self, amount: f32) -> (Rect, Rect) {
+        let y = self.max.y - amount.clamp(0., self.height());
+        (
+            Rect::from_corners(Vec2::new(self.min.x, y), self.max),
+            Rect::from_corners(self.min, Vec2::new(self.max.x, y)),
+        )
+    }
+
+    /// Returns self as [`IRect`] (i32), truncating the coordinates toward zero.
+    ///
+    /// Coordinates out of the `i32` range saturate, and NaN coordinates become zero.
+    #[inline]
+    pub fn as_irect(&self) -> IRect {
         IRect::from_corners(self.min.as_ivec2(), self.max.as_ivec2())
     }
 
-    /// Returns self as [`URect`] (u32)
+    /// Returns self as [`URect`] (u32), truncating the coordinates toward zero.
+    ///
+    /// Coordinates out of the `u32` range saturate, so negative coordinates become zero, like
+    /// NaN coordinates. See [`Rect::as_urect_round()`], [`Rect::as_urect_floor()`] and
+    /// [`Rect::as_urect_ceil()`] for the other roundings.
     #[inline]
-    pub fn as_rect(&self) -> URect {
+    pub fn as_urect(&self) -> URect {
         URect::from_corners(self.min.as_uvec2(), self.max.as_uvec2())
     }
+
+    /// Returns self as [`URect`] (u32), truncating the coordinates toward zero.
+    #[deprecated(since = "0.11.0", note = "renamed to `Rect::as_urect`")]
+    #[inline]
+    pub fn as_rect(&self) -> URect {
+        self.as_urect()
+    }
+
+    /// Returns self as [`URect`] (u32), rounding the coordinates to the nearest integer, half-way
+    /// cases away from zero.
+    ///
+    /// Out of range coordinates saturate like in [`Rect::as_urect()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Rect, URect};
+    /// let r = Rect::new(0.4, 0.5, 2.5, 3.6);
+    /// assert_eq!(r.as_urect_round(), URect::new(0, 1, 3, 4));
+    /// ```
+    #[inline]
+    pub fn as_urect_round(&self) -> URect {
+        URect::from_corners(self.min.round().as_uvec2(), self.max.round().as_uvec2())
+    }
+
+    /// Returns self as [`URect`] (u32), rounding the coordinates down.
+    ///
+    /// Out of range coordinates saturate like in [`Rect::as_urect()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Rect, URect};
+    /// let r = Rect::new(0.4, 0.5, 2.5, 3.6);
+    /// assert_eq!(r.as_urect_floor(), URect::new(0, 0, 2, 3));
+    /// ```
+    #[inline]
+    pub fn as_urect_floor(&self) -> URect {
+        URect::from_corners(self.min.floor().as_uvec2(), self.max.floor().as_uvec2())
+    }
+
+    /// Returns self as [`URect`] (u32), rounding the coordinates up.
+    ///
+    /// Out of range coordinates saturate like in [`Rect::as_urect()`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{Rect, URect};
+    /// let r = Rect::new(0.4, 0.5, 2.5, 3.6);
+    /// assert_eq!(r.as_urect_ceil(), URect::new(1, 1, 3, 4));
+    /// ```
+    #[inline]
+    pub fn as_urect_ceil(&self) -> URect {
+        URect::from_corners(self.min.ceil().as_uvec2(), self.max.ceil().as_uvec2())
+    }
+}
+
+/// Splits `min..max` into consecutive ranges proportional to `weights`, see [`Rect::split_cols`].
+fn split_weights(min: f32, max: f32, weights: &[f32]) -> impl Iterator<Item = (f32, f32)> + '_ {
+    // `f32::max` ignores NaN, so NaN weights count as zero
+    let weight = |weight: f32| weight.max(0.);
+    let total: f32 = weights.iter().copied().map(weight).sum();
+    let scale = if total > 0. && total.is_finite() {
+        (max - min) / total
+    } else {
+        0.
+    };
+    let mut start = min;
+    let mut accumulated = 0.;
+    weights.iter().enumerate().map(move |(index, w)| {
+        accumulated += weight(*w);
+        // Snap the last range to `max` so rounding errors don't leave a gap
+        let end = if index + 1 == weights.len() && scale > 0. {
+            max
+        } else {
+            min + accumulated * scale
+        };
+        let range = (start, end);
+        start = end;
+        range
+    })
 }
 
 #[cfg(test)]
@@ -443,4 +713,112 @@ mod tests {
         assert!(r2.min.abs_diff_eq(Vec2::new(-0.8, -0.8), 1e-5));
         assert!(r2.max.abs_diff_eq(Vec2::new(0.8, 0.8), 1e-5));
     }
+
+    fn samples() -> Vec<Rect> {
+        let mut rects = vec![
+            Rect::default(),
+            Rect::new(-0.5, -0.5, 0.5, 0.5),
+            Rect::new(2., 3., 2., 8.),
+            Rect::new(-10., 4., 1.5, 4.),
+        ];
+        for x in [-3., 0., 2.5] {
+            for y in [-1., 0.25, 4.] {
+                rects.push(Rect::from_center_size(Vec2::new(x, y), Vec2::new(3., 1.5)));
+            }
+        }
+        rects
+    }
+
+    #[test]
+    fn rect_union_contains_both() {
+        for a in samples() {
+            for b in samples() {
+                let u = a.union(b);
+                assert!(u.contains_rect(a) && u.contains_rect(b), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rect_intersect_inside_both() {
+        for a in samples() {
+            for b in samples() {
+                let i = a.intersect(b);
+                assert!(i.width() >= 0. && i.height() >= 0.);
+                if !i.is_empty() {
+                    assert!(a.contains_rect(i) && b.contains_rect(i), "{a:?} {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rect_inflate_deflate_round_trip() {
+        let amount = Vec2::new(0.5, 2.);
+        for r in samples() {
+            let r2 = r.inflate(amount).deflate(amount);
+            assert!(r2.min.abs_diff_eq(r.min, 1e-5) && r2.max.abs_diff_eq(r.max, 1e-5));
+            assert!(r.inflate(amount).contains_rect(r));
+            assert!(r.contains_rect(r.deflate(amount)) || r.deflate(amount).is_empty());
+        }
+        // Deflating past the half-size collapses the rectangle
+        assert!(Rect::new(0., 0., 1., 1.).deflate(Vec2::ONE).is_empty());
+    }
+
+    #[test]
+    fn rect_as_urect() {
+        let r = Rect::new(-1.5, 0.5, 2.5, 3.5);
+        assert_eq!(r.as_urect(), URect::new(0, 0, 2, 3));
+        assert_eq!(r.as_urect_round(), URect::new(0, 1, 3, 4));
+        assert_eq!(r.as_urect_floor(), URect::new(0, 0, 2, 3));
+        assert_eq!(r.as_urect_ceil(), URect::new(0, 1, 3, 4));
+        assert_eq!(r.as_irect(), IRect::new(-1, 0, 2, 3));
+    }
+
+    #[test]
+    fn rect_split() {
+        let r = Rect::new(-1., 2., 9., 4.); // w=10 h=2
+        let columns: Vec<_> = r.split_cols(&[1., 0., f32::NAN, 4.]).collect();
+        assert_eq!(
+            columns,
+            [
+                Rect::new(-1., 2., 1., 4.),
+                Rect::new(1., 2., 1., 4.),
+                Rect::new(1., 2., 1., 4.),
+                Rect::new(1., 2., 9., 4.),
+            ]
+        );
+        // The last column ends on the edge, without rounding errors
+        let columns: Vec<_> = r.split_cols(&[0.1; 7]).collect();
+        assert_eq!(columns.last().unwrap().max.x, 9.);
+        assert_eq!(r.split_cols(&[]).count(), 0);
+
+        // Without a positive weight, the rows are empty
+        let rows: Vec<_> = r.split_rows(&[0., -1.]).collect();
+        assert_eq!(rows, [Rect::new(-1., 2., 9., 2.); 2]);
+    }
+
+    #[test]
+    fn rect_cut() {
+        let r = Rect::new(0., 0., 4., 2.);
+        assert_eq!(
+            r.cut_left(1.),
+            (Rect::new(0., 0., 1., 2.), Rect::new(1., 0., 4., 2.))
+        );
+        assert_eq!(
+            r.cut_right(1.),
+            (Rect::new(3., 0., 4., 2.), Rect::new(0., 0., 3., 2.))
+        );
+        assert_eq!(
+            r.cut_top(0.5),
+            (Rect::new(0., 0., 4., 0.5), Rect::new(0., 0.5, 4., 2.))
+        );
+        assert_eq!(
+            r.cut_bottom(0.5),
+            (Rect::new(0., 1.5, 4., 2.), Rect::new(0., 0., 4., 1.5))
+        );
+        // Amounts are clamped to the rectangle
+        assert_eq!(r.cut_left(10.), (r, Rect::new(4., 0., 4., 2.)));
+        assert_eq!(r.cut_bottom(-1.), (Rect::new(0., 2., 4., 2.), r));
+    }
 }
@@ -330,11 +330,128 @@ impl URect {
         Rect::from_corners(self.min.as_vec2(), self.max.as_vec2())
     }
 
+    /// Create a new rectangle grown by `amount.x` on its left and right sides, and by `amount.y`
+    /// on its bottom and top sides.
+    ///
+    /// Unlike [`URect::inset()`], the amount can differ between the axes. The coordinates
+    /// saturate, so the minimum corner stops at zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{URect, UVec2};
+    /// let r = URect::new(1, 4, 6, 6); // w=5 h=2
+    /// let r2 = r.inflate(UVec2::new(2, 1)); // w=8 h=4
+    /// assert_eq!(r2.min, UVec2::new(0, 3));
+    /// assert_eq!(r2.max, UVec2::new(8, 7));
+    /// ```
+    #[inline]
+    pub fn inflate(&self, amount: UVec2) -> Self {
+        Self {
+            min: self.min.saturating_sub(amount),
+            max: self.max.saturating_add(amount),
+        }
+    }
+
+    /// Create a new rectangle shrunk by `amount.x` on its left and right sides, and by
+    /// `amount.y` on its bottom and top sides.
+    ///
+    /// This is the inverse of [`URect::inflate()`] when it didn't saturate. If the amount is
+    /// larger than the rectangle half-size on an axis, the created rectangle is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{URect, UVec2};
+    /// let r = URect::new(0, 0, 6, 4); // w=6 h=4
+    /// let r2 = r.deflate(UVec2::new(1, 2)); // w=4 h=0
+    /// assert_eq!(r2.min, UVec2::new(1, 2));
+    /// assert_eq!(r2.max, UVec2::new(5, 2));
+    /// assert!(r2.is_empty());
+    /// ```
+    #[inline]
+    pub fn deflate(&self, amount: UVec2) -> Self {
+        let mut r = Self {
+            min: self.min.saturating_add(amount),
+            max: self.max.saturating_sub(amount),
+        };
+        // Collapse min over max to enforce invariants and ensure e.g. width() or
+        // height() never return a negative value.
+        r.min = r.min.min(r.max);
+        r
+    }
+
+    /// Check if a rectangle is entirely within this rectangle, edges included.
+    ///
+    /// An empty rectangle is contained when its corners are.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::URect;
+    /// let r = URect::new(0, 0, 5, 1); // w=5 h=1
+    /// assert!(r.contains_rect(URect::new(1, 0, 5, 1)));
+    /// assert!(!r.contains_rect(URect::new(1, 0, 6, 1)));
+    /// ```
+    #[inline]
+    pub fn contains_rect(&self, other: Self) -> bool {
+        self.min.cmple(other.min).all() && other.max.cmple(self.max).all()
+    }
+
+    /// Iterates over the cells of a grid laid out in the rectangle, in row-major order.
+    ///
+    /// The first cell starts `offset` away from [`URect::min`], and each cell of size `cell` is
+    /// separated from the next by `padding` on both axes. Only the cells entirely within the
+    /// rectangle are returned, so the iterator is empty if `cell` is zero on an axis or doesn't
+    /// fit after the offset.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use bevy_math::{URect, UVec2};
+    /// let r = URect::new(0, 0, 10, 4); // w=10 h=4
+    /// let tiles: Vec<_> = r.tiles(UVec2::new(4, 4), UVec2::new(1, 0), UVec2::ZERO).collect();
+    /// assert_eq!(tiles, [URect::new(0, 0, 4, 4), URect::new(5, 0, 9, 4)]);
+    /// ```
+    pub fn tiles(
+        &self,
+        cell: UVec2,
+        padding: UVec2,
+        offset: UVec2,
+    ) -> impl Iterator<Item = URect> + Clone {
+        let available = self.size().saturating_sub(offset);
+        let step = cell.saturating_add(padding);
+        // The first cell doesn't need padding before it
+        let count = |available: u32, cell: u32, step: u32| match cell {
+            0 => 0,
+            _ if available < cell => 0,
+            _ => (available - cell) / step + 1,
+        };
+        let columns = count(available.x, cell.x, step.x);
+        let rows = count(available.y, cell.y, step.y);
+        let start = self.min.saturating_add(offset);
+        (0..rows).flat_map(move |y| {
+            (0..columns).map(move |x| {
+                let min = start + step * UVec2::new(x, y);
+                URect::from_corners(min, min + cell)
+            })
+        })
+    }
+
     /// Returns self as [`IRect`] (i32)
+    ///
+    /// Coordinates above `i32::MAX` wrap around.
     #[inline]
-    pub fn as_urect(&self) -> IRect {
+    pub fn as_irect(&self) -> IRect {
         IRect::from_corners(self.min.as_ivec2(), self.max.as_ivec2())
     }
+
+    /// Returns self as [`IRect`] (i32)
+    #[deprecated(since = "0.11.0", note = "renamed to `URect::as_irect`")]
+    #[inline]
+    pub fn as_urect(&self) -> IRect {
+        self.as_irect()
+    }
 }
 
 #[cfg(test)]
@@ -458,4 +575,115 @@ mod tests {
         assert_eq!(r2.min, UVec2::new(1, 1));
         assert_eq!(r2.max, UVec2::new(11, 11));
     }
+
+    fn samples() -> Vec<URect> {
+        let mut rects = vec![
+            URect::default(),
+            URect::new(0, 0, 1, 1),
+            URect::new(2, 3, 2, 8),
+            URect::new(10, 4, 15, 4),
+        ];
+        for x in [1, 4, 9] {
+            for y in [2, 3, 8] {
+                rects.push(URect::from_center_size(UVec2::new(x, y), UVec2::new(2, 4)));
+            }
+        }
+        rects
+    }
+
+    #[test]
+    fn rect_union_contains_both() {
+        for a in samples() {
+            for b in samples() {
+                let u = a.union(b);
+                assert!(u.contains_rect(a) && u.contains_rect(b), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn rect_intersect_inside_both() {
+        for a in samples() {
+            for b in samples() {
+                let i = a.intersect(b);
+                if !i.is_empty() {
+                    assert!(a.contains_rect(i) && b.contains_rect(i), "{a:?} {b:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rect_inflate_deflate_round_trip() {
+        let amount = UVec2::new(1, 2);
+        for r in samples() {
+            assert!(r.inflate(amount).contains_rect(r));
+            if r.min.cmpge(amount).all() {
+                assert_eq!(r.inflate(amount).deflate(amount), r);
+            }
+        }
+        // Inflating saturates at zero
+        assert_eq!(
+            URect::new(1, 1, 2, 2).inflate(UVec2::splat(3)),
+            URect::new(0, 0, 5, 5)
+        );
+        // Deflating past the half-size collapses the rectangle
+        assert!(URect::new(0, 0, 2, 2).deflate(UVec2::splat(2)).is_empty());
+    }
+
+    #[test]
+    fn rect_tiles() {
+        let r = URect::new(2, 2, 12, 9); // w=10 h=7
+        let tiles: Vec<_> = r
+            .tiles(UVec2::new(3, 2), UVec2::new(1, 2), UVec2::new(1, 0))
+            .collect();
+        assert_eq!(
+            tiles,
+            [
+                URect::new(3, 2, 6, 4),
+                URect::new(7, 2, 10, 4),
+                URect::new(3, 6, 6, 8),
+                URect::new(7, 6, 10, 8),
+            ]
+        );
+        assert!(tiles.iter().all(|tile| r.contains_rect(*tile)));
+
+        // Exactly fitting cells
+        assert_eq!(
+            r.tiles(UVec2::new(5, 7), UVec2::ZERO, UVec2::ZERO).count(),
+            2
+        );
+    }
+
+    #[test]
+    fn rect_tiles_degenerate() {
+        let r = URect::new(0, 0, 8, 8);
+        assert_eq!(r.tiles(UVec2::ZERO, UVec2::ZERO, UVec2::ZERO).count(), 0);
+        assert_eq!(
+            r.tiles(UVec2::new(0, 4), UVec2::ONE, UVec2::ZERO).count(),
+            0
+        );
+        assert_eq!(
+            r.tiles(UVec2::splat(9), UVec2::ZERO, UVec2::ZERO).count(),
+            0
+        );
+        assert_eq!(
+            r.tiles(UVec2::splat(4), UVec2::ZERO, UVec2::splat(5))
+                .count(),
+            0
+        );
+        assert_eq!(r.tiles(UVec2::splat(4), UVec2::ZERO, UVec2::MAX).count(), 0);
+        // Padding larger than the rectangle leaves room for a single cell
+        assert_eq!(
+            r.tiles(UVec2::splat(4), UVec2::MAX, UVec2::ZERO)
+                .collect::<Vec<_>>(),
+            [URect::new(0, 0, 4, 4)]
+        );
+        assert_eq!(
+            URect::default()
+                .tiles(UVec2::ONE, UVec2::ZERO, UVec2::ZERO)
+                .count(),
+            0
+        );
+    }
 }
@@ -49,3 +49,24 @@ pub enum AlphaMode {
 }
 
 impl Eq for AlphaMode {}
+
+/// Sets how a material's base color alpha channel is used when it casts shadows.
+///
+/// Shadow maps only store depth, so a fragment either casts a shadow or doesn't: transparency is
+/// represented by discarding fragments in the shadow pass.
+#[derive(Debug, Default, Reflect, Copy, Clone, PartialEq, Eq, Hash, FromReflect)]
+#[reflect(Default, Debug)]
+pub enum ShadowTransparency {
+    /// The whole mesh casts a shadow, whatever its alpha.
+    #[default]
+    Opaque,
+    /// Fragments whose alpha is below the alpha cutoff of the material don't cast a shadow.
+    ///
+    /// Useful for alpha-tested geometry like foliage, fences and grilles.
+    Masked,
+    /// Fragments cast a shadow with a probability given by their alpha, following a dither
+    /// pattern, so that the filtering of the shadow map makes a partial shadow.
+    ///
+    /// Useful for tinted glass, smoke and other semi-transparent surfaces.
+    Blended,
+}
@@ -6,12 +6,31 @@ struct EnvironmentMapLight {
     specular: vec3<f32>,
 };
 
+// Corrects the reflection direction `R` at `world_position` for an environment map projected on a
+// box, so that it points from the center of the box, where the map was captured, to where `R`
+// hits the box: https://seblagarde.wordpress.com/2012/09/29/image-based-lighting-approaches-and-parallax-corrected-cubemap/
+fn box_project(world_position: vec3<f32>, R: vec3<f32>) -> vec3<f32> {
+    if (lights.environment_map_box_projection == 0u) {
+        return R;
+    }
+    let box_min = lights.environment_map_box_min;
+    let box_max = lights.environment_map_box_max;
+    // Distances along R to the planes of the box, the furthest plane of every axis is in front
+    let to_max = (box_max - world_position) / R;
+    let to_min = (box_min - world_position) / R;
+    let furthest = max(to_max, to_min);
+    let distance = min(min(furthest.x, furthest.y), furthest.z);
+    let hit = world_position + R * distance;
+    return hit - (box_min + box_max) * 0.5;
+}
+
 fn environment_map_light(
     perceptual_roughness: f32,
     roughness: f32,
     diffuse_color: vec3<f32>,
     NdotV: f32,
     f_ab: vec2<f32>,
+    world_position: vec3<f32>,
     N: vec3<f32>,
     R: vec3<f32>,
     F0: vec3<f32>,
@@ -22,7 +41,8 @@ fn environment_map_light(
     // because textureNumLevels() does not work on WebGL2
     let radiance_level = perceptual_roughness * f32(lights.environment_map_smallest_specular_mip_level);
     let irradiance = textureSample(environment_map_diffuse, environment_map_sampler, vec3(N.xy, -N.z)).rgb;
-    let radiance = textureSampleLevel(environment_map_specular, environment_map_sampler, vec3(R.xy, -R.z), radiance_level).rgb;
+    let projected_R = box_project(world_position, R);
+    let radiance = textureSampleLevel(environment_map_specular, environment_map_sampler, vec3(projected_R.xy, -projected_R.z), radiance_level).rgb;
 
     // Multiscattering approximation: https://www.jcgt.org/published/0008/01/03/paper.pdf
     // Useful reference: https://bruop.github.io/ibl
@@ -2,9 +2,10 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
 use bevy_core_pipeline::prelude::Camera3d;
 use bevy_ecs::{prelude::Component, query::With};
-use bevy_reflect::{Reflect, TypeUuid};
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 use bevy_render::{
     extract_component::{ExtractComponent, ExtractComponentPlugin},
+    primitives::Aabb,
     render_asset::RenderAssets,
     render_resource::{
         BindGroupEntry, BindGroupLayoutEntry, BindingResource, BindingType, SamplerBindingType,
@@ -28,7 +29,9 @@ impl Plugin for EnvironmentMapPlugin {
         );
 
         app.register_type::<EnvironmentMapLight>()
-            .add_plugin(ExtractComponentPlugin::<EnvironmentMapLight>::default());
+            .register_type::<EnvironmentMapProjection>()
+            .add_plugin(ExtractComponentPlugin::<EnvironmentMapLight>::default())
+            .add_plugin(ExtractComponentPlugin::<EnvironmentMapProjection>::default());
     }
 }
 
@@ -70,6 +73,35 @@ impl ExtractComponent for EnvironmentMapLight {
     }
 }
 
+/// How the specular map of an [`EnvironmentMapLight`] is projected onto the scene.
+///
+/// Add this component next to the [`EnvironmentMapLight`] of a 3D camera. Without it, the
+/// environment map is [`Infinite`](EnvironmentMapProjection::Infinite).
+#[derive(Component, Reflect, FromReflect, Clone, Copy, Debug, Default)]
+pub enum EnvironmentMapProjection {
+    /// The environment map is infinitely far away, so reflections only depend on the reflection
+    /// direction. This is right for distant scenery like skies, but reflections of nearby walls
+    /// don't line up with the walls.
+    #[default]
+    Infinite,
+    /// The environment map was captured from the center of `aabb`, a box in world space, like a
+    /// rectangular room. Reflection directions are corrected so that reflections of the sides
+    /// of the box converge to the right place.
+    ///
+    /// Surfaces outside the box get wrong reflections.
+    Box { aabb: Aabb },
+}
+
+impl ExtractComponent for EnvironmentMapProjection {
+    type Query = &'static Self;
+    type Filter = (With<Camera3d>, With<EnvironmentMapLight>);
+    type Out = Self;
+
+    fn extract_component(item: bevy_ecs::query::QueryItem<'_, Self::Query>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
 pub fn get_bindings<'a>(
     environment_map_light: Option<&EnvironmentMapLight>,
     images: &'a RenderAssets<Image>,
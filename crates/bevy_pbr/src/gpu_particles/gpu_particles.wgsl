@@ -0,0 +1,61 @@
+#import bevy_pbr::mesh_view_bindings
+#import bevy_pbr::gpu_particles_types
+
+@group(1) @binding(0)
+var<storage, read> particles: array<Particle>;
+@group(1) @binding(1)
+var<uniform> settings: GpuParticleSettings;
+@group(1) @binding(2)
+var particle_texture: texture_2d<f32>;
+@group(1) @binding(3)
+var particle_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) alpha: f32,
+};
+
+// Every instance is a particle, drawn as a quad of two triangles facing the camera
+@vertex
+fn vertex(
+    @builtin(vertex_index) vertex_index: u32,
+    @builtin(instance_index) instance_index: u32,
+) -> VertexOutput {
+    var out: VertexOutput;
+
+    let particle = particles[instance_index];
+    if particle.age >= particle.lifetime {
+        // All the vertices of a dead particle are at the same position, so nothing is drawn
+        out.clip_position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+        return out;
+    }
+
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let offset = (corner - 0.5) * settings.size;
+
+    // The right and up directions of the camera, in world space
+    let right = view.view[0].xyz;
+    let up = view.view[1].xyz;
+    let position = particle.position + right * offset.x + up * offset.y;
+
+    out.clip_position = view.view_proj * vec4<f32>(position, 1.0);
+    out.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    // Fade out over the lifetime of the particle
+    out.alpha = 1.0 - particle.age / particle.lifetime;
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(particle_texture, particle_sampler, in.uv);
+    return vec4<f32>(color.rgb, color.a * in.alpha);
+}
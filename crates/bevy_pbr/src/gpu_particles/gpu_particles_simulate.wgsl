@@ -0,0 +1,46 @@
+#import bevy_pbr::gpu_particles_types
+
+@group(0) @binding(0)
+var<storage, read_write> particles: array<Particle>;
+@group(0) @binding(1)
+var<uniform> settings: GpuParticleSettings;
+
+// PCG hash, from "Hash Functions for GPU Rendering" by Jarzynski and Olano
+fn hash(value: u32) -> u32 {
+    let state = value * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// A random number between 0 and 1 from a hash
+fn unit_float(value: u32) -> f32 {
+    return f32(value) / 4294967295.0;
+}
+
+@compute @workgroup_size(64, 1, 1)
+fn simulate(@builtin(global_invocation_id) invocation_id: vec3<u32>) {
+    let index = invocation_id.x;
+    if index >= settings.max_particles {
+        return;
+    }
+
+    var particle = particles[index];
+    let slot = (index + settings.max_particles - settings.emit_start) % settings.max_particles;
+    if slot < settings.emit_count {
+        let x = hash(index ^ hash(settings.seed));
+        let y = hash(x);
+        let z = hash(y);
+        let w = hash(z);
+        let t = vec3<f32>(unit_float(x), unit_float(y), unit_float(z));
+
+        particle.position = settings.emitter_position;
+        particle.age = 0.0;
+        particle.velocity = mix(settings.velocity_min, settings.velocity_max, t);
+        particle.lifetime = mix(settings.lifetime_min, settings.lifetime_max, unit_float(w));
+    } else if particle.age < particle.lifetime {
+        particle.velocity += settings.gravity * settings.delta_time;
+        particle.position += particle.velocity * settings.delta_time;
+        particle.age += settings.delta_time;
+    }
+    particles[index] = particle;
+}
@@ -0,0 +1,26 @@
+#define_import_path bevy_pbr::gpu_particles_types
+
+struct Particle {
+    position: vec3<f32>,
+    // The time since the particle was emitted, the particle is dead once it reaches its lifetime
+    age: f32,
+    velocity: vec3<f32>,
+    lifetime: f32,
+};
+
+struct GpuParticleSettings {
+    emitter_position: vec3<f32>,
+    delta_time: f32,
+    velocity_min: vec3<f32>,
+    lifetime_min: f32,
+    velocity_max: vec3<f32>,
+    lifetime_max: f32,
+    gravity: vec3<f32>,
+    size: f32,
+    max_particles: u32,
+    // The particles are emitted in the slots emit_start..emit_start + emit_count of the ring
+    // buffer, wrapping around at max_particles
+    emit_start: u32,
+    emit_count: u32,
+    seed: u32,
+};
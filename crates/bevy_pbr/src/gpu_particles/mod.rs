@@ -0,0 +1,679 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_core_pipeline::core_3d::Transparent3d;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::{Bundle, Component, Entity, ReflectComponent},
+    query::ROQueryItem,
+    schedule::IntoSystemConfigs,
+    system::{lifetimeless::SRes, Commands, Local, Query, Res, ResMut, Resource, SystemParamItem},
+    world::{FromWorld, World},
+};
+use bevy_math::{Mat4, Vec3};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeUuid};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_graph::{Node, NodeRunError, RenderGraph, RenderGraphContext},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult,
+        RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::{BevyDefault, Image, DEFAULT_IMAGE_HANDLE},
+    view::{ComputedVisibility, ExtractedView, Msaa, ViewTarget, Visibility, VisibleEntities},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_time::Time;
+use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::HashMap;
+
+use crate::{
+    MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup, MAX_CASCADES_PER_LIGHT,
+    MAX_DIRECTIONAL_LIGHTS,
+};
+
+pub const GPU_PARTICLES_TYPES_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 4470361139298847773);
+pub const GPU_PARTICLES_SIMULATE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 12068649708351258290);
+pub const GPU_PARTICLES_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 6833374668218073651);
+
+/// Label for the node of the main render graph that simulates the [`GpuParticleSystem`]s.
+pub const GPU_PARTICLES_NODE: &str = "gpu_particles";
+
+/// The size of a particle in the storage buffer of a [`GpuParticleSystem`]: its position, age,
+/// velocity and lifetime.
+const PARTICLE_SIZE: u64 = 32;
+
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Simulates and draws the [`GpuParticleSystem`]s.
+///
+/// Every frame, a compute shader emits the new particles of every system and moves the alive
+/// ones, in the [`GPU_PARTICLES_NODE`] of the main render graph, which runs before the cameras.
+/// The particles of a system are then drawn in the [`Transparent3d`] phase of the 3D views it is
+/// visible from, with a single instanced draw call.
+///
+/// This plugin isn't part of the [`PbrPlugin`](crate::PbrPlugin), because it needs compute
+/// shaders and storage buffers in vertex shaders, which WebGL2 doesn't support.
+pub struct GpuParticlePlugin;
+
+impl Plugin for GpuParticlePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            GPU_PARTICLES_TYPES_SHADER_HANDLE,
+            "gpu_particles_types.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            GPU_PARTICLES_SIMULATE_SHADER_HANDLE,
+            "gpu_particles_simulate.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            GPU_PARTICLES_SHADER_HANDLE,
+            "gpu_particles.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<GpuParticleSystem>()
+            .register_type::<Vec3Range>()
+            .register_type::<F32Range>();
+
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<GpuParticleSystems>()
+            .init_resource::<SpecializedRenderPipelines<GpuParticlePipeline>>()
+            .add_render_command::<Transparent3d, DrawGpuParticleSystem>()
+            .add_systems(ExtractSchedule, extract_gpu_particle_systems)
+            .add_systems(
+                Render,
+                (
+                    prepare_gpu_particle_systems.in_set(RenderSet::Prepare),
+                    queue_gpu_particle_systems.in_set(RenderSet::Queue),
+                ),
+            );
+
+        let mut graph = render_app.world.resource_mut::<RenderGraph>();
+        graph.add_node(GPU_PARTICLES_NODE, GpuParticleNode);
+        graph.add_node_edge(
+            GPU_PARTICLES_NODE,
+            bevy_render::main_graph::node::CAMERA_DRIVER,
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<GpuParticlePipeline>();
+    }
+}
+
+/// A range of [`Vec3`]s, from which the components of a value are picked independently.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Vec3Range {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Vec3Range {
+    pub const fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+}
+
+/// A range of [`f32`]s.
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub struct F32Range {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl F32Range {
+    pub const fn new(min: f32, max: f32) -> Self {
+        Self { min, max }
+    }
+}
+
+/// Particles simulated and drawn on the GPU by the [`GpuParticlePlugin`].
+///
+/// The particles are emitted at the translation of the [`GlobalTransform`] of the entity, and
+/// then live in world space: they don't follow the entity when it moves. Each particle is
+/// drawn as a square of `size` world units facing the camera, with `texture`, and fades out over
+/// its lifetime.
+///
+/// The particles are kept in a ring buffer of `max_particles` slots, so when more particles
+/// are alive than it can hold, the oldest ones are replaced by the new ones. Changing
+/// `max_particles` restarts the system.
+///
+/// Particle systems are never frustum culled, but they can be hidden with their [`Visibility`].
+#[derive(Component, Reflect, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct GpuParticleSystem {
+    /// The number of particles the system can have alive at the same time
+    pub max_particles: u32,
+    /// The number of particles emitted per second
+    pub emit_rate: f32,
+    /// The velocity particles are emitted with, in world units per second
+    pub initial_velocity: Vec3Range,
+    /// The acceleration of the particles, in world units per second squared
+    pub gravity: Vec3,
+    /// The number of seconds the particles live for
+    pub lifetime: F32Range,
+    /// The width and height of the particles, in world units
+    pub size: f32,
+    pub texture: Handle<Image>,
+}
+
+impl Default for GpuParticleSystem {
+    fn default() -> Self {
+        Self {
+            max_particles: 1000,
+            emit_rate: 100.0,
+            initial_velocity: Vec3Range::new(Vec3::new(-1.0, 2.0, -1.0), Vec3::new(1.0, 4.0, 1.0)),
+            gravity: Vec3::new(0.0, -9.81, 0.0),
+            lifetime: F32Range::new(1.0, 2.0),
+            size: 0.1,
+            texture: DEFAULT_IMAGE_HANDLE.typed(),
+        }
+    }
+}
+
+/// A component bundle for [`GpuParticleSystem`] entities.
+#[derive(Bundle, Clone, Debug, Default)]
+pub struct GpuParticleBundle {
+    pub particle_system: GpuParticleSystem,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+#[derive(Component)]
+pub struct ExtractedGpuParticleSystem {
+    pub particle_system: GpuParticleSystem,
+    pub emitter_position: Vec3,
+}
+
+pub fn extract_gpu_particle_systems(
+    mut commands: Commands,
+    mut previous_len: Local<usize>,
+    query: Extract<Query<(Entity, &GpuParticleSystem, &GlobalTransform)>>,
+) {
+    let mut values = Vec::with_capacity(*previous_len);
+    for (entity, particle_system, transform) in &query {
+        values.push((
+            entity,
+            ExtractedGpuParticleSystem {
+                particle_system: particle_system.clone(),
+                emitter_position: transform.translation(),
+            },
+        ));
+    }
+    *previous_len = values.len();
+    commands.insert_or_spawn_batch(values);
+}
+
+#[derive(ShaderType, Clone, Copy, Default)]
+struct GpuParticleSettings {
+    emitter_position: Vec3,
+    delta_time: f32,
+    velocity_min: Vec3,
+    lifetime_min: f32,
+    velocity_max: Vec3,
+    lifetime_max: f32,
+    gravity: Vec3,
+    size: f32,
+    max_particles: u32,
+    emit_start: u32,
+    emit_count: u32,
+    seed: u32,
+}
+
+/// The GPU state of a [`GpuParticleSystem`], which is kept from one frame to the next.
+pub struct PreparedGpuParticleSystem {
+    particles: Buffer,
+    settings: UniformBuffer<GpuParticleSettings>,
+    max_particles: u32,
+    /// The slot of the ring buffer the next particle is emitted in
+    next_slot: u32,
+    /// The fraction of a particle left to emit from the previous frames
+    emit_remainder: f32,
+    frame_count: u32,
+    simulate_bind_group: Option<BindGroup>,
+    render_bind_group: Option<BindGroup>,
+}
+
+impl PreparedGpuParticleSystem {
+    fn new(render_device: &RenderDevice, max_particles: u32) -> Self {
+        // The particles start zeroed, which is dead.
+        let particles = render_device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_particles_buffer"),
+            size: max_particles as u64 * PARTICLE_SIZE,
+            usage: BufferUsages::STORAGE,
+            mapped_at_creation: false,
+        });
+
+        Self {
+            particles,
+            settings: UniformBuffer::default(),
+            max_particles,
+            next_slot: 0,
+            emit_remainder: 0.0,
+            frame_count: 0,
+            simulate_bind_group: None,
+            render_bind_group: None,
+        }
+    }
+}
+
+/// The [`PreparedGpuParticleSystem`] of every [`GpuParticleSystem`] entity.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct GpuParticleSystems(HashMap<Entity, PreparedGpuParticleSystem>);
+
+pub fn prepare_gpu_particle_systems(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    time: Res<Time>,
+    mut gpu_particle_systems: ResMut<GpuParticleSystems>,
+    particle_systems: Query<(Entity, &ExtractedGpuParticleSystem)>,
+) {
+    let delta_time = time.delta_seconds();
+
+    gpu_particle_systems.retain(|&entity, _| particle_systems.contains(entity));
+
+    for (entity, extracted) in &particle_systems {
+        let particle_system = &extracted.particle_system;
+        if particle_system.max_particles == 0 {
+            gpu_particle_systems.remove(&entity);
+            continue;
+        }
+
+        let prepared = gpu_particle_systems.entry(entity).or_insert_with(|| {
+            PreparedGpuParticleSystem::new(&render_device, particle_system.max_particles)
+        });
+        if prepared.max_particles != particle_system.max_particles {
+            *prepared =
+                PreparedGpuParticleSystem::new(&render_device, particle_system.max_particles);
+        }
+
+        let to_emit = prepared.emit_remainder + particle_system.emit_rate.max(0.0) * delta_time;
+        let emit_count = (to_emit as u32).min(prepared.max_particles);
+        prepared.emit_remainder = to_emit.fract();
+        let emit_start = prepared.next_slot;
+        prepared.next_slot = (emit_start + emit_count) % prepared.max_particles;
+        prepared.frame_count = prepared.frame_count.wrapping_add(1);
+
+        prepared.settings.set(GpuParticleSettings {
+            emitter_position: extracted.emitter_position,
+            delta_time,
+            velocity_min: particle_system.initial_velocity.min,
+            lifetime_min: particle_system.lifetime.min,
+            velocity_max: particle_system.initial_velocity.max,
+            lifetime_max: particle_system.lifetime.max,
+            gravity: particle_system.gravity,
+            size: particle_system.size,
+            max_particles: prepared.max_particles,
+            emit_start,
+            emit_count,
+            // Different for every frame and every system
+            seed: prepared.frame_count.wrapping_mul(0x9e37_79b9) ^ entity.index(),
+        });
+        prepared
+            .settings
+            .write_buffer(&render_device, &render_queue);
+    }
+}
+
+#[derive(Resource, Clone)]
+pub struct GpuParticlePipeline {
+    mesh_pipeline: MeshPipeline,
+    pub simulate_layout: BindGroupLayout,
+    pub render_layout: BindGroupLayout,
+    pub simulate_pipeline: CachedComputePipelineId,
+}
+
+impl FromWorld for GpuParticlePipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let simulate_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("gpu_particles_simulate_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: false },
+                        has_dynamic_offset: false,
+                        min_binding_size: BufferSize::new(PARTICLE_SIZE),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::COMPUTE,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GpuParticleSettings::min_size()),
+                    },
+                    count: None,
+                },
+            ],
+        });
+
+        let render_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label: Some("gpu_particles_render_layout"),
+            entries: &[
+                BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Storage { read_only: true },
+                        has_dynamic_offset: false,
+                        min_binding_size: BufferSize::new(PARTICLE_SIZE),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: ShaderStages::VERTEX,
+                    ty: BindingType::Buffer {
+                        ty: BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: Some(GpuParticleSettings::min_size()),
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Texture {
+                        sample_type: TextureSampleType::Float { filterable: true },
+                        view_dimension: TextureViewDimension::D2,
+                        multisampled: false,
+                    },
+                    count: None,
+                },
+                BindGroupLayoutEntry {
+                    binding: 3,
+                    visibility: ShaderStages::FRAGMENT,
+                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                    count: None,
+                },
+            ],
+        });
+
+        let simulate_pipeline =
+            world
+                .resource::<PipelineCache>()
+                .queue_compute_pipeline(ComputePipelineDescriptor {
+                    label: Some("gpu_particles_simulate_pipeline".into()),
+                    layout: vec![simulate_layout.clone()],
+                    push_constant_ranges: Vec::new(),
+                    shader: GPU_PARTICLES_SIMULATE_SHADER_HANDLE.typed(),
+                    shader_defs: Vec::new(),
+                    entry_point: "simulate".into(),
+                });
+
+        Self {
+            mesh_pipeline: world.resource::<MeshPipeline>().clone(),
+            simulate_layout,
+            render_layout,
+            simulate_pipeline,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for GpuParticlePipeline {
+    type Key = MeshPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let shader_defs = vec![
+            ShaderDefVal::UInt(
+                "MAX_DIRECTIONAL_LIGHTS".to_string(),
+                MAX_DIRECTIONAL_LIGHTS as u32,
+            ),
+            ShaderDefVal::UInt(
+                "MAX_CASCADES_PER_LIGHT".to_string(),
+                MAX_CASCADES_PER_LIGHT as u32,
+            ),
+        ];
+
+        let format = if key.contains(MeshPipelineKey::HDR) {
+            ViewTarget::TEXTURE_FORMAT_HDR
+        } else {
+            TextureFormat::bevy_default()
+        };
+
+        let view_layout = if key.msaa_samples() == 1 {
+            self.mesh_pipeline.view_layout.clone()
+        } else {
+            self.mesh_pipeline.view_layout_multisampled.clone()
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: GPU_PARTICLES_SHADER_HANDLE.typed(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: Vec::new(),
+            },
+            fragment: Some(FragmentState {
+                shader: GPU_PARTICLES_SHADER_HANDLE.typed(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![view_layout, self.render_layout.clone()],
+            primitive: PrimitiveState::default(),
+            depth_stencil: Some(DepthStencilState {
+                format: TextureFormat::Depth32Float,
+                depth_write_enabled: false,
+                depth_compare: CompareFunction::Greater,
+                stencil: StencilState::default(),
+                bias: DepthBiasState::default(),
+            }),
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("gpu_particles_pipeline".into()),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn queue_gpu_particle_systems(
+    draw_functions: Res<DrawFunctions<Transparent3d>>,
+    pipeline: Res<GpuParticlePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<GpuParticlePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    msaa: Res<Msaa>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    mut gpu_particle_systems: ResMut<GpuParticleSystems>,
+    particle_systems: Query<&ExtractedGpuParticleSystem>,
+    mut views: Query<(
+        &ExtractedView,
+        &VisibleEntities,
+        &mut RenderPhase<Transparent3d>,
+    )>,
+) {
+    for (&entity, prepared) in gpu_particle_systems.iter_mut() {
+        let Ok(extracted) = particle_systems.get(entity) else {
+            continue;
+        };
+        let Some(settings) = prepared.settings.binding() else {
+            continue;
+        };
+
+        prepared.simulate_bind_group =
+            Some(render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("gpu_particles_simulate_bind_group"),
+                layout: &pipeline.simulate_layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: prepared.particles.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: settings.clone(),
+                    },
+                ],
+            }));
+
+        // The particles are still simulated while their texture loads, but not drawn.
+        prepared.render_bind_group = images.get(&extracted.particle_system.texture).map(|image| {
+            render_device.create_bind_group(&BindGroupDescriptor {
+                label: Some("gpu_particles_render_bind_group"),
+                layout: &pipeline.render_layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: prepared.particles.as_entire_binding(),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: settings,
+                    },
+                    BindGroupEntry {
+                        binding: 2,
+                        resource: BindingResource::TextureView(&image.texture_view),
+                    },
+                    BindGroupEntry {
+                        binding: 3,
+                        resource: BindingResource::Sampler(&image.sampler),
+                    },
+                ],
+            })
+        });
+    }
+
+    let draw_function = draw_functions
+        .read()
+        .get_id::<DrawGpuParticleSystem>()
+        .unwrap();
+
+    for (view, visible_entities, mut transparent_phase) in &mut views {
+        let mesh_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
+            | MeshPipelineKey::from_hdr(view.hdr);
+        let rangefinder = view.rangefinder3d();
+
+        for &entity in &visible_entities.entities {
+            let Ok(extracted) = particle_systems.get(entity) else {
+                continue;
+            };
+            let Some(prepared) = gpu_particle_systems.get(&entity) else {
+                continue;
+            };
+            if prepared.render_bind_group.is_none() {
+                continue;
+            }
+
+            transparent_phase.add(Transparent3d {
+                entity,
+                draw_function,
+                pipeline: pipelines.specialize(&pipeline_cache, &pipeline, mesh_key),
+                distance: rangefinder.distance(&Mat4::from_translation(extracted.emitter_position)),
+            });
+        }
+    }
+}
+
+/// Runs the simulation compute shader of every [`GpuParticleSystem`].
+pub struct GpuParticleNode;
+
+impl Node for GpuParticleNode {
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline = world.resource::<GpuParticlePipeline>();
+        let Some(simulate_pipeline) = world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline(pipeline.simulate_pipeline)
+        else {
+            return Ok(());
+        };
+        let gpu_particle_systems = world.resource::<GpuParticleSystems>();
+        if gpu_particle_systems.is_empty() {
+            return Ok(());
+        }
+
+        let mut pass = render_context.begin_tracked_compute_pass(ComputePassDescriptor {
+            label: Some("gpu_particles_simulate_pass"),
+        });
+        pass.set_compute_pipeline(simulate_pipeline);
+
+        for prepared in gpu_particle_systems.values() {
+            let Some(bind_group) = &prepared.simulate_bind_group else {
+                continue;
+            };
+            pass.set_bind_group(0, bind_group, &[]);
+            let workgroups = (prepared.max_particles as f32 / WORKGROUP_SIZE as f32).ceil();
+            pass.dispatch_workgroups(workgroups as u32, 1, 1);
+        }
+
+        Ok(())
+    }
+}
+
+pub type DrawGpuParticleSystem = (
+    SetItemPipeline,
+    SetMeshViewBindGroup<0>,
+    DrawGpuParticles<1>,
+);
+
+/// Sets the bind group of the particles of a [`GpuParticleSystem`] at index `I`, and draws all
+/// of them at once.
+pub struct DrawGpuParticles<const I: usize>;
+
+impl<P: PhaseItem, const I: usize> RenderCommand<P> for DrawGpuParticles<I> {
+    type Param = SRes<GpuParticleSystems>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    #[inline]
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _item_query: ROQueryItem<'w, Self::ItemWorldQuery>,
+        gpu_particle_systems: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(prepared) = gpu_particle_systems.into_inner().get(&item.entity()) else {
+            return RenderCommandResult::Failure;
+        };
+        let Some(bind_group) = &prepared.render_bind_group else {
+            return RenderCommandResult::Failure;
+        };
+
+        pass.set_bind_group(I, bind_group, &[]);
+        pass.draw(0..6, 0..prepared.max_particles);
+
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,36 @@
+#define_import_path bevy_pbr::irradiance_volume
+
+// The position in the space of the irradiance volume, where the volume is the unit cube centered
+// on the origin
+fn irradiance_volume_local_position(world_position: vec3<f32>) -> vec3<f32> {
+    return (lights.irradiance_volume_world_to_local * vec4<f32>(world_position, 1.0)).xyz;
+}
+
+fn irradiance_volume_contains(local_position: vec3<f32>) -> bool {
+    return all(abs(local_position) <= vec3<f32>(0.5));
+}
+
+// Interpolates the spherical harmonics of the probes around `local_position` and evaluates the
+// diffuse light along the normal N.
+//
+// Coefficient k of the probe (x, y, z) is the texel (x, y, k * probe_count.z + z), so every
+// coefficient is a block of probe_count.z slices. The depth is clamped between the centers of
+// the first and last slices of the block so that the filtering doesn't blend two coefficients.
+fn irradiance_volume_irradiance(local_position: vec3<f32>, N: vec3<f32>) -> vec3<f32> {
+    let probe_count = vec3<f32>(lights.irradiance_volume_probe_count);
+    // The position in probes, the probes are at integer coordinates
+    let probe = clamp((local_position + 0.5) * probe_count - 0.5, vec3<f32>(0.0), probe_count - 1.0);
+    let uv = (probe.xy + 0.5) / probe_count.xy;
+
+    var coefficients: array<vec3<f32>, 9>;
+    for (var k: u32 = 0u; k < 9u; k++) {
+        let w = (f32(k) * probe_count.z + probe.z + 0.5) / (9.0 * probe_count.z);
+        coefficients[k] = textureSampleLevel(
+            irradiance_volume_texture,
+            irradiance_volume_sampler,
+            vec3<f32>(uv, w),
+            0.0
+        ).rgb;
+    }
+    return spherical_harmonics_irradiance(coefficients, N);
+}
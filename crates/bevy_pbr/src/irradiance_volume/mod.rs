@@ -0,0 +1,188 @@
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleUntyped};
+use bevy_ecs::{
+    prelude::{Component, ReflectComponent, ReflectResource},
+    system::{Commands, Res, Resource},
+    world::{FromWorld, World},
+};
+use bevy_math::UVec3;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect, TypeUuid};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_resource::{
+        AddressMode, BindGroupEntry, BindGroupLayoutEntry, BindingResource, BindingType, Extent3d,
+        FilterMode, Sampler, SamplerBindingType, SamplerDescriptor, Shader, ShaderStages,
+        TextureDimension, TextureFormat, TextureSampleType, TextureView, TextureViewDescriptor,
+        TextureViewDimension,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::Image,
+    Extract, ExtractSchedule, RenderApp,
+};
+use bevy_transform::components::GlobalTransform;
+
+pub const IRRADIANCE_VOLUME_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 9624108722211199146);
+
+pub struct IrradianceVolumePlugin;
+
+impl Plugin for IrradianceVolumePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            IRRADIANCE_VOLUME_SHADER_HANDLE,
+            "irradiance_volume.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<IrradianceVolume>()
+            .register_type::<UseIrradianceVolume>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.add_systems(ExtractSchedule, extract_irradiance_volume);
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<IrradianceVolumeFallback>();
+        }
+    }
+}
+
+/// A grid of light probes baked offline, which gives the meshes with a [`UseIrradianceVolume`]
+/// the diffuse indirect light of where they are, in place of the
+/// [`AmbientLight`](crate::AmbientLight).
+///
+/// This is intended for dynamic objects, like characters, moving through a scene with baked
+/// lighting. Every probe holds the [`SphericalHarmonics`](crate::SphericalHarmonics) of the light
+/// coming to its position, and the spherical harmonics of the probes around a fragment are
+/// interpolated linearly.
+///
+/// The probes are stored in `texture`, a 3D texture with a filterable float format that is
+/// `probe_count.x` texels wide, `probe_count.y` texels high and `9 * probe_count.z` texels deep.
+/// Coefficient `k` of the probe `(x, y, z)` is the texel `(x, y, k * probe_count.z + z)`, in
+/// the RGB channels.
+///
+/// The probes are at the centers of the cells of a `probe_count` grid over the unit cube centered
+/// on the origin, which `transform` places in the world. Fragments outside of the volume use
+/// the [`SphericalHarmonics`](crate::SphericalHarmonics) of their mesh if it has one, or the
+/// [`AmbientLight`](crate::AmbientLight). The specular indirect light always uses the
+/// [`AmbientLight`](crate::AmbientLight).
+///
+/// There is a single irradiance volume for the whole world. Like the other ambient lighting, it
+/// is only used by shaders based on the PBR shader of
+/// [`StandardMaterial`](crate::StandardMaterial).
+#[derive(Resource, Reflect, Clone, Debug, Default)]
+#[reflect(Resource)]
+pub struct IrradianceVolume {
+    pub texture: Handle<Image>,
+    pub transform: GlobalTransform,
+    pub probe_count: UVec3,
+}
+
+/// Add this component to a mesh to light it with the [`IrradianceVolume`].
+#[derive(Component, Reflect, Clone, Copy, Debug, Default)]
+#[reflect(Component, Default)]
+pub struct UseIrradianceVolume;
+
+/// The texture bound in place of the texture of the [`IrradianceVolume`] while there is none, and
+/// the sampler all volumes are sampled with, which interpolates the probes linearly.
+#[derive(Resource)]
+pub struct IrradianceVolumeFallback {
+    pub texture_view: TextureView,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for IrradianceVolumeFallback {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let render_queue = world.resource::<RenderQueue>();
+
+        let image = Image::new_fill(
+            Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D3,
+            &[0; 4],
+            TextureFormat::Rgba8Unorm,
+        );
+        let texture = render_device.create_texture_with_data(
+            render_queue,
+            &image.texture_descriptor,
+            &image.data,
+        );
+        let sampler = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("irradiance_volume_sampler"),
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            address_mode_w: AddressMode::ClampToEdge,
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            texture_view: texture.create_view(&TextureViewDescriptor::default()),
+            sampler,
+        }
+    }
+}
+
+pub fn extract_irradiance_volume(
+    mut commands: Commands,
+    irradiance_volume: Extract<Option<Res<IrradianceVolume>>>,
+) {
+    match irradiance_volume.as_ref() {
+        Some(irradiance_volume) => {
+            commands.insert_resource(IrradianceVolume::clone(irradiance_volume))
+        }
+        None => commands.remove_resource::<IrradianceVolume>(),
+    }
+}
+
+pub fn get_bindings<'a>(
+    irradiance_volume: Option<&IrradianceVolume>,
+    images: &'a RenderAssets<Image>,
+    fallback: &'a IrradianceVolumeFallback,
+    bindings: [u32; 2],
+) -> [BindGroupEntry<'a>; 2] {
+    let texture_view = irradiance_volume
+        .and_then(|irradiance_volume| images.get(&irradiance_volume.texture))
+        .map_or(&fallback.texture_view, |image| &image.texture_view);
+
+    [
+        BindGroupEntry {
+            binding: bindings[0],
+            resource: BindingResource::TextureView(texture_view),
+        },
+        BindGroupEntry {
+            binding: bindings[1],
+            resource: BindingResource::Sampler(&fallback.sampler),
+        },
+    ]
+}
+
+pub fn get_bind_group_layout_entries(bindings: [u32; 2]) -> [BindGroupLayoutEntry; 2] {
+    [
+        BindGroupLayoutEntry {
+            binding: bindings[0],
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Texture {
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D3,
+                multisampled: false,
+            },
+            count: None,
+        },
+        BindGroupLayoutEntry {
+            binding: bindings[1],
+            visibility: ShaderStages::FRAGMENT,
+            ty: BindingType::Sampler(SamplerBindingType::Filtering),
+            count: None,
+        },
+    ]
+}
@@ -6,6 +6,8 @@ mod alpha;
 mod bundle;
 mod environment_map;
 mod fog;
+mod gpu_particles;
+mod irradiance_volume;
 mod light;
 mod material;
 mod parallax;
@@ -15,8 +17,10 @@ mod render;
 
 pub use alpha::*;
 pub use bundle::*;
-pub use environment_map::EnvironmentMapLight;
+pub use environment_map::{EnvironmentMapLight, EnvironmentMapProjection};
 pub use fog::*;
+pub use gpu_particles::*;
+pub use irradiance_volume::{IrradianceVolume, UseIrradianceVolume};
 pub use light::*;
 pub use material::*;
 pub use parallax::*;
@@ -27,13 +31,15 @@ pub use render::*;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        alpha::AlphaMode,
+        alpha::{AlphaMode, ShadowTransparency},
         bundle::{
             DirectionalLightBundle, MaterialMeshBundle, PbrBundle, PointLightBundle,
             SpotLightBundle,
         },
-        environment_map::EnvironmentMapLight,
+        environment_map::{EnvironmentMapLight, EnvironmentMapProjection},
         fog::{FogFalloff, FogSettings},
+        gpu_particles::{GpuParticleBundle, GpuParticlePlugin, GpuParticleSystem},
+        irradiance_volume::{IrradianceVolume, UseIrradianceVolume},
         light::{AmbientLight, DirectionalLight, PointLight, SpotLight},
         material::{Material, MaterialPlugin},
         parallax::ParallaxMappingMethod,
@@ -54,6 +60,7 @@ use bevy_ecs::prelude::*;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     camera::CameraUpdateSystem,
+    extract_component::ExtractComponentPlugin,
     extract_resource::ExtractResourcePlugin,
     prelude::Color,
     render_graph::RenderGraph,
@@ -64,6 +71,7 @@ use bevy_render::{
 };
 use bevy_transform::TransformSystem;
 use environment_map::EnvironmentMapPlugin;
+use irradiance_volume::IrradianceVolumePlugin;
 
 pub const PBR_TYPES_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 1708015359337029744);
@@ -166,6 +174,7 @@ impl Plugin for PbrPlugin {
             .register_type::<AmbientLight>()
             .register_type::<Cascade>()
             .register_type::<CascadeShadowConfig>()
+            .register_type::<ContactShadows>()
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
             .register_type::<ClusterConfig>()
@@ -174,10 +183,12 @@ impl Plugin for PbrPlugin {
             .register_type::<CubemapVisibleEntities>()
             .register_type::<DirectionalLight>()
             .register_type::<DirectionalLightShadowMap>()
+            .register_type::<ShadowCascadeBiasMode>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
+            .register_type::<SphericalHarmonics>()
             .register_type::<SpotLight>()
             .add_plugin(MeshRenderPlugin)
             .add_plugin(MaterialPlugin::<StandardMaterial> {
@@ -185,11 +196,13 @@ impl Plugin for PbrPlugin {
                 ..Default::default()
             })
             .add_plugin(EnvironmentMapPlugin)
+            .add_plugin(IrradianceVolumePlugin)
             .init_resource::<AmbientLight>()
             .init_resource::<GlobalVisiblePointLights>()
             .init_resource::<DirectionalLightShadowMap>()
             .init_resource::<PointLightShadowMap>()
             .add_plugin(ExtractResourcePlugin::<AmbientLight>::default())
+            .add_plugin(ExtractComponentPlugin::<ContactShadows>::default())
             .configure_sets(
                 PostUpdate,
                 (
@@ -310,6 +323,11 @@ impl Plugin for PbrPlugin {
     }
 
     fn finish(&self, app: &mut App) {
+        let shadow_cascade_bias_mode = app
+            .world
+            .get_resource::<ShadowCascadeBiasMode>()
+            .copied()
+            .unwrap_or_default();
         let render_app = match app.get_sub_app_mut(RenderApp) {
             Ok(render_app) => render_app,
             Err(_) => return,
@@ -317,6 +335,7 @@ impl Plugin for PbrPlugin {
 
         // Extract the required data from the main world
         render_app
+            .insert_resource(shadow_cascade_bias_mode)
             .init_resource::<ShadowSamplers>()
             .init_resource::<GlobalLightMeta>();
     }
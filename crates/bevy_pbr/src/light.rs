@@ -1,11 +1,14 @@
 use std::collections::HashSet;
 
+use bevy_core_pipeline::core_3d::Camera3d;
 use bevy_ecs::prelude::*;
+use bevy_ecs::query::QueryItem;
 use bevy_math::{Mat4, Rect, UVec2, UVec3, Vec2, Vec3, Vec3A, Vec3Swizzles, Vec4, Vec4Swizzles};
 use bevy_reflect::prelude::*;
 use bevy_render::{
     camera::Camera,
     color::Color,
+    extract_component::ExtractComponent,
     extract_resource::ExtractResource,
     prelude::Projection,
     primitives::{Aabb, CascadesFrusta, CubemapFrusta, Frustum, HalfSpace, Sphere},
@@ -230,6 +233,77 @@ impl Default for DirectionalLightShadowMap {
     }
 }
 
+/// Controls the depth bias of the rasterizer when rendering the cascades of the
+/// [`DirectionalLight`] shadow maps.
+///
+/// The [`DirectionalLight::shadow_depth_bias`] is constant, so surfaces at a shallow angle to the
+/// light can still have shadow acne. [`ShadowCascadeBiasMode::SlopeScale`] adds a bias that grows
+/// with the slope of the shadow casters, on top of the biases of the light.
+///
+/// This resource is read when the shadow pipelines are created, so it must be inserted before the
+/// app runs, and changing it afterwards has no effect.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Resource)]
+pub enum ShadowCascadeBiasMode {
+    /// Only the constant biases of the light are used.
+    #[default]
+    Constant,
+    /// The depths of the shadow casters are biased by the rasterizer, like the `DepthBiasState`
+    /// of `wgpu`.
+    ///
+    /// Bevy uses a reversed depth, so the values should be negative to move the shadow casters
+    /// away from the light.
+    SlopeScale {
+        /// The constant bias, in units of the resolution of the depth buffer
+        constant: i32,
+        /// The bias multiplied by the slope of the shadow casters
+        slope: f32,
+        /// The maximum bias, or `0.0` for no maximum
+        clamp: f32,
+    },
+}
+
+/// Adds screen space contact shadows to the lights of a 3D camera, for details that are too small
+/// for the shadow maps, like grass blades or bolts.
+///
+/// For each light with shadows enabled, a ray is marched from every fragment toward the light,
+/// in the depth of the [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass), which the
+/// camera must have. The fragment is in shadow if the ray goes behind a surface, less than
+/// `thickness` behind it, within `max_distance` of the fragment. This shadow is combined with the
+/// shadow map of the light by taking the darkest of both.
+///
+/// Surfaces that are off screen or hidden can't cast contact shadows.
+#[derive(Component, Reflect, Clone, Copy, Debug, PartialEq)]
+#[reflect(Component, Default)]
+pub struct ContactShadows {
+    /// The length of the rays, in world units
+    pub max_distance: f32,
+    /// How thick the surfaces in the depth prepass are assumed to be, in world units
+    pub thickness: f32,
+    /// The number of steps along each ray
+    pub samples: u8,
+}
+
+impl Default for ContactShadows {
+    fn default() -> Self {
+        Self {
+            max_distance: 0.2,
+            thickness: 0.05,
+            samples: 16,
+        }
+    }
+}
+
+impl ExtractComponent for ContactShadows {
+    type Query = &'static Self;
+    type Filter = With<Camera3d>;
+    type Out = Self;
+
+    fn extract_component(item: QueryItem<Self::Query>) -> Option<Self::Out> {
+        Some(*item)
+    }
+}
+
 /// Controls how cascaded shadow mapping works.
 /// Prefer using [`CascadeShadowConfigBuilder`] to construct an instance.
 ///
@@ -606,6 +680,39 @@ pub struct NotShadowCaster;
 #[reflect(Component, Default)]
 pub struct NotShadowReceiver;
 
+/// Pre-baked indirect diffuse lighting of a [`Mesh`](bevy_render::mesh::Mesh), which replaces
+/// the [`AmbientLight`] in the diffuse term of its lighting.
+///
+/// This is more accurate than a single ambient color for static objects, as the lighting can
+/// depend on the direction of the normal, for example brighter from the sky than from the
+/// ground. Specular ambient lighting still uses the [`AmbientLight`].
+///
+/// The coefficients are the first three bands of the spherical harmonics projection of the
+/// incoming light in world space, in linear RGB and at the scale of the [`AmbientLight`] color
+/// multiplied by its brightness. They are in the order of the real basis functions
+/// `Y(0,0)`, `Y(1,-1)`, `Y(1,0)`, `Y(1,1)`, `Y(2,-2)`, `Y(2,-1)`, `Y(2,0)`, `Y(2,1)`, `Y(2,2)`,
+/// which are `1`, `y`, `z`, `x`, `xy`, `yz`, `3z² - 1`, `xz` and `x² - y²` up to normalization.
+/// The cosine convolution into irradiance is done in the shader.
+///
+/// Like the [`AmbientLight`], it is only used by shaders based on the PBR shader of
+/// [`StandardMaterial`](crate::StandardMaterial).
+#[derive(Component, Reflect, Clone, Copy, Debug, Default, PartialEq)]
+#[reflect(Component, Default)]
+pub struct SphericalHarmonics {
+    pub coefficients: [Vec3; 9],
+}
+
+impl SphericalHarmonics {
+    /// Returns the spherical harmonics of light of `color` coming from every direction, which
+    /// lights a mesh like an [`AmbientLight`] of this color.
+    pub fn uniform(color: Vec3) -> Self {
+        let mut coefficients = [Vec3::ZERO; 9];
+        // The integral of the constant basis function Y(0,0) = 1 / (2 sqrt(π)) over the sphere
+        coefficients[0] = color * (2.0 * std::f32::consts::PI.sqrt());
+        Self { coefficients }
+    }
+}
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SimulationLightSystems {
     AddClusters,
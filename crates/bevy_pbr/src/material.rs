@@ -1,14 +1,14 @@
 use crate::{
-    render, AlphaMode, DrawMesh, DrawPrepass, EnvironmentMapLight, MeshPipeline, MeshPipelineKey,
-    MeshUniform, PrepassPipelinePlugin, PrepassPlugin, RenderLightSystems, SetMeshBindGroup,
-    SetMeshViewBindGroup, Shadow,
+    render, AlphaMode, ContactShadows, DrawMesh, DrawPrepass, EnvironmentMapLight, MeshPipeline,
+    MeshPipelineKey, MeshUniform, PrepassPipelinePlugin, PrepassPlugin, RenderLightSystems,
+    SetMeshBindGroup, SetMeshViewBindGroup, Shadow, ShadowTransparency,
 };
 use bevy_app::{App, Plugin};
 use bevy_asset::{AddAsset, AssetEvent, AssetServer, Assets, Handle};
 use bevy_core_pipeline::{
     core_3d::{AlphaMask3d, Opaque3d, Transparent3d},
     experimental::taa::TemporalAntiAliasSettings,
-    prepass::NormalPrepass,
+    prepass::{DepthPrepass, NormalPrepass},
     tonemapping::{DebandDither, Tonemapping},
 };
 use bevy_derive::{Deref, DerefMut};
@@ -126,6 +126,13 @@ pub trait Material: AsBindGroup + Send + Sync + Clone + TypeUuid + TypePath + Si
         AlphaMode::Opaque
     }
 
+    /// Returns this material's [`ShadowTransparency`]. If `None` is returned, which is the default,
+    /// the shadow pass discards fragments according to the [`AlphaMode`] of the material.
+    #[inline]
+    fn shadow_transparency(&self) -> Option<ShadowTransparency> {
+        None
+    }
+
     #[inline]
     /// Add a bias to the view depth of the mesh which can be used to force a specific render order
     /// for meshes with similar depth, to avoid z-fighting.
@@ -389,6 +396,7 @@ pub fn queue_material_meshes<M: Material>(
         Option<&EnvironmentMapLight>,
         Option<&NormalPrepass>,
         Option<&TemporalAntiAliasSettings>,
+        (Option<&ContactShadows>, Option<&DepthPrepass>),
         &mut RenderPhase<Opaque3d>,
         &mut RenderPhase<AlphaMask3d>,
         &mut RenderPhase<Transparent3d>,
@@ -404,6 +412,7 @@ pub fn queue_material_meshes<M: Material>(
         environment_map,
         normal_prepass,
         taa_settings,
+        (contact_shadows, depth_prepass),
         mut opaque_phase,
         mut alpha_mask_phase,
         mut transparent_phase,
@@ -424,6 +433,11 @@ pub fn queue_material_meshes<M: Material>(
             view_key |= MeshPipelineKey::TAA;
         }
 
+        // Contact shadows are marched in the depth prepass
+        if contact_shadows.is_some() && depth_prepass.is_some() {
+            view_key |= MeshPipelineKey::CONTACT_SHADOWS;
+        }
+
         let environment_map_loaded = match environment_map {
             Some(environment_map) => environment_map.is_loaded(&images),
             None => false,
@@ -543,6 +557,8 @@ pub fn queue_material_meshes<M: Material>(
 pub struct MaterialProperties {
     /// The [`AlphaMode`] of this material.
     pub alpha_mode: AlphaMode,
+    /// The [`ShadowTransparency`] of this material, if it doesn't follow its [`AlphaMode`].
+    pub shadow_transparency: Option<ShadowTransparency>,
     /// Add a bias to the view depth of the mesh which can be used to force a specific render order
     /// for meshes with equal depth, to avoid z-fighting.
     /// The bias is in depth-texture units so large values may be needed to overcome small depth differences.
@@ -600,6 +616,7 @@ pub fn extract_materials<M: Material>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::Unused { .. } => {}
         }
     }
 
@@ -699,6 +716,7 @@ fn prepare_material<M: Material>(
         key: prepared.data,
         properties: MaterialProperties {
             alpha_mode: material.alpha_mode(),
+            shadow_transparency: material.shadow_transparency(),
             depth_bias: material.depth_bias(),
         },
     })
@@ -1,6 +1,6 @@
 use crate::{
     AlphaMode, Material, MaterialPipeline, MaterialPipelineKey, ParallaxMappingMethod,
-    PBR_PREPASS_SHADER_HANDLE, PBR_SHADER_HANDLE,
+    ShadowTransparency, PBR_PREPASS_SHADER_HANDLE, PBR_SHADER_HANDLE,
 };
 use bevy_asset::Handle;
 use bevy_math::Vec4;
@@ -220,6 +220,17 @@ pub struct StandardMaterial {
     /// See [`AlphaMode`] for details. Defaults to [`AlphaMode::Opaque`].
     pub alpha_mode: AlphaMode,
 
+    /// How the alpha channel of the `base_color_texture`, multiplied by the alpha of the
+    /// `base_color`, is applied to the shadows this material casts.
+    ///
+    /// [`ShadowTransparency::Masked`] uses the cutoff of [`AlphaMode::Mask`], or 0.5 with the
+    /// other alpha modes.
+    ///
+    /// Defaults to `None`, which discards the same fragments in the shadow pass as in the depth
+    /// prepass: those below the cutoff of [`AlphaMode::Mask`], and the nearly invisible ones of
+    /// the blended alpha modes.
+    pub shadow_transparency: Option<ShadowTransparency>,
+
     /// Adjust rendered depth.
     ///
     /// A material with a positive depth bias will render closer to the
@@ -309,6 +320,47 @@ pub struct StandardMaterial {
     ///
     /// Default is `16.0`.
     pub max_parallax_layer_count: f32,
+
+    /// The color of the light scattered below the surface of the material, for materials like
+    /// skin or wax.
+    ///
+    /// Only used if [`subsurface_strength`] is above `0.0`.
+    ///
+    /// Defaults to [`Color::WHITE`].
+    ///
+    /// [`subsurface_strength`]: StandardMaterial::subsurface_strength
+    pub subsurface_color: Color,
+
+    /// How far, in `[0.0, 1.0]`, light wraps around the material by scattering below its
+    /// surface.
+    ///
+    /// This is a fast approximation of subsurface scattering using "wrap lighting": parts of the
+    /// material facing away from a light, up to a right angle for `1.0`, still receive some of
+    /// its light, tinted by [`subsurface_color`]. This softens the transition from lit to unlit
+    /// parts of the material.
+    ///
+    /// Defaults to `0.0`, which disables subsurface scattering.
+    ///
+    /// [`subsurface_color`]: StandardMaterial::subsurface_color
+    pub subsurface_strength: f32,
+
+    /// The thickness of the material, used to attenuate the [`transmission_tint`].
+    ///
+    /// The red channel goes from `0.0`, thin, to `1.0`, thick enough to let no light through.
+    /// Without a texture, the material is thin everywhere.
+    ///
+    /// [`transmission_tint`]: StandardMaterial::transmission_tint
+    #[texture(13)]
+    #[sampler(14)]
+    pub subsurface_thickness_texture: Option<Handle<Image>>,
+
+    /// The color of the light going through thin parts of the material, seen when a light is
+    /// behind them, like ears or fingers held up to a light.
+    ///
+    /// Transmitted light isn't shadowed, since the material itself would shadow it.
+    ///
+    /// Defaults to [`Color::BLACK`], which doesn't let any light through.
+    pub transmission_tint: Color,
 }
 
 impl Default for StandardMaterial {
@@ -337,11 +389,16 @@ impl Default for StandardMaterial {
             unlit: false,
             fog_enabled: true,
             alpha_mode: AlphaMode::Opaque,
+            shadow_transparency: None,
             depth_bias: 0.0,
             depth_map: None,
             parallax_depth_scale: 0.1,
             max_parallax_layer_count: 16.0,
             parallax_mapping_method: ParallaxMappingMethod::Occlusion,
+            subsurface_color: Color::WHITE,
+            subsurface_strength: 0.0,
+            subsurface_thickness_texture: None,
+            transmission_tint: Color::BLACK,
         }
     }
 }
@@ -385,6 +442,8 @@ bitflags::bitflags! {
         const FLIP_NORMAL_MAP_Y          = (1 << 7);
         const FOG_ENABLED                = (1 << 8);
         const DEPTH_MAP                  = (1 << 9); // Used for parallax mapping
+        const SUBSURFACE                 = (1 << 10);
+        const SUBSURFACE_THICKNESS_TEXTURE = (1 << 11);
         const ALPHA_MODE_RESERVED_BITS   = (Self::ALPHA_MODE_MASK_BITS << Self::ALPHA_MODE_SHIFT_BITS); // ← Bitmask reserving bits for the `AlphaMode`
         const ALPHA_MODE_OPAQUE          = (0 << Self::ALPHA_MODE_SHIFT_BITS);                          // ← Values are just sequential values bitshifted into
         const ALPHA_MODE_MASK            = (1 << Self::ALPHA_MODE_SHIFT_BITS);                          //   the bitmask, and can range from 0 to 7.
@@ -434,6 +493,12 @@ pub struct StandardMaterialUniform {
     /// Using [`ParallaxMappingMethod::Relief`], how many additional
     /// steps to use at most to find the depth value.
     pub max_relief_mapping_search_steps: u32,
+    /// The [`StandardMaterial::subsurface_color`], only used with the subsurface flag.
+    pub subsurface_color: Vec4,
+    /// The [`StandardMaterial::transmission_tint`], only used with the subsurface flag.
+    pub transmission_tint: Vec4,
+    /// The [`StandardMaterial::subsurface_strength`], only used with the subsurface flag.
+    pub subsurface_strength: f32,
 }
 
 impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
@@ -463,6 +528,12 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
         if self.depth_map.is_some() {
             flags |= StandardMaterialFlags::DEPTH_MAP;
         }
+        if self.subsurface_strength > 0.0 || self.transmission_tint != Color::BLACK {
+            flags |= StandardMaterialFlags::SUBSURFACE;
+        }
+        if self.subsurface_thickness_texture.is_some() {
+            flags |= StandardMaterialFlags::SUBSURFACE_THICKNESS_TEXTURE;
+        }
         let has_normal_map = self.normal_map_texture.is_some();
         if has_normal_map {
             if let Some(texture) = images.get(self.normal_map_texture.as_ref().unwrap()) {
@@ -506,6 +577,9 @@ impl AsBindGroupShaderType<StandardMaterialUniform> for StandardMaterial {
             parallax_depth_scale: self.parallax_depth_scale,
             max_parallax_layer_count: self.max_parallax_layer_count,
             max_relief_mapping_search_steps: self.parallax_mapping_method.max_steps(),
+            subsurface_color: self.subsurface_color.as_linear_rgba_f32().into(),
+            transmission_tint: self.transmission_tint.as_linear_rgba_f32().into(),
+            subsurface_strength: self.subsurface_strength,
         }
     }
 }
@@ -573,6 +647,11 @@ impl Material for StandardMaterial {
         self.alpha_mode
     }
 
+    #[inline]
+    fn shadow_transparency(&self) -> Option<ShadowTransparency> {
+        self.shadow_transparency
+    }
+
     #[inline]
     fn depth_bias(&self) -> f32 {
         self.depth_bias
@@ -47,7 +47,7 @@ use bevy_utils::tracing::error;
 use crate::{
     prepare_lights, AlphaMode, DrawMesh, Material, MaterialPipeline, MaterialPipelineKey,
     MeshPipeline, MeshPipelineKey, MeshUniform, RenderMaterials, SetMaterialBindGroup,
-    SetMeshBindGroup, MAX_CASCADES_PER_LIGHT, MAX_DIRECTIONAL_LIGHTS,
+    SetMeshBindGroup, ShadowCascadeBiasMode, MAX_CASCADES_PER_LIGHT, MAX_DIRECTIONAL_LIGHTS,
 };
 
 use std::{hash::Hash, marker::PhantomData};
@@ -225,6 +225,8 @@ pub struct PrepassPipeline<M: Material> {
     pub material_vertex_shader: Option<Handle<Shader>>,
     pub material_fragment_shader: Option<Handle<Shader>>,
     pub material_pipeline: MaterialPipeline<M>,
+    /// The depth bias of the [`DirectionalLight`](crate::DirectionalLight) shadow cascades
+    pub shadow_cascade_bias_mode: ShadowCascadeBiasMode,
     _marker: PhantomData<M>,
 }
 
@@ -321,6 +323,10 @@ impl<M: Material> FromWorld for PrepassPipeline<M> {
             },
             material_layout: M::bind_group_layout(render_device),
             material_pipeline: world.resource::<MaterialPipeline<M>>().clone(),
+            shadow_cascade_bias_mode: world
+                .get_resource::<ShadowCascadeBiasMode>()
+                .copied()
+                .unwrap_or_default(),
             _marker: PhantomData,
         }
     }
@@ -360,6 +366,13 @@ where
             shader_defs.push("MAY_DISCARD".into());
         }
 
+        if key.mesh_key.contains(MeshPipelineKey::SHADOW_ALPHA_MASK) {
+            shader_defs.push("SHADOW_ALPHA_MASK".into());
+        }
+        if key.mesh_key.contains(MeshPipelineKey::SHADOW_ALPHA_BLEND) {
+            shader_defs.push("SHADOW_ALPHA_BLEND".into());
+        }
+
         let blend_key = key
             .mesh_key
             .intersection(MeshPipelineKey::BLEND_RESERVED_BITS);
@@ -458,6 +471,24 @@ where
         // The fragment shader is only used when the normal prepass or motion vectors prepass
         // is enabled or the material uses alpha cutoff values and doesn't rely on the standard
         // prepass shader
+        // Only the directional light shadow cascades use the depth clamp
+        let bias = match self.shadow_cascade_bias_mode {
+            ShadowCascadeBiasMode::SlopeScale {
+                constant,
+                slope,
+                clamp,
+            } if key.mesh_key.contains(MeshPipelineKey::DEPTH_CLAMP_ORTHO) => DepthBiasState {
+                constant,
+                slope_scale: slope,
+                clamp,
+            },
+            _ => DepthBiasState {
+                constant: 0,
+                slope_scale: 0.0,
+                clamp: 0.0,
+            },
+        };
+
         let fragment_required = !targets.is_empty()
             || (key.mesh_key.contains(MeshPipelineKey::MAY_DISCARD)
                 && self.material_fragment_shader.is_some());
@@ -512,11 +543,7 @@ where
                     read_mask: 0,
                     write_mask: 0,
                 },
-                bias: DepthBiasState {
-                    constant: 0,
-                    slope_scale: 0.0,
-                    clamp: 0.0,
-                },
+                bias,
             }),
             multisample: MultisampleState {
                 count: key.mesh_key.msaa_samples(),
@@ -793,7 +820,9 @@ pub fn queue_prepass_material_meshes<M: Material>(
         let rangefinder = view.rangefinder3d();
 
         for visible_entity in &visible_entities.entities {
-            let Ok((material_handle, mesh_handle, mesh_uniform)) = material_meshes.get(*visible_entity) else {
+            let Ok((material_handle, mesh_handle, mesh_uniform)) =
+                material_meshes.get(*visible_entity)
+            else {
                 continue;
             };
 
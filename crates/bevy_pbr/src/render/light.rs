@@ -1,9 +1,10 @@
 use crate::{
     directional_light_order, point_light_order, AlphaMode, AmbientLight, Cascade,
-    CascadeShadowConfig, Cascades, CascadesVisibleEntities, Clusters, CubemapVisibleEntities,
-    DirectionalLight, DirectionalLightShadowMap, DrawPrepass, EnvironmentMapLight,
-    GlobalVisiblePointLights, Material, MaterialPipelineKey, MeshPipeline, MeshPipelineKey,
-    NotShadowCaster, PointLight, PointLightShadowMap, PrepassPipeline, RenderMaterials, SpotLight,
+    CascadeShadowConfig, Cascades, CascadesVisibleEntities, Clusters, ContactShadows,
+    CubemapVisibleEntities, DirectionalLight, DirectionalLightShadowMap, DrawPrepass,
+    EnvironmentMapLight, EnvironmentMapProjection, GlobalVisiblePointLights, IrradianceVolume,
+    Material, MaterialPipelineKey, MeshPipeline, MeshPipelineKey, NotShadowCaster, PointLight,
+    PointLightShadowMap, PrepassPipeline, RenderMaterials, ShadowTransparency, SpotLight,
     VisiblePointLights,
 };
 use bevy_asset::Handle;
@@ -212,6 +213,18 @@ pub struct GpuLights {
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
     environment_map_smallest_specular_mip_level: u32,
+    // the box of an `EnvironmentMapProjection::Box`, only used if the flag is 1
+    environment_map_box_min: Vec3,
+    environment_map_box_projection: u32,
+    environment_map_box_max: Vec3,
+    contact_shadows_max_distance: f32,
+    contact_shadows_thickness: f32,
+    // 0 disables the contact shadows
+    contact_shadows_samples: u32,
+    irradiance_volume_world_to_local: Mat4,
+    irradiance_volume_probe_count: UVec3,
+    // 0 if there is no `IrradianceVolume` or its texture isn't loaded
+    irradiance_volume_enabled: u32,
 }
 
 // NOTE: this must be kept in sync with the same constants in pbr.frag
@@ -657,10 +670,13 @@ pub fn prepare_lights(
             &ExtractedView,
             &ExtractedClusterConfig,
             Option<&EnvironmentMapLight>,
+            Option<&EnvironmentMapProjection>,
+            Option<&ContactShadows>,
         ),
         With<RenderPhase<Transparent3d>>,
     >,
     ambient_light: Res<AmbientLight>,
+    irradiance_volume: Option<Res<IrradianceVolume>>,
     point_light_shadow_map: Res<PointLightShadowMap>,
     directional_light_shadow_map: Res<DirectionalLightShadowMap>,
     mut max_directional_lights_warning_emitted: Local<bool>,
@@ -894,7 +910,15 @@ pub fn prepare_lights(
         .write_buffer(&render_device, &render_queue);
 
     // set up light data for each view
-    for (entity, extracted_view, clusters, environment_map) in &views {
+    for (
+        entity,
+        extracted_view,
+        clusters,
+        environment_map,
+        environment_map_projection,
+        contact_shadows,
+    ) in &views
+    {
         let point_light_depth_texture = texture_cache.get(
             &render_device,
             TextureDescriptor {
@@ -965,7 +989,31 @@ pub fn prepare_lights(
                 .and_then(|env_map| images.get(&env_map.specular_map))
                 .map(|specular_map| specular_map.mip_level_count - 1)
                 .unwrap_or(0),
+            environment_map_box_min: Vec3::ZERO,
+            environment_map_box_projection: 0,
+            environment_map_box_max: Vec3::ZERO,
+            contact_shadows_max_distance: contact_shadows.map_or(0.0, |c| c.max_distance),
+            contact_shadows_thickness: contact_shadows.map_or(0.0, |c| c.thickness),
+            contact_shadows_samples: contact_shadows.map_or(0, |c| c.samples as u32),
+            irradiance_volume_world_to_local: Mat4::IDENTITY,
+            irradiance_volume_probe_count: UVec3::ZERO,
+            irradiance_volume_enabled: 0,
         };
+        if let Some(EnvironmentMapProjection::Box { aabb }) = environment_map_projection {
+            gpu_lights.environment_map_box_min = aabb.min().into();
+            gpu_lights.environment_map_box_projection = 1;
+            gpu_lights.environment_map_box_max = aabb.max().into();
+        }
+        if let Some(irradiance_volume) = irradiance_volume
+            .as_deref()
+            .filter(|irradiance_volume| images.get(&irradiance_volume.texture).is_some())
+        {
+            gpu_lights.irradiance_volume_world_to_local =
+                irradiance_volume.transform.compute_matrix().inverse();
+            gpu_lights.irradiance_volume_probe_count =
+                irradiance_volume.probe_count.max(UVec3::ONE);
+            gpu_lights.irradiance_volume_enabled = 1;
+        }
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
         for &(light_entity, light) in point_lights
@@ -1606,15 +1654,25 @@ pub fn queue_shadows<M: Material>(
                         if is_directional_light {
                             mesh_key |= MeshPipelineKey::DEPTH_CLAMP_ORTHO;
                         }
-                        let alpha_mode = material.properties.alpha_mode;
-                        match alpha_mode {
-                            AlphaMode::Mask(_)
-                            | AlphaMode::Blend
-                            | AlphaMode::Premultiplied
-                            | AlphaMode::Add => {
-                                mesh_key |= MeshPipelineKey::MAY_DISCARD;
+                        match material.properties.shadow_transparency {
+                            Some(ShadowTransparency::Opaque) => {}
+                            Some(ShadowTransparency::Masked) => {
+                                mesh_key |= MeshPipelineKey::MAY_DISCARD
+                                    | MeshPipelineKey::SHADOW_ALPHA_MASK;
+                            }
+                            Some(ShadowTransparency::Blended) => {
+                                mesh_key |= MeshPipelineKey::MAY_DISCARD
+                                    | MeshPipelineKey::SHADOW_ALPHA_BLEND;
                             }
-                            _ => {}
+                            None => match material.properties.alpha_mode {
+                                AlphaMode::Mask(_)
+                                | AlphaMode::Blend
+                                | AlphaMode::Premultiplied
+                                | AlphaMode::Add => {
+                                    mesh_key |= MeshPipelineKey::MAY_DISCARD;
+                                }
+                                _ => {}
+                            },
                         }
                         let pipeline_id = pipelines.specialize(
                             &pipeline_cache,
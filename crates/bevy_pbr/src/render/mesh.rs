@@ -1,9 +1,10 @@
 use crate::{
-    environment_map, prepass, EnvironmentMapLight, FogMeta, GlobalLightMeta, GpuFog, GpuLights,
-    GpuPointLights, LightMeta, NotShadowCaster, NotShadowReceiver, PreviousGlobalTransform,
-    ShadowSamplers, ViewClusterBindings, ViewFogUniformOffset, ViewLightsUniformOffset,
-    ViewShadowBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_CASCADES_PER_LIGHT,
-    MAX_DIRECTIONAL_LIGHTS,
+    environment_map, irradiance_volume, irradiance_volume::IrradianceVolumeFallback, prepass,
+    EnvironmentMapLight, FogMeta, GlobalLightMeta, GpuFog, GpuLights, GpuPointLights,
+    IrradianceVolume, LightMeta, NotShadowCaster, NotShadowReceiver, PreviousGlobalTransform,
+    ShadowSamplers, SphericalHarmonics, UseIrradianceVolume, ViewClusterBindings,
+    ViewFogUniformOffset, ViewLightsUniformOffset, ViewShadowBindings,
+    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT, MAX_CASCADES_PER_LIGHT, MAX_DIRECTIONAL_LIGHTS,
 };
 use bevy_app::Plugin;
 use bevy_asset::{load_internal_asset, Assets, Handle, HandleUntyped};
@@ -18,7 +19,7 @@ use bevy_ecs::{
     query::ROQueryItem,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
-use bevy_math::{Mat3A, Mat4, Vec2};
+use bevy_math::{Mat3A, Mat4, Vec2, Vec3};
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
@@ -132,6 +133,8 @@ pub struct MeshUniform {
     pub previous_transform: Mat4,
     pub inverse_transpose_model: Mat4,
     pub flags: u32,
+    /// The [`SphericalHarmonics`] of the mesh, only used with the spherical harmonics flag
+    pub spherical_harmonics: [Vec3; 9],
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_types.wgsl!
@@ -139,6 +142,8 @@ bitflags::bitflags! {
     #[repr(transparent)]
     struct MeshFlags: u32 {
         const SHADOW_RECEIVER            = (1 << 0);
+        const SPHERICAL_HARMONICS        = (1 << 1);
+        const IRRADIANCE_VOLUME          = (1 << 2);
         // Indicates the sign of the determinant of the 3x3 model matrix. If the sign is positive,
         // then the flag should be set, else it should not be set.
         const SIGN_DETERMINANT_MODEL_3X3 = (1 << 31);
@@ -160,6 +165,8 @@ pub fn extract_meshes(
             &Handle<Mesh>,
             Option<With<NotShadowReceiver>>,
             Option<With<NotShadowCaster>>,
+            Option<&SphericalHarmonics>,
+            Option<With<UseIrradianceVolume>>,
         )>,
     >,
 ) {
@@ -167,8 +174,17 @@ pub fn extract_meshes(
     let mut not_caster_commands = Vec::with_capacity(*prev_not_caster_commands_len);
     let visible_meshes = meshes_query.iter().filter(|(_, vis, ..)| vis.is_visible());
 
-    for (entity, _, transform, previous_transform, handle, not_receiver, not_caster) in
-        visible_meshes
+    for (
+        entity,
+        _,
+        transform,
+        previous_transform,
+        handle,
+        not_receiver,
+        not_caster,
+        spherical_harmonics,
+        use_irradiance_volume,
+    ) in visible_meshes
     {
         let transform = transform.compute_matrix();
         let previous_transform = previous_transform.map(|t| t.0).unwrap_or(transform);
@@ -180,11 +196,20 @@ pub fn extract_meshes(
         if Mat3A::from_mat4(transform).determinant().is_sign_positive() {
             flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
         }
+        if spherical_harmonics.is_some() {
+            flags |= MeshFlags::SPHERICAL_HARMONICS;
+        }
+        if use_irradiance_volume.is_some() {
+            flags |= MeshFlags::IRRADIANCE_VOLUME;
+        }
         let uniform = MeshUniform {
             flags: flags.bits(),
             transform,
             previous_transform,
             inverse_transpose_model: transform.inverse().transpose(),
+            spherical_harmonics: spherical_harmonics
+                .map(|spherical_harmonics| spherical_harmonics.coefficients)
+                .unwrap_or_default(),
         };
         if not_caster.is_some() {
             not_caster_commands.push((entity, (handle.clone_weak(), uniform, NotShadowCaster)));
@@ -453,6 +478,11 @@ impl FromWorld for MeshPipeline {
                 ));
             }
 
+            // IrradianceVolume
+            let irradiance_volume_entries =
+                irradiance_volume::get_bind_group_layout_entries([19, 20]);
+            entries.extend_from_slice(&irradiance_volume_entries);
+
             entries
         }
 
@@ -588,6 +618,9 @@ bitflags::bitflags! {
         const ENVIRONMENT_MAP                   = (1 << 7);
         const DEPTH_CLAMP_ORTHO                 = (1 << 8);
         const TAA                               = (1 << 9);
+        const CONTACT_SHADOWS                   = (1 << 10);
+        const SHADOW_ALPHA_MASK                 = (1 << 11); // ← Shadow pass variants of a material with a `ShadowTransparency`
+        const SHADOW_ALPHA_BLEND                = (1 << 12);
         const BLEND_RESERVED_BITS               = Self::BLEND_MASK_BITS << Self::BLEND_SHIFT_BITS; // ← Bitmask reserving bits for the blend state
         const BLEND_OPAQUE                      = (0 << Self::BLEND_SHIFT_BITS);                   // ← Values are just sequential within the mask, and can range from 0 to 3
         const BLEND_PREMULTIPLIED_ALPHA         = (1 << Self::BLEND_SHIFT_BITS);                   //
@@ -810,6 +843,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("TAA".into());
         }
 
+        if key.contains(MeshPipelineKey::CONTACT_SHADOWS) {
+            shader_defs.push("CONTACT_SHADOWS".into());
+        }
+
         let format = if key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -981,7 +1018,11 @@ pub fn queue_mesh_view_bind_groups(
     images: Res<RenderAssets<Image>>,
     mut fallback_images: FallbackImagesMsaa,
     mut fallback_depths: FallbackImagesDepth,
-    fallback_cubemap: Res<FallbackImageCubemap>,
+    (fallback_cubemap, irradiance_volume, irradiance_volume_fallback): (
+        Res<FallbackImageCubemap>,
+        Option<Res<IrradianceVolume>>,
+        Res<IrradianceVolumeFallback>,
+    ),
     msaa: Res<Msaa>,
     globals_buffer: Res<GlobalsBuffer>,
     tonemapping_luts: Res<TonemappingLuts>,
@@ -1090,6 +1131,14 @@ pub fn queue_mesh_view_bind_groups(
                 ));
             }
 
+            let irradiance_volume_entries = irradiance_volume::get_bindings(
+                irradiance_volume.as_deref(),
+                &images,
+                &irradiance_volume_fallback,
+                [19, 20],
+            );
+            entries.extend_from_slice(&irradiance_volume_entries);
+
             let view_bind_group = render_device.create_bind_group(&BindGroupDescriptor {
                 entries: &entries,
                 label: Some("mesh_view_bind_group"),
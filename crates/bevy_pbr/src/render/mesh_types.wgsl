@@ -6,6 +6,8 @@ struct Mesh {
     inverse_transpose_model: mat4x4<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
+    // The spherical harmonics of the indirect diffuse light, if MESH_FLAGS_SPHERICAL_HARMONICS_BIT is set
+    spherical_harmonics: array<vec3<f32>, 9>,
 };
 
 #ifdef SKINNED
@@ -15,5 +17,7 @@ struct SkinnedMesh {
 #endif
 
 const MESH_FLAGS_SHADOW_RECEIVER_BIT: u32 = 1u;
+const MESH_FLAGS_SPHERICAL_HARMONICS_BIT: u32 = 2u;
+const MESH_FLAGS_IRRADIANCE_VOLUME_BIT: u32 = 4u;
 // 2^31 - if the flag is set, the sign is positive, else it is negative
 const MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT: u32 = 2147483648u;
@@ -73,3 +73,8 @@ var normal_prepass_texture: texture_2d<f32>;
 @group(0) @binding(18)
 var motion_vector_prepass_texture: texture_2d<f32>;
 #endif
+
+@group(0) @binding(19)
+var irradiance_volume_texture: texture_3d<f32>;
+@group(0) @binding(20)
+var irradiance_volume_sampler: sampler;
@@ -60,6 +60,18 @@ struct Lights {
     n_directional_lights: u32,
     spot_light_shadowmap_offset: i32,
     environment_map_smallest_specular_mip_level: u32,
+    // the box the environment map is projected on, only used if environment_map_box_projection is 1u
+    environment_map_box_min: vec3<f32>,
+    environment_map_box_projection: u32,
+    environment_map_box_max: vec3<f32>,
+    // see `ContactShadows`, 0u samples disables the contact shadows
+    contact_shadows_max_distance: f32,
+    contact_shadows_thickness: f32,
+    contact_shadows_samples: u32,
+    // see `IrradianceVolume`, only used if irradiance_volume_enabled is 1u
+    irradiance_volume_world_to_local: mat4x4<f32>,
+    irradiance_volume_probe_count: vec3<u32>,
+    irradiance_volume_enabled: u32,
 };
 
 struct Fog {
@@ -6,6 +6,7 @@
 #import bevy_pbr::clustered_forward
 #import bevy_pbr::lighting
 #import bevy_pbr::pbr_ambient
+#import bevy_pbr::irradiance_volume
 #import bevy_pbr::shadows
 #import bevy_pbr::fog
 #import bevy_pbr::pbr_functions
@@ -65,6 +66,9 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         pbr_input.material.reflectance = material.reflectance;
         pbr_input.material.flags = material.flags;
         pbr_input.material.alpha_cutoff = material.alpha_cutoff;
+        pbr_input.material.subsurface_color = material.subsurface_color;
+        pbr_input.material.transmission_tint = material.transmission_tint;
+        pbr_input.material.subsurface_strength = material.subsurface_strength;
 
         // TODO use .a for exposure compensation in HDR
         var emissive: vec4<f32> = material.emissive;
@@ -94,6 +98,14 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
             occlusion = textureSample(occlusion_texture, occlusion_sampler, uv).r;
         }
 #endif
+        var subsurface_thickness: f32 = 0.0;
+#ifdef VERTEX_UVS
+        if ((material.flags & STANDARD_MATERIAL_FLAGS_SUBSURFACE_THICKNESS_TEXTURE_BIT) != 0u) {
+            subsurface_thickness = textureSample(subsurface_thickness_texture, subsurface_thickness_sampler, uv).r;
+        }
+#endif
+        pbr_input.subsurface_thickness = subsurface_thickness;
+
         pbr_input.frag_coord = in.frag_coord;
         pbr_input.world_position = in.world_position;
 
@@ -125,6 +137,17 @@ fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
         pbr_input.occlusion = occlusion;
 
         pbr_input.flags = mesh.flags;
+        if ((mesh.flags & MESH_FLAGS_SPHERICAL_HARMONICS_BIT) != 0u) {
+            pbr_input.diffuse_ambient_color = spherical_harmonics_irradiance(mesh.spherical_harmonics, pbr_input.N);
+        } else {
+            pbr_input.diffuse_ambient_color = lights.ambient_color.rgb;
+        }
+        if ((mesh.flags & MESH_FLAGS_IRRADIANCE_VOLUME_BIT) != 0u && lights.irradiance_volume_enabled != 0u) {
+            let local_position = irradiance_volume_local_position(pbr_input.world_position.xyz);
+            if (irradiance_volume_contains(local_position)) {
+                pbr_input.diffuse_ambient_color = irradiance_volume_irradiance(local_position, pbr_input.N);
+            }
+        }
 
         output_color = pbr(pbr_input);
     } else {
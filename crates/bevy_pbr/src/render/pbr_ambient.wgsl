@@ -11,9 +11,30 @@ fn ambient_light(
     specular_color: vec3<f32>,
     perceptual_roughness: f32,
     occlusion: f32,
+    diffuse_ambient_color: vec3<f32>,
 ) -> vec3<f32> {
     let diffuse_ambient = EnvBRDFApprox(diffuse_color, F_AB(1.0, NdotV)) * occlusion;
     let specular_ambient = EnvBRDFApprox(specular_color, F_AB(perceptual_roughness, NdotV));
 
-    return (diffuse_ambient + specular_ambient) * lights.ambient_color.rgb;
+    return diffuse_ambient * diffuse_ambient_color + specular_ambient * lights.ambient_color.rgb;
+}
+
+// Evaluates the diffuse light along the normal N from the spherical harmonics of the incoming
+// light, see `SphericalHarmonics`.
+//
+// The bands are convolved with the cosine lobe and divided by π, so that uniform light gives
+// the same result as an ambient light of the same color: https://cseweb.ucsd.edu/~ravir/papers/envmap/envmap.pdf
+fn spherical_harmonics_irradiance(coefficients: array<vec3<f32>, 9>, N: vec3<f32>) -> vec3<f32> {
+    // The normalization of each basis function multiplied with the convolution of its band
+    // (1, 2/3 and 1/4)
+    var irradiance = coefficients[0] * 0.282095;
+    irradiance += coefficients[1] * (0.325735 * N.y);
+    irradiance += coefficients[2] * (0.325735 * N.z);
+    irradiance += coefficients[3] * (0.325735 * N.x);
+    irradiance += coefficients[4] * (0.273137 * N.x * N.y);
+    irradiance += coefficients[5] * (0.273137 * N.y * N.z);
+    irradiance += coefficients[6] * (0.078848 * (3.0 * N.z * N.z - 1.0));
+    irradiance += coefficients[7] * (0.273137 * N.x * N.z);
+    irradiance += coefficients[8] * (0.136569 * (N.x * N.x - N.y * N.y));
+    return max(irradiance, vec3(0.0));
 }
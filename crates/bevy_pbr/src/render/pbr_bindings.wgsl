@@ -28,3 +28,7 @@ var normal_map_sampler: sampler;
 var depth_map_texture: texture_2d<f32>;
 @group(1) @binding(12)
 var depth_map_sampler: sampler;
+@group(1) @binding(13)
+var subsurface_thickness_texture: texture_2d<f32>;
+@group(1) @binding(14)
+var subsurface_thickness_sampler: sampler;
@@ -127,6 +127,8 @@ fn calculate_view(
 struct PbrInput {
     material: StandardMaterial,
     occlusion: f32,
+    // The thickness used to attenuate the transmission of subsurface scattering, from 0.0 to 1.0
+    subsurface_thickness: f32,
     frag_coord: vec4<f32>,
     world_position: vec4<f32>,
     // Normalized world normal used for shadow mapping as normal-mapping is not used for shadow
@@ -139,6 +141,9 @@ struct PbrInput {
     V: vec3<f32>,
     is_orthographic: bool,
     flags: u32,
+    // The ambient light of the diffuse indirect term, the `AmbientLight` or the
+    // `SphericalHarmonics` of the mesh along N
+    diffuse_ambient_color: vec3<f32>,
 };
 
 // Creates a PbrInput with default values
@@ -147,6 +152,7 @@ fn pbr_input_new() -> PbrInput {
 
     pbr_input.material = standard_material_new();
     pbr_input.occlusion = 1.0;
+    pbr_input.subsurface_thickness = 0.0;
 
     pbr_input.frag_coord = vec4<f32>(0.0, 0.0, 0.0, 1.0);
     pbr_input.world_position = vec4<f32>(0.0, 0.0, 0.0, 1.0);
@@ -158,6 +164,7 @@ fn pbr_input_new() -> PbrInput {
     pbr_input.V = vec3<f32>(1.0, 0.0, 0.0);
 
     pbr_input.flags = 0u;
+    pbr_input.diffuse_ambient_color = lights.ambient_color.rgb;
 
     return pbr_input;
 }
@@ -197,6 +204,12 @@ fn pbr(
 
     var direct_light: vec3<f32> = vec3<f32>(0.0);
 
+    let subsurface_enabled = (in.material.flags & STANDARD_MATERIAL_FLAGS_SUBSURFACE_BIT) != 0u;
+    var subsurface: SubsurfaceInput;
+    subsurface.color = in.material.subsurface_color.rgb;
+    subsurface.strength = in.material.subsurface_strength;
+    subsurface.transmission = in.material.transmission_tint.rgb * (1.0 - in.subsurface_thickness);
+
     let view_z = dot(vec4<f32>(
         view.inverse_view[0].z,
         view.inverse_view[1].z,
@@ -213,9 +226,17 @@ fn pbr(
         if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (point_lights.data[light_id].flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = fetch_point_shadow(light_id, in.world_position, in.world_normal);
+#ifdef CONTACT_SHADOWS
+            let surface_to_light = point_lights.data[light_id].position_radius.xyz - in.world_position.xyz;
+            let light_distance = length(surface_to_light);
+            shadow = min(shadow, fetch_contact_shadow(in.frag_coord.xy, in.world_position.xyz, surface_to_light / light_distance, light_distance));
+#endif
         }
         let light_contrib = point_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
+        if (subsurface_enabled) {
+            direct_light += point_light_subsurface(in.world_position.xyz, light_id, in.N, in.V, subsurface, shadow);
+        }
     }
 
     // Spot lights (direct)
@@ -225,9 +246,18 @@ fn pbr(
         if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (point_lights.data[light_id].flags & POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = fetch_spot_shadow(light_id, in.world_position, in.world_normal);
+#ifdef CONTACT_SHADOWS
+            let surface_to_light = point_lights.data[light_id].position_radius.xyz - in.world_position.xyz;
+            let light_distance = length(surface_to_light);
+            shadow = min(shadow, fetch_contact_shadow(in.frag_coord.xy, in.world_position.xyz, surface_to_light / light_distance, light_distance));
+#endif
         }
         let light_contrib = spot_light(in.world_position.xyz, light_id, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
         direct_light += light_contrib * shadow;
+        if (subsurface_enabled) {
+            direct_light += point_light_subsurface(in.world_position.xyz, light_id, in.N, in.V, subsurface, shadow)
+                * spot_light_attenuation(in.world_position.xyz, light_id);
+        }
     }
 
     // Directional lights (direct)
@@ -237,20 +267,27 @@ fn pbr(
         if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
                 && (lights.directional_lights[i].flags & DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
+#ifdef CONTACT_SHADOWS
+            let direction_to_light = lights.directional_lights[i].direction_to_light;
+            shadow = min(shadow, fetch_contact_shadow(in.frag_coord.xy, in.world_position.xyz, direction_to_light, lights.contact_shadows_max_distance));
+#endif
         }
         var light_contrib = directional_light(i, roughness, NdotV, in.N, in.V, R, F0, f_ab, diffuse_color);
 #ifdef DIRECTIONAL_LIGHT_SHADOW_MAP_DEBUG_CASCADES
         light_contrib = cascade_debug_visualization(light_contrib, i, view_z);
 #endif
         direct_light += light_contrib * shadow;
+        if (subsurface_enabled) {
+            direct_light += directional_light_subsurface(i, in.N, in.V, subsurface, shadow);
+        }
     }
 
     // Ambient light (indirect)
-    var indirect_light = ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, occlusion);
+    var indirect_light = ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, occlusion, in.diffuse_ambient_color);
 
     // Environment map light (indirect)
 #ifdef ENVIRONMENT_MAP
-    let environment_light = environment_map_light(perceptual_roughness, roughness, diffuse_color, NdotV, f_ab, in.N, R, F0);
+    let environment_light = environment_map_light(perceptual_roughness, roughness, diffuse_color, NdotV, f_ab, in.world_position.xyz, in.N, R, F0);
     indirect_light += (environment_light.diffuse * occlusion) + environment_light.specular;
 #endif
 
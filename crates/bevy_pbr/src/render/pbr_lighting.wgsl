@@ -244,6 +244,11 @@ fn spot_light(
     // reuse the point light calculations
     let point_light = point_light(world_position, light_id, roughness, NdotV, N, V, R, F0, f_ab, diffuseColor);
 
+    return point_light * spot_light_attenuation(world_position, light_id);
+}
+
+// The attenuation of a spot light at `world_position` from the angle to its direction
+fn spot_light_attenuation(world_position: vec3<f32>, light_id: u32) -> f32 {
     let light = &point_lights.data[light_id];
 
     // reconstruct spot dir from x/z and y-direction flag
@@ -259,9 +264,7 @@ fn spot_light(
     // note we normalize here to get "l" from the filament listing. spot_dir is already normalized
     let cd = dot(-spot_dir, normalize(light_to_frag));
     let attenuation = saturate(cd * (*light).light_custom_data.z + (*light).light_custom_data.w);
-    let spot_attenuation = attenuation * attenuation;
-
-    return point_light * spot_attenuation;
+    return attenuation * attenuation;
 }
 
 fn directional_light(light_id: u32, roughness: f32, NdotV: f32, normal: vec3<f32>, view: vec3<f32>, R: vec3<f32>, F0: vec3<f32>, f_ab: vec2<f32>, diffuseColor: vec3<f32>) -> vec3<f32> {
@@ -280,3 +283,56 @@ fn directional_light(light_id: u32, roughness: f32, NdotV: f32, normal: vec3<f32
 
     return (specular_light + diffuse) * (*light).color.rgb * NoL;
 }
+
+// The subsurface scattering properties of a material, see `StandardMaterial::subsurface_strength`
+struct SubsurfaceInput {
+    color: vec3<f32>,
+    strength: f32,
+    // The transmission tint, already attenuated by the thickness
+    transmission: vec3<f32>,
+};
+
+// How much the transmitted light is bent toward the normal, and how focused it is
+const SUBSURFACE_TRANSMISSION_DISTORTION: f32 = 0.2;
+const SUBSURFACE_TRANSMISSION_POWER: f32 = 4.0;
+
+// Approximates the light scattered below the surface from a light in direction `L`, on top of
+// the regular diffuse term.
+//
+// Light wraps around the surface ("wrap lighting"), and light behind thin surfaces goes
+// through them toward the view, see https://www.ea.com/frostbite/news/approximating-translucency-for-a-fast-cheap-and-convincing-subsurface-scattering-look
+// The shadow only applies to the wrapped light, as the surface itself shadows transmitted light.
+fn subsurface_scattering(N: vec3<f32>, V: vec3<f32>, L: vec3<f32>, subsurface: SubsurfaceInput, shadow: f32) -> vec3<f32> {
+    let NdotL = dot(N, L);
+    let wrapped = saturate((NdotL + subsurface.strength) / (1.0 + subsurface.strength));
+    let scattered = subsurface.color * (wrapped - saturate(NdotL)) * (1.0 / PI);
+
+    let transmitted_L = normalize(L + N * SUBSURFACE_TRANSMISSION_DISTORTION);
+    let transmitted = subsurface.transmission * pow(saturate(dot(V, -transmitted_L)), SUBSURFACE_TRANSMISSION_POWER);
+
+    return scattered * shadow + transmitted;
+}
+
+fn point_light_subsurface(
+    world_position: vec3<f32>,
+    light_id: u32,
+    N: vec3<f32>,
+    V: vec3<f32>,
+    subsurface: SubsurfaceInput,
+    shadow: f32,
+) -> vec3<f32> {
+    let light = &point_lights.data[light_id];
+    let light_to_frag = (*light).position_radius.xyz - world_position.xyz;
+    let distance_square = dot(light_to_frag, light_to_frag);
+    let rangeAttenuation = getDistanceAttenuation(distance_square, (*light).color_inverse_square_range.w);
+    let L = normalize(light_to_frag);
+
+    return subsurface_scattering(N, V, L, subsurface, shadow) * (*light).color_inverse_square_range.rgb * rangeAttenuation;
+}
+
+fn directional_light_subsurface(light_id: u32, N: vec3<f32>, V: vec3<f32>, subsurface: SubsurfaceInput, shadow: f32) -> vec3<f32> {
+    let light = &lights.directional_lights[light_id];
+    let L = (*light).direction_to_light.xyz;
+
+    return subsurface_scattering(N, V, L, subsurface, shadow) * (*light).color.rgb;
+}
@@ -27,6 +27,14 @@ struct FragmentInput {
 // Cutoff used for the premultiplied alpha modes BLEND and ADD.
 const PREMULTIPLIED_ALPHA_CUTOFF = 0.05;
 
+#ifdef SHADOW_ALPHA_BLEND
+// A per-pixel threshold in [0, 1) that is evenly distributed over small areas of the shadow map
+// https://www.iryoku.com/next-generation-post-processing-in-call-of-duty-advanced-warfare
+fn interleaved_gradient_noise(pixel_coordinates: vec2<f32>) -> f32 {
+    return fract(52.9829189 * fract(dot(pixel_coordinates, vec2(0.06711056, 0.00583715))));
+}
+#endif // SHADOW_ALPHA_BLEND
+
 // We can use a simplified version of alpha_discard() here since we only need to handle the alpha_cutoff
 fn prepass_alpha_discard(in: FragmentInput) {
 
@@ -39,6 +47,17 @@ fn prepass_alpha_discard(in: FragmentInput) {
     }
 #endif // VERTEX_UVS
 
+#ifdef SHADOW_ALPHA_MASK
+    if output_color.a < material.alpha_cutoff {
+        discard;
+    }
+#else ifdef SHADOW_ALPHA_BLEND
+    // Casts a shadow from a share of the fragments given by the alpha, which the shadow filtering
+    // then averages into a partial shadow
+    if output_color.a <= interleaved_gradient_noise(in.frag_coord.xy) {
+        discard;
+    }
+#else
     let alpha_mode = material.flags & STANDARD_MATERIAL_FLAGS_ALPHA_MODE_RESERVED_BITS;
     if alpha_mode == STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK {
         if output_color.a < material.alpha_cutoff {
@@ -53,6 +72,7 @@ fn prepass_alpha_discard(in: FragmentInput) {
             discard;
         }
     }
+#endif // SHADOW_ALPHA_MASK
 
 #endif // MAY_DISCARD
 }
@@ -12,6 +12,9 @@ struct StandardMaterial {
     parallax_depth_scale: f32,
     max_parallax_layer_count: f32,
     max_relief_mapping_search_steps: u32,
+    subsurface_color: vec4<f32>,
+    transmission_tint: vec4<f32>,
+    subsurface_strength: f32,
 };
 
 const STANDARD_MATERIAL_FLAGS_BASE_COLOR_TEXTURE_BIT: u32         = 1u;
@@ -24,6 +27,8 @@ const STANDARD_MATERIAL_FLAGS_TWO_COMPONENT_NORMAL_MAP: u32       = 64u;
 const STANDARD_MATERIAL_FLAGS_FLIP_NORMAL_MAP_Y: u32              = 128u;
 const STANDARD_MATERIAL_FLAGS_FOG_ENABLED_BIT: u32                = 256u;
 const STANDARD_MATERIAL_FLAGS_DEPTH_MAP_BIT: u32                  = 512u;
+const STANDARD_MATERIAL_FLAGS_SUBSURFACE_BIT: u32                 = 1024u;
+const STANDARD_MATERIAL_FLAGS_SUBSURFACE_THICKNESS_TEXTURE_BIT: u32 = 2048u;
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_RESERVED_BITS: u32       = 3758096384u; // (0b111u32 << 29)
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_OPAQUE: u32              = 0u;          // (0u32 << 29)
 const STANDARD_MATERIAL_FLAGS_ALPHA_MODE_MASK: u32                = 536870912u;  // (1u32 << 29)
@@ -49,6 +54,9 @@ fn standard_material_new() -> StandardMaterial {
     material.parallax_depth_scale = 0.1;
     material.max_parallax_layer_count = 16.0;
     material.max_relief_mapping_search_steps = 5u;
+    material.subsurface_color = vec4<f32>(1.0, 1.0, 1.0, 1.0);
+    material.transmission_tint = vec4<f32>(0.0, 0.0, 0.0, 1.0);
+    material.subsurface_strength = 0.0;
 
     return material;
 }
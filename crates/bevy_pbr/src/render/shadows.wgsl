@@ -182,6 +182,46 @@ fn fetch_directional_shadow(light_id: u32, frag_position: vec4<f32>, surface_nor
     return shadow;
 }
 
+#ifdef CONTACT_SHADOWS
+// Returns the view space z of a position in normalized device coordinates
+fn ndc_to_view_z(ndc: vec3<f32>) -> f32 {
+    let view_position = view.inverse_projection * vec4(ndc, 1.0);
+    return view_position.z / view_position.w;
+}
+
+// Marches a ray from the fragment toward the light in the depth prepass, up to
+// `max_distance` away. Returns 0.0 if the ray goes behind a surface of the depth prepass,
+// less than `lights.contact_shadows_thickness` behind it, and 1.0 otherwise.
+fn fetch_contact_shadow(frag_coord: vec2<f32>, frag_position: vec3<f32>, direction_to_light: vec3<f32>, max_distance: f32) -> f32 {
+    let samples = lights.contact_shadows_samples;
+    if (samples == 0u) {
+        return 1.0;
+    }
+    let ray_step = direction_to_light * (min(max_distance, lights.contact_shadows_max_distance) / f32(samples));
+    let depth_size = vec2<f32>(textureDimensions(depth_prepass_texture));
+    // Interleaved gradient noise, to offset the start of the ray per pixel and hide the banding of the steps
+    // https://www.iryoku.com/next-generation-post-processing-in-call-of-duty-advanced-warfare
+    let jitter = fract(52.9829189 * fract(dot(frag_coord, vec2(0.06711056, 0.00583715))));
+
+    for (var i = 0u; i < samples; i += 1u) {
+        let ray_position = frag_position + ray_step * (f32(i) + jitter);
+        let clip_position = view.view_proj * vec4(ray_position, 1.0);
+        let ndc = clip_position.xyz / clip_position.w;
+        let uv = ndc.xy * vec2(0.5, -0.5) + vec2(0.5);
+        if (any(uv < vec2(0.0)) || any(uv >= vec2(1.0))) {
+            break;
+        }
+        let depth = textureLoad(depth_prepass_texture, vec2<i32>(uv * depth_size), 0);
+        // How far the ray is behind the surface, -z is in front of the camera
+        let behind = ndc_to_view_z(vec3(ndc.xy, depth)) - ndc_to_view_z(ndc);
+        if (behind > 0.0 && behind < lights.contact_shadows_thickness) {
+            return 0.0;
+        }
+    }
+    return 1.0;
+}
+#endif // CONTACT_SHADOWS
+
 fn cascade_debug_visualization(
     output_color: vec3<f32>,
     light_id: u32,
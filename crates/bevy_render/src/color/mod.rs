@@ -1812,6 +1812,18 @@ mod tests {
         assert_eq!(Color::hex("##fff"), Err(HexColorError::Char('#')));
     }
 
+    #[test]
+    fn linear_conversion_is_unclamped() {
+        // HDR colors must survive to the shader for bloom
+        assert_eq!(
+            Color::rgb_linear(4.0, 2.0, 0.5).as_linear_rgba_f32(),
+            [4.0, 2.0, 0.5, 1.0]
+        );
+        let [red, green, ..] = Color::rgb(2.0, 1.0, 0.0).as_linear_rgba_f32();
+        assert!(red > 4.0);
+        assert_eq!(green, 1.0);
+    }
+
     #[test]
     fn conversions_vec4() {
         let starting_vec4 = Vec4::new(0.4, 0.5, 0.6, 1.0);
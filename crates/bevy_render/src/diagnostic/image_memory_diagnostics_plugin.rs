@@ -0,0 +1,65 @@
+use crate::texture::{Image, ImageMemoryTag};
+use bevy_app::prelude::*;
+use bevy_asset::Assets;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics, RegisterDiagnostic};
+use bevy_ecs::prelude::*;
+
+/// Adds diagnostics reporting the estimated GPU memory used by [`Image`] assets to an [`App`].
+///
+/// The total is reported along with a breakdown by [`ImageMemoryTag`], in megabytes.
+/// See [`Image::estimated_gpu_size_bytes`] for how the size of each image is estimated.
+#[derive(Default)]
+pub struct ImageMemoryDiagnosticsPlugin;
+
+impl Plugin for ImageMemoryDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::TOTAL, "image_memory", 20).with_suffix("MB"));
+        for (id, tag) in Self::TAGGED {
+            let name = match tag {
+                ImageMemoryTag::Ui => "image_memory ui",
+                ImageMemoryTag::SpriteAtlas => "image_memory sprite_atlas",
+                ImageMemoryTag::FontAtlas => "image_memory font_atlas",
+                ImageMemoryTag::Other => "image_memory other",
+            };
+            app.register_diagnostic(Diagnostic::new(id, name, 20).with_suffix("MB"));
+        }
+        app.add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl ImageMemoryDiagnosticsPlugin {
+    pub const TOTAL: DiagnosticId =
+        DiagnosticId::from_u128(204716426360420370132738334368341328114);
+    pub const UI: DiagnosticId = DiagnosticId::from_u128(92279514614584870734154688525349588543);
+    pub const SPRITE_ATLAS: DiagnosticId =
+        DiagnosticId::from_u128(313307313441199071630429505397111197968);
+    pub const FONT_ATLAS: DiagnosticId =
+        DiagnosticId::from_u128(23165046287282426458172608834243212161);
+    pub const OTHER: DiagnosticId =
+        DiagnosticId::from_u128(157803283246437440284375530334704522339);
+
+    const TAGGED: [(DiagnosticId, ImageMemoryTag); 4] = [
+        (Self::UI, ImageMemoryTag::Ui),
+        (Self::SPRITE_ATLAS, ImageMemoryTag::SpriteAtlas),
+        (Self::FONT_ATLAS, ImageMemoryTag::FontAtlas),
+        (Self::OTHER, ImageMemoryTag::Other),
+    ];
+
+    /// Updates the estimated memory usage of all [`Image`] assets.
+    pub fn diagnostic_system(mut diagnostics: Diagnostics, images: Res<Assets<Image>>) {
+        let mut tagged_bytes = [0; 4];
+        for (_, image) in images.iter() {
+            let index = Self::TAGGED
+                .iter()
+                .position(|(_, tag)| *tag == image.memory_tag)
+                .unwrap();
+            tagged_bytes[index] += image.estimated_gpu_size_bytes();
+        }
+
+        let to_megabytes = |bytes: usize| bytes as f64 / (1024.0 * 1024.0);
+        diagnostics.add_measurement(Self::TOTAL, || to_megabytes(tagged_bytes.iter().sum()));
+        for ((id, _), bytes) in Self::TAGGED.into_iter().zip(tagged_bytes) {
+            diagnostics.add_measurement(id, || to_megabytes(bytes));
+        }
+    }
+}
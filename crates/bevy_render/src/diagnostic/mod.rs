@@ -0,0 +1,4 @@
+//! Diagnostic providers for `bevy_diagnostic`.
+
+mod image_memory_diagnostics_plugin;
+pub use image_memory_diagnostics_plugin::ImageMemoryDiagnosticsPlugin;
@@ -0,0 +1,280 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use async_channel::{Receiver, Sender};
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_ecs::prelude::*;
+use bevy_log::error;
+use bevy_utils::HashMap;
+use parking_lot::Mutex;
+use wgpu::{
+    BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoderDescriptor, ImageCopyBuffer,
+    ImageCopyTexture, ImageDataLayout, Maintain, MapMode, Origin3d, TextureAspect, TextureUsages,
+};
+
+use crate::{
+    render_resource::{Buffer, Texture},
+    renderer::{render_system, RenderDevice, RenderQueue},
+    Render, RenderApp, RenderSet,
+};
+
+/// Adds support for reading textures back from the GPU through the [`GpuReadback`] resource.
+pub struct GpuReadbackPlugin;
+
+impl Plugin for GpuReadbackPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = async_channel::unbounded();
+
+        app.add_event::<GpuReadbackComplete>()
+            .insert_resource(GpuReadbackReceiver(receiver))
+            .add_systems(PreUpdate, send_readback_events);
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(GpuReadback::new(sender))
+                .add_systems(
+                    Render,
+                    (
+                        submit_readbacks
+                            .in_set(RenderSet::Render)
+                            .after(render_system),
+                        collect_readbacks.in_set(RenderSet::Cleanup),
+                    ),
+                );
+        }
+    }
+}
+
+/// Identifies a readback requested with [`GpuReadback::request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GpuReadbackHandle(u64);
+
+/// Sent in the main world once the data of a readback is available on the CPU.
+///
+/// The data is tightly packed: row padding required by the copy is removed, and array layers
+/// (or depth slices) follow each other.
+#[derive(Event, Clone, Debug)]
+pub struct GpuReadbackComplete {
+    pub handle: GpuReadbackHandle,
+    pub data: Vec<u8>,
+}
+
+/// A render world resource used to copy textures back to the CPU.
+///
+/// Requests are copied at the end of the frame's rendering, and the result is sent as a
+/// [`GpuReadbackComplete`] event in the main world once the GPU has finished the copy.
+#[derive(Resource)]
+pub struct GpuReadback {
+    next_handle: AtomicU64,
+    // this is in a mutex so that requests can be made with only an immutable reference,
+    // for example from a render graph node
+    requests: Mutex<Vec<ReadbackRequest>>,
+    in_flight: HashMap<GpuReadbackHandle, InFlightReadback>,
+    mapped_sender: Sender<(GpuReadbackHandle, Result<(), BufferAsyncError>)>,
+    mapped_receiver: Receiver<(GpuReadbackHandle, Result<(), BufferAsyncError>)>,
+    complete_sender: Sender<GpuReadbackComplete>,
+}
+
+struct ReadbackRequest {
+    handle: GpuReadbackHandle,
+    texture: Texture,
+    mip: u32,
+}
+
+struct InFlightReadback {
+    buffer: Buffer,
+    unpadded_bytes_per_row: usize,
+    padded_bytes_per_row: usize,
+}
+
+impl GpuReadback {
+    fn new(complete_sender: Sender<GpuReadbackComplete>) -> Self {
+        let (mapped_sender, mapped_receiver) = async_channel::unbounded();
+        Self {
+            next_handle: AtomicU64::new(0),
+            requests: Default::default(),
+            in_flight: Default::default(),
+            mapped_sender,
+            mapped_receiver,
+            complete_sender,
+        }
+    }
+
+    /// Requests a copy of the given mip level of `texture` back to the CPU.
+    ///
+    /// The texture must have been created with [`TextureUsages::COPY_SRC`]. All array layers of
+    /// the mip level are read back.
+    pub fn request(&self, texture: &Texture, mip: u32) -> GpuReadbackHandle {
+        let handle = GpuReadbackHandle(self.next_handle.fetch_add(1, Ordering::Relaxed));
+        self.requests.lock().push(ReadbackRequest {
+            handle,
+            texture: texture.clone(),
+            mip,
+        });
+        handle
+    }
+}
+
+fn submit_readbacks(
+    mut readback: ResMut<GpuReadback>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let requests = std::mem::take(readback.requests.get_mut());
+    if requests.is_empty() {
+        return;
+    }
+
+    let mut encoder = render_device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("gpu_readback_encoder"),
+    });
+    let mut submitted = Vec::with_capacity(requests.len());
+    for ReadbackRequest {
+        handle,
+        texture,
+        mip,
+    } in requests
+    {
+        if mip >= texture.mip_level_count() {
+            error!(
+                "Cannot read back mip level {mip} of a texture with {} mip levels",
+                texture.mip_level_count()
+            );
+            continue;
+        }
+        if !texture.usage().contains(TextureUsages::COPY_SRC) {
+            error!("Cannot read back a texture created without TextureUsages::COPY_SRC");
+            continue;
+        }
+        let format = texture.format();
+        let Some(block_size) = format.block_size(None) else {
+            error!("Cannot read back a texture with format {format:?}");
+            continue;
+        };
+
+        let (block_width, block_height) = format.block_dimensions();
+        let extent = texture
+            .size()
+            .mip_level_size(mip, texture.dimension())
+            .physical_size(format);
+        let rows_per_image = extent.height / block_height;
+        let unpadded_bytes_per_row = (extent.width / block_width * block_size) as usize;
+        let padded_bytes_per_row = RenderDevice::align_copy_bytes_per_row(unpadded_bytes_per_row);
+        let rows = rows_per_image as usize * extent.depth_or_array_layers as usize;
+
+        let buffer = render_device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_readback_buffer"),
+            size: (padded_bytes_per_row * rows) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            ImageCopyTexture {
+                texture: &texture,
+                mip_level: mip,
+                origin: Origin3d::ZERO,
+                aspect: TextureAspect::All,
+            },
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row as u32),
+                    rows_per_image: Some(rows_per_image),
+                },
+            },
+            extent,
+        );
+        submitted.push((
+            handle,
+            InFlightReadback {
+                buffer,
+                unpadded_bytes_per_row,
+                padded_bytes_per_row,
+            },
+        ));
+    }
+    render_queue.submit([encoder.finish()]);
+
+    for (handle, in_flight) in submitted {
+        let sender = readback.mapped_sender.clone();
+        // The polling for this map call is done every frame when the command queue is submitted,
+        // and in `collect_readbacks`.
+        in_flight
+            .buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| {
+                let _ = sender.try_send((handle, result));
+            });
+        readback.in_flight.insert(handle, in_flight);
+    }
+}
+
+fn collect_readbacks(mut readback: ResMut<GpuReadback>, render_device: Res<RenderDevice>) {
+    if readback.in_flight.is_empty() {
+        return;
+    }
+    render_device.poll(Maintain::Poll);
+
+    let readback = &mut *readback;
+    while let Ok((handle, result)) = readback.mapped_receiver.try_recv() {
+        let Some(in_flight) = readback.in_flight.remove(&handle) else {
+            continue;
+        };
+        if let Err(err) = result {
+            error!("Failed to map GPU readback buffer: {err}");
+            continue;
+        }
+        let slice = in_flight.buffer.slice(..);
+        // we immediately move the data to CPU memory to avoid holding the mapped view for long
+        let data = strip_row_padding(
+            &slice.get_mapped_range(),
+            in_flight.unpadded_bytes_per_row,
+            in_flight.padded_bytes_per_row,
+        );
+        in_flight.buffer.unmap();
+        let _ = readback
+            .complete_sender
+            .try_send(GpuReadbackComplete { handle, data });
+    }
+}
+
+/// Removes the padding added at the end of each row to satisfy
+/// [`COPY_BYTES_PER_ROW_ALIGNMENT`](wgpu::COPY_BYTES_PER_ROW_ALIGNMENT).
+fn strip_row_padding(
+    padded: &[u8],
+    unpadded_bytes_per_row: usize,
+    padded_bytes_per_row: usize,
+) -> Vec<u8> {
+    if unpadded_bytes_per_row == padded_bytes_per_row {
+        return padded.to_vec();
+    }
+    padded
+        .chunks(padded_bytes_per_row)
+        .flat_map(|row| &row[..unpadded_bytes_per_row])
+        .copied()
+        .collect()
+}
+
+#[derive(Resource)]
+struct GpuReadbackReceiver(Receiver<GpuReadbackComplete>);
+
+fn send_readback_events(
+    receiver: Res<GpuReadbackReceiver>,
+    mut events: EventWriter<GpuReadbackComplete>,
+) {
+    while let Ok(complete) = receiver.0.try_recv() {
+        events.send(complete);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::strip_row_padding;
+
+    #[test]
+    fn strip_row_padding_removes_row_tails() {
+        let padded = [1, 2, 3, 0, 0, 4, 5, 6, 0, 0];
+        assert_eq!(strip_row_padding(&padded, 3, 5), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(strip_row_padding(&padded, 5, 5), padded.to_vec());
+    }
+}
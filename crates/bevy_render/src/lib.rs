@@ -7,10 +7,12 @@ extern crate core;
 
 pub mod camera;
 pub mod color;
+pub mod diagnostic;
 pub mod extract_component;
 mod extract_param;
 pub mod extract_resource;
 pub mod globals;
+pub mod gpu_readback;
 pub mod mesh;
 pub mod pipelined_rendering;
 pub mod primitives;
@@ -49,6 +51,7 @@ use wgpu::Instance;
 
 use crate::{
     camera::CameraPlugin,
+    gpu_readback::GpuReadbackPlugin,
     mesh::MeshPlugin,
     render_resource::{PipelineCache, Shader, ShaderLoader},
     renderer::{render_system, RenderInstance},
@@ -334,7 +337,8 @@ impl Plugin for RenderPlugin {
             .add_plugin(CameraPlugin)
             .add_plugin(ViewPlugin)
             .add_plugin(MeshPlugin)
-            .add_plugin(GlobalsPlugin);
+            .add_plugin(GlobalsPlugin)
+            .add_plugin(GpuReadbackPlugin);
 
         app.register_type::<color::Color>()
             .register_type::<primitives::Aabb>()
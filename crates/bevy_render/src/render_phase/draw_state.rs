@@ -2,14 +2,14 @@ use crate::{
     camera::Viewport,
     prelude::Color,
     render_resource::{
-        BindGroup, BindGroupId, Buffer, BufferId, BufferSlice, RenderPipeline, RenderPipelineId,
-        ShaderStages,
+        BindGroup, BindGroupId, Buffer, BufferId, BufferSlice, ComputePipeline, ComputePipelineId,
+        RenderPipeline, RenderPipelineId, ShaderStages,
     },
     renderer::RenderDevice,
 };
 use bevy_utils::{default, detailed_trace};
 use std::ops::Range;
-use wgpu::{IndexFormat, RenderPass};
+use wgpu::{ComputePass, IndexFormat, RenderPass};
 
 /// Tracks the state of a [`TrackedRenderPass`].
 ///
@@ -598,3 +598,182 @@ impl<'a> TrackedRenderPass<'a> {
         self.pass.set_blend_constant(wgpu::Color::from(color));
     }
 }
+
+/// Tracks the state of a [`TrackedComputePass`], like [`DrawState`] for a [`TrackedRenderPass`].
+#[derive(Debug, Default)]
+struct ComputeState {
+    pipeline: Option<ComputePipelineId>,
+    bind_groups: Vec<(Option<BindGroupId>, Vec<u32>)>,
+}
+
+impl ComputeState {
+    /// Marks the `pipeline` as bound.
+    pub fn set_pipeline(&mut self, pipeline: ComputePipelineId) {
+        self.pipeline = Some(pipeline);
+    }
+
+    /// Checks, whether the `pipeline` is already bound.
+    pub fn is_pipeline_set(&self, pipeline: ComputePipelineId) -> bool {
+        self.pipeline == Some(pipeline)
+    }
+
+    /// Marks the `bind_group` as bound to the `index`.
+    pub fn set_bind_group(
+        &mut self,
+        index: usize,
+        bind_group: BindGroupId,
+        dynamic_indices: &[u32],
+    ) {
+        let group = &mut self.bind_groups[index];
+        group.0 = Some(bind_group);
+        group.1.clear();
+        group.1.extend(dynamic_indices);
+    }
+
+    /// Checks, whether the `bind_group` is already bound to the `index`.
+    pub fn is_bind_group_set(
+        &self,
+        index: usize,
+        bind_group: BindGroupId,
+        dynamic_indices: &[u32],
+    ) -> bool {
+        if let Some(current_bind_group) = self.bind_groups.get(index) {
+            current_bind_group.0 == Some(bind_group) && dynamic_indices == current_bind_group.1
+        } else {
+            false
+        }
+    }
+}
+
+/// A [`ComputePass`], which tracks the current pipeline state to skip redundant operations.
+///
+/// It is the compute counterpart of a [`TrackedRenderPass`]: it is used to set the current
+/// [`ComputePipeline`] and [`BindGroup`]s, after which workgroups can be dispatched. Create one
+/// with [`RenderContext::begin_tracked_compute_pass`](crate::renderer::RenderContext::begin_tracked_compute_pass).
+pub struct TrackedComputePass<'a> {
+    pass: ComputePass<'a>,
+    state: ComputeState,
+}
+
+impl<'a> TrackedComputePass<'a> {
+    /// Tracks the supplied compute pass.
+    pub fn new(device: &RenderDevice, pass: ComputePass<'a>) -> Self {
+        let max_bind_groups = device.limits().max_bind_groups as usize;
+        Self {
+            state: ComputeState {
+                bind_groups: vec![(None, Vec::new()); max_bind_groups],
+                ..default()
+            },
+            pass,
+        }
+    }
+
+    /// Sets the active [`ComputePipeline`].
+    ///
+    /// Subsequent dispatches will run the shader of the `pipeline`.
+    pub fn set_compute_pipeline(&mut self, pipeline: &'a ComputePipeline) {
+        detailed_trace!("set compute pipeline: {:?}", pipeline);
+        if self.state.is_pipeline_set(pipeline.id()) {
+            return;
+        }
+        self.pass.set_pipeline(pipeline);
+        self.state.set_pipeline(pipeline.id());
+    }
+
+    /// Sets the active bind group for a given bind group index. The bind group layout
+    /// in the active pipeline when any `dispatch()` function is called must match the layout of
+    /// this bind group.
+    ///
+    /// If the bind group have dynamic offsets, provide them in binding order.
+    pub fn set_bind_group(
+        &mut self,
+        index: usize,
+        bind_group: &'a BindGroup,
+        dynamic_uniform_indices: &[u32],
+    ) {
+        if self
+            .state
+            .is_bind_group_set(index, bind_group.id(), dynamic_uniform_indices)
+        {
+            detailed_trace!(
+                "set compute bind_group {} (already set): {:?} ({:?})",
+                index,
+                bind_group,
+                dynamic_uniform_indices
+            );
+            return;
+        }
+        detailed_trace!(
+            "set compute bind_group {}: {:?} ({:?})",
+            index,
+            bind_group,
+            dynamic_uniform_indices
+        );
+
+        self.pass
+            .set_bind_group(index as u32, bind_group, dynamic_uniform_indices);
+        self.state
+            .set_bind_group(index, bind_group.id(), dynamic_uniform_indices);
+    }
+
+    /// Set push constant data.
+    ///
+    /// `Features::PUSH_CONSTANTS` must be enabled on the device in order to call this function.
+    pub fn set_push_constants(&mut self, offset: u32, data: &[u8]) {
+        detailed_trace!(
+            "set compute push constants: offset: {} data.len: {}",
+            offset,
+            data.len()
+        );
+        self.pass.set_push_constants(offset, data);
+    }
+
+    /// Dispatches `x` by `y` by `z` workgroups of the active pipeline.
+    pub fn dispatch_workgroups(&mut self, x: u32, y: u32, z: u32) {
+        detailed_trace!("dispatch workgroups: {} {} {}", x, y, z);
+        self.pass.dispatch_workgroups(x, y, z);
+    }
+
+    /// Dispatches workgroups of the active pipeline, with their count read from the
+    /// `indirect_buffer` at `indirect_offset`.
+    ///
+    /// The structure expected in `indirect_buffer` must conform to
+    /// [`DispatchIndirect`](https://docs.rs/wgpu/latest/wgpu/util/struct.DispatchIndirect.html).
+    pub fn dispatch_workgroups_indirect(
+        &mut self,
+        indirect_buffer: &'a Buffer,
+        indirect_offset: u64,
+    ) {
+        detailed_trace!(
+            "dispatch workgroups indirect: {:?} {}",
+            indirect_buffer,
+            indirect_offset
+        );
+        self.pass
+            .dispatch_workgroups_indirect(indirect_buffer, indirect_offset);
+    }
+
+    /// Insert a single debug marker.
+    ///
+    /// This is a GPU debugging feature. This has no effect on the computation itself.
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        detailed_trace!("insert compute debug marker: {}", label);
+        self.pass.insert_debug_marker(label);
+    }
+
+    /// Start a new debug group, see [`TrackedRenderPass::push_debug_group`].
+    ///
+    /// This is a GPU debugging feature. This has no effect on the computation itself.
+    pub fn push_debug_group(&mut self, label: &str) {
+        detailed_trace!("push_debug_group compute marker: {}", label);
+        self.pass.push_debug_group(label);
+    }
+
+    /// End the current debug group, see [`TrackedRenderPass::pop_debug_group`].
+    ///
+    /// This is a GPU debugging feature. This has no effect on the computation itself.
+    pub fn pop_debug_group(&mut self) {
+        detailed_trace!("pop_debug_group compute");
+        self.pass.pop_debug_group();
+    }
+}
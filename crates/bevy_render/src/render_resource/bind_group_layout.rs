@@ -1,5 +1,10 @@
-use crate::{define_atomic_id, render_resource::resource_macros::*};
-use std::ops::Deref;
+use crate::{define_atomic_id, render_resource::resource_macros::*, renderer::RenderDevice};
+use std::{num::NonZeroU64, ops::Deref};
+use wgpu::{
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingType, BufferBindingType,
+    SamplerBindingType, ShaderStages, StorageTextureAccess, TextureFormat, TextureSampleType,
+    TextureViewDimension,
+};
 
 define_atomic_id!(BindGroupLayoutId);
 render_resource_wrapper!(ErasedBindGroupLayout, wgpu::BindGroupLayout);
@@ -45,3 +50,210 @@ impl Deref for BindGroupLayout {
         &self.value
     }
 }
+
+/// Builds a [`BindGroupLayout`] one entry at a time, numbering the bindings in the order the
+/// entries are added.
+///
+/// ```ignore
+/// let layout = BindGroupLayoutBuilder::new()
+///     .label("sprite_material_layout")
+///     .texture(
+///         ShaderStages::FRAGMENT,
+///         TextureSampleType::Float { filterable: true },
+///         TextureViewDimension::D2,
+///     )
+///     .sampler(ShaderStages::FRAGMENT, SamplerBindingType::Filtering)
+///     .build(&render_device);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BindGroupLayoutBuilder<'a> {
+    label: Option<&'a str>,
+    entries: Vec<BindGroupLayoutEntry>,
+}
+
+impl<'a> BindGroupLayoutBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the debug label of the layout.
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = Some(label);
+        self
+    }
+
+    /// Adds an entry of any [`BindingType`] at the next binding index.
+    pub fn entry(mut self, visibility: ShaderStages, ty: BindingType) -> Self {
+        self.entries.push(BindGroupLayoutEntry {
+            binding: self.entries.len() as u32,
+            visibility,
+            ty,
+            count: None,
+        });
+        self
+    }
+
+    /// Adds a single-sampled texture entry.
+    pub fn texture(
+        self,
+        visibility: ShaderStages,
+        sample_type: TextureSampleType,
+        view_dimension: TextureViewDimension,
+    ) -> Self {
+        self.entry(
+            visibility,
+            BindingType::Texture {
+                sample_type,
+                view_dimension,
+                multisampled: false,
+            },
+        )
+    }
+
+    /// Adds a multisampled texture entry.
+    pub fn multisampled_texture(
+        self,
+        visibility: ShaderStages,
+        sample_type: TextureSampleType,
+        view_dimension: TextureViewDimension,
+    ) -> Self {
+        self.entry(
+            visibility,
+            BindingType::Texture {
+                sample_type,
+                view_dimension,
+                multisampled: true,
+            },
+        )
+    }
+
+    /// Adds a sampler entry.
+    pub fn sampler(self, visibility: ShaderStages, binding_type: SamplerBindingType) -> Self {
+        self.entry(visibility, BindingType::Sampler(binding_type))
+    }
+
+    /// Adds a uniform buffer entry.
+    ///
+    /// `min_binding_size` is usually the [`ShaderType::min_size`](encase::ShaderType::min_size)
+    /// of the uniform.
+    pub fn uniform_buffer(
+        self,
+        visibility: ShaderStages,
+        has_dynamic_offset: bool,
+        min_binding_size: Option<NonZeroU64>,
+    ) -> Self {
+        self.entry(
+            visibility,
+            BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset,
+                min_binding_size,
+            },
+        )
+    }
+
+    /// Adds a storage buffer entry, which is writable unless `read_only` is set.
+    pub fn storage_buffer(
+        self,
+        visibility: ShaderStages,
+        read_only: bool,
+        has_dynamic_offset: bool,
+        min_binding_size: Option<NonZeroU64>,
+    ) -> Self {
+        self.entry(
+            visibility,
+            BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset,
+                min_binding_size,
+            },
+        )
+    }
+
+    /// Adds a storage texture entry.
+    pub fn storage_texture(
+        self,
+        visibility: ShaderStages,
+        access: StorageTextureAccess,
+        format: TextureFormat,
+        view_dimension: TextureViewDimension,
+    ) -> Self {
+        self.entry(
+            visibility,
+            BindingType::StorageTexture {
+                access,
+                format,
+                view_dimension,
+            },
+        )
+    }
+
+    /// The entries added so far, in binding order.
+    pub fn entries(&self) -> &[BindGroupLayoutEntry] {
+        &self.entries
+    }
+
+    /// Returns the descriptor of the layout, to create it with
+    /// [`RenderDevice::create_bind_group_layout`].
+    pub fn descriptor(&self) -> BindGroupLayoutDescriptor<'_> {
+        BindGroupLayoutDescriptor {
+            label: self.label,
+            entries: &self.entries,
+        }
+    }
+
+    /// Creates the layout on the `render_device`.
+    pub fn build(&self, render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&self.descriptor())
+    }
+}
+
+impl From<(BindGroupLayoutBuilder<'_>, &RenderDevice)> for BindGroupLayout {
+    fn from((builder, render_device): (BindGroupLayoutBuilder<'_>, &RenderDevice)) -> Self {
+        builder.build(render_device)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_numbers_bindings_in_order() {
+        let builder = BindGroupLayoutBuilder::new()
+            .label("layout")
+            .uniform_buffer(ShaderStages::VERTEX, true, NonZeroU64::new(16))
+            .texture(
+                ShaderStages::FRAGMENT,
+                TextureSampleType::Float { filterable: true },
+                TextureViewDimension::D2,
+            )
+            .sampler(ShaderStages::FRAGMENT, SamplerBindingType::Filtering)
+            .storage_texture(
+                ShaderStages::COMPUTE,
+                StorageTextureAccess::WriteOnly,
+                TextureFormat::Rgba8Unorm,
+                TextureViewDimension::D2,
+            );
+
+        let descriptor = builder.descriptor();
+        assert_eq!(descriptor.label, Some("layout"));
+        assert_eq!(
+            descriptor
+                .entries
+                .iter()
+                .map(|entry| entry.binding)
+                .collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            descriptor.entries[2],
+            BindGroupLayoutEntry {
+                binding: 2,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Sampler(SamplerBindingType::Filtering),
+                count: None,
+            }
+        );
+    }
+}
@@ -7,8 +7,9 @@ use crate::{
         ShaderProcessor, ShaderReflectError,
     },
     renderer::RenderDevice,
-    Extract,
+    Extract, ExtractSchedule, RenderApp,
 };
+use bevy_app::App;
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::system::{Res, ResMut};
 use bevy_ecs::{event::EventReader, system::Resource};
@@ -18,7 +19,7 @@ use bevy_utils::{
     Entry, HashMap, HashSet,
 };
 use parking_lot::Mutex;
-use std::{hash::Hash, iter::FusedIterator, mem, ops::Deref};
+use std::{any::TypeId, collections::BTreeMap, hash::Hash, iter::FusedIterator, mem, ops::Deref};
 use thiserror::Error;
 use wgpu::{
     PipelineLayoutDescriptor, PushConstantRange, VertexBufferLayout as RawVertexBufferLayout,
@@ -129,6 +130,7 @@ struct ShaderCache {
     shaders: HashMap<Handle<Shader>, Shader>,
     import_path_shaders: HashMap<ShaderImport, Handle<Shader>>,
     waiting_on_import: HashMap<ShaderImport, Vec<Handle<Shader>>>,
+    global_shader_defs: BTreeMap<TypeId, Vec<ShaderDefVal>>,
     processor: ShaderProcessor,
 }
 
@@ -205,6 +207,7 @@ impl ShaderCache {
                     String::from("AVAILABLE_STORAGE_BUFFER_BINDINGS"),
                     render_device.limits().max_storage_buffers_per_shader_stage,
                 ));
+                shader_defs.extend(self.global_shader_defs.values().flatten().cloned());
 
                 debug!(
                     "processing shader {:?}, with shader defs {:?}",
@@ -262,6 +265,16 @@ impl ShaderCache {
         pipelines_to_queue
     }
 
+    fn clear_all(&mut self) -> Vec<CachedPipelineId> {
+        let mut pipelines_to_queue = Vec::new();
+        for data in self.data.values_mut() {
+            data.processed_shaders.clear();
+            pipelines_to_queue.extend(data.pipelines.iter().cloned());
+        }
+
+        pipelines_to_queue
+    }
+
     fn set_shader(&mut self, handle: &Handle<Shader>, shader: Shader) -> Vec<CachedPipelineId> {
         let pipelines_to_queue = self.clear(handle);
         if let Some(path) = shader.import_path() {
@@ -530,6 +543,38 @@ impl PipelineCache {
         }
     }
 
+    /// Returns the shader defs added to every shader processed by this cache.
+    ///
+    /// See [`AddShaderDefs::add_shader_defs_from_resource()`].
+    pub fn global_shader_defs(&self) -> impl Iterator<Item = &ShaderDefVal> {
+        self.shader_cache.global_shader_defs.values().flatten()
+    }
+
+    /// Sets the global shader defs contributed by `source`, re-specializing every pipeline
+    /// if they differ from the previous ones.
+    fn set_global_shader_defs(&mut self, source: TypeId, shader_defs: Vec<ShaderDefVal>) {
+        let global_shader_defs = &mut self.shader_cache.global_shader_defs;
+        let unchanged = match global_shader_defs.get(&source) {
+            Some(previous) => *previous == shader_defs,
+            None => shader_defs.is_empty(),
+        };
+        if unchanged {
+            return;
+        }
+
+        if shader_defs.is_empty() {
+            global_shader_defs.remove(&source);
+        } else {
+            global_shader_defs.insert(source, shader_defs);
+        }
+
+        let pipelines_to_queue = self.shader_cache.clear_all();
+        for cached_pipeline in pipelines_to_queue {
+            self.pipelines[cached_pipeline].state = CachedPipelineState::Queued;
+            self.waiting_pipelines.insert(cached_pipeline);
+        }
+    }
+
     fn process_render_pipeline(
         &mut self,
         id: CachedPipelineId,
@@ -732,11 +777,69 @@ impl PipelineCache {
                     }
                 }
                 AssetEvent::Removed { handle } => cache.remove_shader(handle),
+                AssetEvent::Unused { .. } => {}
             }
         }
     }
 }
 
+/// Adds shader defs driven by a main world resource to every pipeline of the [`PipelineCache`].
+pub trait AddShaderDefs {
+    /// Runs `shader_defs` on the resource `R` each frame during extraction and adds the
+    /// returned defs to every shader processed by the [`PipelineCache`].
+    ///
+    /// Pipelines are re-specialized whenever the returned defs change. When the resource is
+    /// missing, it contributes no shader defs. Calling this again for the same resource
+    /// replaces the previous closure.
+    ///
+    /// ```ignore
+    /// app.add_shader_defs_from_resource::<ShadowSettings>(|settings| {
+    ///     if settings.enabled {
+    ///         vec!["SHADOWS_ENABLED".into()]
+    ///     } else {
+    ///         vec![]
+    ///     }
+    /// });
+    /// ```
+    fn add_shader_defs_from_resource<R: Resource>(
+        &mut self,
+        shader_defs: impl Fn(&R) -> Vec<ShaderDefVal> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AddShaderDefs for App {
+    fn add_shader_defs_from_resource<R: Resource>(
+        &mut self,
+        shader_defs: impl Fn(&R) -> Vec<ShaderDefVal> + Send + Sync + 'static,
+    ) -> &mut Self {
+        if let Ok(render_app) = self.get_sub_app_mut(RenderApp) {
+            if !render_app
+                .world
+                .contains_resource::<ResourceShaderDefs<R>>()
+            {
+                render_app.add_systems(ExtractSchedule, extract_resource_shader_defs::<R>);
+            }
+            render_app.insert_resource(ResourceShaderDefs::<R>(Box::new(shader_defs)));
+        }
+        self
+    }
+}
+
+#[derive(Resource)]
+struct ResourceShaderDefs<R: Resource>(Box<dyn Fn(&R) -> Vec<ShaderDefVal> + Send + Sync>);
+
+fn extract_resource_shader_defs<R: Resource>(
+    mut cache: ResMut<PipelineCache>,
+    shader_defs: Res<ResourceShaderDefs<R>>,
+    resource: Extract<Option<Res<R>>>,
+) {
+    let defs = resource
+        .as_deref()
+        .map(|resource| (shader_defs.0)(resource))
+        .unwrap_or_default();
+    cache.set_global_shader_defs(TypeId::of::<R>(), defs);
+}
+
 fn log_shader_error(source: &ProcessedShader, error: &AsModuleDescriptorError) {
     use codespan_reporting::{
         diagnostic::{Diagnostic, Label},
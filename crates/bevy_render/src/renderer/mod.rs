@@ -8,8 +8,8 @@ pub use render_device::*;
 
 use crate::{
     render_graph::RenderGraph,
-    render_phase::TrackedRenderPass,
-    render_resource::RenderPassDescriptor,
+    render_phase::{TrackedComputePass, TrackedRenderPass},
+    render_resource::{ComputePassDescriptor, RenderPassDescriptor},
     settings::{WgpuSettings, WgpuSettingsPriority},
     view::{ExtractedWindows, ViewTarget},
 };
@@ -331,6 +331,21 @@ impl RenderContext {
         TrackedRenderPass::new(&self.render_device, render_pass)
     }
 
+    /// Creates a new [`TrackedComputePass`] for the context,
+    /// configured using the provided `descriptor`.
+    pub fn begin_tracked_compute_pass(
+        &mut self,
+        descriptor: ComputePassDescriptor<'_>,
+    ) -> TrackedComputePass<'_> {
+        // Cannot use command_encoder() as we need to split the borrow on self
+        let command_encoder = self.command_encoder.get_or_insert_with(|| {
+            self.render_device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor::default())
+        });
+        let compute_pass = command_encoder.begin_compute_pass(&descriptor);
+        TrackedComputePass::new(&self.render_device, compute_pass)
+    }
+
     /// Append a [`CommandBuffer`] to the queue.
     ///
     /// If present, this will flush the currently unflushed [`CommandEncoder`]
@@ -1,4 +1,4 @@
-use ddsfile::{D3DFormat, Dds, DxgiFormat};
+use ddsfile::{AlphaMode, D3D10ResourceDimension, D3DFormat, Dds, DxgiFormat, NewDxgiParams};
 use std::io::Cursor;
 use wgpu::{Extent3d, TextureDimension, TextureFormat};
 
@@ -41,6 +41,60 @@ pub fn dds_buffer_to_image(
     Ok(image)
 }
 
+/// Encodes an uncompressed 2D [`Image`], including its mip levels and array layers, as a DDS
+/// file.
+pub fn image_to_dds_buffer(image: &Image) -> Result<Vec<u8>, TextureError> {
+    let descriptor = &image.texture_descriptor;
+    let format = match descriptor.format {
+        TextureFormat::Rgba8Unorm => DxgiFormat::R8G8B8A8_UNorm,
+        TextureFormat::Rgba8UnormSrgb => DxgiFormat::R8G8B8A8_UNorm_sRGB,
+        TextureFormat::Bgra8Unorm => DxgiFormat::B8G8R8A8_UNorm,
+        TextureFormat::Bgra8UnormSrgb => DxgiFormat::B8G8R8A8_UNorm_sRGB,
+        TextureFormat::R8Unorm => DxgiFormat::R8_UNorm,
+        TextureFormat::Rg8Unorm => DxgiFormat::R8G8_UNorm,
+        TextureFormat::Rgba16Float => DxgiFormat::R16G16B16A16_Float,
+        TextureFormat::Rgba32Float => DxgiFormat::R32G32B32A32_Float,
+        format => {
+            return Err(TextureError::UnsupportedTextureFormat(format!(
+                "{format:?} can't be encoded as DDS"
+            )))
+        }
+    };
+    if descriptor.dimension != TextureDimension::D2 {
+        return Err(TextureError::UnsupportedTextureFormat(format!(
+            "{:?} textures can't be encoded as DDS",
+            descriptor.dimension
+        )));
+    }
+
+    let mut dds = Dds::new_dxgi(NewDxgiParams {
+        height: descriptor.size.height,
+        width: descriptor.size.width,
+        depth: None,
+        format,
+        mipmap_levels: Some(descriptor.mip_level_count),
+        array_layers: Some(descriptor.size.depth_or_array_layers),
+        caps2: None,
+        is_cubemap: false,
+        resource_dimension: D3D10ResourceDimension::Texture2D,
+        alpha_mode: AlphaMode::Straight,
+    })
+    .map_err(|err| TextureError::InvalidData(format!("Failed to create DDS: {err}")))?;
+    if dds.data.len() != image.data.len() {
+        return Err(TextureError::InvalidData(format!(
+            "Image data is {} bytes, but its descriptor requires {} bytes",
+            image.data.len(),
+            dds.data.len()
+        )));
+    }
+    dds.data.copy_from_slice(&image.data);
+
+    let mut buffer = Vec::new();
+    dds.write(&mut buffer)
+        .map_err(|err| TextureError::InvalidData(format!("Failed to write DDS: {err}")))?;
+    Ok(buffer)
+}
+
 pub fn dds_format_to_texture_format(
     dds: &Dds,
     is_srgb: bool,
@@ -235,3 +289,33 @@ pub fn dds_format_to_texture_format(
         ));
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn dds_round_trip() {
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            (0..16).collect(),
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let buffer = image_to_dds_buffer(&image).unwrap();
+        let loaded = dds_buffer_to_image(&buffer, CompressedImageFormats::NONE, true).unwrap();
+        assert_eq!(
+            loaded.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(
+            loaded.texture_descriptor.size,
+            image.texture_descriptor.size
+        );
+        assert_eq!(loaded.data, image.data);
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{
+    color::Color,
+    texture::{Image, ImageSampler},
+};
+use bevy_asset::{AssetServer, Assets, Handle, HandleId, HandleUntyped, LoadState};
+use bevy_ecs::prelude::*;
+use bevy_log::warn;
+use bevy_math::UVec2;
+use bevy_reflect::TypeUuid;
+use bevy_utils::HashSet;
+
+/// The image drawn in place of images that failed to load, see [`FallbackImageSettings`].
+pub const FAILED_IMAGE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Image::TYPE_UUID, 2330147853701750677);
+
+/// What sprites and UI images draw when their image failed to load.
+///
+/// Images that are still loading are never replaced.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub enum FallbackImageSettings {
+    /// Don't draw anything
+    Invisible,
+    /// Draw a solid color
+    Solid(Color),
+    /// Draw a magenta and black checkerboard
+    #[default]
+    Checkerboard,
+}
+
+impl FallbackImageSettings {
+    /// Creates the image to draw, or `None` if nothing should be drawn.
+    pub fn image(&self) -> Option<Image> {
+        let mut image = match self {
+            FallbackImageSettings::Invisible => return None,
+            FallbackImageSettings::Solid(color) => {
+                Image::checkerboard(UVec2::ONE, 1, *color, *color)
+            }
+            FallbackImageSettings::Checkerboard => {
+                Image::checkerboard(UVec2::splat(64), 8, Color::FUCHSIA, Color::BLACK)
+            }
+        };
+        image.sampler_descriptor = ImageSampler::nearest();
+        Some(image)
+    }
+}
+
+/// Tracks which images failed to load, so they can be replaced according to the
+/// [`FallbackImageSettings`].
+///
+/// Images are only tracked once they have been [watched](FailedImages::watch), which is done
+/// for every `Handle<Image>` component.
+#[derive(Resource, Debug, Default)]
+pub struct FailedImages {
+    pending: HashSet<Handle<Image>>,
+    failed: HashSet<Handle<Image>>,
+    fallback_visible: bool,
+}
+
+impl FailedImages {
+    /// Starts tracking the load state of `handle`, if the image isn't available yet.
+    pub fn watch(&mut self, images: &Assets<Image>, handle: &Handle<Image>) {
+        if !images.contains(handle) && !self.failed.contains(handle) {
+            self.pending.insert(handle.clone_weak());
+        }
+    }
+
+    /// Returns `true` if the image failed to load.
+    pub fn contains(&self, id: HandleId) -> bool {
+        // Avoid hashing in the common case where nothing failed
+        !self.failed.is_empty() && self.failed.contains(&Handle::<Image>::weak(id))
+    }
+
+    /// Returns the image to draw for the image `id`: itself, the fallback image if it failed to
+    /// load, or `None` if it failed to load and the fallback is invisible.
+    pub fn resolve(&self, id: HandleId) -> Option<HandleId> {
+        if !self.contains(id) {
+            Some(id)
+        } else if self.fallback_visible {
+            Some(FAILED_IMAGE_HANDLE.id())
+        } else {
+            None
+        }
+    }
+}
+
+/// Watches the images of every `Handle<Image>` component.
+pub fn watch_image_handles(
+    images: Res<Assets<Image>>,
+    mut failed_images: ResMut<FailedImages>,
+    handles: Query<&Handle<Image>, Changed<Handle<Image>>>,
+) {
+    for handle in &handles {
+        failed_images.watch(&images, handle);
+    }
+}
+
+/// Updates the load state of watched images, warning once for each image that failed to load.
+pub fn update_failed_images(
+    asset_server: Res<AssetServer>,
+    images: Res<Assets<Image>>,
+    mut failed_images: ResMut<FailedImages>,
+) {
+    let FailedImages {
+        pending, failed, ..
+    } = &mut *failed_images;
+    pending.retain(|handle| match asset_server.get_load_state(handle) {
+        LoadState::NotLoaded | LoadState::Loading => !images.contains(handle),
+        LoadState::Failed => {
+            match asset_server.get_handle_path(handle) {
+                Some(path) => warn!("Image {path:?} failed to load, drawing a fallback image"),
+                None => warn!("Image {handle:?} failed to load, drawing a fallback image"),
+            }
+            failed.insert(handle.clone_weak());
+            false
+        }
+        LoadState::Loaded | LoadState::Unloaded => false,
+    });
+    // A failed image can still load later, for example if it was fixed and hot reloaded
+    if !failed.is_empty() {
+        failed.retain(|handle| !images.contains(handle));
+    }
+}
+
+/// Keeps the [`FAILED_IMAGE_HANDLE`] image in sync with the [`FallbackImageSettings`].
+pub fn update_failed_image_fallback(
+    settings: Res<FallbackImageSettings>,
+    mut images: ResMut<Assets<Image>>,
+    mut failed_images: ResMut<FailedImages>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let image = settings.image();
+    failed_images.fallback_visible = image.is_some();
+    if let Some(image) = image {
+        images.set_untracked(FAILED_IMAGE_HANDLE, image);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_failed_image() {
+        let handle = Handle::<Image>::weak(HandleId::random::<Image>());
+        let mut failed_images = FailedImages::default();
+        assert_eq!(failed_images.resolve(handle.id()), Some(handle.id()));
+
+        failed_images.failed.insert(handle.clone_weak());
+        assert_eq!(failed_images.resolve(handle.id()), None);
+        failed_images.fallback_visible = true;
+        assert_eq!(
+            failed_images.resolve(handle.id()),
+            Some(FAILED_IMAGE_HANDLE.id())
+        );
+    }
+}
@@ -6,20 +6,24 @@ use super::dds::*;
 use super::ktx2::*;
 
 use crate::{
+    color::{Color, SrgbColorSpace},
     render_asset::{PrepareAssetError, RenderAsset},
     render_resource::{Sampler, Texture, TextureView},
     renderer::{RenderDevice, RenderQueue},
     texture::BevyDefault,
 };
-use bevy_asset::HandleUntyped;
+use bevy_asset::{AssetByteSize, HandleUntyped};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::system::{lifetimeless::SRes, Resource, SystemParamItem};
-use bevy_math::Vec2;
+use bevy_math::{URect, UVec2, Vec2, Vec4};
 use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 
 use std::hash::Hash;
 use thiserror::Error;
-use wgpu::{Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor};
+use wgpu::{
+    Extent3d, TextureDimension, TextureFormat, TextureUsages, TextureViewDescriptor,
+    TextureViewDimension,
+};
 
 pub const TEXTURE_ASSET_INDEX: u64 = 0;
 pub const SAMPLER_ASSET_INDEX: u64 = 1;
@@ -113,6 +117,23 @@ pub struct Image {
     /// The [`ImageSampler`] to use during rendering.
     pub sampler_descriptor: ImageSampler,
     pub texture_view_descriptor: Option<wgpu::TextureViewDescriptor<'static>>,
+    /// What the image is used for, so its memory usage can be attributed to the right category.
+    pub memory_tag: ImageMemoryTag,
+}
+
+/// A coarse category used to group [`Image`]s when reporting their memory usage,
+/// see [`ImageMemoryDiagnosticsPlugin`](crate::diagnostic::ImageMemoryDiagnosticsPlugin).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ImageMemoryTag {
+    /// Images used by UI nodes.
+    Ui,
+    /// Texture atlases of sprite sheets.
+    SpriteAtlas,
+    /// Texture atlases of rasterized font glyphs.
+    FontAtlas,
+    /// Any other image.
+    #[default]
+    Other,
 }
 
 /// Used in [`Image`], this determines what image sampler to use when rendering. The default setting,
@@ -161,6 +182,30 @@ impl ImageSampler {
             ..Default::default()
         }
     }
+
+    /// Returns the image sampler for a named preset, or `None` if the name is unknown.
+    ///
+    /// The available presets are:
+    /// - `"pixel"`: [`Nearest`](crate::render_resource::FilterMode::Nearest) filtering, for pixel art
+    /// - `"linear_clamp"`: [`Linear`](crate::render_resource::FilterMode::Linear) filtering,
+    ///   clamping texture coordinates to the edge
+    /// - `"repeat_aniso16"`: [`Linear`](crate::render_resource::FilterMode::Linear) filtering,
+    ///   repeating texture coordinates, with 16x anisotropic filtering
+    pub fn from_preset(name: &str) -> Option<ImageSampler> {
+        let descriptor = match name {
+            "pixel" => Self::nearest_descriptor(),
+            "linear_clamp" => Self::linear_descriptor(),
+            "repeat_aniso16" => wgpu::SamplerDescriptor {
+                address_mode_u: wgpu::AddressMode::Repeat,
+                address_mode_v: wgpu::AddressMode::Repeat,
+                address_mode_w: wgpu::AddressMode::Repeat,
+                anisotropy_clamp: 16,
+                ..Self::linear_descriptor()
+            },
+            _ => return None,
+        };
+        Some(ImageSampler::Descriptor(descriptor))
+    }
 }
 
 /// A rendering resource for the default image sampler which is set during renderer
@@ -194,6 +239,7 @@ impl Default for Image {
             },
             sampler_descriptor: ImageSampler::Default,
             texture_view_descriptor: None,
+            memory_tag: ImageMemoryTag::Other,
         }
     }
 }
@@ -257,6 +303,189 @@ impl Image {
         value
     }
 
+    /// Creates a 2D image of `size` filled with a checkerboard of `cell` pixel wide squares,
+    /// alternating between `color_a` (starting in the top left corner) and `color_b`.
+    pub fn checkerboard(size: UVec2, cell: u32, color_a: Color, color_b: Color) -> Self {
+        let cell = cell.max(1);
+        let (color_a, color_b) = (color_a.as_rgba_u8(), color_b.as_rgba_u8());
+        let data = (0..size.y)
+            .flat_map(|y| (0..size.x).map(move |x| (x / cell + y / cell) % 2 == 0))
+            .flat_map(|is_a| if is_a { color_a } else { color_b })
+            .collect();
+        Image::new(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    /// Creates a 2D array texture with `layers` layers of `size`, initialized to zero.
+    ///
+    /// Layers can then be filled with [`Image::set_layer`]. The image is always viewed as an
+    /// array, even if it only has a single layer.
+    pub fn new_layered(
+        size: UVec2,
+        layers: u32,
+        format: TextureFormat,
+        usages: TextureUsages,
+    ) -> Self {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: layers,
+            },
+            TextureDimension::D2,
+            &vec![0; format.pixel_size()],
+            format,
+        );
+        image.texture_descriptor.usage = usages;
+        image.texture_view_descriptor = Some(TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            ..Default::default()
+        });
+        image
+    }
+
+    /// Copies the data of the single layer 2D image `src` into `layer` of this 2D array texture.
+    ///
+    /// `src` must have the same size, format and mip level count as a layer of this image.
+    pub fn set_layer(&mut self, layer: u32, src: &Image) -> Result<(), TextureError> {
+        let descriptor = &self.texture_descriptor;
+        let src_descriptor = &src.texture_descriptor;
+        let layers = descriptor.size.depth_or_array_layers;
+        if descriptor.dimension != TextureDimension::D2
+            || src_descriptor.dimension != TextureDimension::D2
+            || src_descriptor.size.depth_or_array_layers != 1
+        {
+            return Err(TextureError::InvalidData(
+                "Only a single layer 2D image can be copied into a 2D array texture".to_string(),
+            ));
+        }
+        if layer >= layers {
+            return Err(TextureError::InvalidData(format!(
+                "Layer {layer} is out of bounds for an image with {layers} layers"
+            )));
+        }
+        if src_descriptor.format != descriptor.format
+            || src_descriptor.mip_level_count != descriptor.mip_level_count
+            || src_descriptor.size.width != descriptor.size.width
+            || src_descriptor.size.height != descriptor.size.height
+        {
+            return Err(TextureError::InvalidData(format!(
+                "Can't copy a {}x{} {:?} image with {} mip levels into the {}x{} {:?} layers \
+                with {} mip levels",
+                src_descriptor.size.width,
+                src_descriptor.size.height,
+                src_descriptor.format,
+                src_descriptor.mip_level_count,
+                descriptor.size.width,
+                descriptor.size.height,
+                descriptor.format,
+                descriptor.mip_level_count,
+            )));
+        }
+        // Layers (with all their mips) are stored one after the other
+        let layer_bytes = src.data.len();
+        if self.data.len() != layer_bytes * layers as usize {
+            return Err(TextureError::InvalidData(format!(
+                "Image data is {} bytes, but {layers} layers of {layer_bytes} bytes were expected",
+                self.data.len(),
+            )));
+        }
+        let begin = layer as usize * layer_bytes;
+        self.data[begin..begin + layer_bytes].copy_from_slice(&src.data);
+        Ok(())
+    }
+
+    /// Returns the bytes of the pixel at `coord` in the first layer of the image, or `None` if
+    /// `coord` is out of bounds or the format doesn't have whole pixels, like compressed formats.
+    pub fn get_pixel(&self, coord: UVec2) -> Option<&[u8]> {
+        self.pixel_range(coord).map(|range| &self.data[range])
+    }
+
+    /// Overwrites the pixel at `coord` in the first layer of the image with `bytes`, which must
+    /// hold exactly one pixel of the image format.
+    pub fn set_pixel(&mut self, coord: UVec2, bytes: &[u8]) -> Result<(), TextureError> {
+        let Some(range) = self.pixel_range(coord) else {
+            let size = self.texture_descriptor.size;
+            return Err(TextureError::InvalidData(format!(
+                "Pixel {coord} is out of bounds or not addressable in a {}x{} {:?} image",
+                size.width, size.height, self.texture_descriptor.format,
+            )));
+        };
+        if bytes.len() != range.len() {
+            return Err(TextureError::InvalidData(format!(
+                "Expected {} bytes for a {:?} pixel, got {}",
+                range.len(),
+                self.texture_descriptor.format,
+                bytes.len(),
+            )));
+        }
+        self.data[range].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    /// Returns the range of `data` holding the pixel at `coord` in the first layer of the image.
+    fn pixel_range(&self, coord: UVec2) -> Option<std::ops::Range<usize>> {
+        let descriptor = &self.texture_descriptor;
+        let format = descriptor.format;
+        if format.block_dimensions() != (1, 1) {
+            return None;
+        }
+        // Depth and stencil formats without a single aspect have no pixel size
+        let pixel_size = format.block_size(None)? as usize;
+        if coord.x >= descriptor.size.width || coord.y >= descriptor.size.height {
+            return None;
+        }
+        let begin =
+            (coord.y as usize * descriptor.size.width as usize + coord.x as usize) * pixel_size;
+        let range = begin..begin + pixel_size;
+        (range.end <= self.data.len()).then_some(range)
+    }
+
+    /// Returns `true` if the pixels of this image inside `rect` are equal to the pixels of the
+    /// whole first layer of `other`, which must have the same format and the size of `rect`.
+    ///
+    /// This is mostly useful in tests, to check where images were copied into a texture atlas.
+    /// Returns `false` if `rect` is out of bounds or either image has too little data.
+    pub fn region_eq(&self, rect: URect, other: &Image) -> bool {
+        let descriptor = &self.texture_descriptor;
+        let other_descriptor = &other.texture_descriptor;
+        if descriptor.format != other_descriptor.format
+            || rect.max.x > descriptor.size.width
+            || rect.max.y > descriptor.size.height
+            || rect.width() != other_descriptor.size.width
+            || rect.height() != other_descriptor.size.height
+        {
+            return false;
+        }
+        if rect.is_empty() {
+            return true;
+        }
+        // Compare whole rows of the rect at once, they are contiguous in both images
+        (0..rect.height()).all(|y| {
+            let row = self
+                .pixel_range(UVec2::new(rect.min.x, rect.min.y + y))
+                .zip(self.pixel_range(UVec2::new(rect.max.x - 1, rect.min.y + y)));
+            let other_row = other
+                .pixel_range(UVec2::new(0, y))
+                .zip(other.pixel_range(UVec2::new(rect.width() - 1, y)));
+            match (row, other_row) {
+                (Some((first, last)), Some((other_first, other_last))) => {
+                    self.data[first.start..last.end]
+                        == other.data[other_first.start..other_last.end]
+                }
+                _ => false,
+            }
+        })
+    }
+
     /// Returns the aspect ratio (height/width) of a 2D image.
     pub fn aspect_2d(&self) -> f32 {
         self.texture_descriptor.size.height as f32 / self.texture_descriptor.size.width as f32
@@ -344,6 +573,47 @@ impl Image {
             .map(|(dyn_img, is_srgb)| Self::from_dynamic(dyn_img, is_srgb))
     }
 
+    /// Returns a copy of the image in the `target` format, converting each pixel.
+    ///
+    /// Both formats must be one of `R8Unorm`, `Rg8Unorm`, `Rgba8Unorm`, `Rgba8UnormSrgb`,
+    /// `R16Unorm`, `Rg16Unorm` or `Rgba16Unorm`, otherwise `None` is returned. Like in
+    /// [`Image::convert`], one channel formats are treated as grayscale and two channel formats
+    /// as grayscale with alpha, so `R8Unorm` is broadcast to the RGB channels of `Rgba8Unorm`
+    /// with an opaque alpha, and the red and alpha channels are kept when converting from four
+    /// channels to two.
+    ///
+    /// Stored values are kept as is between sRGB and linear formats, only rescaled between 8 and
+    /// 16 bits channels: converting `Rgba8Unorm` to `Rgba8UnormSrgb` reinterprets the same bytes.
+    pub fn to_format(&self, target: TextureFormat) -> Option<Image> {
+        let format = self.texture_descriptor.format;
+        let source_texel = UnormTexelFormat::from_texture_format(format)?;
+        let target_texel = UnormTexelFormat::from_texture_format(target)?;
+        let data = if source_texel == target_texel {
+            self.data.clone()
+        } else {
+            let mut data = vec![0; self.data.len() / format.pixel_size() * target.pixel_size()];
+            for (texel, target) in self
+                .data
+                .chunks_exact(format.pixel_size())
+                .zip(data.chunks_exact_mut(target.pixel_size()))
+            {
+                target_texel.write(target, source_texel.read(texel));
+            }
+            data
+        };
+        Some(Image {
+            data,
+            texture_descriptor: wgpu::TextureDescriptor {
+                format: target,
+                view_formats: &[],
+                ..self.texture_descriptor.clone()
+            },
+            sampler_descriptor: self.sampler_descriptor.clone(),
+            texture_view_descriptor: None,
+            memory_tag: self.memory_tag,
+        })
+    }
+
     /// Load a bytes buffer in a [`Image`], according to type `image_type`, using the `image`
     /// crate
     pub fn from_buffer(
@@ -397,6 +667,336 @@ impl Image {
                 .required_features()
                 .contains(wgpu::Features::TEXTURE_COMPRESSION_ETC2)
     }
+
+    /// Returns an estimate of the GPU memory used by the image once it is prepared for rendering,
+    /// in bytes.
+    ///
+    /// This accounts for every mip level, array layer and sample of the texture, but not for any
+    /// padding or alignment the driver may add.
+    pub fn estimated_gpu_size_bytes(&self) -> usize {
+        let descriptor = &self.texture_descriptor;
+        let format = descriptor.format;
+        let block_size = format.block_size(None).unwrap_or_else(|| {
+            format
+                .block_size(Some(wgpu::TextureAspect::DepthOnly))
+                .unwrap_or(0)
+                + format
+                    .block_size(Some(wgpu::TextureAspect::StencilOnly))
+                    .unwrap_or(0)
+        }) as usize;
+        let (block_width, block_height) = format.block_dimensions();
+
+        (0..descriptor.mip_level_count)
+            .map(|level| {
+                let size = descriptor.mip_level_size(level).unwrap_or_default();
+                let blocks_x = (size.width + block_width - 1) / block_width;
+                let blocks_y = (size.height + block_height - 1) / block_height;
+                (blocks_x * blocks_y * size.depth_or_array_layers) as usize * block_size
+            })
+            .sum::<usize>()
+            * descriptor.sample_count as usize
+    }
+
+    /// Converts the image from straight to premultiplied alpha, multiplying the color channels
+    /// of every pixel by its alpha.
+    ///
+    /// sRGB formats are converted to linear space before multiplying and back afterwards.
+    /// Only uncompressed RGBA formats with 8-bit or 32-bit float channels are supported:
+    /// - `TextureFormat::Rgba8Unorm`
+    /// - `TextureFormat::Rgba8UnormSrgb`
+    /// - `TextureFormat::Bgra8Unorm`
+    /// - `TextureFormat::Bgra8UnormSrgb`
+    /// - `TextureFormat::Rgba32Float`
+    pub fn premultiply_alpha(&mut self) -> Result<(), TextureError> {
+        self.map_rgba_linear(|[r, g, b, a]| [r * a, g * a, b * a, a])
+    }
+
+    /// Converts the image from premultiplied to straight alpha, dividing the color channels of
+    /// every pixel by its alpha. Fully transparent pixels are left untouched.
+    ///
+    /// Supports the same formats as [`Image::premultiply_alpha`].
+    pub fn unpremultiply_alpha(&mut self) -> Result<(), TextureError> {
+        self.map_rgba_linear(|[r, g, b, a]| {
+            if a > 0.0 {
+                [r / a, g / a, b / a, a]
+            } else {
+                [r, g, b, a]
+            }
+        })
+    }
+
+    /// Returns `true` if any fully transparent pixel of the image carries a non-black color.
+    ///
+    /// Such pixels are the usual cause of dark or colored fringes around sprites when straight
+    /// alpha images are filtered or composited as if they were premultiplied. Returns `false`
+    /// for formats not supported by [`Image::premultiply_alpha`].
+    pub fn detect_straight_alpha_bleed(&self) -> bool {
+        let format = self.texture_descriptor.format;
+        let Some(rgba) = RgbaTexelFormat::from_texture_format(format) else {
+            return false;
+        };
+        self.data
+            .chunks_exact(format.pixel_size())
+            .map(|texel| rgba.read(texel))
+            .any(|[r, g, b, a]| a == 0.0 && (r != 0.0 || g != 0.0 || b != 0.0))
+    }
+
+    /// Returns a copy of the image rescaled to `new_size`, sampling its contents with `filter`.
+    ///
+    /// Filtering is done in linear space, so sRGB images are rescaled correctly. Each layer of
+    /// an array texture is rescaled independently. Supports the same formats as
+    /// [`Image::premultiply_alpha`].
+    pub fn resized(&self, new_size: UVec2, filter: ImageFilter) -> Result<Image, TextureError> {
+        let format = self.texture_descriptor.format;
+        let rgba = RgbaTexelFormat::from_texture_format(format)
+            .ok_or_else(|| TextureError::UnsupportedTextureFormat(format!("{format:?}")))?;
+        let is_srgb = format.is_srgb();
+        let pixel_size = format.pixel_size();
+        let size = self.texture_descriptor.size;
+        let (width, height) = (size.width as usize, size.height as usize);
+
+        let read = |layer: usize, x: usize, y: usize| {
+            let begin = ((layer * height + y) * width + x) * pixel_size;
+            let mut color = rgba.read(&self.data[begin..begin + pixel_size]);
+            if is_srgb {
+                for channel in &mut color[..3] {
+                    *channel = channel.nonlinear_to_linear_srgb();
+                }
+            }
+            Vec4::from(color)
+        };
+
+        let new_extent = Extent3d {
+            width: new_size.x,
+            height: new_size.y,
+            depth_or_array_layers: size.depth_or_array_layers,
+        };
+        let mut resized = self.clone();
+        resized.texture_descriptor.size = new_extent;
+        resized.texture_descriptor.mip_level_count = 1;
+        resized.data = vec![0; new_extent.volume() * pixel_size];
+        if width == 0 || height == 0 {
+            return Ok(resized);
+        }
+
+        let scale = Vec2::new(width as f32, height as f32) / new_size.as_vec2();
+        let mut texels = resized.data.chunks_exact_mut(pixel_size);
+        for layer in 0..size.depth_or_array_layers as usize {
+            for y in 0..new_size.y {
+                for x in 0..new_size.x {
+                    let center = (UVec2::new(x, y).as_vec2() + 0.5) * scale;
+                    let color = match filter {
+                        ImageFilter::Nearest => {
+                            let source = center
+                                .as_uvec2()
+                                .min(UVec2::new(width as u32 - 1, height as u32 - 1));
+                            read(layer, source.x as usize, source.y as usize)
+                        }
+                        ImageFilter::Bilinear => {
+                            let position = (center - 0.5).max(Vec2::ZERO);
+                            let x0 = (position.x as usize).min(width - 1);
+                            let y0 = (position.y as usize).min(height - 1);
+                            let x1 = (x0 + 1).min(width - 1);
+                            let y1 = (y0 + 1).min(height - 1);
+                            let t = position - Vec2::new(x0 as f32, y0 as f32);
+                            let top = read(layer, x0, y0).lerp(read(layer, x1, y0), t.x);
+                            let bottom = read(layer, x0, y1).lerp(read(layer, x1, y1), t.x);
+                            top.lerp(bottom, t.y)
+                        }
+                    };
+                    let mut color = color.to_array();
+                    if is_srgb {
+                        for channel in &mut color[..3] {
+                            *channel = channel.linear_to_nonlinear_srgb();
+                        }
+                    }
+                    rgba.write(texels.next().unwrap(), color);
+                }
+            }
+        }
+        Ok(resized)
+    }
+
+    /// Returns the largest size with the aspect ratio of this 2D image that fits within `bounds`.
+    pub fn scale_to_fit(&self, bounds: UVec2) -> UVec2 {
+        let size = self.size();
+        let scale = (bounds.as_vec2() / size).min_element();
+        (size * scale).round().as_uvec2().min(bounds)
+    }
+
+    /// Returns the smallest size with the aspect ratio of this 2D image that covers `bounds`.
+    pub fn scale_to_fill(&self, bounds: UVec2) -> UVec2 {
+        let size = self.size();
+        let scale = (bounds.as_vec2() / size).max_element();
+        (size * scale).round().as_uvec2().max(bounds)
+    }
+
+    /// Applies `f` to every pixel of the image, passing and receiving the channels as linear
+    /// `[r, g, b, a]` floats.
+    fn map_rgba_linear(
+        &mut self,
+        mut f: impl FnMut([f32; 4]) -> [f32; 4],
+    ) -> Result<(), TextureError> {
+        let format = self.texture_descriptor.format;
+        let rgba = RgbaTexelFormat::from_texture_format(format)
+            .ok_or_else(|| TextureError::UnsupportedTextureFormat(format!("{format:?}")))?;
+        let is_srgb = format.is_srgb();
+        for texel in self.data.chunks_exact_mut(format.pixel_size()) {
+            let mut color = rgba.read(texel);
+            if is_srgb {
+                for channel in &mut color[..3] {
+                    *channel = channel.nonlinear_to_linear_srgb();
+                }
+            }
+            let mut color = f(color);
+            if is_srgb {
+                for channel in &mut color[..3] {
+                    *channel = channel.linear_to_nonlinear_srgb();
+                }
+            }
+            rgba.write(texel, color);
+        }
+        Ok(())
+    }
+}
+
+/// The filter used to sample an [`Image`] when rescaling it with [`Image::resized`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFilter {
+    /// Use the color of the closest source pixel.
+    Nearest,
+    /// Interpolate linearly between the four closest source pixels.
+    #[default]
+    Bilinear,
+}
+
+/// The memory layouts of uncompressed RGBA texture formats that [`Image`] can read and write
+/// pixels of on the CPU.
+#[derive(Clone, Copy, Debug)]
+enum RgbaTexelFormat {
+    Rgba8,
+    Bgra8,
+    Rgba32Float,
+}
+
+impl RgbaTexelFormat {
+    fn from_texture_format(format: TextureFormat) -> Option<Self> {
+        match format {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => Some(Self::Rgba8),
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => Some(Self::Bgra8),
+            TextureFormat::Rgba32Float => Some(Self::Rgba32Float),
+            _ => None,
+        }
+    }
+
+    /// Reads a texel as `[r, g, b, a]` floats, without any color space conversion.
+    fn read(self, texel: &[u8]) -> [f32; 4] {
+        let unorm = |byte: u8| byte as f32 / u8::MAX as f32;
+        match self {
+            Self::Rgba8 => [
+                unorm(texel[0]),
+                unorm(texel[1]),
+                unorm(texel[2]),
+                unorm(texel[3]),
+            ],
+            Self::Bgra8 => [
+                unorm(texel[2]),
+                unorm(texel[1]),
+                unorm(texel[0]),
+                unorm(texel[3]),
+            ],
+            Self::Rgba32Float => {
+                let mut color = [0.0; 4];
+                for (channel, bytes) in color.iter_mut().zip(texel.chunks_exact(4)) {
+                    *channel = f32::from_le_bytes(bytes.try_into().unwrap());
+                }
+                color
+            }
+        }
+    }
+
+    /// Writes `[r, g, b, a]` floats into a texel, without any color space conversion.
+    fn write(self, texel: &mut [u8], [r, g, b, a]: [f32; 4]) {
+        let unorm = |value: f32| (value.clamp(0.0, 1.0) * u8::MAX as f32).round() as u8;
+        match self {
+            Self::Rgba8 => texel.copy_from_slice(&[unorm(r), unorm(g), unorm(b), unorm(a)]),
+            Self::Bgra8 => texel.copy_from_slice(&[unorm(b), unorm(g), unorm(r), unorm(a)]),
+            Self::Rgba32Float => {
+                for (bytes, channel) in texel.chunks_exact_mut(4).zip([r, g, b, a]) {
+                    bytes.copy_from_slice(&channel.to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+/// The memory layouts of the uncompressed unsigned normalized formats [`Image::to_format`]
+/// converts between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct UnormTexelFormat {
+    /// 1, 2 or 4 channels
+    channels: usize,
+    /// 1 or 2 bytes per channel
+    channel_size: usize,
+}
+
+impl UnormTexelFormat {
+    fn from_texture_format(format: TextureFormat) -> Option<Self> {
+        let (channels, channel_size) = match format {
+            TextureFormat::R8Unorm => (1, 1),
+            TextureFormat::Rg8Unorm => (2, 1),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => (4, 1),
+            TextureFormat::R16Unorm => (1, 2),
+            TextureFormat::Rg16Unorm => (2, 2),
+            TextureFormat::Rgba16Unorm => (4, 2),
+            _ => return None,
+        };
+        Some(Self {
+            channels,
+            channel_size,
+        })
+    }
+
+    /// Reads a texel as `[r, g, b, a]` floats, broadcasting grayscale to the RGB channels.
+    fn read(self, texel: &[u8]) -> [f32; 4] {
+        let unorm = |bytes: &[u8]| match bytes.len() {
+            1 => bytes[0] as f32 / u8::MAX as f32,
+            _ => u16::from_le_bytes([bytes[0], bytes[1]]) as f32 / u16::MAX as f32,
+        };
+        let mut channels = texel.chunks_exact(self.channel_size).map(unorm);
+        let mut next = || channels.next().unwrap();
+        match self.channels {
+            1 => {
+                let luma = next();
+                [luma, luma, luma, 1.0]
+            }
+            2 => {
+                let luma = next();
+                [luma, luma, luma, next()]
+            }
+            _ => [next(), next(), next(), next()],
+        }
+    }
+
+    /// Writes `[r, g, b, a]` floats into a texel, keeping the red channel as grayscale.
+    fn write(self, texel: &mut [u8], [r, g, b, a]: [f32; 4]) {
+        // Only the first `self.channels` values are written
+        let channels = match self.channels {
+            1 => [r, 0.0, 0.0, 0.0],
+            2 => [r, a, 0.0, 0.0],
+            _ => [r, g, b, a],
+        };
+        for (bytes, value) in texel.chunks_exact_mut(self.channel_size).zip(channels) {
+            let value = value.clamp(0.0, 1.0);
+            match self.channel_size {
+                1 => bytes[0] = (value * u8::MAX as f32).round() as u8,
+                _ => {
+                    let value = (value * u16::MAX as f32).round() as u16;
+                    bytes.copy_from_slice(&value.to_le_bytes());
+                }
+            }
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -492,6 +1092,13 @@ impl TextureFormatPixelInfo for TextureFormat {
 
 /// The GPU-representation of an [`Image`].
 /// Consists of the [`Texture`], its [`TextureView`] and the corresponding [`Sampler`], and the texture's size.
+/// Images are budgeted by the size of their texture, see [`Image::estimated_gpu_size_bytes`].
+impl AssetByteSize for Image {
+    fn byte_size(&self) -> usize {
+        self.estimated_gpu_size_bytes()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct GpuImage {
     pub texture: Texture,
@@ -635,9 +1242,265 @@ mod test {
             image.size()
         );
     }
+    #[test]
+    fn image_checkerboard() {
+        let image = Image::checkerboard(UVec2::new(4, 2), 2, Color::WHITE, Color::BLACK);
+        let pixels: Vec<u8> = image.data.chunks_exact(4).map(|pixel| pixel[0]).collect();
+        assert_eq!(pixels, vec![255, 255, 0, 0, 255, 255, 0, 0]);
+    }
+
+    #[test]
+    fn image_set_layer() {
+        let mut array = Image::new_layered(
+            UVec2::new(2, 1),
+            3,
+            TextureFormat::R8Unorm,
+            TextureUsages::TEXTURE_BINDING,
+        );
+        let layer = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![1, 2],
+            TextureFormat::R8Unorm,
+        );
+        array.set_layer(1, &layer).unwrap();
+        assert_eq!(array.data, vec![0, 0, 1, 2, 0, 0]);
+
+        assert!(array.set_layer(3, &layer).is_err());
+        let wrong_format = layer.convert(TextureFormat::Rgba8UnormSrgb).unwrap();
+        assert!(array.set_layer(0, &wrong_format).is_err());
+    }
+
+    #[test]
+    fn image_to_format_broadcasts_channels() {
+        let size = Extent3d {
+            width: 2,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        let gray = Image::new(
+            size,
+            TextureDimension::D2,
+            vec![0, 128],
+            TextureFormat::R8Unorm,
+        );
+        let rgba = gray.to_format(TextureFormat::Rgba8Unorm).unwrap();
+        assert_eq!(rgba.texture_descriptor.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(rgba.data, vec![0, 0, 0, 255, 128, 128, 128, 255]);
+
+        let rgba16 = gray.to_format(TextureFormat::Rgba16Unorm).unwrap();
+        assert_eq!(
+            &rgba16.data[8..],
+            &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xff, 0xff]
+        );
+        let gray_alpha = rgba16.to_format(TextureFormat::Rg8Unorm).unwrap();
+        assert_eq!(gray_alpha.data, vec![0, 255, 128, 255]);
+
+        assert!(gray.to_format(TextureFormat::Bgra8Unorm).is_none());
+        assert!(gray.to_format(TextureFormat::Rgba32Float).is_none());
+    }
+
+    #[test]
+    fn image_to_format_reinterprets_srgb() {
+        let linear = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[10, 20, 30, 40],
+            TextureFormat::Rgba8Unorm,
+        );
+        let srgb = linear.to_format(TextureFormat::Rgba8UnormSrgb).unwrap();
+        assert_eq!(
+            srgb.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(srgb.data, linear.data);
+        assert_eq!(
+            srgb.to_format(TextureFormat::Rgba8Unorm).unwrap().data,
+            linear.data
+        );
+    }
+
+    #[test]
+    fn image_region_eq() {
+        let size = |width, height| Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let atlas = Image::new(
+            size(3, 2),
+            TextureDimension::D2,
+            vec![0, 1, 2, 3, 4, 5],
+            TextureFormat::R8Unorm,
+        );
+        let region = Image::new(
+            size(2, 2),
+            TextureDimension::D2,
+            vec![1, 2, 4, 5],
+            TextureFormat::R8Unorm,
+        );
+        assert!(atlas.region_eq(URect::new(1, 0, 3, 2), &region));
+        assert!(!atlas.region_eq(URect::new(0, 0, 2, 2), &region));
+        // Out of bounds, or a size or format mismatch
+        assert!(!atlas.region_eq(URect::new(2, 0, 4, 2), &region));
+        assert!(!atlas.region_eq(URect::new(1, 0, 3, 1), &region));
+        let rg = Image::new(
+            size(1, 2),
+            TextureDimension::D2,
+            vec![1, 2, 4, 5],
+            TextureFormat::Rg8Unorm,
+        );
+        assert!(!atlas.region_eq(URect::new(1, 0, 2, 2), &rg));
+    }
+
+    #[test]
+    fn image_get_and_set_pixel() {
+        let mut image = Image::checkerboard(UVec2::new(3, 2), 1, Color::WHITE, Color::BLACK);
+        assert_eq!(image.get_pixel(UVec2::new(1, 0)), Some(&[0, 0, 0, 255][..]));
+        assert_eq!(image.get_pixel(UVec2::new(3, 0)), None);
+
+        image.set_pixel(UVec2::new(2, 1), &[1, 2, 3, 4]).unwrap();
+        assert_eq!(&image.data[20..24], &[1, 2, 3, 4]);
+        assert!(image.set_pixel(UVec2::new(0, 2), &[1, 2, 3, 4]).is_err());
+        assert!(image.set_pixel(UVec2::ZERO, &[1, 2, 3]).is_err());
+    }
+
     #[test]
     fn image_default_size() {
         let image = Image::default();
         assert_eq!(Vec2::ONE, image.size());
     }
+
+    #[test]
+    fn image_estimated_gpu_size() {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 2,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+        );
+        assert_eq!(image.estimated_gpu_size_bytes(), 4 * 4 * 2 * 4);
+
+        // 4x4 + 2x2 + 1x1 texels per layer
+        image.texture_descriptor.mip_level_count = 3;
+        assert_eq!(image.estimated_gpu_size_bytes(), (16 + 4 + 1) * 2 * 4);
+
+        image.texture_descriptor.mip_level_count = 1;
+        image.texture_descriptor.format = TextureFormat::Bc1RgbaUnorm;
+        assert_eq!(image.estimated_gpu_size_bytes(), 8 * 2);
+    }
+
+    #[test]
+    fn image_sampler_presets() {
+        let Some(ImageSampler::Descriptor(pixel)) = ImageSampler::from_preset("pixel") else {
+            panic!("pixel preset should exist");
+        };
+        assert_eq!(pixel.mag_filter, wgpu::FilterMode::Nearest);
+
+        let Some(ImageSampler::Descriptor(aniso)) = ImageSampler::from_preset("repeat_aniso16")
+        else {
+            panic!("repeat_aniso16 preset should exist");
+        };
+        assert_eq!(aniso.anisotropy_clamp, 16);
+        assert_eq!(aniso.address_mode_u, wgpu::AddressMode::Repeat);
+
+        assert!(ImageSampler::from_preset("unknown").is_none());
+    }
+
+    #[test]
+    fn premultiply_alpha_round_trip() {
+        let mut image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![255, 128, 0, 128, 10, 20, 30, 0],
+            TextureFormat::Rgba8Unorm,
+        );
+        assert!(image.detect_straight_alpha_bleed());
+
+        image.premultiply_alpha().unwrap();
+        assert_eq!(image.data, vec![128, 64, 0, 128, 0, 0, 0, 0]);
+        assert!(!image.detect_straight_alpha_bleed());
+
+        image.unpremultiply_alpha().unwrap();
+        assert_eq!(image.data, vec![255, 128, 0, 128, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn image_resized() {
+        let image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            vec![0, 0, 0, 255, 255, 255, 255, 255],
+            TextureFormat::Rgba8Unorm,
+        );
+
+        let nearest = image
+            .resized(UVec2::new(4, 2), ImageFilter::Nearest)
+            .unwrap();
+        assert_eq!(nearest.size(), Vec2::new(4., 2.));
+        assert_eq!(
+            &nearest.data[..16],
+            &[0, 0, 0, 255, 0, 0, 0, 255, 255, 255, 255, 255, 255, 255, 255, 255]
+        );
+
+        let bilinear = image
+            .resized(UVec2::new(4, 1), ImageFilter::Bilinear)
+            .unwrap();
+        let reds: Vec<u8> = bilinear
+            .data
+            .chunks_exact(4)
+            .map(|texel| texel[0])
+            .collect();
+        assert_eq!(reds, vec![0, 64, 191, 255]);
+    }
+
+    #[test]
+    fn image_scale_to_fit_and_fill() {
+        let image = Image::new_fill(
+            Extent3d {
+                width: 200,
+                height: 100,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8Unorm,
+        );
+        assert_eq!(
+            image.scale_to_fit(UVec2::new(100, 100)),
+            UVec2::new(100, 50)
+        );
+        assert_eq!(
+            image.scale_to_fill(UVec2::new(100, 100)),
+            UVec2::new(200, 100)
+        );
+    }
+
+    #[test]
+    fn premultiply_alpha_unsupported_format() {
+        let mut image = Image::new_fill(
+            Extent3d::default(),
+            TextureDimension::D2,
+            &[0],
+            TextureFormat::R8Unorm,
+        );
+        assert!(image.premultiply_alpha().is_err());
+        assert!(!image.detect_straight_alpha_bleed());
+    }
 }
@@ -0,0 +1,196 @@
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+#[cfg(feature = "png")]
+use wgpu::TextureFormat;
+
+use super::{Image, TextureError};
+
+/// Encodes [`Image`]s as the bytes of an image file, to write back images made at runtime like
+/// packed texture atlases.
+pub trait ImageSaver: Send + Sync + 'static {
+    /// The extension of the files written by this saver, without the leading dot
+    fn extension(&self) -> &str;
+
+    /// Encodes `image` as the bytes of a file, failing with the name of the format if it isn't
+    /// supported.
+    fn save(&self, image: &Image) -> Result<Vec<u8>, TextureError>;
+}
+
+/// An error that occurs when saving an [`Image`] to a file.
+#[derive(Error, Debug)]
+pub enum ImageSaveError {
+    #[error("the image could not be encoded: {0}")]
+    Texture(#[from] TextureError),
+    #[error("failed to write {path:?}: {error}")]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+}
+
+impl Image {
+    /// Encodes this image with `saver` and writes it to the file at `path`.
+    pub fn save_with(
+        &self,
+        saver: &impl ImageSaver,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ImageSaveError> {
+        let path = path.as_ref();
+        let bytes = saver.save(self)?;
+        std::fs::write(path, bytes).map_err(|error| ImageSaveError::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Writes this image to the PNG file at `path`, see [`PngSaver`].
+    #[cfg(feature = "png")]
+    pub fn save_to_png(&self, path: impl AsRef<Path>) -> Result<(), ImageSaveError> {
+        self.save_with(&PngSaver, path)
+    }
+
+    /// Writes this image to the KTX2 file at `path` without supercompression, see [`Ktx2Saver`].
+    #[cfg(feature = "ktx2")]
+    pub fn save_to_ktx2(&self, path: impl AsRef<Path>) -> Result<(), ImageSaveError> {
+        self.save_with(&Ktx2Saver::default(), path)
+    }
+}
+
+/// Saves 8-bit [`Image`]s as PNG files, see [`image_to_png_buffer`].
+#[cfg(feature = "png")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PngSaver;
+
+#[cfg(feature = "png")]
+impl ImageSaver for PngSaver {
+    fn extension(&self) -> &str {
+        "png"
+    }
+
+    fn save(&self, image: &Image) -> Result<Vec<u8>, TextureError> {
+        image_to_png_buffer(image)
+    }
+}
+
+/// Saves [`Image`]s as KTX2 files, keeping their format and mip levels, see
+/// [`image_to_ktx2_buffer_supercompressed`](super::image_to_ktx2_buffer_supercompressed).
+#[cfg(feature = "ktx2")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Ktx2Saver {
+    /// The compression applied to each mip level
+    pub supercompression: super::Ktx2Supercompression,
+}
+
+#[cfg(feature = "ktx2")]
+impl ImageSaver for Ktx2Saver {
+    fn extension(&self) -> &str {
+        "ktx2"
+    }
+
+    fn save(&self, image: &Image) -> Result<Vec<u8>, TextureError> {
+        super::image_to_ktx2_buffer_supercompressed(image, self.supercompression)
+    }
+}
+
+/// Encodes the first mip level of an 8-bit R, RG, RGBA or BGRA 2D [`Image`] as a PNG file.
+///
+/// The file is flagged as sRGB for sRGB formats, and with a gamma of 1 for linear formats, so
+/// it is loaded back with the right colors.
+#[cfg(feature = "png")]
+pub fn image_to_png_buffer(image: &Image) -> Result<Vec<u8>, TextureError> {
+    let descriptor = &image.texture_descriptor;
+    let (color_type, swap_red_blue) = match descriptor.format {
+        TextureFormat::R8Unorm => (png::ColorType::Grayscale, false),
+        TextureFormat::Rg8Unorm => (png::ColorType::GrayscaleAlpha, false),
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => (png::ColorType::Rgba, false),
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => (png::ColorType::Rgba, true),
+        format => {
+            return Err(TextureError::UnsupportedTextureFormat(format!(
+                "{format:?} can't be encoded as PNG"
+            )))
+        }
+    };
+    if descriptor.dimension != wgpu::TextureDimension::D2
+        || descriptor.size.depth_or_array_layers > 1
+    {
+        return Err(TextureError::UnsupportedTextureFormat(format!(
+            "{:?} textures with {} layers can't be encoded as PNG",
+            descriptor.dimension, descriptor.size.depth_or_array_layers
+        )));
+    }
+
+    let (width, height) = (descriptor.size.width, descriptor.size.height);
+    let level_bytes = width as usize * height as usize * color_type.samples();
+    let Some(data) = image.data.get(..level_bytes) else {
+        return Err(TextureError::InvalidData(format!(
+            "Image data is {} bytes, but its first mip level requires {level_bytes} bytes",
+            image.data.len(),
+        )));
+    };
+    let mut data = data.to_vec();
+    if swap_red_blue {
+        for pixel in data.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+
+    let mut buffer = Vec::new();
+    let mut encoder = png::Encoder::new(&mut buffer, width, height);
+    encoder.set_color(color_type);
+    encoder.set_depth(png::BitDepth::Eight);
+    if descriptor.format.is_srgb() {
+        encoder.set_source_srgb(png::SrgbRenderingIntent::Perceptual);
+    } else {
+        encoder.set_source_gamma(png::ScaledFloat::new(1.0));
+    }
+    encoder
+        .write_header()
+        .and_then(|mut writer| writer.write_image_data(&data))
+        .map_err(|err| TextureError::InvalidData(format!("Failed to encode PNG: {err}")))?;
+    Ok(buffer)
+}
+
+#[cfg(all(test, feature = "png"))]
+mod tests {
+    use super::*;
+    use wgpu::{Extent3d, TextureDimension};
+
+    #[test]
+    fn png_round_trip() {
+        let size = Extent3d {
+            width: 2,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let data: Vec<u8> = (0..16).collect();
+        let image = Image::new(
+            size,
+            TextureDimension::D2,
+            data.clone(),
+            TextureFormat::Bgra8UnormSrgb,
+        );
+
+        let buffer = PngSaver.save(&image).unwrap();
+        let mut reader = png::Decoder::new(buffer.as_slice()).read_info().unwrap();
+        assert!(reader.info().srgb.is_some());
+        let mut decoded = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut decoded).unwrap();
+        let mut expected = data;
+        for pixel in expected.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+        assert_eq!(decoded, expected);
+
+        let image = Image::new(
+            size,
+            TextureDimension::D2,
+            vec![0; 8],
+            TextureFormat::R16Float,
+        );
+        assert_eq!(
+            PngSaver.save(&image).unwrap_err().to_string(),
+            "unsupported texture format: R16Float can't be encoded as PNG"
+        );
+    }
+}
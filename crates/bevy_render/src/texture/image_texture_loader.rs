@@ -1,12 +1,15 @@
+use std::path::{Path, PathBuf};
+
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
-use bevy_ecs::prelude::{FromWorld, World};
+use bevy_ecs::prelude::{FromWorld, Resource, World};
+use bevy_log::warn;
 use bevy_utils::BoxedFuture;
 use thiserror::Error;
 
 use crate::{
     renderer::RenderDevice,
-    texture::{Image, ImageType, TextureError},
+    texture::{Image, ImageSampler, ImageType, TextureError},
 };
 
 use super::CompressedImageFormats;
@@ -15,6 +18,48 @@ use super::CompressedImageFormats;
 #[derive(Clone)]
 pub struct ImageTextureLoader {
     supported_compressed_formats: CompressedImageFormats,
+    settings: ImageLoaderSettings,
+}
+
+/// Settings applied by the [`ImageTextureLoader`] to every image it loads.
+///
+/// Configured through [`ImagePlugin::loader_settings`](super::ImagePlugin::loader_settings).
+#[derive(Resource, Debug, Default, Clone)]
+pub struct ImageLoaderSettings {
+    /// Convert loaded images from straight to premultiplied alpha, see [`Image::premultiply_alpha`].
+    /// Images in formats that don't support the conversion are loaded unchanged.
+    pub premultiply_alpha: bool,
+    /// The samplers assigned to the loaded images whose file doesn't specify one, like the sampler
+    /// info of some KTX2 files, by the asset path the images are loaded from. See
+    /// [`ImageSampler::from_preset`] for common configurations.
+    ///
+    /// An image uses the sampler of the longest path its own path starts with, compared by
+    /// component, so `"sprites"` matches `"sprites/player.png"` but not `"sprites_hd/player.png"`.
+    /// From highest to lowest precedence, an image is sampled with the sampler of its file, then
+    /// with the one matching its path, then with the
+    /// [`ImagePlugin::default_sampler`](super::ImagePlugin::default_sampler).
+    pub samplers: Vec<(PathBuf, ImageSampler)>,
+}
+
+impl ImageLoaderSettings {
+    /// Assigns `sampler` to the images loaded from `path`, a directory or a single file, see
+    /// [`ImageLoaderSettings::samplers`].
+    #[must_use]
+    pub fn with_sampler(mut self, path: impl Into<PathBuf>, sampler: ImageSampler) -> Self {
+        self.samplers.push((path.into(), sampler));
+        self
+    }
+
+    /// Returns the sampler assigned to the image loaded from `path`, if any.
+    ///
+    /// When several paths of the same length match, the last one added is used.
+    pub fn sampler(&self, path: &Path) -> Option<&ImageSampler> {
+        self.samplers
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.components().count())
+            .map(|(_, sampler)| sampler)
+    }
 }
 
 const FILE_EXTENSIONS: &[&str] = &[
@@ -56,7 +101,7 @@ impl AssetLoader for ImageTextureLoader {
             // use the file extension for the image type
             let ext = load_context.path().extension().unwrap().to_str().unwrap();
 
-            let dyn_img = Image::from_buffer(
+            let mut dyn_img = Image::from_buffer(
                 bytes,
                 ImageType::Extension(ext),
                 self.supported_compressed_formats,
@@ -67,6 +112,22 @@ impl AssetLoader for ImageTextureLoader {
                 path: format!("{}", load_context.path().display()),
             })?;
 
+            if self.settings.premultiply_alpha {
+                if let Err(err) = dyn_img.premultiply_alpha() {
+                    warn!(
+                        "Could not premultiply alpha of image {}: {err}",
+                        load_context.path().display()
+                    );
+                }
+            }
+
+            if let (Some(sampler), ImageSampler::Default) = (
+                self.settings.sampler(load_context.path()),
+                &dyn_img.sampler_descriptor,
+            ) {
+                dyn_img.sampler_descriptor = sampler.clone();
+            }
+
             load_context.set_default_asset(LoadedAsset::new(dyn_img));
             Ok(())
         })
@@ -86,6 +147,10 @@ impl FromWorld for ImageTextureLoader {
         };
         Self {
             supported_compressed_formats,
+            settings: world
+                .get_resource::<ImageLoaderSettings>()
+                .cloned()
+                .unwrap_or_default(),
         }
     }
 }
@@ -105,3 +170,30 @@ impl std::fmt::Display for FileTextureError {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::ImageLoaderSettings;
+    use crate::texture::ImageSampler;
+
+    #[test]
+    fn samplers_match_the_longest_path() {
+        let settings = ImageLoaderSettings::default()
+            .with_sampler("sprites", ImageSampler::nearest())
+            .with_sampler("sprites/smooth", ImageSampler::linear());
+        let sampler = |path: &str| settings.sampler(Path::new(path));
+
+        assert!(matches!(
+            sampler("sprites/player.png"),
+            Some(ImageSampler::Descriptor(descriptor)) if descriptor == &ImageSampler::nearest_descriptor()
+        ));
+        assert!(matches!(
+            sampler("sprites/smooth/cloud.png"),
+            Some(ImageSampler::Descriptor(descriptor)) if descriptor == &ImageSampler::linear_descriptor()
+        ));
+        assert!(sampler("sprites_hd/player.png").is_none());
+        assert!(sampler("player.png").is_none());
+    }
+}
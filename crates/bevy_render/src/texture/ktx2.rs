@@ -10,8 +10,8 @@ use bevy_utils::default;
 #[cfg(any(feature = "flate2", feature = "ruzstd"))]
 use ktx2::SupercompressionScheme;
 use ktx2::{
-    BasicDataFormatDescriptor, ChannelTypeQualifiers, ColorModel, DataFormatDescriptorHeader,
-    Header, SampleInformation,
+    BasicDataFormatDescriptor, ChannelTypeQualifiers, ColorModel, ColorPrimaries,
+    DataFormatDescriptorHeader, DataFormatFlags, Header, SampleInformation, TransferFunction,
 };
 use wgpu::{
     AstcBlock, AstcChannel, Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor,
@@ -310,6 +310,233 @@ pub fn ktx2_buffer_to_image(
     Ok(image)
 }
 
+/// The supercompression applied to the mip levels of the KTX2 files written by
+/// [`image_to_ktx2_buffer_supercompressed`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ktx2Supercompression {
+    /// Store the levels as they are
+    #[default]
+    None,
+    /// Compress the levels with zlib
+    #[cfg(feature = "zlib")]
+    Zlib,
+}
+
+/// Encodes an uncompressed 2D [`Image`], including its mip levels and array layers, as a KTX2
+/// file without supercompression.
+///
+/// 8-bit and 16-bit unorm and 16-bit and 32-bit float formats with one, two or four channels
+/// are supported, and 8-bit BGRA.
+pub fn image_to_ktx2_buffer(image: &Image) -> Result<Vec<u8>, TextureError> {
+    image_to_ktx2_buffer_supercompressed(image, Ktx2Supercompression::None)
+}
+
+/// Encodes an uncompressed 2D [`Image`] like [`image_to_ktx2_buffer`], with each mip level
+/// compressed by `supercompression`.
+pub fn image_to_ktx2_buffer_supercompressed(
+    image: &Image,
+    supercompression: Ktx2Supercompression,
+) -> Result<Vec<u8>, TextureError> {
+    const IDENTIFIER: [u8; 12] = [
+        0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+    ];
+    const HEADER_BYTES: usize = 80;
+    const LEVEL_INDEX_ENTRY_BYTES: usize = 24;
+    // KHR_DF_CHANNEL_RGBSDA_* ids
+    const RED: u8 = 0;
+    const GREEN: u8 = 1;
+    const BLUE: u8 = 2;
+    const ALPHA: u8 = 15;
+    const R: &[u8] = &[RED];
+    const RG: &[u8] = &[RED, GREEN];
+    const RGBA: &[u8] = &[RED, GREEN, BLUE, ALPHA];
+    const BGRA: &[u8] = &[BLUE, GREEN, RED, ALPHA];
+
+    let descriptor = &image.texture_descriptor;
+    // The format, the channels in memory order, the bits of each channel and whether they are
+    // floats instead of unorms
+    let (format, channels, bits, float) = match descriptor.format {
+        TextureFormat::R8Unorm => (ktx2::Format::R8_UNORM, R, 8, false),
+        TextureFormat::Rg8Unorm => (ktx2::Format::R8G8_UNORM, RG, 8, false),
+        TextureFormat::Rgba8Unorm => (ktx2::Format::R8G8B8A8_UNORM, RGBA, 8, false),
+        TextureFormat::Rgba8UnormSrgb => (ktx2::Format::R8G8B8A8_SRGB, RGBA, 8, false),
+        TextureFormat::Bgra8Unorm => (ktx2::Format::B8G8R8A8_UNORM, BGRA, 8, false),
+        TextureFormat::Bgra8UnormSrgb => (ktx2::Format::B8G8R8A8_SRGB, BGRA, 8, false),
+        TextureFormat::R16Unorm => (ktx2::Format::R16_UNORM, R, 16, false),
+        TextureFormat::Rg16Unorm => (ktx2::Format::R16G16_UNORM, RG, 16, false),
+        TextureFormat::Rgba16Unorm => (ktx2::Format::R16G16B16A16_UNORM, RGBA, 16, false),
+        TextureFormat::R16Float => (ktx2::Format::R16_SFLOAT, R, 16, true),
+        TextureFormat::Rg16Float => (ktx2::Format::R16G16_SFLOAT, RG, 16, true),
+        TextureFormat::Rgba16Float => (ktx2::Format::R16G16B16A16_SFLOAT, RGBA, 16, true),
+        TextureFormat::R32Float => (ktx2::Format::R32_SFLOAT, R, 32, true),
+        TextureFormat::Rg32Float => (ktx2::Format::R32G32_SFLOAT, RG, 32, true),
+        TextureFormat::Rgba32Float => (ktx2::Format::R32G32B32A32_SFLOAT, RGBA, 32, true),
+        format => {
+            return Err(TextureError::UnsupportedTextureFormat(format!(
+                "{format:?} can't be encoded as KTX2"
+            )))
+        }
+    };
+    if descriptor.dimension != TextureDimension::D2 {
+        return Err(TextureError::UnsupportedTextureFormat(format!(
+            "{:?} textures can't be encoded as KTX2",
+            descriptor.dimension
+        )));
+    }
+    let is_srgb = descriptor.format.is_srgb();
+    let (width, height) = (descriptor.size.width, descriptor.size.height);
+    let layer_count = descriptor.size.depth_or_array_layers.max(1);
+    let level_count = descriptor.mip_level_count.max(1);
+    let pixel_bytes = channels.len() * bits as usize / 8;
+
+    // Split the wgpu LayerYMipX ordered data into levels
+    let level_bytes = |level: u32| {
+        (width as usize >> level).max(1) * (height as usize >> level).max(1) * pixel_bytes
+    };
+    let layer_bytes: usize = (0..level_count).map(level_bytes).sum();
+    if image.data.len() != layer_bytes * layer_count as usize {
+        return Err(TextureError::InvalidData(format!(
+            "Image data is {} bytes, but its descriptor requires {} bytes",
+            image.data.len(),
+            layer_bytes * layer_count as usize
+        )));
+    }
+    let levels: Vec<Vec<u8>> = (0..level_count)
+        .map(|level| {
+            let level_offset: usize = (0..level).map(level_bytes).sum();
+            (0..layer_count as usize)
+                .flat_map(|layer| {
+                    let begin = layer * layer_bytes + level_offset;
+                    image.data[begin..begin + level_bytes(level)]
+                        .iter()
+                        .copied()
+                })
+                .collect()
+        })
+        .collect();
+    let (scheme, compressed_levels) = match supercompression {
+        Ktx2Supercompression::None => (0, None),
+        #[cfg(feature = "zlib")]
+        Ktx2Supercompression::Zlib => {
+            let compressed = levels
+                .iter()
+                .map(|level| {
+                    use std::io::Write;
+                    let mut encoder =
+                        flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::best());
+                    encoder.write_all(level)?;
+                    encoder.finish()
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| {
+                    TextureError::InvalidData(format!("Failed to compress with zlib: {err}"))
+                })?;
+            (SupercompressionScheme::ZLIB.0.get(), Some(compressed))
+        }
+    };
+
+    // Basic data format descriptor, with one sample per channel
+    let mut dfd = Vec::new();
+    let descriptor_block_bytes = 24 + 16 * channels.len() as u32;
+    dfd.extend_from_slice(&(4 + descriptor_block_bytes).to_le_bytes());
+    // vendor id and descriptor type are both 0 for the basic descriptor block
+    dfd.extend_from_slice(&0u32.to_le_bytes());
+    dfd.extend_from_slice(&(2 | (descriptor_block_bytes << 16)).to_le_bytes());
+    let transfer_function = if is_srgb {
+        TransferFunction::SRGB
+    } else {
+        TransferFunction::Linear
+    };
+    dfd.extend_from_slice(&[
+        ColorModel::RGBSDA.0.get() as u8,
+        ColorPrimaries::BT709.0.get() as u8,
+        transfer_function.0.get() as u8,
+        DataFormatFlags::STRAIGHT_ALPHA.bits() as u8,
+    ]);
+    // texel block dimensions, minus one
+    dfd.extend_from_slice(&[0; 4]);
+    // bytes per plane, which is unknown for supercompressed data
+    let plane_bytes = if compressed_levels.is_some() {
+        0
+    } else {
+        pixel_bytes as u8
+    };
+    dfd.extend_from_slice(&[plane_bytes, 0, 0, 0, 0, 0, 0, 0]);
+    for (index, &channel) in channels.iter().enumerate() {
+        let mut qualifiers = ChannelTypeQualifiers::empty();
+        // Alpha is always stored linearly
+        if is_srgb && channel == ALPHA {
+            qualifiers |= ChannelTypeQualifiers::LINEAR;
+        }
+        if float {
+            qualifiers |= ChannelTypeQualifiers::FLOAT | ChannelTypeQualifiers::SIGNED;
+        }
+        let (lower, upper) = if float {
+            ((-1f32).to_bits(), 1f32.to_bits())
+        } else {
+            (0, u32::MAX >> (32 - bits))
+        };
+        dfd.extend_from_slice(&(bits as u16 * index as u16).to_le_bytes());
+        dfd.push(bits - 1);
+        dfd.push(channel | ((qualifiers.bits() as u8) << 4));
+        dfd.extend_from_slice(&[0; 4]);
+        dfd.extend_from_slice(&lower.to_le_bytes());
+        dfd.extend_from_slice(&upper.to_le_bytes());
+    }
+
+    let dfd_offset = HEADER_BYTES + LEVEL_INDEX_ENTRY_BYTES * level_count as usize;
+    let mut buffer = Vec::with_capacity(dfd_offset + dfd.len() + image.data.len() + 4);
+    buffer.extend_from_slice(&IDENTIFIER);
+    for value in [
+        format.0.get(),
+        // type size
+        bits as u32 / 8,
+        width,
+        height,
+        // pixel depth
+        0,
+        if layer_count > 1 { layer_count } else { 0 },
+        // face count
+        1,
+        level_count,
+        scheme,
+    ] {
+        buffer.extend_from_slice(&value.to_le_bytes());
+    }
+    buffer.extend_from_slice(&(dfd_offset as u32).to_le_bytes());
+    buffer.extend_from_slice(&(dfd.len() as u32).to_le_bytes());
+    // no key/value data or supercompression global data
+    buffer.extend_from_slice(&[0; 24]);
+
+    // Levels are stored smallest first, each aligned to 4 bytes, or unaligned if supercompressed
+    let stored_levels = compressed_levels.as_ref().unwrap_or(&levels);
+    let align = |offset: usize| {
+        if compressed_levels.is_some() {
+            offset
+        } else {
+            (offset + 3) & !3
+        }
+    };
+    let data_offset = align(dfd_offset + dfd.len());
+    let mut level_offsets = vec![0; levels.len()];
+    let mut offset = data_offset;
+    for (level, data) in stored_levels.iter().enumerate().rev() {
+        level_offsets[level] = offset;
+        offset = align(offset + data.len());
+    }
+    for (level, data) in stored_levels.iter().enumerate() {
+        for value in [level_offsets[level], data.len(), levels[level].len()] {
+            buffer.extend_from_slice(&(value as u64).to_le_bytes());
+        }
+    }
+    buffer.extend_from_slice(&dfd);
+    for (level, data) in stored_levels.iter().enumerate().rev() {
+        buffer.resize(level_offsets[level], 0);
+        buffer.extend_from_slice(data);
+    }
+    Ok(buffer)
+}
+
 #[cfg(feature = "basis-universal")]
 pub fn get_transcoded_formats(
     supported_compressed_formats: CompressedImageFormats,
@@ -1493,3 +1720,90 @@ pub fn ktx2_format_to_texture_format(
         }
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ktx2_round_trip() {
+        let mut image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            (0..32).collect(),
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        // Add a 2x1 mip level
+        image.texture_descriptor.mip_level_count = 2;
+        image.data.extend(100..108);
+
+        let buffer = image_to_ktx2_buffer(&image).unwrap();
+        let reader = ktx2::Reader::new(&buffer).unwrap();
+        let dfd = reader.data_format_descriptors().next().unwrap();
+        assert_eq!(dfd.header, DataFormatDescriptorHeader::BASIC);
+        let basic = BasicDataFormatDescriptor::parse(dfd.data).unwrap();
+        assert_eq!(basic.transfer_function, Some(TransferFunction::SRGB));
+        assert_eq!(basic.sample_information().count(), 4);
+
+        let loaded = ktx2_buffer_to_image(&buffer, CompressedImageFormats::NONE, true).unwrap();
+        assert_eq!(
+            loaded.texture_descriptor.format,
+            TextureFormat::Rgba8UnormSrgb
+        );
+        assert_eq!(
+            loaded.texture_descriptor.size,
+            image.texture_descriptor.size
+        );
+        assert_eq!(loaded.texture_descriptor.mip_level_count, 2);
+        assert_eq!(loaded.data, image.data);
+    }
+
+    #[test]
+    fn ktx2_float_round_trip() {
+        let mut image = Image::new(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 2,
+            },
+            TextureDimension::D2,
+            (0..64).collect(),
+            TextureFormat::Rg32Float,
+        );
+        image.texture_descriptor.mip_level_count = 2;
+        // A 1x1 mip level after each layer
+        image.data.splice(32..32, 200..208);
+        image.data.extend(208..216);
+
+        #[allow(unused_mut)]
+        let mut supercompressions = vec![Ktx2Supercompression::None];
+        #[cfg(feature = "zlib")]
+        supercompressions.push(Ktx2Supercompression::Zlib);
+        for supercompression in supercompressions {
+            let buffer = image_to_ktx2_buffer_supercompressed(&image, supercompression).unwrap();
+            let reader = ktx2::Reader::new(&buffer).unwrap();
+            assert_eq!(reader.header().format, Some(ktx2::Format::R32G32_SFLOAT));
+            assert_eq!(reader.header().type_size, 4);
+            let dfd = reader.data_format_descriptors().next().unwrap();
+            let basic = BasicDataFormatDescriptor::parse(dfd.data).unwrap();
+            assert_eq!(basic.transfer_function, Some(TransferFunction::Linear));
+            let sample = basic.sample_information().nth(1).unwrap();
+            assert_eq!((sample.bit_offset, sample.bit_length), (32, 32));
+
+            let loaded =
+                ktx2_buffer_to_image(&buffer, CompressedImageFormats::NONE, false).unwrap();
+            assert_eq!(loaded.texture_descriptor.format, TextureFormat::Rg32Float);
+            assert_eq!(loaded.data, image.data);
+        }
+
+        image.texture_descriptor.format = TextureFormat::Rgb9e5Ufloat;
+        assert_eq!(
+            image_to_ktx2_buffer(&image).unwrap_err().to_string(),
+            "unsupported texture format: Rgb9e5Ufloat can't be encoded as KTX2"
+        );
+    }
+}
@@ -4,11 +4,13 @@ mod basis;
 mod dds;
 #[cfg(feature = "exr")]
 mod exr_texture_loader;
+mod failed_image;
 mod fallback_image;
 #[cfg(feature = "hdr")]
 mod hdr_texture_loader;
 #[allow(clippy::module_inception)]
 mod image;
+mod image_saver;
 mod image_texture_loader;
 #[cfg(feature = "ktx2")]
 mod ktx2;
@@ -17,6 +19,7 @@ mod texture_cache;
 pub(crate) mod image_texture_conversion;
 
 pub use self::image::*;
+pub use self::image_saver::*;
 #[cfg(feature = "ktx2")]
 pub use self::ktx2::*;
 #[cfg(feature = "dds")]
@@ -26,6 +29,7 @@ pub use exr_texture_loader::*;
 #[cfg(feature = "hdr")]
 pub use hdr_texture_loader::*;
 
+pub use failed_image::*;
 pub use fallback_image::*;
 pub use image_texture_loader::*;
 pub use texture_cache::*;
@@ -35,7 +39,7 @@ use crate::{
     renderer::RenderDevice,
     Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_asset::{AddAsset, Assets};
 use bevy_ecs::prelude::*;
 
@@ -43,7 +47,14 @@ use bevy_ecs::prelude::*;
 /// Adds the [`Image`] as an asset and makes sure that they are extracted and prepared for the GPU.
 pub struct ImagePlugin {
     /// The default image sampler to use when [`ImageSampler`] is set to `Default`.
+    ///
+    /// Loaded images without a sampler in their file use the sampler assigned to their path in
+    /// [`ImageLoaderSettings::samplers`] instead, if there is one.
     pub default_sampler: wgpu::SamplerDescriptor<'static>,
+    /// The settings the [`ImageTextureLoader`] applies to every loaded image.
+    pub loader_settings: ImageLoaderSettings,
+    /// What to draw in place of images that failed to load.
+    pub fallback: FallbackImageSettings,
 }
 
 impl Default for ImagePlugin {
@@ -57,6 +68,8 @@ impl ImagePlugin {
     pub fn default_linear() -> ImagePlugin {
         ImagePlugin {
             default_sampler: ImageSampler::linear_descriptor(),
+            loader_settings: ImageLoaderSettings::default(),
+            fallback: FallbackImageSettings::default(),
         }
     }
 
@@ -64,12 +77,18 @@ impl ImagePlugin {
     pub fn default_nearest() -> ImagePlugin {
         ImagePlugin {
             default_sampler: ImageSampler::nearest_descriptor(),
+            loader_settings: ImageLoaderSettings::default(),
+            fallback: FallbackImageSettings::default(),
         }
     }
 }
 
 impl Plugin for ImagePlugin {
     fn build(&self, app: &mut App) {
+        app.insert_resource(self.loader_settings.clone())
+            .insert_resource(self.fallback.clone())
+            .init_resource::<FailedImages>();
+
         #[cfg(any(
             feature = "png",
             feature = "dds",
@@ -102,6 +121,15 @@ impl Plugin for ImagePlugin {
         app.world
             .resource_mut::<Assets<Image>>()
             .set_untracked(DEFAULT_IMAGE_HANDLE, Image::default());
+        app.add_systems(
+            PostUpdate,
+            (
+                watch_image_handles,
+                update_failed_images,
+                update_failed_image_fallback,
+            )
+                .chain(),
+        );
 
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<TextureCache>().add_systems(
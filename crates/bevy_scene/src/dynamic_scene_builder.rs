@@ -38,9 +38,12 @@ use std::collections::BTreeMap;
 /// ```
 pub struct DynamicSceneBuilder<'w> {
     extracted_resources: BTreeMap<ComponentId, Box<dyn Reflect>>,
-    extracted_scene: BTreeMap<Entity, DynamicEntity>,
+    pub(crate) extracted_scene: BTreeMap<Entity, DynamicEntity>,
+    /// The [`InlineAssets`](crate::InlineAssets) of the scene, see
+    /// [`DynamicSceneBuilder::extract_assets`]
+    pub(crate) extracted_assets: Vec<Box<dyn Reflect>>,
     type_registry: AppTypeRegistry,
-    original_world: &'w World,
+    pub(crate) original_world: &'w World,
 }
 
 impl<'w> DynamicSceneBuilder<'w> {
@@ -50,6 +53,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         Self {
             extracted_resources: default(),
             extracted_scene: default(),
+            extracted_assets: default(),
             type_registry: world.resource::<AppTypeRegistry>().clone(),
             original_world: world,
         }
@@ -61,6 +65,7 @@ impl<'w> DynamicSceneBuilder<'w> {
         Self {
             extracted_resources: default(),
             extracted_scene: default(),
+            extracted_assets: default(),
             type_registry,
             original_world: world,
         }
@@ -72,7 +77,11 @@ impl<'w> DynamicSceneBuilder<'w> {
     /// [`Self::remove_empty_entities`] before building the scene.
     pub fn build(self) -> DynamicScene {
         DynamicScene {
-            resources: self.extracted_resources.into_values().collect(),
+            resources: self
+                .extracted_resources
+                .into_values()
+                .chain(self.extracted_assets)
+                .collect(),
             entities: self.extracted_scene.into_values().collect(),
         }
     }
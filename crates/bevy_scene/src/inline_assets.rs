@@ -0,0 +1,191 @@
+use std::marker::PhantomData;
+
+use bevy_app::{App, Plugin, Update};
+use bevy_asset::{AddAsset, Asset, Assets, Handle, HandleId};
+use bevy_ecs::{
+    prelude::{ReflectResource, Resource},
+    schedule::IntoSystemConfigs,
+    system::ResMut,
+};
+use bevy_reflect::{FromReflect, GetTypeRegistration, Reflect};
+use bevy_utils::HashMap;
+
+use crate::{scene_spawner_system, DynamicSceneBuilder};
+
+/// Assets stored in a [`DynamicScene`](crate::DynamicScene) next to the entities using them.
+///
+/// Scenes only store the ids of the [`Handle`]s of their entities, so the assets created at
+/// runtime, like a [`TextureAtlas`] made from a grid, are missing when the scene is loaded in
+/// another app. Small assets like these can be stored in the scene as this resource with
+/// [`DynamicSceneBuilder::extract_assets`], and are added back to their [`Assets`] with the same
+/// ids when the scene is spawned, so the handles of its entities point to them again.
+///
+/// Spawning scenes with inline assets of type `T` needs the [`InlineAssetsPlugin<T>`], next to
+/// adding `T` with [`AddAsset::add_asset`]:
+///
+/// ```
+/// # use bevy_app::App;
+/// # use bevy_reflect::{Reflect, FromReflect, TypeUuid};
+/// # use bevy_scene::InlineAssetsPlugin;
+/// #[derive(Reflect, FromReflect, TypeUuid, Clone)]
+/// #[uuid = "7c9a0fe5-3d0b-4d2e-9a6f-2f1e5b7c8d40"]
+/// struct Layout {
+///     columns: usize,
+/// }
+///
+/// # let mut app = App::new();
+/// app.add_plugin(InlineAssetsPlugin::<Layout>::default());
+/// ```
+///
+/// [`TextureAtlas`]: https://docs.rs/bevy/latest/bevy/sprite/struct.TextureAtlas.html
+#[derive(Resource, Reflect, FromReflect)]
+#[reflect(Resource)]
+pub struct InlineAssets<T: Asset + FromReflect> {
+    /// The assets by the id of their handle
+    pub assets: HashMap<HandleId, T>,
+}
+
+impl<T: Asset + FromReflect> Default for InlineAssets<T> {
+    fn default() -> Self {
+        Self {
+            assets: HashMap::default(),
+        }
+    }
+}
+
+/// Adds the [`InlineAssets<T>`] of spawned scenes to [`Assets<T>`].
+pub struct InlineAssetsPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for InlineAssetsPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Asset + FromReflect + GetTypeRegistration> Plugin for InlineAssetsPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.register_asset_reflect::<T>()
+            .register_type::<InlineAssets<T>>()
+            .register_type::<HashMap<HandleId, T>>()
+            .add_systems(
+                Update,
+                insert_inline_assets::<T>.after(scene_spawner_system),
+            );
+    }
+}
+
+/// Moves the [`InlineAssets<T>`] written by spawned scenes into [`Assets<T>`].
+pub fn insert_inline_assets<T: Asset + FromReflect>(
+    inline_assets: Option<ResMut<InlineAssets<T>>>,
+    mut assets: ResMut<Assets<T>>,
+) {
+    let Some(mut inline_assets) = inline_assets else {
+        return;
+    };
+    if inline_assets.assets.is_empty() {
+        return;
+    }
+    for (id, asset) in inline_assets.assets.drain() {
+        assets.set_untracked(id, asset);
+    }
+}
+
+impl DynamicSceneBuilder<'_> {
+    /// Extract the assets of type `T` used by the [`Handle<T>`] components of the extracted
+    /// entities into the [`InlineAssets<T>`] of the scene.
+    ///
+    /// Only the entities extracted before calling this method are searched for handles, and
+    /// handles nested in other components are not followed. Handles stored in the assets
+    /// themselves keep their ids, but the assets they point to are not extracted.
+    pub fn extract_assets<T: Asset + FromReflect + Clone>(&mut self) -> &mut Self {
+        let Some(assets) = self.original_world.get_resource::<Assets<T>>() else {
+            return self;
+        };
+        let mut extracted: HashMap<HandleId, T> = self
+            .extracted_scene
+            .keys()
+            .filter_map(|entity| self.original_world.get::<Handle<T>>(*entity))
+            .filter_map(|handle| Some((handle.id(), assets.get(handle)?.clone())))
+            .collect();
+        if extracted.is_empty() {
+            return self;
+        }
+
+        if let Some(inline_assets) = self
+            .extracted_assets
+            .iter_mut()
+            .find_map(|resource| resource.downcast_mut::<InlineAssets<T>>())
+        {
+            inline_assets.assets.extend(extracted.drain());
+        } else {
+            self.extracted_assets
+                .push(Box::new(InlineAssets { assets: extracted }));
+        }
+        self
+    }
+}
+
+#[cfg(all(test, feature = "serialize"))]
+mod tests {
+    use super::*;
+    use crate::serde::SceneDeserializer;
+    use bevy_app::AppTypeRegistry;
+    use bevy_asset::AssetPlugin;
+    use bevy_ecs::entity::EntityMap;
+    use bevy_reflect::TypeUuid;
+    use bevy_tasks::IoTaskPool;
+    use serde::de::DeserializeSeed;
+
+    #[derive(Reflect, FromReflect, TypeUuid, Clone, Debug, PartialEq)]
+    #[uuid = "4d6c9f0e-8a1b-4c2d-9e3f-5a6b7c8d9e01"]
+    struct Layout {
+        columns: usize,
+    }
+
+    fn app() -> App {
+        IoTaskPool::init(Default::default);
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_asset::<Layout>()
+            .add_plugin(InlineAssetsPlugin::<Layout>::default());
+        app
+    }
+
+    #[test]
+    fn inline_assets_round_trip() {
+        let mut source = app();
+        let handle = source
+            .world
+            .resource_mut::<Assets<Layout>>()
+            .add(Layout { columns: 4 });
+        let entity = source.world.spawn(handle.clone()).id();
+
+        let mut builder = DynamicSceneBuilder::from_world(&source.world);
+        builder.extract_entity(entity).extract_assets::<Layout>();
+        let registry = source.world.resource::<AppTypeRegistry>().0.clone();
+        let ron = builder.build().serialize_ron(&registry).unwrap();
+
+        let mut destination = app();
+        let scene = {
+            let registry = destination.world.resource::<AppTypeRegistry>().read();
+            let mut deserializer = ron::de::Deserializer::from_str(&ron).unwrap();
+            SceneDeserializer {
+                type_registry: &registry,
+            }
+            .deserialize(&mut deserializer)
+            .unwrap()
+        };
+        let mut entity_map = EntityMap::default();
+        scene
+            .write_to_world(&mut destination.world, &mut entity_map)
+            .unwrap();
+        destination.update();
+
+        let entity = entity_map.get(entity).unwrap();
+        let handle = destination.world.get::<Handle<Layout>>(entity).unwrap();
+        assert_eq!(
+            destination.world.resource::<Assets<Layout>>().get(handle),
+            Some(&Layout { columns: 4 })
+        );
+    }
+}
@@ -3,6 +3,7 @@
 mod bundle;
 mod dynamic_scene;
 mod dynamic_scene_builder;
+mod inline_assets;
 mod scene;
 mod scene_loader;
 mod scene_spawner;
@@ -13,6 +14,7 @@ pub mod serde;
 pub use bundle::*;
 pub use dynamic_scene::*;
 pub use dynamic_scene_builder::*;
+pub use inline_assets::*;
 pub use scene::*;
 pub use scene_loader::*;
 pub use scene_spawner::*;
@@ -0,0 +1,223 @@
+use crate::TextureAtlasSprite;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::{Event, EventWriter},
+    reflect::ReflectComponent,
+    system::{Query, Res},
+};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_time::{Time, Timer, TimerMode};
+use std::time::Duration;
+
+/// Plays a sequence of [`TextureAtlas`](crate::TextureAtlas) frames on a [`TextureAtlasSprite`].
+///
+/// The current frame is tracked by an [`AnimatedSpriteState`], which must be added to the
+/// same entity.
+#[derive(Component, Debug, Default, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct AnimatedSprite {
+    /// The indices in the [`TextureAtlas`](crate::TextureAtlas) of the frames to play, in order
+    pub frames: Vec<usize>,
+    /// The number of frames played per second. The animation is paused if this isn't positive, is
+    /// NaN, or is so small that a frame would last longer than a [`Duration`] can hold.
+    pub fps: f32,
+    /// Whether to restart from the first frame after the last one, instead of stopping there
+    pub looping: bool,
+}
+
+impl AnimatedSprite {
+    /// Create a new looping [`AnimatedSprite`] playing `frames` at `fps` frames per second
+    pub fn new(frames: Vec<usize>, fps: f32) -> Self {
+        Self {
+            frames,
+            fps,
+            looping: true,
+        }
+    }
+}
+
+/// The playback state of an [`AnimatedSprite`].
+#[derive(Component, Debug, Default, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct AnimatedSpriteState {
+    /// Position of the current frame in [`AnimatedSprite::frames`]
+    pub current_frame: usize,
+    /// Timer ticking once per frame. Its duration is kept in sync with [`AnimatedSprite::fps`].
+    pub timer: Timer,
+    /// Whether the non-looping animation reached its last frame and sent [`AnimationFinished`].
+    /// It is reset when the current frame is moved back before the last one.
+    pub finished: bool,
+}
+
+/// Sent when a non-looping [`AnimatedSprite`] reaches its last frame, right away if it only has
+/// one frame.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationFinished {
+    /// The entity of the finished [`AnimatedSprite`]
+    pub entity: Entity,
+}
+
+/// Advances every [`AnimatedSprite`] and updates the index of its [`TextureAtlasSprite`].
+pub fn animate_sprites(
+    time: Res<Time>,
+    mut animations: Query<(
+        Entity,
+        &AnimatedSprite,
+        &mut AnimatedSpriteState,
+        &mut TextureAtlasSprite,
+    )>,
+    mut finished_events: EventWriter<AnimationFinished>,
+) {
+    for (entity, animation, mut state, mut sprite) in &mut animations {
+        // `fps` is public, so it can be anything: treat what isn't a usable rate as paused
+        if animation.frames.is_empty() || animation.fps.is_nan() || animation.fps <= 0.0 {
+            continue;
+        }
+        let Ok(frame_duration) = Duration::try_from_secs_f32(1.0 / animation.fps) else {
+            continue;
+        };
+        if state.timer.duration() != frame_duration || state.timer.mode() != TimerMode::Repeating {
+            state.timer.set_duration(frame_duration);
+            state.timer.set_mode(TimerMode::Repeating);
+        }
+
+        let last_frame = animation.frames.len() - 1;
+        let previous_frame = state.current_frame;
+        let advanced = state.timer.tick(time.delta()).times_finished_this_tick() as usize;
+        let next_frame = previous_frame.saturating_add(advanced);
+        state.current_frame = if animation.looping {
+            next_frame % animation.frames.len()
+        } else {
+            next_frame.min(last_frame)
+        };
+
+        if !animation.looping {
+            if state.current_frame < last_frame {
+                state.finished = false;
+            } else if !state.finished {
+                state.finished = true;
+                finished_events.send(AnimationFinished { entity });
+            }
+        }
+
+        let index = animation.frames[state.current_frame];
+        // only trigger change detection when the new value is different
+        if sprite.index != index {
+            sprite.index = index;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+    use bevy_utils::Instant;
+
+    fn run(world: &mut World, schedule: &mut Schedule, elapsed: Duration) {
+        let startup = world.resource::<Time>().startup();
+        world
+            .resource_mut::<Time>()
+            .update_with_instant(startup + elapsed);
+        schedule.run(world);
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.insert_resource(Time::new(Instant::now()));
+        world.init_resource::<Events<AnimationFinished>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(animate_sprites);
+        (world, schedule)
+    }
+
+    #[test]
+    fn animated_sprite_advances_and_finishes() {
+        let (mut world, mut schedule) = setup();
+
+        let entity = world
+            .spawn((
+                AnimatedSprite {
+                    frames: vec![4, 5, 6],
+                    fps: 10.0,
+                    looping: false,
+                },
+                AnimatedSpriteState::default(),
+                TextureAtlasSprite::new(4),
+            ))
+            .id();
+
+        // First update only establishes the delta
+        run(&mut world, &mut schedule, Duration::ZERO);
+        run(&mut world, &mut schedule, Duration::from_millis(150));
+        assert_eq!(world.get::<TextureAtlasSprite>(entity).unwrap().index, 5);
+        assert!(world.resource::<Events<AnimationFinished>>().is_empty());
+
+        run(&mut world, &mut schedule, Duration::from_millis(500));
+        assert_eq!(world.get::<TextureAtlasSprite>(entity).unwrap().index, 6);
+        let events = world.resource::<Events<AnimationFinished>>();
+        assert_eq!(
+            events
+                .iter_current_update_events()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![AnimationFinished { entity }]
+        );
+    }
+
+    #[test]
+    fn unusable_fps_pauses_the_animation() {
+        let (mut world, mut schedule) = setup();
+        let entities: Vec<_> = [0.0, -5.0, f32::NAN, f32::MIN_POSITIVE]
+            .into_iter()
+            .map(|fps| {
+                world
+                    .spawn((
+                        AnimatedSprite::new(vec![1, 2], fps),
+                        AnimatedSpriteState::default(),
+                        TextureAtlasSprite::new(1),
+                    ))
+                    .id()
+            })
+            .collect();
+
+        run(&mut world, &mut schedule, Duration::ZERO);
+        run(&mut world, &mut schedule, Duration::from_secs(10));
+        for entity in entities {
+            assert_eq!(world.get::<TextureAtlasSprite>(entity).unwrap().index, 1);
+        }
+    }
+
+    #[test]
+    fn single_frame_animation_finishes_once() {
+        let (mut world, mut schedule) = setup();
+        let entity = world
+            .spawn((
+                AnimatedSprite {
+                    frames: vec![3],
+                    fps: 10.0,
+                    looping: false,
+                },
+                AnimatedSpriteState::default(),
+                TextureAtlasSprite::new(0),
+            ))
+            .id();
+
+        run(&mut world, &mut schedule, Duration::ZERO);
+        assert_eq!(world.get::<TextureAtlasSprite>(entity).unwrap().index, 3);
+        let events = world.resource::<Events<AnimationFinished>>();
+        assert_eq!(
+            events
+                .iter_current_update_events()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![AnimationFinished { entity }]
+        );
+
+        world.resource_mut::<Events<AnimationFinished>>().update();
+        run(&mut world, &mut schedule, Duration::from_millis(500));
+        let events = world.resource::<Events<AnimationFinished>>();
+        assert_eq!(events.iter_current_update_events().count(), 0);
+    }
+}
@@ -0,0 +1,384 @@
+use std::{
+    fmt,
+    ops::Range,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use bevy_asset::{AssetLoader, AssetPath, Handle, LoadContext, LoadedAsset};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{TypePath, TypeUuid};
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer,
+};
+use thiserror::Error;
+
+use crate::TextureAtlas;
+
+/// The label of the [`TextureAtlas`] loaded by the [`AsepriteSheetLoader`].
+pub const ASEPRITE_LAYOUT_LABEL: &str = "layout";
+/// The label of the [`SpriteSheetAnimations`] loaded by the [`AsepriteSheetLoader`].
+pub const ASEPRITE_ANIMATIONS_LABEL: &str = "animations";
+
+/// Loads the JSON data exported by [Aseprite](https://www.aseprite.org) for a sprite sheet.
+///
+/// Both the "Hash" and "Array" frame layouts are supported. The sheet is loaded as two labeled
+/// assets: its [`TextureAtlas`] as `sheet.aseprite.json#layout`, whose texture is the image named
+/// by the sheet relative to the JSON file, and its tags as [`SpriteSheetAnimations`] in
+/// `sheet.aseprite.json#animations`.
+///
+/// The loader is registered for the `aseprite.json` extension, so the sheets must be exported or
+/// renamed to, for example, `sheet.aseprite.json`. This leaves other `.json` files to the
+/// loaders of other plugins.
+#[derive(Default)]
+pub struct AsepriteSheetLoader;
+
+impl AssetLoader for AsepriteSheetLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+        Box::pin(async move {
+            let path = load_context.path();
+            let sheet = AsepriteSheet::from_slice(bytes, path)?;
+            let animations = sheet.animations(path)?;
+
+            let image_path = AssetPath::new(
+                path.parent()
+                    .unwrap_or(Path::new(""))
+                    .join(&sheet.meta.image),
+                None,
+            );
+            let texture_atlas = TextureAtlas {
+                textures: sheet
+                    .frames
+                    .0
+                    .iter()
+                    .map(|frame| frame.frame.rect())
+                    .collect(),
+                ..TextureAtlas::new_empty(
+                    load_context.get_handle(image_path.clone()),
+                    Vec2::new(sheet.meta.size.w, sheet.meta.size.h),
+                )
+            };
+            let layout = load_context.set_labeled_asset(
+                ASEPRITE_LAYOUT_LABEL,
+                LoadedAsset::new(texture_atlas).with_dependency(image_path),
+            );
+            load_context.set_labeled_asset(
+                ASEPRITE_ANIMATIONS_LABEL,
+                LoadedAsset::new(SpriteSheetAnimations { layout, animations }),
+            );
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["aseprite.json"]
+    }
+}
+
+/// An error that occurs when loading an Aseprite sheet.
+#[derive(Error, Debug)]
+pub enum AsepriteSheetError {
+    #[error("invalid Aseprite sheet {path:?}: {source}")]
+    InvalidJson {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+    #[error(
+        "the tag {tag:?} of the Aseprite sheet {path:?} uses frames {from} to {to}, \
+        but the sheet only has {frames} frames"
+    )]
+    TagOutOfBounds {
+        path: PathBuf,
+        tag: String,
+        from: usize,
+        to: usize,
+        frames: usize,
+    },
+}
+
+/// The tagged animations of a sprite sheet, see [`AsepriteSheetLoader`].
+#[derive(Debug, Clone, TypeUuid, TypePath)]
+#[uuid = "a1c3b6b6-5e7c-4c1e-9a51-3a5c0f3e2d87"]
+pub struct SpriteSheetAnimations {
+    /// The layout of the sheet, whose indices the animations refer to
+    pub layout: Handle<TextureAtlas>,
+    /// The animations, by tag name
+    pub animations: HashMap<String, SpriteSheetAnimation>,
+}
+
+impl SpriteSheetAnimations {
+    /// Returns the animation with the tag `name`.
+    pub fn get(&self, name: &str) -> Option<&SpriteSheetAnimation> {
+        self.animations.get(name)
+    }
+}
+
+/// A tagged range of frames of a sprite sheet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpriteSheetAnimation {
+    /// The indices in the [`TextureAtlas`] of the frames of the animation
+    pub frames: Range<usize>,
+    /// How long each frame is shown, in the same order as [`SpriteSheetAnimation::frames`]
+    pub durations: Vec<Duration>,
+    /// The order the frames are played in
+    pub direction: AnimationDirection,
+}
+
+impl SpriteSheetAnimation {
+    /// Returns the indices of the frames in the order they are played, for one cycle.
+    ///
+    /// A ping-pong cycle goes one way then back, without repeating the first and last frames.
+    pub fn frame_indices(&self) -> Vec<usize> {
+        match self.direction {
+            AnimationDirection::Forward => self.frames.clone().collect(),
+            AnimationDirection::Reverse => self.frames.clone().rev().collect(),
+            AnimationDirection::PingPong => {
+                let inner = self.frames.start + 1..self.frames.end.saturating_sub(1);
+                self.frames.clone().chain(inner.rev()).collect()
+            }
+            AnimationDirection::PingPongReverse => {
+                let inner = self.frames.start + 1..self.frames.end.saturating_sub(1);
+                self.frames.clone().rev().chain(inner).collect()
+            }
+        }
+    }
+}
+
+/// The order the frames of a [`SpriteSheetAnimation`] are played in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnimationDirection {
+    /// From the first frame to the last
+    #[default]
+    Forward,
+    /// From the last frame to the first
+    Reverse,
+    /// Forward, then in reverse
+    PingPong,
+    /// In reverse, then forward
+    #[serde(rename = "pingpong_reverse")]
+    PingPongReverse,
+}
+
+#[derive(Deserialize)]
+struct AsepriteSheet {
+    frames: AsepriteFrames,
+    meta: AsepriteMeta,
+}
+
+impl AsepriteSheet {
+    fn from_slice(bytes: &[u8], path: &Path) -> Result<Self, AsepriteSheetError> {
+        serde_json::from_slice(bytes).map_err(|source| AsepriteSheetError::InvalidJson {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    fn animations(
+        &self,
+        path: &Path,
+    ) -> Result<HashMap<String, SpriteSheetAnimation>, AsepriteSheetError> {
+        let frames = &self.frames.0;
+        self.meta
+            .frame_tags
+            .iter()
+            .map(|tag| {
+                if tag.from > tag.to || tag.to >= frames.len() {
+                    return Err(AsepriteSheetError::TagOutOfBounds {
+                        path: path.to_path_buf(),
+                        tag: tag.name.clone(),
+                        from: tag.from,
+                        to: tag.to,
+                        frames: frames.len(),
+                    });
+                }
+                let range = tag.from..tag.to + 1;
+                let animation = SpriteSheetAnimation {
+                    durations: frames[range.clone()]
+                        .iter()
+                        .map(|frame| Duration::from_millis(frame.duration))
+                        .collect(),
+                    frames: range,
+                    direction: tag.direction,
+                };
+                Ok((tag.name.clone(), animation))
+            })
+            .collect()
+    }
+}
+
+/// The frames of a sheet, in the order they were exported.
+///
+/// The "Hash" layout stores them in an object keyed by file name, which is read in order
+/// instead of going through a map.
+struct AsepriteFrames(Vec<AsepriteFrame>);
+
+impl<'de> Deserialize<'de> for AsepriteFrames {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FramesVisitor;
+
+        impl<'de> Visitor<'de> for FramesVisitor {
+            type Value = AsepriteFrames;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an array or an object of frames")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(frame) = seq.next_element()? {
+                    frames.push(frame);
+                }
+                Ok(AsepriteFrames(frames))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut frames = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some((_, frame)) = map.next_entry::<String, _>()? {
+                    frames.push(frame);
+                }
+                Ok(AsepriteFrames(frames))
+            }
+        }
+
+        deserializer.deserialize_any(FramesVisitor)
+    }
+}
+
+#[derive(Deserialize)]
+struct AsepriteFrame {
+    frame: AsepriteRect,
+    /// In milliseconds
+    duration: u64,
+}
+
+#[derive(Deserialize)]
+struct AsepriteRect {
+    x: f32,
+    y: f32,
+    w: f32,
+    h: f32,
+}
+
+impl AsepriteRect {
+    fn rect(&self) -> Rect {
+        Rect::new(self.x, self.y, self.x + self.w, self.y + self.h)
+    }
+}
+
+#[derive(Deserialize)]
+struct AsepriteSize {
+    w: f32,
+    h: f32,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AsepriteMeta {
+    image: String,
+    size: AsepriteSize,
+    #[serde(default)]
+    frame_tags: Vec<AsepriteTag>,
+}
+
+#[derive(Deserialize)]
+struct AsepriteTag {
+    name: String,
+    from: usize,
+    to: usize,
+    #[serde(default)]
+    direction: AnimationDirection,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const META: &str = r#""meta": {
+        "image": "sheet.png",
+        "size": { "w": 32, "h": 16 },
+        "frameTags": [
+            { "name": "walk", "from": 1, "to": 2, "direction": "pingpong" }
+        ]
+    }"#;
+
+    #[test]
+    fn parse_hash_and_array_sheets() {
+        let hash = format!(
+            r#"{{
+                "frames": {{
+                    "sheet 10.aseprite": {{ "frame": {{ "x": 16, "y": 0, "w": 8, "h": 16 }}, "duration": 50 }},
+                    "sheet 2.aseprite": {{ "frame": {{ "x": 0, "y": 0, "w": 16, "h": 16 }}, "duration": 100 }},
+                    "sheet 3.aseprite": {{ "frame": {{ "x": 24, "y": 0, "w": 8, "h": 16 }}, "duration": 200 }}
+                }},
+                {META}
+            }}"#
+        );
+        let array = format!(
+            r#"{{
+                "frames": [
+                    {{ "filename": "a", "frame": {{ "x": 16, "y": 0, "w": 8, "h": 16 }}, "duration": 50 }},
+                    {{ "filename": "b", "frame": {{ "x": 0, "y": 0, "w": 16, "h": 16 }}, "duration": 100 }},
+                    {{ "filename": "c", "frame": {{ "x": 24, "y": 0, "w": 8, "h": 16 }}, "duration": 200 }}
+                ],
+                {META}
+            }}"#
+        );
+
+        for json in [hash, array] {
+            let path = Path::new("sheet.aseprite.json");
+            let sheet = AsepriteSheet::from_slice(json.as_bytes(), path).unwrap();
+            let rects: Vec<Rect> = sheet.frames.0.iter().map(|f| f.frame.rect()).collect();
+            assert_eq!(rects[0], Rect::new(16.0, 0.0, 24.0, 16.0));
+            assert_eq!(rects[1], Rect::new(0.0, 0.0, 16.0, 16.0));
+            assert_eq!(sheet.meta.image, "sheet.png");
+
+            let animations = sheet.animations(path).unwrap();
+            let walk = &animations["walk"];
+            assert_eq!(walk.frames, 1..3);
+            assert_eq!(
+                walk.durations,
+                vec![Duration::from_millis(100), Duration::from_millis(200)]
+            );
+            assert_eq!(walk.direction, AnimationDirection::PingPong);
+        }
+    }
+
+    #[test]
+    fn invalid_sheet_errors_name_the_path() {
+        let path = Path::new("sprites/sheet.aseprite.json");
+        let error = AsepriteSheet::from_slice(b"{ \"frames\": ", path)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("sprites/sheet.aseprite.json"));
+
+        let json = format!(r#"{{ "frames": [], {META} }}"#);
+        let sheet = AsepriteSheet::from_slice(json.as_bytes(), path).unwrap();
+        assert!(matches!(
+            sheet.animations(path),
+            Err(AsepriteSheetError::TagOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn frame_indices_follow_direction() {
+        let mut animation = SpriteSheetAnimation {
+            frames: 2..6,
+            durations: Vec::new(),
+            direction: AnimationDirection::Forward,
+        };
+        assert_eq!(animation.frame_indices(), vec![2, 3, 4, 5]);
+        animation.direction = AnimationDirection::Reverse;
+        assert_eq!(animation.frame_indices(), vec![5, 4, 3, 2]);
+        animation.direction = AnimationDirection::PingPong;
+        assert_eq!(animation.frame_indices(), vec![2, 3, 4, 5, 4, 3]);
+        animation.direction = AnimationDirection::PingPongReverse;
+        assert_eq!(animation.frame_indices(), vec![5, 4, 3, 2, 3, 4]);
+    }
+}
@@ -50,3 +50,103 @@ pub struct SpriteSheetBundle {
     /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
     pub computed_visibility: ComputedVisibility,
 }
+
+impl SpriteSheetBundle {
+    /// Returns a bundle for every `(index, transform)` pair, drawing the sprite at `index` in
+    /// `texture_atlas`, to be spawned with [`Commands::spawn_batch`] or [`World::spawn_batch`].
+    ///
+    /// Every bundle holds a weak copy of `texture_atlas`: cloning and dropping strong handles
+    /// sends a message to the asset server each time, which adds up for large tile maps. The atlas
+    /// is only kept loaded by the strong handles held elsewhere, so keep a strong handle to it
+    /// alive for as long as the sprites are drawn, for example in a resource.
+    ///
+    /// The returned iterator owns its data, so it can be passed to [`Commands::spawn_batch`]:
+    ///
+    /// ```
+    /// # use bevy_asset::Handle;
+    /// # use bevy_ecs::system::Commands;
+    /// # use bevy_sprite::{SpriteSheetBundle, TextureAtlas};
+    /// # use bevy_transform::components::Transform;
+    /// fn spawn_tiles(mut commands: Commands, texture_atlas: Handle<TextureAtlas>) {
+    ///     let tiles = (0..100).map(|i| (i % 4, Transform::from_xyz(i as f32 * 16., 0., 0.)));
+    ///     commands.spawn_batch(SpriteSheetBundle::batch(texture_atlas, tiles));
+    /// }
+    /// ```
+    ///
+    /// [`Commands::spawn_batch`]: bevy_ecs::system::Commands::spawn_batch
+    /// [`World::spawn_batch`]: bevy_ecs::world::World::spawn_batch
+    pub fn batch<I>(
+        texture_atlas: Handle<TextureAtlas>,
+        sprites: I,
+    ) -> impl Iterator<Item = SpriteSheetBundle> + Send + Sync + 'static
+    where
+        I: IntoIterator<Item = (usize, Transform)>,
+        I::IntoIter: Send + Sync + 'static,
+    {
+        sprites
+            .into_iter()
+            .map(move |(index, transform)| SpriteSheetBundle {
+                sprite: TextureAtlasSprite::new(index),
+                texture_atlas: texture_atlas.clone_weak(),
+                transform,
+                ..Default::default()
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::HandleId;
+    use bevy_ecs::{
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+    use bevy_math::Vec3;
+
+    use super::*;
+
+    #[test]
+    fn batch_spawns_weak_handles() {
+        let texture_atlas = Handle::<TextureAtlas>::weak(HandleId::random::<TextureAtlas>());
+        let mut world = World::new();
+        let sprites = (0..4).map(|i| (i, Transform::from_xyz(i as f32, 0.0, 0.0)));
+        let entities: Vec<_> = world
+            .spawn_batch(SpriteSheetBundle::batch(
+                texture_atlas.clone_weak(),
+                sprites,
+            ))
+            .collect();
+
+        assert_eq!(entities.len(), 4);
+        for (i, entity) in entities.into_iter().enumerate() {
+            let entity = world.entity(entity);
+            assert_eq!(entity.get::<TextureAtlasSprite>().unwrap().index, i);
+            assert_eq!(
+                entity.get::<Transform>().unwrap().translation,
+                Vec3::new(i as f32, 0.0, 0.0)
+            );
+            let handle = entity.get::<Handle<TextureAtlas>>().unwrap();
+            assert!(handle.is_weak());
+            assert_eq!(handle.id(), texture_atlas.id());
+        }
+    }
+
+    #[test]
+    fn batch_spawns_with_commands() {
+        let texture_atlas = Handle::<TextureAtlas>::weak(HandleId::random::<TextureAtlas>());
+        let mut world = World::new();
+        let mut queue = CommandQueue::default();
+        let sprites = (0..3).map(|i| (i, Transform::default()));
+        Commands::new(&mut queue, &world)
+            .spawn_batch(SpriteSheetBundle::batch(texture_atlas.clone(), sprites));
+        queue.apply(&mut world);
+
+        let mut indices: Vec<_> = world
+            .query::<&TextureAtlasSprite>()
+            .iter(&world)
+            .map(|sprite| sprite.index)
+            .collect();
+        indices.sort_unstable();
+        assert_eq!(indices, [0, 1, 2]);
+    }
+}
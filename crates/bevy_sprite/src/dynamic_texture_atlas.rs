@@ -0,0 +1,495 @@
+use crate::{
+    DynamicTextureAtlasBuilder, DynamicTextureAtlasBuilderError, TextureAtlas, TextureAtlasSprite,
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{AssetServer, Assets, Handle, LoadState};
+use bevy_ecs::{
+    prelude::{Event, EventReader, EventWriter, Resource, World},
+    schedule::IntoSystemConfigs,
+    system::{Deferred, Query, Res, ResMut, SystemBuffer, SystemMeta, SystemParam},
+};
+use bevy_math::Vec2;
+use bevy_render::{
+    render_resource::{Extent3d, TextureDimension, TextureFormat},
+    renderer::RenderDevice,
+    texture::{Image, ImageMemoryTag, TextureFormatPixelInfo},
+};
+use bevy_utils::HashMap;
+use std::cmp::Reverse;
+
+/// Adds a [`DynamicTextureAtlas`] resource, which packs the images queued with [`AtlasCommands`]
+/// into a single [`TextureAtlas`] as they are loaded, like the icons of a UI.
+///
+/// The index of each packed image in the atlas is sent in a [`DynamicTextureAtlasEvent`]. For more
+/// control, a [`DynamicTextureAtlasBuilder`] can still be used directly.
+///
+/// # Panics
+///
+/// Panics if the size is larger than the maximum texture dimension of the [`RenderDevice`].
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicTextureAtlasPlugin {
+    /// The size of the atlas texture in pixels
+    pub size: Vec2,
+    /// The gap between the images in the atlas in pixels
+    pub padding: i32,
+    /// The format of the atlas texture, images in other formats are converted to it
+    pub format: TextureFormat,
+}
+
+impl Default for DynamicTextureAtlasPlugin {
+    fn default() -> Self {
+        Self {
+            size: Vec2::splat(1024.),
+            padding: 1,
+            format: TextureFormat::Rgba8UnormSrgb,
+        }
+    }
+}
+
+impl Plugin for DynamicTextureAtlasPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<DynamicTextureAtlasEvent>().add_systems(
+            PostUpdate,
+            (
+                pack_dynamic_texture_atlas,
+                remap_dynamic_texture_atlas_sprites,
+            )
+                .chain(),
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        // `Assets<Image>` and `Assets<TextureAtlas>` are added by other plugins, and so is the
+        // `RenderDevice` limiting the size of the atlas
+        let max_size = app
+            .world
+            .get_resource::<RenderDevice>()
+            .map_or(u32::MAX, |render_device| {
+                render_device.limits().max_texture_dimension_2d
+            });
+        let builder = DynamicTextureAtlasBuilder::try_new(self.size, self.padding, max_size)
+            .unwrap_or_else(|err| panic!("invalid DynamicTextureAtlasPlugin size: {err}"));
+        let atlas = DynamicTextureAtlas::new(&mut app.world, builder, self.size, self.format);
+        app.insert_resource(atlas);
+    }
+}
+
+/// A [`TextureAtlas`] packed on the fly with the images queued with [`AtlasCommands`], added by
+/// the [`DynamicTextureAtlasPlugin`].
+#[derive(Resource)]
+pub struct DynamicTextureAtlas {
+    builder: DynamicTextureAtlasBuilder,
+    texture_atlas: Handle<TextureAtlas>,
+    format: TextureFormat,
+    /// The images waiting to be loaded before they are packed
+    queued: Vec<Handle<Image>>,
+    indices: HashMap<Handle<Image>, usize>,
+    repack: bool,
+}
+
+impl DynamicTextureAtlas {
+    fn new(
+        world: &mut World,
+        builder: DynamicTextureAtlasBuilder,
+        size: Vec2,
+        format: TextureFormat,
+    ) -> Self {
+        let mut atlas_image = Image::new_fill(
+            Extent3d {
+                width: size.x as u32,
+                height: size.y as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &vec![0; format.pixel_size()],
+            format,
+        );
+        atlas_image.memory_tag = ImageMemoryTag::SpriteAtlas;
+        let atlas_texture = world.resource_mut::<Assets<Image>>().add(atlas_image);
+        let texture_atlas = world
+            .resource_mut::<Assets<TextureAtlas>>()
+            .add(TextureAtlas::new_empty(atlas_texture, size));
+        Self {
+            builder,
+            texture_atlas,
+            format,
+            queued: Vec::new(),
+            indices: HashMap::default(),
+            repack: false,
+        }
+    }
+
+    /// The handle of the packed [`TextureAtlas`]
+    pub fn texture_atlas(&self) -> &Handle<TextureAtlas> {
+        &self.texture_atlas
+    }
+
+    /// The index of `image` in the atlas, if it was packed
+    pub fn index(&self, image: &Handle<Image>) -> Option<usize> {
+        self.indices.get(image).copied()
+    }
+
+    /// Queues `image` to be packed once it is loaded, see [`AtlasCommands::pack`].
+    pub fn queue(&mut self, image: Handle<Image>) {
+        self.queued.push(image);
+    }
+
+    /// Repacks the atlas before the queued images are packed, see [`AtlasCommands::repack`].
+    pub fn repack(&mut self) {
+        self.repack = true;
+    }
+
+    /// Packs the images of the atlas again from scratch, largest first, and returns how their
+    /// indices changed.
+    ///
+    /// Images that were unloaded since they were packed are left out, freeing their space. The
+    /// [`user_data`](TextureAtlas::user_data) of the images packed again follows them to their
+    /// new index, and so does when they were last used, for eviction.
+    fn repack_images(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        events: &mut EventWriter<DynamicTextureAtlasEvent>,
+    ) -> Vec<(usize, usize)> {
+        let mut packed: Vec<_> = self
+            .indices
+            .drain()
+            .filter_map(|(image, index)| {
+                let size = textures.get(&image)?.texture_descriptor.size;
+                Some((image, index, self.builder.last_used(index), size))
+            })
+            .collect();
+        packed
+            .sort_by_key(|(_, index, _, size)| (Reverse(size.height), Reverse(size.width), *index));
+
+        // The builder keeps its configuration, only its textures are cleared
+        self.builder.clear();
+        texture_atlas.textures.clear();
+        let user_data = std::mem::take(&mut texture_atlas.user_data);
+        if let Some(atlas_texture) = textures.get_mut(&texture_atlas.texture) {
+            atlas_texture.data.fill(0);
+        }
+
+        let mut remap = Vec::with_capacity(packed.len());
+        for (image, old_index, last_used, _) in packed {
+            match pack_image(
+                &mut self.builder,
+                self.format,
+                texture_atlas,
+                textures,
+                &image,
+            ) {
+                Ok(new_index) => {
+                    if let Some(last_used) = last_used {
+                        self.builder.set_last_used(new_index, last_used);
+                    }
+                    let data = user_data.get(old_index).copied().flatten();
+                    texture_atlas.set_data(new_index, data);
+                    self.indices.insert(image, new_index);
+                    remap.push((old_index, new_index));
+                }
+                Err(error) => events.send(DynamicTextureAtlasEvent::Failed { image, error }),
+            }
+        }
+        remap.sort_unstable();
+        remap
+    }
+}
+
+/// The result of packing an image queued with [`AtlasCommands`] into the [`DynamicTextureAtlas`]
+#[derive(Event, Debug)]
+pub enum DynamicTextureAtlasEvent {
+    /// The image was packed at `index` in the atlas, or already was
+    Packed { image: Handle<Image>, index: usize },
+    /// The image couldn't be packed, it isn't queued anymore
+    Failed {
+        image: Handle<Image>,
+        error: DynamicTextureAtlasBuilderError,
+    },
+    /// The atlas was repacked, and the images packed at the first index of each pair are now at
+    /// the second one, sorted by the old index.
+    ///
+    /// Indices missing from `remap` belonged to images that were unloaded or that couldn't be
+    /// packed again. The [`TextureAtlasSprite`]s of the atlas are updated by
+    /// [`remap_dynamic_texture_atlas_sprites`], other components holding indices into the atlas
+    /// must be updated when this is received.
+    Repacked { remap: Vec<(usize, usize)> },
+}
+
+/// A [`SystemParam`] queueing images to be packed into the [`DynamicTextureAtlas`].
+///
+/// The images are queued when the system's commands are applied, so systems using this don't
+/// conflict with each other or with the atlas.
+#[derive(SystemParam)]
+pub struct AtlasCommands<'s> {
+    queue: Deferred<'s, AtlasQueue>,
+}
+
+impl AtlasCommands<'_> {
+    /// Queues `image` to be packed into the atlas once it is loaded.
+    ///
+    /// A [`DynamicTextureAtlasEvent`] is sent with its index once it is packed, or immediately if
+    /// it already is.
+    pub fn pack(&mut self, image: Handle<Image>) {
+        self.queue.images.push(image);
+    }
+
+    /// Packs all the images of the atlas again, largest first, to reclaim the space of the
+    /// images that were unloaded and of the gaps left by the packing order.
+    ///
+    /// The indices of the images change, they are sent in a
+    /// [`DynamicTextureAtlasEvent::Repacked`].
+    pub fn repack(&mut self) {
+        self.queue.repack = true;
+    }
+}
+
+#[derive(Default)]
+struct AtlasQueue {
+    images: Vec<Handle<Image>>,
+    repack: bool,
+}
+
+impl SystemBuffer for AtlasQueue {
+    fn apply(&mut self, _system_meta: &SystemMeta, world: &mut World) {
+        if self.images.is_empty() && !self.repack {
+            return;
+        }
+        let mut atlas = world.resource_mut::<DynamicTextureAtlas>();
+        atlas.queued.append(&mut self.images);
+        atlas.repack |= std::mem::take(&mut self.repack);
+    }
+}
+
+/// Repacks the [`DynamicTextureAtlas`] if requested, then packs the queued images that are
+/// loaded into it, and sends a [`DynamicTextureAtlasEvent`] for each of them.
+///
+/// Queued images that fail to load are dropped with a [`DynamicTextureAtlasEvent::Failed`].
+pub fn pack_dynamic_texture_atlas(
+    mut atlas: ResMut<DynamicTextureAtlas>,
+    mut textures: ResMut<Assets<Image>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    mut events: EventWriter<DynamicTextureAtlasEvent>,
+) {
+    if atlas.queued.is_empty() && !atlas.repack {
+        return;
+    }
+    let atlas = &mut *atlas;
+    let Some(texture_atlas) = texture_atlases.get_mut(&atlas.texture_atlas) else {
+        return;
+    };
+
+    if std::mem::take(&mut atlas.repack) {
+        if texture_atlas.is_frozen() {
+            bevy_log::warn!("The dynamic texture atlas is frozen, it can't be repacked");
+        } else {
+            let remap = atlas.repack_images(texture_atlas, &mut textures, &mut events);
+            events.send(DynamicTextureAtlasEvent::Repacked { remap });
+        }
+    }
+
+    atlas.queued.retain(|image| {
+        if let Some(&index) = atlas.indices.get(image) {
+            events.send(DynamicTextureAtlasEvent::Packed {
+                image: image.clone_weak(),
+                index,
+            });
+            return false;
+        }
+        if textures.get(image).is_none() {
+            if asset_server.get_load_state(image) == LoadState::Failed {
+                events.send(DynamicTextureAtlasEvent::Failed {
+                    image: image.clone_weak(),
+                    error: DynamicTextureAtlasBuilderError::MissingTexture(image.id()),
+                });
+                return false;
+            }
+            // Not loaded yet
+            return true;
+        }
+
+        match pack_image(
+            &mut atlas.builder,
+            atlas.format,
+            texture_atlas,
+            &mut textures,
+            image,
+        ) {
+            Ok(index) => {
+                atlas.indices.insert(image.clone_weak(), index);
+                events.send(DynamicTextureAtlasEvent::Packed {
+                    image: image.clone_weak(),
+                    index,
+                });
+            }
+            Err(error) => events.send(DynamicTextureAtlasEvent::Failed {
+                image: image.clone_weak(),
+                error,
+            }),
+        }
+        false
+    });
+}
+
+/// Updates the indices of the [`TextureAtlasSprite`]s of the [`DynamicTextureAtlas`] when it is
+/// repacked.
+pub fn remap_dynamic_texture_atlas_sprites(
+    atlas: Res<DynamicTextureAtlas>,
+    mut events: EventReader<DynamicTextureAtlasEvent>,
+    mut sprites: Query<(&Handle<TextureAtlas>, &mut TextureAtlasSprite)>,
+) {
+    for event in events.iter() {
+        let DynamicTextureAtlasEvent::Repacked { remap } = event else {
+            continue;
+        };
+        for (texture_atlas, mut sprite) in &mut sprites {
+            if *texture_atlas != atlas.texture_atlas {
+                continue;
+            }
+            if let Ok(position) = remap.binary_search_by_key(&sprite.index, |(old, _)| *old) {
+                sprite.index = remap[position].1;
+            }
+        }
+    }
+}
+
+/// Packs the loaded `image` with `builder`, converting it to `format` if needed.
+fn pack_image(
+    builder: &mut DynamicTextureAtlasBuilder,
+    format: TextureFormat,
+    texture_atlas: &mut TextureAtlas,
+    textures: &mut Assets<Image>,
+    image: &Handle<Image>,
+) -> Result<usize, DynamicTextureAtlasBuilderError> {
+    let texture = textures
+        .get(image)
+        .ok_or(DynamicTextureAtlasBuilderError::MissingTexture(image.id()))?;
+    if texture.texture_descriptor.format == format {
+        return builder.add_texture_by_handle(texture_atlas, textures, image);
+    }
+    match texture
+        .to_format(format)
+        .or_else(|| texture.convert(format))
+    {
+        Some(texture) => builder.try_add_texture(texture_atlas, textures, &texture),
+        None => Err(DynamicTextureAtlasBuilderError::UnsupportedFormat(
+            texture.texture_descriptor.format,
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_ecs::event::Events;
+
+    fn image(size: u32) -> Image {
+        Image::new_fill(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255; 4],
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default())
+            .add_plugin(DynamicTextureAtlasPlugin {
+                size: Vec2::splat(16.),
+                padding: 0,
+                ..Default::default()
+            })
+            .add_asset::<Image>()
+            .add_asset::<TextureAtlas>();
+        app.finish();
+        app
+    }
+
+    #[test]
+    fn repack_remaps_sprites() {
+        let mut app = app();
+
+        let mut images = app.world.resource_mut::<Assets<Image>>();
+        let small = images.add(image(2));
+        let unused = images.add(image(4)).clone_weak();
+        let large = images.add(image(8));
+        let mut atlas = app.world.resource_mut::<DynamicTextureAtlas>();
+        for image in [&small, &unused, &large] {
+            atlas.queue(image.clone_weak());
+        }
+        let texture_atlas = atlas.texture_atlas().clone();
+        app.update();
+        let index = |app: &App, image| app.world.resource::<DynamicTextureAtlas>().index(image);
+        assert_eq!(index(&app, &large), Some(2));
+
+        let sprite = app
+            .world
+            .spawn((texture_atlas.clone(), TextureAtlasSprite::new(2)))
+            .id();
+        app.world.resource_mut::<Assets<Image>>().remove(&unused);
+        app.world.resource_mut::<DynamicTextureAtlas>().repack();
+        app.update();
+
+        // The largest image is packed first, the unloaded one is left out
+        let events = app.world.resource::<Events<DynamicTextureAtlasEvent>>();
+        let remap = events
+            .iter_current_update_events()
+            .find_map(|event| match event {
+                DynamicTextureAtlasEvent::Repacked { remap } => Some(remap.clone()),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(remap, [(0, 1), (2, 0)]);
+        assert_eq!(index(&app, &large), Some(0));
+        assert_eq!(index(&app, &small), Some(1));
+        assert_eq!(index(&app, &unused), None);
+        assert_eq!(
+            app.world.get::<TextureAtlasSprite>(sprite).unwrap().index,
+            0
+        );
+        let texture_atlases = app.world.resource::<Assets<TextureAtlas>>();
+        assert_eq!(texture_atlases.get(&texture_atlas).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn repack_keeps_user_data() {
+        let mut app = app();
+        let mut images = app.world.resource_mut::<Assets<Image>>();
+        let handles = [2, 4, 8].map(|size| images.add(image(size)));
+        let mut atlas = app.world.resource_mut::<DynamicTextureAtlas>();
+        for image in &handles {
+            atlas.queue(image.clone_weak());
+        }
+        let texture_atlas = atlas.texture_atlas().clone();
+        app.update();
+
+        let indices = |app: &App| {
+            let atlas = app.world.resource::<DynamicTextureAtlas>();
+            handles.clone().map(|image| atlas.index(&image).unwrap())
+        };
+        let old_indices = indices(&app);
+        let mut texture_atlases = app.world.resource_mut::<Assets<TextureAtlas>>();
+        let atlas = texture_atlases.get_mut(&texture_atlas).unwrap();
+        for (payload, index) in old_indices.into_iter().enumerate() {
+            atlas.set_data(index, Some(payload as u64));
+        }
+        app.world.resource_mut::<DynamicTextureAtlas>().repack();
+        app.update();
+
+        // The images moved, largest first, and their payloads with them
+        let new_indices = indices(&app);
+        assert_eq!(new_indices, [2, 1, 0]);
+        let texture_atlases = app.world.resource::<Assets<TextureAtlas>>();
+        let atlas = texture_atlases.get(&texture_atlas).unwrap();
+        for (payload, index) in new_indices.into_iter().enumerate() {
+            assert_eq!(atlas.data(index), Some(payload as u64));
+        }
+    }
+}
@@ -1,8 +1,73 @@
 use crate::TextureAtlas;
-use bevy_asset::Assets;
-use bevy_math::{IVec2, Rect, Vec2};
-use bevy_render::texture::{Image, TextureFormatPixelInfo};
-use guillotiere::{size2, Allocation, AtlasAllocator};
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_math::{URect, UVec2, Vec2};
+use bevy_render::{
+    render_resource::{Extent3d, TextureFormat},
+    texture::{Image, TextureFormatPixelInfo},
+};
+use bevy_utils::HashMap;
+use fixedbitset::FixedBitSet;
+use guillotiere::{point2, size2, AllocId, Allocation, AtlasAllocator};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DynamicTextureAtlasBuilderError {
+    /// The atlas has no free space large enough for the texture.
+    #[error("could not allocate space for the texture in the atlas")]
+    NotEnoughSpace,
+    /// A texture placed with [`DynamicTextureAtlasBuilder::place_at`] would overlap a texture
+    /// already in the atlas.
+    #[error("the texture would overlap a texture already in the atlas")]
+    Occupied,
+    /// A texture placed with [`DynamicTextureAtlasBuilder::place_at`] would reach past the edge
+    /// of the atlas.
+    #[error("texture placed at {min} with size {size} would reach past the edge of the atlas")]
+    PlacementOutOfBounds { min: UVec2, size: UVec2 },
+    /// The [`TextureAtlas`] is [frozen](TextureAtlas::is_frozen).
+    #[error("the texture atlas is frozen, textures can't be added to it")]
+    Frozen,
+    /// A row of texture data isn't as long as a row of the texture in the atlas format, like a
+    /// row returned by the conversion of [`DynamicTextureAtlasBuilder::add_texture_with`].
+    #[error("converted texture row is {actual} bytes, expected {expected} bytes")]
+    InvalidRowLength { expected: usize, actual: usize },
+    /// The texture would be written past the end of the atlas texture data.
+    #[error("texture row would end at byte {end}, past the end of the {len} byte atlas")]
+    AtlasOutOfBounds { end: usize, len: usize },
+    /// The texture would be written past the right edge of the atlas texture.
+    #[error("texture rectangle would end at x = {max_x}, past the {width} pixel wide atlas")]
+    RectOutOfBounds { max_x: usize, width: usize },
+    /// The texture data is shorter than its size, when the builder is
+    /// [strict](DynamicTextureAtlasBuilder::strict).
+    #[error("texture row would end at byte {end}, past the end of the {len} byte texture")]
+    TextureOutOfBounds { end: usize, len: usize },
+    /// The allocator returned a rectangle with a negative coordinate.
+    #[error("texture allocation {coordinate} = {value} is not a valid texture coordinate")]
+    InvalidAllocation {
+        /// The coordinate of the allocated rectangle, like `min.x`
+        coordinate: &'static str,
+        value: i32,
+    },
+    /// The size of the texture with the padding doesn't fit in the allocator coordinates.
+    #[error("texture size {width}x{height} with {padding} pixels of padding overflows")]
+    SizeOverflow {
+        width: u32,
+        height: u32,
+        padding: i32,
+    },
+    /// The texture to add is not loaded, or failed to load.
+    #[error("the texture {0:?} is not loaded")]
+    MissingTexture(HandleId),
+    /// The texture format can't be converted to the format of the atlas.
+    #[error("the texture format {0:?} can't be converted to the format of the atlas")]
+    UnsupportedFormat(TextureFormat),
+    /// The atlas would be larger than its maximum size, see
+    /// [`DynamicTextureAtlasBuilder::try_new`].
+    #[error("atlas size {width}x{height} exceeds the maximum texture dimension of {max} pixels")]
+    ExceedsMaxSize { width: u32, height: u32, max: u32 },
+    /// [`DynamicTextureAtlasBuilder::grow`] was given a size smaller than the atlas.
+    #[error("atlas of size {size} can't shrink to {new_size}")]
+    CannotShrink { size: UVec2, new_size: UVec2 },
+}
 
 /// Helper utility to update [`TextureAtlas`] on the fly.
 ///
@@ -10,7 +75,48 @@ use guillotiere::{size2, Allocation, AtlasAllocator};
 /// e.g: in a font glyph [`TextureAtlas`], only add the [`Image`] texture for letters to be rendered.
 pub struct DynamicTextureAtlasBuilder {
     atlas_allocator: AtlasAllocator,
+    /// The textures added with [`DynamicTextureAtlasBuilder::place_at`], which the allocator
+    /// doesn't know about
+    placed: Vec<PlacedTexture>,
     padding: i32,
+    /// The largest width and height of the atlas, see [`DynamicTextureAtlasBuilder::try_new`]
+    max_size: u32,
+    strict: bool,
+    /// The pixels of the atlas already written to, when overlaps are checked
+    coverage: Option<FixedBitSet>,
+    /// The textures allocated by the builder, by index in the atlas, which can be evicted
+    allocated: HashMap<usize, AllocatedTexture>,
+    /// The indices of the evicted textures, reused by the next textures added
+    free_indices: Vec<usize>,
+    /// Incremented every time a texture is used, to order the textures by last use
+    use_count: u64,
+}
+
+/// A texture allocated by a [`DynamicTextureAtlasBuilder`].
+#[derive(Debug, Clone, Copy)]
+struct AllocatedTexture {
+    id: AllocId,
+    /// The area of the texture, without the padding
+    rect: URect,
+    last_used: u64,
+}
+
+/// The textures deallocated to make room for a new texture, see
+/// [`DynamicTextureAtlasBuilder::allocate_evicting`].
+struct Eviction {
+    /// The indices of the textures, from the least recently used
+    indices: Vec<usize>,
+    /// The allocator before the textures were deallocated, to undo the eviction
+    atlas_allocator: AtlasAllocator,
+}
+
+/// A texture added with [`DynamicTextureAtlasBuilder::place_at`].
+#[derive(Debug, Clone, Copy)]
+struct PlacedTexture {
+    /// The area of the texture, without the padding
+    rect: URect,
+    /// The area kept free of other textures, with the padding
+    reserved: guillotiere::Rectangle,
 }
 
 impl DynamicTextureAtlasBuilder {
@@ -23,10 +129,67 @@ impl DynamicTextureAtlasBuilder {
     pub fn new(size: Vec2, padding: i32) -> Self {
         Self {
             atlas_allocator: AtlasAllocator::new(to_size2(size)),
+            placed: Vec::new(),
             padding,
+            max_size: u32::MAX,
+            strict: false,
+            coverage: None,
+            allocated: HashMap::default(),
+            free_indices: Vec::new(),
+            use_count: 0,
         }
     }
 
+    /// Create a new [`DynamicTextureAtlasBuilder`], failing if `size` exceeds `max_size` on either
+    /// axis.
+    ///
+    /// Textures larger than the device supports only fail once they are uploaded to the GPU, this
+    /// catches them up front. The limit of the device is
+    /// `render_device.limits().max_texture_dimension_2d`. It is kept by the builder, so
+    /// [`grow`](DynamicTextureAtlasBuilder::grow) fails past it too.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - total size for the atlas
+    /// * `padding` - gap added between textures in the atlas, both in x axis and y axis
+    /// * `max_size` - largest width and height of a texture
+    pub fn try_new(
+        size: Vec2,
+        padding: i32,
+        max_size: u32,
+    ) -> Result<Self, DynamicTextureAtlasBuilderError> {
+        let (width, height) = (size.x as u32, size.y as u32);
+        if width > max_size || height > max_size {
+            return Err(DynamicTextureAtlasBuilderError::ExceedsMaxSize {
+                width,
+                height,
+                max: max_size,
+            });
+        }
+        Ok(Self {
+            max_size,
+            ..Self::new(size, padding)
+        })
+    }
+
+    /// Control whether textures with less data than their size requires are rejected.
+    ///
+    /// By default the missing rows are silently left untouched in the atlas.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Control whether every texture written to the atlas is checked against the pixels already
+    /// written, panicking if it would overwrite another texture.
+    ///
+    /// This catches allocation bugs that would otherwise silently corrupt the atlas, at the cost
+    /// of one bit per atlas pixel. It is meant for debugging and disabled by default.
+    pub fn debug_overlap_check(mut self, enabled: bool) -> Self {
+        self.coverage = enabled.then(FixedBitSet::new);
+        self
+    }
+
     /// Add a new texture to [`TextureAtlas`].
     /// It is user's responsibility to pass in the correct [`TextureAtlas`]
     pub fn add_texture(
@@ -35,52 +198,1204 @@ impl DynamicTextureAtlasBuilder {
         textures: &mut Assets<Image>,
         texture: &Image,
     ) -> Option<usize> {
-        let allocation = self.atlas_allocator.allocate(size2(
-            texture.texture_descriptor.size.width as i32 + self.padding,
-            texture.texture_descriptor.size.height as i32 + self.padding,
-        ));
-        if let Some(allocation) = allocation {
-            let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
-            self.place_texture(atlas_texture, allocation, texture);
-            let mut rect: Rect = to_rect(allocation.rectangle);
-            rect.max -= self.padding as f32;
-            Some(texture_atlas.add_texture(rect))
+        self.try_add_texture(texture_atlas, textures, texture).ok()
+    }
+
+    /// Add a new texture to [`TextureAtlas`], returning why it couldn't be added on failure.
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn try_add_texture(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture: &Image,
+    ) -> Result<usize, DynamicTextureAtlasBuilderError> {
+        self.add_texture_data(
+            texture_atlas,
+            textures,
+            texture.texture_descriptor.size,
+            &texture.data,
+            None,
+        )
+    }
+
+    /// Add a new texture to [`TextureAtlas`], evicting the least recently used textures if there
+    /// isn't enough space left for it, and return its index and the indices of the evicted
+    /// textures.
+    ///
+    /// This bounds the size of caches filled at runtime, like glyph atlases. Textures are ordered
+    /// by when they were added or last passed to [`mark_used`](Self::mark_used), and are only
+    /// evicted if the new texture fits once they are. Textures added with
+    /// [`place_at`](Self::place_at) are never evicted.
+    ///
+    /// The pixels of the evicted textures are cleared, and their indices are reused by the next
+    /// textures added, so anything still referring to them must be updated.
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn try_add_texture_evicting(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture: &Image,
+    ) -> Result<(usize, Vec<usize>), DynamicTextureAtlasBuilderError> {
+        let mut evicted = Vec::new();
+        let index = self.add_texture_data(
+            texture_atlas,
+            textures,
+            texture.texture_descriptor.size,
+            &texture.data,
+            Some(&mut evicted),
+        )?;
+        Ok((index, evicted))
+    }
+
+    /// Marks the texture at `index` as used, so that
+    /// [`try_add_texture_evicting`](Self::try_add_texture_evicting) evicts it after the textures
+    /// used less recently.
+    ///
+    /// This should be called whenever a texture already in the atlas is looked up again, like a
+    /// glyph that is drawn again.
+    pub fn mark_used(&mut self, index: usize) {
+        if let Some(texture) = self.allocated.get_mut(&index) {
+            self.use_count += 1;
+            texture.last_used = self.use_count;
+        }
+    }
+
+    /// Forgets every texture added to the atlas, so that they can be packed again from scratch.
+    ///
+    /// The configuration of the builder, like its padding, maximum size and overlap check, is
+    /// kept. Clearing the atlas texture and the [`TextureAtlas`] is up to the caller.
+    pub fn clear(&mut self) {
+        self.atlas_allocator.clear();
+        self.placed.clear();
+        if let Some(coverage) = &mut self.coverage {
+            coverage.clear();
+        }
+        self.allocated.clear();
+        self.free_indices.clear();
+    }
+
+    /// Returns when the texture at `index` was last used, see [`mark_used`](Self::mark_used).
+    pub(crate) fn last_used(&self, index: usize) -> Option<u64> {
+        self.allocated.get(&index).map(|texture| texture.last_used)
+    }
+
+    /// Sets when the texture at `index` was last used, to carry it over when the texture moves.
+    pub(crate) fn set_last_used(&mut self, index: usize, last_used: u64) {
+        if let Some(texture) = self.allocated.get_mut(&index) {
+            texture.last_used = last_used;
+        }
+    }
+
+    /// Returns the number of bytes the atlas texture occupies on the GPU, with every mip level,
+    /// array layer and sample of its format, as estimated by
+    /// [`Image::estimated_gpu_size_bytes`].
+    ///
+    /// `atlas_texture` is the texture of the [`TextureAtlas`] this builder adds to. Compared to
+    /// [`useful_pixels`](Self::useful_pixels), this tells how much of the memory of an atlas is
+    /// wasted on padding and free space.
+    pub fn memory_usage(&self, atlas_texture: &Image) -> usize {
+        atlas_texture.estimated_gpu_size_bytes()
+    }
+
+    /// Returns the number of pixels of the atlas covered by its textures, without their padding
+    /// and the free space between them.
+    ///
+    /// Textures evicted by [`try_add_texture_evicting`](Self::try_add_texture_evicting) aren't
+    /// counted.
+    pub fn useful_pixels(&self) -> u64 {
+        let allocated = self
+            .allocated
+            .values()
+            .map(|texture| texture.rect.width() as u64 * texture.rect.height() as u64);
+        let placed = self
+            .placed
+            .iter()
+            .map(|texture| texture.rect.width() as u64 * texture.rect.height() as u64);
+        allocated.chain(placed).sum()
+    }
+
+    /// Grows the atlas to `new_size`, keeping the textures already in it where they are.
+    ///
+    /// Growing only the height appends rows to the atlas texture, which keep the same stride, so
+    /// its data is only extended. Growing the width changes the stride of every row, so the whole
+    /// atlas is copied row by row into a new buffer: prefer growing the height when possible.
+    /// Fails if `new_size` exceeds the maximum size given to
+    /// [`try_new`](DynamicTextureAtlasBuilder::try_new).
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn grow(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        new_size: UVec2,
+    ) -> Result<(), DynamicTextureAtlasBuilderError> {
+        if texture_atlas.is_frozen() {
+            return Err(DynamicTextureAtlasBuilderError::Frozen);
+        }
+        if new_size.x > self.max_size || new_size.y > self.max_size {
+            return Err(DynamicTextureAtlasBuilderError::ExceedsMaxSize {
+                width: new_size.x,
+                height: new_size.y,
+                max: self.max_size,
+            });
+        }
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        let size = atlas_texture.texture_descriptor.size;
+        let size = UVec2::new(size.width, size.height);
+        if new_size.x < size.x || new_size.y < size.y {
+            return Err(DynamicTextureAtlasBuilderError::CannotShrink { size, new_size });
+        }
+        let new_extent = Extent3d {
+            width: new_size.x,
+            height: new_size.y,
+            depth_or_array_layers: 1,
+        };
+
+        if new_size.x == size.x {
+            atlas_texture.resize(new_extent);
         } else {
-            None
+            let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+            let stride = size.x as usize * format_size;
+            let new_stride = new_size.x as usize * format_size;
+            let mut data = vec![0; new_stride * new_size.y as usize];
+            if stride > 0 {
+                for (new_row, row) in data
+                    .chunks_exact_mut(new_stride)
+                    .zip(atlas_texture.data.chunks_exact(stride))
+                {
+                    new_row[..stride].copy_from_slice(row);
+                }
+            }
+            atlas_texture.data = data;
+            atlas_texture.texture_descriptor.size = new_extent;
+
+            // The overlap check indexes pixels by row too
+            if let Some(coverage) = &mut self.coverage {
+                let (width, new_width) = (size.x as usize, new_size.x as usize);
+                let mut new_coverage = FixedBitSet::with_capacity(new_width * size.y as usize);
+                for pixel in coverage.ones() {
+                    new_coverage.insert(pixel / width * new_width + pixel % width);
+                }
+                *coverage = new_coverage;
+            }
+        }
+
+        self.atlas_allocator
+            .grow(size2(new_size.x as i32, new_size.y as i32));
+        texture_atlas.size = new_size.as_vec2();
+        Ok(())
+    }
+
+    /// Add a new texture to [`TextureAtlas`] from an [`Image`] already in `textures`, like one
+    /// loaded by the asset server, returning why it couldn't be added on failure.
+    ///
+    /// Unlike [`DynamicTextureAtlasBuilder::try_add_texture`], the image doesn't need to be
+    /// cloned out of `textures` first. Only its pixel data is copied, since the atlas texture is
+    /// borrowed mutably from the same [`Assets`].
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn add_texture_by_handle(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture_handle: &Handle<Image>,
+    ) -> Result<usize, DynamicTextureAtlasBuilderError> {
+        let texture =
+            textures
+                .get(texture_handle)
+                .ok_or(DynamicTextureAtlasBuilderError::MissingTexture(
+                    texture_handle.id(),
+                ))?;
+        let size = texture.texture_descriptor.size;
+        let data = texture.data.clone();
+        self.add_texture_data(texture_atlas, textures, size, &data, None)
+    }
+
+    /// Allocates a texture of `size` in the atlas and copies `data` into it.
+    ///
+    /// With `evicted`, the least recently used textures are evicted to make room for it, and
+    /// their indices are pushed to it.
+    fn add_texture_data(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        size: Extent3d,
+        data: &[u8],
+        evicted: Option<&mut Vec<usize>>,
+    ) -> Result<usize, DynamicTextureAtlasBuilderError> {
+        if texture_atlas.is_frozen() {
+            return Err(DynamicTextureAtlasBuilderError::Frozen);
+        }
+        let size = self.padded_size(size)?;
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        let (allocation, eviction) = match evicted {
+            Some(_) => self.allocate_evicting(size)?,
+            None => (self.allocate(size)?, None),
+        };
+        // The texture is checked before anything is evicted, so a failure leaves the atlas as it
+        // was
+        let checked = to_urect(self.texture_rectangle(allocation)).and_then(|rect| {
+            self.check_placement(atlas_texture, rect, data)
+                .map(|()| rect)
+        });
+        let rect = match checked {
+            Ok(rect) => rect,
+            Err(err) => {
+                match eviction {
+                    Some(eviction) => self.atlas_allocator = eviction.atlas_allocator,
+                    None => self.atlas_allocator.deallocate(allocation.id),
+                }
+                return Err(err);
+            }
+        };
+        if let (Some(eviction), Some(evicted)) = (eviction, evicted) {
+            self.evict(atlas_texture, &eviction.indices);
+            evicted.extend(eviction.indices);
+        }
+        if let Err(err) = self.place_texture(atlas_texture, rect, data) {
+            self.atlas_allocator.deallocate(allocation.id);
+            return Err(err);
+        }
+        Ok(self.insert_texture(texture_atlas, allocation.id, rect))
+    }
+
+    /// Add a new texture to [`TextureAtlas`], passing each row of the texture's data through
+    /// `convert` before it is copied to the atlas.
+    ///
+    /// This allows arbitrary per-pixel processing, like converting between formats the
+    /// [`Image`] doesn't support converting itself. Each row returned by `convert` must be
+    /// exactly as long as a row of the texture in the atlas format, this is validated before
+    /// anything is allocated or copied.
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn add_texture_with(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture: &Image,
+        convert: impl Fn(&[u8]) -> Vec<u8>,
+    ) -> Result<usize, DynamicTextureAtlasBuilderError> {
+        if texture_atlas.is_frozen() {
+            return Err(DynamicTextureAtlasBuilderError::Frozen);
+        }
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        let size = texture.texture_descriptor.size;
+        let source_stride = size.width as usize * texture.texture_descriptor.format.pixel_size();
+        let atlas_stride =
+            size.width as usize * atlas_texture.texture_descriptor.format.pixel_size();
+
+        let rows: Vec<Vec<u8>> = if source_stride == 0 {
+            Vec::new()
+        } else {
+            texture
+                .data
+                .chunks_exact(source_stride)
+                .take(size.height as usize)
+                .map(convert)
+                .collect()
+        };
+        if let Some(row) = rows.iter().find(|row| row.len() != atlas_stride) {
+            return Err(DynamicTextureAtlasBuilderError::InvalidRowLength {
+                expected: atlas_stride,
+                actual: row.len(),
+            });
+        }
+
+        let allocation = self.allocate(self.padded_size(size)?)?;
+        let placed = to_urect(self.texture_rectangle(allocation)).and_then(|rect| {
+            self.place_rows(atlas_texture, rect, rows.iter().map(Vec::as_slice))
+                .map(|()| rect)
+        });
+        match placed {
+            Ok(rect) => Ok(self.insert_texture(texture_atlas, allocation.id, rect)),
+            Err(err) => {
+                self.atlas_allocator.deallocate(allocation.id);
+                Err(err)
+            }
+        }
+    }
+
+    /// Add a new texture to [`TextureAtlas`] with its top left corner at `min`, instead of
+    /// where the builder would allocate it.
+    ///
+    /// This allows reproducing the layout of an atlas made by another tool. Like allocated
+    /// textures, the texture is followed by the padding of the builder on its right and bottom
+    /// sides, except where it reaches the edge of the atlas. Fails if the texture or its padding
+    /// would overlap a texture already in the atlas, or if the texture would reach past its edge.
+    /// It is user's responsibility to pass in the correct [`TextureAtlas`]
+    pub fn place_at(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        textures: &mut Assets<Image>,
+        texture: &Image,
+        min: UVec2,
+    ) -> Result<usize, DynamicTextureAtlasBuilderError> {
+        if texture_atlas.is_frozen() {
+            return Err(DynamicTextureAtlasBuilderError::Frozen);
+        }
+        let size = texture.texture_descriptor.size;
+        let size = UVec2::new(size.width, size.height);
+        let atlas_size = self.atlas_allocator.size();
+        let atlas_rect = URect::new(0, 0, atlas_size.width as u32, atlas_size.height as u32);
+        let rect = URect::from_corners(min, min.saturating_add(size));
+        if !atlas_rect.contains_rect(rect) {
+            return Err(DynamicTextureAtlasBuilderError::PlacementOutOfBounds { min, size });
+        }
+        let padding = UVec2::splat(self.padding.max(0) as u32);
+        let padded_max = rect.max.saturating_add(padding).min(atlas_rect.max);
+        let reserved = guillotiere::Rectangle {
+            min: point2(rect.min.x as i32, rect.min.y as i32),
+            max: point2(padded_max.x as i32, padded_max.y as i32),
+        };
+        if self.is_occupied(&reserved) {
+            return Err(DynamicTextureAtlasBuilderError::Occupied);
+        }
+
+        let atlas_texture = textures.get_mut(&texture_atlas.texture).unwrap();
+        self.place_texture(atlas_texture, rect, &texture.data)?;
+        self.placed.push(PlacedTexture { rect, reserved });
+        Ok(texture_atlas.add_texture(rect.as_rect()))
+    }
+
+    /// Allocates `size` in the atlas, away from the textures placed with
+    /// [`DynamicTextureAtlasBuilder::place_at`].
+    fn allocate(
+        &mut self,
+        size: guillotiere::Size,
+    ) -> Result<Allocation, DynamicTextureAtlasBuilderError> {
+        // Allocations over placed textures are held until a free one is found, so the allocator
+        // doesn't return them again
+        let mut overlapping = Vec::new();
+        let allocation = loop {
+            let Some(allocation) = self.atlas_allocator.allocate(size) else {
+                break None;
+            };
+            if !self
+                .placed
+                .iter()
+                .any(|placed| placed.reserved.intersects(&allocation.rectangle))
+            {
+                break Some(allocation);
+            }
+            overlapping.push(allocation.id);
+        };
+        for id in overlapping {
+            self.atlas_allocator.deallocate(id);
+        }
+        allocation.ok_or(DynamicTextureAtlasBuilderError::NotEnoughSpace)
+    }
+
+    /// Allocates `size` like [`allocate`](Self::allocate), deallocating the least recently used
+    /// textures until it fits.
+    ///
+    /// The deallocated textures are only removed from the builder by [`evict`](Self::evict), so
+    /// that the eviction can be undone if the new texture can't be placed. Nothing is deallocated
+    /// if the texture doesn't fit even once every texture is.
+    fn allocate_evicting(
+        &mut self,
+        size: guillotiere::Size,
+    ) -> Result<(Allocation, Option<Eviction>), DynamicTextureAtlasBuilderError> {
+        if let Ok(allocation) = self.allocate(size) {
+            return Ok((allocation, None));
+        }
+        let mut candidates: Vec<_> = self
+            .allocated
+            .iter()
+            .map(|(&index, texture)| (texture.last_used, index))
+            .collect();
+        candidates.sort_unstable();
+
+        // Kept to undo the evictions if the texture doesn't fit
+        let atlas_allocator = self.atlas_allocator.clone();
+        for (count, &(_, index)) in candidates.iter().enumerate() {
+            self.atlas_allocator.deallocate(self.allocated[&index].id);
+            let Ok(allocation) = self.allocate(size) else {
+                continue;
+            };
+            let indices = candidates[..=count]
+                .iter()
+                .map(|&(_, index)| index)
+                .collect();
+            return Ok((
+                allocation,
+                Some(Eviction {
+                    indices,
+                    atlas_allocator,
+                }),
+            ));
+        }
+        self.atlas_allocator = atlas_allocator;
+        Err(DynamicTextureAtlasBuilderError::NotEnoughSpace)
+    }
+
+    /// Removes the textures at `indices`, already deallocated by
+    /// [`allocate_evicting`](Self::allocate_evicting), clearing their pixels and freeing their
+    /// indices.
+    fn evict(&mut self, atlas_texture: &mut Image, indices: &[usize]) {
+        for index in indices {
+            let texture = self.allocated.remove(index).unwrap();
+            self.clear_texture(atlas_texture, texture.rect);
+            self.free_indices.push(*index);
+        }
+    }
+
+    /// Adds the texture allocated at `id` to the atlas, at the index of an evicted texture if
+    /// there is one, and returns its index.
+    fn insert_texture(
+        &mut self,
+        texture_atlas: &mut TextureAtlas,
+        id: AllocId,
+        rect: URect,
+    ) -> usize {
+        let index = match self.free_indices.pop() {
+            Some(index) => {
+                texture_atlas.textures[index] = rect.as_rect();
+                // The payload belonged to the evicted texture
+                texture_atlas.set_data(index, None);
+                index
+            }
+            None => texture_atlas.add_texture(rect.as_rect()),
+        };
+        self.use_count += 1;
+        self.allocated.insert(
+            index,
+            AllocatedTexture {
+                id,
+                rect,
+                last_used: self.use_count,
+            },
+        );
+        index
+    }
+
+    /// Clears the pixels of an evicted texture, and its pixels in the coverage of the overlap
+    /// check.
+    fn clear_texture(&mut self, atlas_texture: &mut Image, rect: URect) {
+        let atlas_width = atlas_texture.texture_descriptor.size.width as usize;
+        let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+        for y in rect.min.y as usize..rect.max.y as usize {
+            let begin = y * atlas_width + rect.min.x as usize;
+            let pixels = begin..begin + rect.width() as usize;
+            if let Some(row) = atlas_texture
+                .data
+                .get_mut(pixels.start * format_size..pixels.end * format_size)
+            {
+                row.fill(0);
+            }
+            if let Some(coverage) = &mut self.coverage {
+                coverage.set_range(pixels, false);
+            }
         }
     }
 
+    /// Returns `true` if `rectangle` overlaps a texture in the atlas.
+    fn is_occupied(&self, rectangle: &guillotiere::Rectangle) -> bool {
+        let mut occupied = self
+            .placed
+            .iter()
+            .any(|placed| placed.reserved.intersects(rectangle));
+        self.atlas_allocator
+            .for_each_allocated_rectangle(|_, allocated| {
+                occupied |= allocated.intersects(rectangle);
+            });
+        occupied
+    }
+
+    /// The area of the texture in `allocation`, without the padding.
+    fn texture_rectangle(&self, allocation: Allocation) -> guillotiere::Rectangle {
+        let mut rectangle = allocation.rectangle;
+        if self.padding == 0 {
+            return rectangle;
+        }
+        rectangle.max.x -= self.padding;
+        rectangle.max.y -= self.padding;
+        rectangle
+    }
+
+    /// The size to allocate for a texture of `size`, with the padding added to it.
+    fn padded_size(
+        &self,
+        size: Extent3d,
+    ) -> Result<guillotiere::Size, DynamicTextureAtlasBuilderError> {
+        // Without padding, like in most font atlases, only the conversion can fail
+        let pad = |length: u32| {
+            let length = i32::try_from(length).ok()?;
+            if self.padding == 0 {
+                return Some(length);
+            }
+            length.checked_add(self.padding)
+        };
+        match (pad(size.width), pad(size.height)) {
+            (Some(width), Some(height)) => Ok(size2(width, height)),
+            _ => Err(DynamicTextureAtlasBuilderError::SizeOverflow {
+                width: size.width,
+                height: size.height,
+                padding: self.padding,
+            }),
+        }
+    }
+
+    /// Copies the pixel `data` of a texture, in the atlas format, into the texture rectangle.
     fn place_texture(
         &mut self,
         atlas_texture: &mut Image,
-        allocation: Allocation,
-        texture: &Image,
-    ) {
-        let mut rect = allocation.rectangle;
-        rect.max.x -= self.padding;
-        rect.max.y -= self.padding;
+        rect: URect,
+        data: &[u8],
+    ) -> Result<(), DynamicTextureAtlasBuilderError> {
+        self.check_placement(atlas_texture, rect, data)?;
+        let stride = rect.width() as usize * atlas_texture.texture_descriptor.format.pixel_size();
+        if stride == 0 {
+            return Ok(());
+        }
+        self.place_rows(atlas_texture, rect, data.chunks_exact(stride))
+    }
+
+    /// Checks that [`place_texture`](Self::place_texture) can copy `data` into the texture
+    /// rectangle, without changing anything.
+    fn check_placement(
+        &self,
+        atlas_texture: &Image,
+        rect: URect,
+        data: &[u8],
+    ) -> Result<(), DynamicTextureAtlasBuilderError> {
+        let stride = rect.width() as usize * atlas_texture.texture_descriptor.format.pixel_size();
+        if stride == 0 {
+            return Ok(());
+        }
+        let texture_end = rect.height() as usize * stride;
+        if self.strict && texture_end > data.len() {
+            return Err(DynamicTextureAtlasBuilderError::TextureOutOfBounds {
+                end: texture_end,
+                len: data.len(),
+            });
+        }
+        check_rect_bounds(atlas_texture, rect)
+    }
+
+    /// Copies `rows` of pixel data, already in the atlas format, into the texture rectangle.
+    ///
+    /// The rectangle is checked against the atlas data, and against the textures already written
+    /// if [`debug_overlap_check`](Self::debug_overlap_check) is enabled, before anything is
+    /// copied.
+    fn place_rows<'a>(
+        &mut self,
+        atlas_texture: &mut Image,
+        rect: URect,
+        rows: impl Iterator<Item = &'a [u8]>,
+    ) -> Result<(), DynamicTextureAtlasBuilderError> {
         let atlas_width = atlas_texture.texture_descriptor.size.width as usize;
         let rect_width = rect.width() as usize;
         let format_size = atlas_texture.texture_descriptor.format.pixel_size();
-
-        for (texture_y, bound_y) in (rect.min.y..rect.max.y).map(|i| i as usize).enumerate() {
+        let row_range = |bound_y: usize| {
             let begin = (bound_y * atlas_width + rect.min.x as usize) * format_size;
-            let end = begin + rect_width * format_size;
-            let texture_begin = texture_y * rect_width * format_size;
-            let texture_end = texture_begin + rect_width * format_size;
-            atlas_texture.data[begin..end]
-                .copy_from_slice(&texture.data[texture_begin..texture_end]);
+            begin..begin + rect_width * format_size
+        };
+
+        check_rect_bounds(atlas_texture, rect)?;
+        let pixel_rows = (rect.min.y..rect.max.y).map(|y| {
+            let begin = y as usize * atlas_width + rect.min.x as usize;
+            begin..begin + rect_width
+        });
+        if let Some(coverage) = &mut self.coverage {
+            coverage.grow(rect.max.y as usize * atlas_width);
+            assert!(
+                pixel_rows
+                    .clone()
+                    .all(|pixels| coverage.count_ones(pixels) == 0),
+                "texture at {rect:?} overlaps a texture already written to the atlas"
+            );
+        }
+        for (bound_y, row) in (rect.min.y..rect.max.y).map(|i| i as usize).zip(rows) {
+            let range = row_range(bound_y);
+            if row.len() != range.len() {
+                return Err(DynamicTextureAtlasBuilderError::InvalidRowLength {
+                    expected: range.len(),
+                    actual: row.len(),
+                });
+            }
+            atlas_texture.data[range].copy_from_slice(row);
+        }
+        if let Some(coverage) = &mut self.coverage {
+            pixel_rows.for_each(|pixels| coverage.insert_range(pixels));
         }
+        Ok(())
     }
 }
 
-fn to_rect(rectangle: guillotiere::Rectangle) -> Rect {
-    Rect {
-        min: IVec2::new(rectangle.min.x, rectangle.min.y).as_vec2(),
-        max: IVec2::new(rectangle.max.x, rectangle.max.y).as_vec2(),
+/// Checks that the texture rectangle `rect` is inside the atlas texture and its data.
+fn check_rect_bounds(
+    atlas_texture: &Image,
+    rect: URect,
+) -> Result<(), DynamicTextureAtlasBuilderError> {
+    if rect.max.y <= rect.min.y {
+        return Ok(());
     }
+    let atlas_width = atlas_texture.texture_descriptor.size.width as usize;
+    if rect.max.x as usize > atlas_width {
+        return Err(DynamicTextureAtlasBuilderError::RectOutOfBounds {
+            max_x: rect.max.x as usize,
+            width: atlas_width,
+        });
+    }
+    let format_size = atlas_texture.texture_descriptor.format.pixel_size();
+    let end = ((rect.max.y as usize - 1) * atlas_width + rect.max.x as usize) * format_size;
+    if end > atlas_texture.data.len() {
+        return Err(DynamicTextureAtlasBuilderError::AtlasOutOfBounds {
+            end,
+            len: atlas_texture.data.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Converts an allocated rectangle to texture coordinates, failing with the first coordinate
+/// that doesn't fit in a `u32`.
+fn to_urect(rectangle: guillotiere::Rectangle) -> Result<URect, DynamicTextureAtlasBuilderError> {
+    let coordinate = |coordinate: &'static str, value: i32| {
+        u32::try_from(value)
+            .map_err(|_| DynamicTextureAtlasBuilderError::InvalidAllocation { coordinate, value })
+    };
+    Ok(URect {
+        min: UVec2::new(
+            coordinate("min.x", rectangle.min.x)?,
+            coordinate("min.y", rectangle.min.y)?,
+        ),
+        max: UVec2::new(
+            coordinate("max.x", rectangle.max.x)?,
+            coordinate("max.y", rectangle.max.y)?,
+        ),
+    })
 }
 
 fn to_size2(vec2: Vec2) -> guillotiere::Size {
     guillotiere::Size::new(vec2.x as i32, vec2.y as i32)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin};
+    use bevy_render::render_resource::{TextureDimension, TextureFormat};
+
+    fn image(width: u32, height: u32, data: Vec<u8>) -> Image {
+        image_with_format(width, height, data, TextureFormat::R8Unorm)
+    }
+
+    fn image_with_format(width: u32, height: u32, data: Vec<u8>, format: TextureFormat) -> Image {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &vec![0; format.pixel_size()],
+            format,
+        );
+        image.data = data;
+        image
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugin(AssetPlugin::default()).add_asset::<Image>();
+        app
+    }
+
+    #[test]
+    fn strict_rejects_short_texture() {
+        let mut atlas = image(4, 4, vec![0; 16]);
+        let texture = image(2, 2, vec![1, 2, 3]);
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 0);
+        let allocation = builder.atlas_allocator.allocate(size2(2, 2)).unwrap();
+
+        // The incomplete last row is skipped
+        builder
+            .place_texture(
+                &mut atlas,
+                to_urect(builder.texture_rectangle(allocation)).unwrap(),
+                &texture.data,
+            )
+            .unwrap();
+        assert_eq!(&atlas.data[..6], &[1, 2, 0, 0, 0, 0]);
+
+        let mut builder = builder.strict(true);
+        assert!(matches!(
+            builder.place_texture(
+                &mut atlas,
+                to_urect(builder.texture_rectangle(allocation)).unwrap(),
+                &texture.data
+            ),
+            Err(DynamicTextureAtlasBuilderError::TextureOutOfBounds { end: 4, len: 3 })
+        ));
+    }
+
+    #[test]
+    fn rejects_rect_outside_atlas() {
+        // The allocator is larger than the atlas texture
+        let mut atlas = image(4, 4, vec![0; 16]);
+        let texture = image(2, 2, vec![1; 4]);
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(8.0), 0);
+        let allocation = builder.atlas_allocator.allocate(size2(8, 2)).unwrap();
+        assert!(matches!(
+            builder.place_texture(
+                &mut atlas,
+                to_urect(builder.texture_rectangle(allocation)).unwrap(),
+                &texture.data
+            ),
+            Err(DynamicTextureAtlasBuilderError::RectOutOfBounds { max_x: 8, width: 4 })
+        ));
+        assert!(atlas.data.iter().all(|&byte| byte == 0));
+    }
+
+    #[test]
+    fn allocations_avoid_placed_textures() {
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 0);
+        // Where the allocator puts its first texture
+        let placed = guillotiere::Rectangle {
+            min: point2(0, 0),
+            max: point2(2, 2),
+        };
+        builder.placed.push(PlacedTexture {
+            rect: URect::new(0, 0, 2, 2),
+            reserved: placed,
+        });
+        assert!(builder.is_occupied(&placed));
+
+        let allocations: Vec<_> = (0..3)
+            .map(|_| builder.allocate(size2(2, 2)).unwrap().rectangle)
+            .collect();
+        for (i, allocation) in allocations.iter().enumerate() {
+            assert!(!allocation.intersects(&placed));
+            assert!(allocations[..i]
+                .iter()
+                .all(|other| !other.intersects(allocation)));
+        }
+        assert!(matches!(
+            builder.allocate(size2(2, 2)),
+            Err(DynamicTextureAtlasBuilderError::NotEnoughSpace)
+        ));
+        assert!(builder.is_occupied(&guillotiere::Rectangle {
+            min: point2(1, 1),
+            max: point2(3, 3),
+        }));
+    }
+
+    #[test]
+    fn invalid_allocation_names_coordinate() {
+        let rectangle = guillotiere::Rectangle {
+            min: point2(0, -3),
+            max: point2(2, 2),
+        };
+        let err = to_urect(rectangle).unwrap_err();
+        assert!(matches!(
+            err,
+            DynamicTextureAtlasBuilderError::InvalidAllocation {
+                coordinate: "min.y",
+                value: -3
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "texture allocation min.y = -3 is not a valid texture coordinate"
+        );
+    }
+
+    #[test]
+    fn try_new_rejects_oversized_atlas() {
+        assert!(DynamicTextureAtlasBuilder::try_new(Vec2::splat(2048.0), 1, 2048).is_ok());
+        let err = DynamicTextureAtlasBuilder::try_new(Vec2::new(1024.0, 4096.0), 1, 2048)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            DynamicTextureAtlasBuilderError::ExceedsMaxSize {
+                width: 1024,
+                height: 4096,
+                max: 2048
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "atlas size 1024x4096 exceeds the maximum texture dimension of 2048 pixels"
+        );
+    }
+
+    #[test]
+    fn padded_size_overflow() {
+        let builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 2);
+        let size = |width, height| Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        assert_eq!(builder.padded_size(size(4, 6)).unwrap(), size2(6, 8));
+        assert!(matches!(
+            builder.padded_size(size(i32::MAX as u32, 6)),
+            Err(DynamicTextureAtlasBuilderError::SizeOverflow { padding: 2, .. })
+        ));
+        assert!(matches!(
+            builder.padded_size(size(4, u32::MAX - 1)),
+            Err(DynamicTextureAtlasBuilderError::SizeOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn zero_padding_keeps_sizes() {
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(8.0), 0);
+        let size = builder
+            .padded_size(Extent3d {
+                width: 3,
+                height: 5,
+                depth_or_array_layers: 1,
+            })
+            .unwrap();
+        assert_eq!(size, size2(3, 5));
+        let allocation = builder.allocate(size).unwrap();
+        assert_eq!(builder.texture_rectangle(allocation), allocation.rectangle);
+        assert!(matches!(
+            builder.padded_size(Extent3d {
+                width: u32::MAX,
+                height: 5,
+                depth_or_array_layers: 1,
+            }),
+            Err(DynamicTextureAtlasBuilderError::SizeOverflow { padding: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn places_8_byte_pixels() {
+        // Rgba16Float pixels are 8 bytes wide, each texture pixel is filled with its index
+        let format = TextureFormat::Rgba16Float;
+        let mut atlas = image_with_format(4, 4, vec![0; 4 * 4 * 8], format);
+        let data = (1..=4).flat_map(|pixel| [pixel; 8]).collect();
+        let texture = image_with_format(2, 2, data, format);
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 1);
+        let allocation = builder.atlas_allocator.allocate(size2(3, 3)).unwrap();
+        builder
+            .place_texture(
+                &mut atlas,
+                to_urect(builder.texture_rectangle(allocation)).unwrap(),
+                &texture.data,
+            )
+            .unwrap();
+
+        let min = allocation.rectangle.min;
+        let pixel = |x: i32, y: i32| {
+            let begin = ((y * 4 + x) * 8) as usize;
+            &atlas.data[begin..begin + 8]
+        };
+        assert_eq!(pixel(min.x, min.y), &[1; 8]);
+        assert_eq!(pixel(min.x + 1, min.y), &[2; 8]);
+        assert_eq!(pixel(min.x, min.y + 1), &[3; 8]);
+        assert_eq!(pixel(min.x + 1, min.y + 1), &[4; 8]);
+        // The padding is left untouched
+        assert_eq!(pixel(min.x + 2, min.y), &[0; 8]);
+        assert_eq!(atlas.data.iter().filter(|&&byte| byte != 0).count(), 4 * 8);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlaps a texture already written to the atlas")]
+    fn overlap_check_panics_on_double_write() {
+        let mut atlas = image(4, 4, vec![0; 16]);
+        let texture = image(2, 2, vec![1; 4]);
+        let mut builder =
+            DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 0).debug_overlap_check(true);
+        let allocation = builder.atlas_allocator.allocate(size2(2, 2)).unwrap();
+        let rect = to_urect(builder.texture_rectangle(allocation)).unwrap();
+        builder
+            .place_texture(&mut atlas, rect, &texture.data)
+            .unwrap();
+
+        // Textures next to each other don't overlap
+        let allocation = builder.atlas_allocator.allocate(size2(2, 2)).unwrap();
+        let next = to_urect(builder.texture_rectangle(allocation)).unwrap();
+        builder
+            .place_texture(&mut atlas, next, &texture.data)
+            .unwrap();
+
+        let _ = builder.place_texture(&mut atlas, rect, &texture.data);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_textures() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_texture = textures.add(image(4, 4, vec![0; 16]));
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::splat(4.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 0);
+        let mut add = |builder: &mut DynamicTextureAtlasBuilder, size, value| {
+            builder.try_add_texture_evicting(
+                &mut texture_atlas,
+                &mut textures,
+                &image(size, size, vec![value; (size * size) as usize]),
+            )
+        };
+
+        // The atlas fits four 2x2 textures, the first one is used again after the others
+        let indices: Vec<_> = (1..=4)
+            .map(|value| add(&mut builder, 2, value).unwrap())
+            .collect();
+        assert!(indices.iter().all(|(_, evicted)| evicted.is_empty()));
+        builder.mark_used(0);
+        let (index, evicted) = add(&mut builder, 2, 5).unwrap();
+        assert_eq!(evicted, [1]);
+        assert_eq!(index, 1);
+
+        // Too large to fit even once everything is evicted, nothing is
+        assert!(matches!(
+            add(&mut builder, 8, 6),
+            Err(DynamicTextureAtlasBuilderError::NotEnoughSpace)
+        ));
+        let (index, evicted) = add(&mut builder, 4, 7).unwrap();
+        assert_eq!(evicted, [2, 3, 0, 1]);
+        assert_eq!(index, 1);
+        assert_eq!(texture_atlas.len(), 4);
+        let atlas_texture = textures.get(&atlas_texture).unwrap();
+        assert!(atlas_texture.data.iter().all(|&pixel| pixel == 7));
+    }
+
+    #[test]
+    fn failed_placement_keeps_evicted_textures() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_texture = textures.add(image(4, 4, vec![0; 16]));
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::splat(4.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(4.0), 0).strict(true);
+        for value in 1..=4 {
+            let texture = image(2, 2, vec![value; 4]);
+            builder
+                .try_add_texture_evicting(&mut texture_atlas, &mut textures, &texture)
+                .unwrap();
+        }
+        let data = textures.get(&atlas_texture).unwrap().data.clone();
+
+        // Evicting every texture makes room for it, but its data is too short
+        let texture = image(4, 4, vec![5; 15]);
+        assert!(matches!(
+            builder.try_add_texture_evicting(&mut texture_atlas, &mut textures, &texture),
+            Err(DynamicTextureAtlasBuilderError::TextureOutOfBounds { end: 16, len: 15 })
+        ));
+        assert_eq!(textures.get(&atlas_texture).unwrap().data, data);
+        assert!((0..4).all(|index| builder.last_used(index).is_some()));
+
+        // The atlas is still full, so the least recently used texture is evicted
+        let texture = image(2, 2, vec![6; 4]);
+        let (index, evicted) = builder
+            .try_add_texture_evicting(&mut texture_atlas, &mut textures, &texture)
+            .unwrap();
+        assert_eq!((index, evicted), (0, vec![0]));
+    }
+
+    #[test]
+    fn memory_usage_and_useful_pixels() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_image = image_with_format(8, 8, vec![0; 256], TextureFormat::Rgba8Unorm);
+        let atlas_texture = textures.add(atlas_image.clone());
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture, Vec2::splat(8.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(8.0), 1);
+        assert_eq!(builder.memory_usage(&atlas_image), 256);
+        assert_eq!(builder.useful_pixels(), 0);
+
+        let texture = image_with_format(2, 3, vec![1; 24], TextureFormat::Rgba8Unorm);
+        builder
+            .try_add_texture(&mut texture_atlas, &mut textures, &texture)
+            .unwrap();
+        let texture = image_with_format(4, 1, vec![1; 16], TextureFormat::Rgba8Unorm);
+        builder
+            .place_at(
+                &mut texture_atlas,
+                &mut textures,
+                &texture,
+                UVec2::new(4, 7),
+            )
+            .unwrap();
+        // The padding isn't counted
+        assert_eq!(builder.useful_pixels(), 10);
+        assert_eq!(builder.memory_usage(&atlas_image), 256);
+    }
+
+    #[test]
+    fn add_texture_with_converts_rows() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_image = image_with_format(4, 2, vec![0; 32], TextureFormat::Rgba8Unorm);
+        let atlas_texture = textures.add(atlas_image);
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::new(4.0, 2.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::new(4.0, 2.0), 0);
+
+        // Single channel pixels are expanded to opaque gray
+        let texture = image(2, 2, vec![1, 2, 3, 4]);
+        let index = builder
+            .add_texture_with(&mut texture_atlas, &mut textures, &texture, |row| {
+                row.iter()
+                    .flat_map(|&value| [value, value, value, 255])
+                    .collect()
+            })
+            .unwrap();
+        let rect = texture_atlas.textures[index];
+        assert_eq!(rect.size(), Vec2::splat(2.0));
+
+        let data = &textures.get(&atlas_texture).unwrap().data;
+        for (y, row) in [[1, 2], [3, 4]].into_iter().enumerate() {
+            for (x, value) in row.into_iter().enumerate() {
+                let begin = ((rect.min.y as usize + y) * 4 + rect.min.x as usize + x) * 4;
+                assert_eq!(data[begin..begin + 4], [value, value, value, 255]);
+            }
+        }
+    }
+
+    #[test]
+    fn add_texture_with_rejects_wrong_row_length() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_image = image_with_format(4, 2, vec![0; 32], TextureFormat::Rgba8Unorm);
+        let atlas_texture = textures.add(atlas_image);
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::new(4.0, 2.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::new(4.0, 2.0), 0);
+
+        // The rows are left in the single channel format
+        let texture = image(2, 2, vec![1, 2, 3, 4]);
+        assert!(matches!(
+            builder.add_texture_with(&mut texture_atlas, &mut textures, &texture, <[u8]>::to_vec),
+            Err(DynamicTextureAtlasBuilderError::InvalidRowLength {
+                expected: 8,
+                actual: 2
+            })
+        ));
+        // Nothing was allocated or copied
+        assert!(texture_atlas.textures.is_empty());
+        assert!(textures
+            .get(&atlas_texture)
+            .unwrap()
+            .data
+            .iter()
+            .all(|&byte| byte == 0));
+        // The whole atlas is still free
+        let texture = image(4, 2, vec![1; 8]);
+        builder
+            .add_texture_with(&mut texture_atlas, &mut textures, &texture, |row| {
+                row.repeat(4)
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn place_at_uses_top_left_origin_and_padding() {
+        use bevy_math::Rect;
+
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_texture = textures.add(image(8, 8, vec![0; 64]));
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::splat(8.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(8.0), 1);
+
+        let texture = image(2, 2, vec![1, 2, 3, 4]);
+        let index = builder
+            .place_at(
+                &mut texture_atlas,
+                &mut textures,
+                &texture,
+                UVec2::new(2, 1),
+            )
+            .unwrap();
+        assert_eq!(texture_atlas.textures[index], Rect::new(2.0, 1.0, 4.0, 3.0));
+        // The first row of the texture is the second row of the atlas
+        let data = &textures.get(&atlas_texture).unwrap().data;
+        assert_eq!(data[8..16], [0, 0, 1, 2, 0, 0, 0, 0]);
+        assert_eq!(data[16..24], [0, 0, 3, 4, 0, 0, 0, 0]);
+
+        // The padding on the right and bottom sides is kept free
+        for min in [UVec2::new(4, 1), UVec2::new(2, 3)] {
+            assert!(matches!(
+                builder.place_at(&mut texture_atlas, &mut textures, &texture, min),
+                Err(DynamicTextureAtlasBuilderError::Occupied)
+            ));
+        }
+        builder
+            .place_at(
+                &mut texture_atlas,
+                &mut textures,
+                &texture,
+                UVec2::new(5, 1),
+            )
+            .unwrap();
+        // There is no padding past the edge of the atlas
+        builder
+            .place_at(
+                &mut texture_atlas,
+                &mut textures,
+                &texture,
+                UVec2::new(6, 6),
+            )
+            .unwrap();
+        assert_eq!(builder.useful_pixels(), 12);
+    }
+
+    #[test]
+    fn grow_keeps_textures_in_place() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_texture = textures.add(image(2, 2, vec![0; 4]));
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::splat(2.0));
+        let mut builder = DynamicTextureAtlasBuilder::new(Vec2::splat(2.0), 0);
+        builder
+            .try_add_texture(
+                &mut texture_atlas,
+                &mut textures,
+                &image(2, 2, vec![1, 2, 3, 4]),
+            )
+            .unwrap();
+        let texture = image(2, 2, vec![5; 4]);
+        assert!(builder
+            .try_add_texture(&mut texture_atlas, &mut textures, &texture)
+            .is_err());
+
+        // Growing the height appends rows after the texture
+        builder
+            .grow(&mut texture_atlas, &mut textures, UVec2::new(2, 4))
+            .unwrap();
+        assert_eq!(
+            textures.get(&atlas_texture).unwrap().data,
+            [1, 2, 3, 4, 0, 0, 0, 0]
+        );
+        builder
+            .try_add_texture(&mut texture_atlas, &mut textures, &texture)
+            .unwrap();
+
+        // Growing the width moves the rows to their new stride
+        builder
+            .grow(&mut texture_atlas, &mut textures, UVec2::new(3, 4))
+            .unwrap();
+        let atlas_image = textures.get(&atlas_texture).unwrap();
+        assert_eq!(atlas_image.texture_descriptor.size.width, 3);
+        assert_eq!(atlas_image.data, [1, 2, 0, 3, 4, 0, 5, 5, 0, 5, 5, 0]);
+        assert_eq!(texture_atlas.size, Vec2::new(3.0, 4.0));
+        assert!(matches!(
+            builder.grow(&mut texture_atlas, &mut textures, UVec2::new(2, 8)),
+            Err(DynamicTextureAtlasBuilderError::CannotShrink { .. })
+        ));
+    }
+
+    #[test]
+    fn grow_stops_at_max_size() {
+        let mut app = app();
+        let mut textures = app.world.resource_mut::<Assets<Image>>();
+        let atlas_texture = textures.add(image(2, 2, vec![0; 4]));
+        let mut texture_atlas = TextureAtlas::new_empty(atlas_texture.clone(), Vec2::splat(2.0));
+        let mut builder = DynamicTextureAtlasBuilder::try_new(Vec2::splat(2.0), 0, 4).unwrap();
+
+        builder
+            .grow(&mut texture_atlas, &mut textures, UVec2::new(4, 4))
+            .unwrap();
+        assert!(matches!(
+            builder.grow(&mut texture_atlas, &mut textures, UVec2::new(4, 8)),
+            Err(DynamicTextureAtlasBuilderError::ExceedsMaxSize {
+                width: 4,
+                height: 8,
+                max: 4
+            })
+        ));
+        assert_eq!(texture_atlas.size, Vec2::splat(4.0));
+        assert_eq!(textures.get(&atlas_texture).unwrap().data.len(), 16);
+    }
+}
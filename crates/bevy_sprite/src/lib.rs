@@ -1,37 +1,66 @@
 #![allow(clippy::type_complexity)]
 
+mod animated_sprite;
+#[cfg(feature = "aseprite")]
+mod aseprite_loader;
 mod bundle;
+mod dynamic_texture_atlas;
 mod dynamic_texture_atlas_builder;
 mod mesh2d;
+mod nine_slice;
+mod outline;
 mod render;
+mod spatial_index;
 mod sprite;
+mod static_sprite_batch;
 mod texture_atlas;
 mod texture_atlas_builder;
+#[cfg(feature = "serialize")]
+mod texture_atlas_export;
+#[cfg(feature = "serialize")]
+mod texture_atlas_loader;
 
 pub mod collide_aabb;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        animated_sprite::{AnimatedSprite, AnimatedSpriteState, AnimationFinished},
         bundle::{SpriteBundle, SpriteSheetBundle},
+        nine_slice::{NineSliceCenter, NineSliceSprite, NineSliceSpriteBundle},
+        outline::{OutlineMode, SpriteOutline},
         sprite::Sprite,
+        static_sprite_batch::{StaticSpriteBatch, StaticSpriteBatchBundle},
         texture_atlas::{TextureAtlas, TextureAtlasSprite},
         ColorMaterial, ColorMesh2dBundle, TextureAtlasBuilder,
     };
 }
 
+pub use animated_sprite::*;
+#[cfg(feature = "aseprite")]
+pub use aseprite_loader::*;
 pub use bundle::*;
+pub use dynamic_texture_atlas::*;
 pub use dynamic_texture_atlas_builder::*;
 pub use mesh2d::*;
+pub use nine_slice::*;
+pub use outline::*;
 pub use render::*;
+pub use spatial_index::*;
 pub use sprite::*;
+pub use static_sprite_batch::*;
 pub use texture_atlas::*;
 pub use texture_atlas_builder::*;
+#[cfg(feature = "serialize")]
+pub use texture_atlas_export::*;
+#[cfg(feature = "serialize")]
+pub use texture_atlas_loader::*;
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Assets, Handle, HandleUntyped};
 use bevy_core_pipeline::core_2d::Transparent2d;
 use bevy_ecs::prelude::*;
+use bevy_math::Rect;
 use bevy_reflect::TypeUuid;
 use bevy_render::{
     mesh::Mesh,
@@ -42,6 +71,7 @@ use bevy_render::{
     view::{NoFrustumCulling, VisibilitySystems},
     ExtractSchedule, Render, RenderApp, RenderSet,
 };
+use bevy_utils::HashMap;
 
 #[derive(Default)]
 pub struct SpritePlugin;
@@ -49,9 +79,15 @@ pub struct SpritePlugin;
 pub const SPRITE_SHADER_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 2763343953151597127);
 
+/// The path of a 1 pixel white [`Image`], the same as the default image of sprites, to load it
+/// with the `AssetServer` from anywhere.
+pub const WHITE_TEXTURE_PATH: &str = "embedded://bevy_sprite/white.png";
+
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub enum SpriteSystem {
     ExtractSprites,
+    AnimateSprites,
+    UpdateSpatialIndex,
 }
 
 impl Plugin for SpritePlugin {
@@ -61,17 +97,38 @@ impl Plugin for SpritePlugin {
         shaders.set_untracked(SPRITE_SHADER_HANDLE, sprite_shader);
         app.add_asset::<TextureAtlas>()
             .register_asset_reflect::<TextureAtlas>()
+            // NOTE: used by TextureAtlas, to store it in scenes
+            .register_type::<Vec<Rect>>()
+            .register_type::<Option<HashMap<Handle<Image>, usize>>>()
+            .register_type::<HashMap<Handle<Image>, usize>>()
+            .register_type::<Vec<Option<u64>>>()
+            .register_type::<Option<u64>>()
             .register_type::<Sprite>()
             .register_type::<TextureAtlasSprite>()
             .register_type::<Anchor>()
+            .register_type::<NineSliceSprite>()
+            .register_type::<StaticSpriteBatch>()
             .register_type::<Mesh2dHandle>()
+            .register_type::<AnimatedSprite>()
+            .register_type::<AnimatedSpriteState>()
+            .register_builtin_asset(WHITE_TEXTURE_PATH, Image::default())
+            .add_event::<AnimationFinished>()
             .add_plugin(Mesh2dRenderPlugin)
             .add_plugin(ColorMaterialPlugin)
+            .add_plugin(SpriteOutlinePlugin)
+            .add_systems(Update, animate_sprites.in_set(SpriteSystem::AnimateSprites))
             .add_systems(
                 PostUpdate,
                 calculate_bounds_2d.in_set(VisibilitySystems::CalculateBounds),
             );
 
+        #[cfg(feature = "aseprite")]
+        app.add_asset::<SpriteSheetAnimations>()
+            .init_asset_loader::<AsepriteSheetLoader>();
+        #[cfg(feature = "serialize")]
+        app.add_asset::<AtlasSprite>()
+            .init_asset_loader::<TextureAtlasManifestLoader>();
+
         if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ImageBindGroups>()
@@ -79,19 +136,31 @@ impl Plugin for SpritePlugin {
                 .init_resource::<SpriteMeta>()
                 .init_resource::<ExtractedSprites>()
                 .init_resource::<SpriteAssetEvents>()
+                .init_resource::<StaticSpriteBatches>()
                 .add_render_command::<Transparent2d, DrawSprite>()
+                .add_render_command::<Transparent2d, DrawStaticSprites>()
                 .add_systems(
                     ExtractSchedule,
                     (
                         extract_sprites.in_set(SpriteSystem::ExtractSprites),
+                        extract_nine_slice_sprites
+                            .in_set(SpriteSystem::ExtractSprites)
+                            .after(extract_sprites),
                         extract_sprite_events,
+                        extract_static_sprite_batches,
                     ),
                 )
                 .add_systems(
                     Render,
-                    queue_sprites
-                        .in_set(RenderSet::Queue)
-                        .ambiguous_with(queue_material2d_meshes::<ColorMaterial>),
+                    (
+                        remove_changed_image_bind_groups.in_set(RenderSet::Prepare),
+                        queue_sprites
+                            .in_set(RenderSet::Queue)
+                            .ambiguous_with(queue_material2d_meshes::<ColorMaterial>),
+                        queue_static_sprite_batches
+                            .in_set(RenderSet::Queue)
+                            .after(queue_sprites),
+                    ),
                 );
         };
     }
@@ -474,6 +474,7 @@ pub fn extract_materials_2d<M: Material2d>(
                 changed_assets.remove(handle);
                 removed.push(handle.clone_weak());
             }
+            AssetEvent::Unused { .. } => {}
         }
     }
 
@@ -0,0 +1,276 @@
+use crate::Anchor;
+use bevy_asset::Handle;
+use bevy_ecs::{bundle::Bundle, component::Component, reflect::ReflectComponent};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_render::{
+    color::Color,
+    texture::{Image, DEFAULT_IMAGE_HANDLE},
+    view::{ComputedVisibility, Visibility},
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// The size of each edge of a nine-sliced texture like a [`NineSliceSprite`], in texture pixels.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+pub struct BorderRect {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
+}
+
+impl BorderRect {
+    /// Creates a border with the same size on every edge.
+    pub const fn all(size: f32) -> Self {
+        Self {
+            left: size,
+            right: size,
+            top: size,
+            bottom: size,
+        }
+    }
+
+    /// Creates a border with `horizontal` on the left and right edges and `vertical` on the top
+    /// and bottom edges.
+    pub const fn axes(horizontal: f32, vertical: f32) -> Self {
+        Self {
+            left: horizontal,
+            right: horizontal,
+            top: vertical,
+            bottom: vertical,
+        }
+    }
+
+    /// Splits a texture of `image_size` with these borders into the quads needed to draw it at
+    /// `draw_size`, filling the center according to `center_mode`.
+    ///
+    /// If the borders don't fit in `draw_size`, they are scaled down to fit.
+    pub fn compute_slices(
+        &self,
+        center_mode: NineSliceCenter,
+        image_size: Vec2,
+        draw_size: Vec2,
+    ) -> Vec<TextureSlice> {
+        let fit = |start: f32, end: f32, size: f32| {
+            if start + end > size {
+                size / (start + end)
+            } else {
+                1.0
+            }
+        };
+        let scale = Vec2::new(
+            fit(self.left, self.right, draw_size.x),
+            fit(self.top, self.bottom, draw_size.y),
+        );
+        let texture_columns = [0.0, self.left, image_size.x - self.right, image_size.x];
+        let texture_rows = [0.0, self.top, image_size.y - self.bottom, image_size.y];
+        let draw_columns = [
+            0.0,
+            self.left * scale.x,
+            draw_size.x - self.right * scale.x,
+            draw_size.x,
+        ];
+        let draw_rows = [
+            0.0,
+            self.top * scale.y,
+            draw_size.y - self.bottom * scale.y,
+            draw_size.y,
+        ];
+
+        let mut slices = Vec::with_capacity(9);
+        for row in 0..3 {
+            for column in 0..3 {
+                let texture_rect = Rect::new(
+                    texture_columns[column],
+                    texture_rows[row],
+                    texture_columns[column + 1],
+                    texture_rows[row + 1],
+                );
+                // Cells are laid out from the top left corner, in the same direction as the texture
+                let draw_rect = Rect::new(
+                    draw_columns[column],
+                    draw_rows[row],
+                    draw_columns[column + 1],
+                    draw_rows[row + 1],
+                );
+                if texture_rect.is_empty() || draw_rect.is_empty() {
+                    continue;
+                }
+                let is_center = row == 1 && column == 1;
+                match center_mode {
+                    NineSliceCenter::Hidden if is_center => {}
+                    NineSliceCenter::Tile if is_center => {
+                        tile(texture_rect, draw_rect, draw_size, &mut slices);
+                    }
+                    _ => slices.push(slice(texture_rect, draw_rect, draw_size)),
+                }
+            }
+        }
+        slices
+    }
+}
+
+/// How the center cell of a [`NineSliceSprite`] fills the space between the borders.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum NineSliceCenter {
+    /// Repeat the center of the texture at its original size, cropping the last row and column
+    Tile,
+    /// Stretch the center of the texture to fill the space
+    #[default]
+    Stretch,
+    /// Don't draw the center, leaving only a frame
+    Hidden,
+}
+
+/// A sprite drawn as a 3×3 grid, where the corners keep their size, the edges stretch along one
+/// axis, and the center fills the rest according to [`NineSliceCenter`].
+///
+/// This is useful for panels and buttons that need to scale without distorting their borders.
+/// It can be drawn in the world with a [`NineSliceSpriteBundle`], or as the background of a UI
+/// node by adding it to the node, in which case the node size is used instead of [`Self::size`].
+#[derive(Component, Debug, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct NineSliceSprite {
+    pub texture: Handle<Image>,
+    /// The size of the borders in the texture
+    pub border: BorderRect,
+    pub center_mode: NineSliceCenter,
+    /// The sprite's color tint
+    pub color: Color,
+    /// The drawn size of the sprite in the world
+    pub size: Vec2,
+    /// [`Anchor`] point of the sprite in the world
+    pub anchor: Anchor,
+}
+
+impl Default for NineSliceSprite {
+    fn default() -> Self {
+        Self {
+            texture: DEFAULT_IMAGE_HANDLE.typed(),
+            border: BorderRect::default(),
+            center_mode: NineSliceCenter::default(),
+            color: Color::WHITE,
+            size: Vec2::ONE,
+            anchor: Anchor::default(),
+        }
+    }
+}
+
+/// A cell of a [`NineSliceSprite`], see [`NineSliceSprite::compute_slices`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureSlice {
+    /// The area of the texture to sample, in pixels
+    pub texture_rect: Rect,
+    /// The drawn size of the cell
+    pub draw_size: Vec2,
+    /// The center of the cell relative to the center of the sprite, with `Y` pointing up
+    pub offset: Vec2,
+}
+
+impl NineSliceSprite {
+    /// Splits a texture of `image_size` into the quads needed to draw it at `draw_size`.
+    ///
+    /// If the borders don't fit in `draw_size`, they are scaled down to fit.
+    pub fn compute_slices(&self, image_size: Vec2, draw_size: Vec2) -> Vec<TextureSlice> {
+        self.border
+            .compute_slices(self.center_mode, image_size, draw_size)
+    }
+}
+
+fn slice(texture_rect: Rect, draw_rect: Rect, draw_size: Vec2) -> TextureSlice {
+    let center = draw_rect.center() - draw_size / 2.0;
+    TextureSlice {
+        texture_rect,
+        draw_size: draw_rect.size(),
+        offset: Vec2::new(center.x, -center.y),
+    }
+}
+
+fn tile(texture_rect: Rect, draw_rect: Rect, draw_size: Vec2, slices: &mut Vec<TextureSlice>) {
+    let tile_size = texture_rect.size();
+    let mut y = draw_rect.min.y;
+    while y < draw_rect.max.y {
+        let height = tile_size.y.min(draw_rect.max.y - y);
+        let mut x = draw_rect.min.x;
+        while x < draw_rect.max.x {
+            let width = tile_size.x.min(draw_rect.max.x - x);
+            let size = Vec2::new(width, height);
+            slices.push(slice(
+                Rect::from_corners(texture_rect.min, texture_rect.min + size),
+                Rect::from_corners(Vec2::new(x, y), Vec2::new(x, y) + size),
+                draw_size,
+            ));
+            x += width;
+        }
+        y += height;
+    }
+}
+
+/// A Bundle of components for drawing a [`NineSliceSprite`] in the world
+#[derive(Bundle, Clone, Default)]
+pub struct NineSliceSpriteBundle {
+    pub sprite: NineSliceSprite,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(center_mode: NineSliceCenter) -> NineSliceSprite {
+        NineSliceSprite {
+            border: BorderRect::all(4.0),
+            center_mode,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stretch_keeps_corner_size() {
+        let slices =
+            sprite(NineSliceCenter::Stretch).compute_slices(Vec2::splat(16.0), Vec2::splat(100.0));
+        assert_eq!(slices.len(), 9);
+
+        let top_left = slices[0];
+        assert_eq!(top_left.texture_rect, Rect::new(0.0, 0.0, 4.0, 4.0));
+        assert_eq!(top_left.draw_size, Vec2::splat(4.0));
+        assert_eq!(top_left.offset, Vec2::new(-48.0, 48.0));
+
+        let center = slices[4];
+        assert_eq!(center.texture_rect, Rect::new(4.0, 4.0, 12.0, 12.0));
+        assert_eq!(center.draw_size, Vec2::splat(92.0));
+        assert_eq!(center.offset, Vec2::ZERO);
+    }
+
+    #[test]
+    fn hidden_and_tiled_center() {
+        let image_size = Vec2::splat(16.0);
+        let draw_size = Vec2::new(28.0, 16.0);
+        let hidden = sprite(NineSliceCenter::Hidden).compute_slices(image_size, draw_size);
+        assert_eq!(hidden.len(), 8);
+
+        // A 20x8 center is covered by two full 8x8 tiles and a cropped 4x8 tile
+        let tiled = sprite(NineSliceCenter::Tile).compute_slices(image_size, draw_size);
+        assert_eq!(tiled.len(), 11);
+        let last_tile = tiled[6];
+        assert_eq!(last_tile.texture_rect, Rect::new(4.0, 4.0, 8.0, 12.0));
+        assert_eq!(last_tile.draw_size, Vec2::new(4.0, 8.0));
+        assert_eq!(last_tile.offset, Vec2::new(8.0, 0.0));
+    }
+
+    #[test]
+    fn borders_shrink_to_fit() {
+        let slices =
+            sprite(NineSliceCenter::Stretch).compute_slices(Vec2::splat(16.0), Vec2::splat(4.0));
+        // The center and edges collapse, leaving the four scaled down corners
+        assert_eq!(slices.len(), 4);
+        assert!(slices
+            .iter()
+            .all(|slice| slice.draw_size == Vec2::splat(2.0)));
+    }
+}
@@ -0,0 +1,239 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, AddAsset, Assets, Handle, HandleUntyped};
+use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_math::{Rect, Vec2, Vec4};
+use bevy_reflect::{prelude::*, TypeUuid};
+use bevy_render::{
+    color::Color,
+    mesh::{shape, Mesh},
+    prelude::Shader,
+    render_asset::RenderAssets,
+    render_resource::*,
+    texture::Image,
+};
+use bevy_transform::{components::Transform, TransformSystem};
+
+use crate::{Material2d, Material2dPlugin, MaterialMesh2dBundle, Mesh2dHandle, Sprite};
+
+pub const SPRITE_OUTLINE_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7329318145685721749);
+
+const SPRITE_OUTLINE_QUAD_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 4798306933727592445);
+
+/// How far in front of its sprite an outline is drawn.
+const SPRITE_OUTLINE_Z_OFFSET: f32 = 0.001;
+
+/// Draws a [`SpriteOutline`] around sprites.
+#[derive(Default)]
+pub struct SpriteOutlinePlugin;
+
+impl Plugin for SpriteOutlinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SPRITE_OUTLINE_SHADER_HANDLE,
+            "sprite_outline.wgsl",
+            Shader::from_wgsl
+        );
+        if let Some(mut meshes) = app.world.get_resource_mut::<Assets<Mesh>>() {
+            meshes.set_untracked(
+                SPRITE_OUTLINE_QUAD_HANDLE,
+                Mesh::from(shape::Quad::new(Vec2::ONE)),
+            );
+        }
+
+        app.add_plugin(Material2dPlugin::<SpriteOutlineMaterial>::default())
+            .register_asset_reflect::<SpriteOutlineMaterial>()
+            .register_type::<SpriteOutline>()
+            .add_systems(
+                PostUpdate,
+                update_sprite_outlines.before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Where a [`SpriteOutline`] is drawn relative to the edge of the sprite.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+pub enum OutlineMode {
+    /// Inside the sprite, over its edge pixels
+    Inner,
+    /// Outside the sprite, around its silhouette
+    #[default]
+    Outer,
+    /// Straddling the edge, half inside and half outside
+    Center,
+}
+
+/// Draws an outline following the silhouette of a [`Sprite`], based on its texture's alpha.
+///
+/// The outline is drawn by a child entity, slightly in front of the sprite.
+#[derive(Component, Debug, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct SpriteOutline {
+    pub color: Color,
+    /// The width of the outline, in the same units as the sprite size
+    pub thickness: f32,
+    pub mode: OutlineMode,
+}
+
+impl Default for SpriteOutline {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            thickness: 1.0,
+            mode: OutlineMode::default(),
+        }
+    }
+}
+
+/// The child entity drawing the [`SpriteOutline`] of this sprite.
+#[derive(Component, Debug)]
+pub struct SpriteOutlineEntity(pub Entity);
+
+/// A [2d material](Material2d) that draws the outline of a sprite around its texture's alpha.
+#[derive(AsBindGroup, Reflect, FromReflect, Debug, Clone, PartialEq, TypeUuid)]
+#[reflect(Debug)]
+#[uuid = "5d1f9ac2-3c3e-4fd4-9a4b-8c4a51f2e7b0"]
+#[uniform(0, SpriteOutlineMaterialUniform)]
+pub struct SpriteOutlineMaterial {
+    pub color: Color,
+    pub mode: OutlineMode,
+    /// The outline thickness, relative to the sprite size
+    pub thickness: Vec2,
+    /// The area of the texture the sprite samples, in UV coordinates. `min` is greater than
+    /// `max` on flipped axes.
+    pub uv_rect: Rect,
+    #[texture(1)]
+    #[sampler(2)]
+    pub texture: Handle<Image>,
+}
+
+/// The GPU representation of the uniform data of a [`SpriteOutlineMaterial`].
+#[derive(Clone, Default, ShaderType)]
+pub struct SpriteOutlineMaterialUniform {
+    pub color: Vec4,
+    pub uv_min: Vec2,
+    pub uv_max: Vec2,
+    pub thickness: Vec2,
+    pub mode: u32,
+}
+
+impl AsBindGroupShaderType<SpriteOutlineMaterialUniform> for SpriteOutlineMaterial {
+    fn as_bind_group_shader_type(
+        &self,
+        _images: &RenderAssets<Image>,
+    ) -> SpriteOutlineMaterialUniform {
+        // NOTE: These must match the constants in bevy_sprite/src/outline/sprite_outline.wgsl!
+        let mode = match self.mode {
+            OutlineMode::Inner => 0,
+            OutlineMode::Outer => 1,
+            OutlineMode::Center => 2,
+        };
+        SpriteOutlineMaterialUniform {
+            color: self.color.as_linear_rgba_f32().into(),
+            uv_min: self.uv_rect.min,
+            uv_max: self.uv_rect.max,
+            thickness: self.thickness,
+            mode,
+        }
+    }
+}
+
+impl Material2d for SpriteOutlineMaterial {
+    fn fragment_shader() -> ShaderRef {
+        SPRITE_OUTLINE_SHADER_HANDLE.typed().into()
+    }
+}
+
+/// Spawns, updates and removes the child entities drawing each [`SpriteOutline`].
+pub fn update_sprite_outlines(
+    mut commands: Commands,
+    images: Res<Assets<Image>>,
+    mut materials: ResMut<Assets<SpriteOutlineMaterial>>,
+    sprites: Query<(
+        Entity,
+        &Sprite,
+        &Handle<Image>,
+        &SpriteOutline,
+        Option<&SpriteOutlineEntity>,
+    )>,
+    mut outlines: Query<(&mut Transform, &Handle<SpriteOutlineMaterial>)>,
+    outline_entities: Query<&SpriteOutlineEntity, Without<SpriteOutline>>,
+    mut removed: RemovedComponents<SpriteOutline>,
+) {
+    for entity in removed.iter() {
+        if let Ok(SpriteOutlineEntity(outline_entity)) = outline_entities.get(entity) {
+            commands.entity(*outline_entity).despawn_recursive();
+            commands.entity(entity).remove::<SpriteOutlineEntity>();
+        }
+    }
+
+    for (entity, sprite, texture, outline, outline_entity) in &sprites {
+        // The sprite size is only known once its image is loaded
+        let Some(image) = images.get(texture) else {
+            continue;
+        };
+        let image_size = image.size();
+        let rect = sprite.rect.unwrap_or(Rect {
+            min: Vec2::ZERO,
+            max: image_size,
+        });
+        let size = sprite.custom_size.unwrap_or_else(|| rect.size());
+        if size.x <= 0.0 || size.y <= 0.0 {
+            continue;
+        }
+
+        let mut uv_rect = Rect {
+            min: rect.min / image_size,
+            max: rect.max / image_size,
+        };
+        if sprite.flip_x {
+            std::mem::swap(&mut uv_rect.min.x, &mut uv_rect.max.x);
+        }
+        if sprite.flip_y {
+            std::mem::swap(&mut uv_rect.min.y, &mut uv_rect.max.y);
+        }
+        let material = SpriteOutlineMaterial {
+            color: outline.color,
+            mode: outline.mode,
+            thickness: outline.thickness / size,
+            uv_rect,
+            texture: texture.clone_weak(),
+        };
+        // The quad covers the sprite, extended by the thickness on every side
+        let transform = Transform::from_translation(
+            (-sprite.anchor.as_vec() * size).extend(SPRITE_OUTLINE_Z_OFFSET),
+        )
+        .with_scale((size + 2.0 * outline.thickness).extend(1.0));
+
+        match outline_entity.and_then(|SpriteOutlineEntity(e)| outlines.get_mut(*e).ok()) {
+            Some((mut outline_transform, material_handle)) => {
+                if *outline_transform != transform {
+                    *outline_transform = transform;
+                }
+                // Only touch the material when it changes, to avoid preparing it every frame
+                if materials.get(material_handle) != Some(&material) {
+                    if let Some(current) = materials.get_mut(material_handle) {
+                        *current = material;
+                    }
+                }
+            }
+            None => {
+                let outline_entity = commands
+                    .spawn(MaterialMesh2dBundle {
+                        mesh: Mesh2dHandle(SPRITE_OUTLINE_QUAD_HANDLE.typed()),
+                        material: materials.add(material),
+                        transform,
+                        ..Default::default()
+                    })
+                    .id();
+                commands
+                    .entity(entity)
+                    .add_child(outline_entity)
+                    .insert(SpriteOutlineEntity(outline_entity));
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+#import bevy_sprite::mesh2d_types
+#import bevy_sprite::mesh2d_view_bindings
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+struct SpriteOutlineMaterial {
+    color: vec4<f32>,
+    uv_min: vec2<f32>,
+    uv_max: vec2<f32>,
+    // The outline thickness, relative to the sprite size
+    thickness: vec2<f32>,
+    mode: u32,
+};
+const OUTLINE_MODE_INNER: u32 = 0u;
+const OUTLINE_MODE_OUTER: u32 = 1u;
+const OUTLINE_MODE_CENTER: u32 = 2u;
+
+// Samples are taken on rings around each fragment to find the nearby silhouette
+const OUTLINE_RINGS: i32 = 2;
+const OUTLINE_RING_SAMPLES: i32 = 16;
+const TAU: f32 = 6.28318530718;
+
+@group(1) @binding(0)
+var<uniform> material: SpriteOutlineMaterial;
+@group(1) @binding(1)
+var texture: texture_2d<f32>;
+@group(1) @binding(2)
+var texture_sampler: sampler;
+
+@group(2) @binding(0)
+var<uniform> mesh: Mesh2d;
+
+struct FragmentInput {
+    #import bevy_sprite::mesh2d_vertex_output
+};
+
+// Returns the sprite alpha at `position`, which goes from 0 to 1 across the sprite
+fn sprite_alpha(position: vec2<f32>) -> f32 {
+    if (any(position < vec2<f32>(0.0)) || any(position > vec2<f32>(1.0))) {
+        return 0.0;
+    }
+    let uv = mix(material.uv_min, material.uv_max, position);
+    return textureSampleLevel(texture, texture_sampler, uv, 0.0).a;
+}
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    // The quad extends past the sprite by the thickness on every side
+    let position = in.uv * (1.0 + 2.0 * material.thickness) - material.thickness;
+    var radius = material.thickness;
+    if (material.mode == OUTLINE_MODE_CENTER) {
+        radius = radius * 0.5;
+    }
+
+    let alpha = sprite_alpha(position);
+    var max_alpha = alpha;
+    var min_alpha = alpha;
+    for (var ring = 1; ring <= OUTLINE_RINGS; ring = ring + 1) {
+        let ring_radius = radius * f32(ring) / f32(OUTLINE_RINGS);
+        for (var i = 0; i < OUTLINE_RING_SAMPLES; i = i + 1) {
+            let angle = f32(i) * TAU / f32(OUTLINE_RING_SAMPLES);
+            let neighbor = sprite_alpha(position + vec2<f32>(cos(angle), sin(angle)) * ring_radius);
+            max_alpha = max(max_alpha, neighbor);
+            min_alpha = min(min_alpha, neighbor);
+        }
+    }
+
+    // Outside the sprite near its silhouette, or inside it near transparent pixels
+    let outer = max_alpha * (1.0 - alpha);
+    let inner = alpha * (1.0 - min_alpha);
+    var coverage = max(outer, inner);
+    if (material.mode == OUTLINE_MODE_INNER) {
+        coverage = inner;
+    } else if (material.mode == OUTLINE_MODE_OUTER) {
+        coverage = outer;
+    }
+
+    var output_color = vec4<f32>(material.color.rgb, material.color.a * coverage);
+#ifdef TONEMAP_IN_SHADER
+    output_color = tone_mapping(output_color);
+#endif
+    return output_color;
+}
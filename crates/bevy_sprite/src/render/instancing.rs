@@ -0,0 +1,425 @@
+use super::{
+    map_sprite_chunks, push_tonemapping_shader_defs, queue_image_bind_group, queue_sprites,
+    sort_extracted_sprites, view_pipeline_key, ExtractedSprite, ExtractedSprites, ImageBindGroups,
+    SetSpriteTextureBindGroup, SetSpriteViewBindGroup, SpriteBatch, SpritePipeline,
+    SpritePipelineKey, QUAD_INDICES,
+};
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, Handle, HandleId, HandleUntyped};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemParamItem},
+};
+use bevy_math::{Mat4, Rect, Vec2};
+use bevy_reflect::{TypeUuid, Uuid};
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_phase::{
+        AddRenderCommand, BatchedPhaseItem, DrawFunctions, RenderCommand, RenderCommandResult,
+        RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{BevyDefault, Image},
+    view::{ExtractedView, Msaa, ViewTarget, VisibleEntities},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::FloatOrd;
+use bytemuck::{Pod, Zeroable};
+use fixedbitset::FixedBitSet;
+
+pub const SPRITE_INSTANCED_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 3392965313961464139);
+
+/// Draws sprites with GPU instancing, instead of generating the vertices of every sprite on the
+/// CPU.
+///
+/// Each sprite only uploads its transform, texture rect, color and flip flags, and every run of
+/// sprites sharing a texture (such as the sprites of a [`TextureAtlas`](crate::TextureAtlas)) is
+/// drawn with a single instanced draw call, whatever their color. This replaces the default
+/// sprite rendering for all sprites, and must be added after the [`SpritePlugin`](crate::SpritePlugin).
+#[derive(Default)]
+pub struct SpriteInstancingPlugin;
+
+impl Plugin for SpriteInstancingPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            SPRITE_INSTANCED_SHADER_HANDLE,
+            "sprite_instanced.wgsl",
+            Shader::from_wgsl
+        );
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .init_resource::<SpecializedRenderPipelines<SpriteInstancedPipeline>>()
+                .init_resource::<SpriteInstanceMeta>()
+                .add_render_command::<Transparent2d, DrawSpriteInstanced>()
+                .add_systems(
+                    Render,
+                    queue_instanced_sprites
+                        .in_set(RenderSet::Queue)
+                        .before(queue_sprites),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<SpriteInstancedPipeline>();
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SpriteInstancedPipeline {
+    view_layout: BindGroupLayout,
+    material_layout: BindGroupLayout,
+    quad_indices: Buffer,
+}
+
+impl FromWorld for SpriteInstancedPipeline {
+    fn from_world(world: &mut World) -> Self {
+        world.init_resource::<SpritePipeline>();
+        let sprite_pipeline = world.resource::<SpritePipeline>();
+        let render_device = world.resource::<RenderDevice>();
+
+        let quad_indices = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("sprite_instanced_quad_indices"),
+            contents: bytemuck::cast_slice(&QUAD_INDICES.map(|i| i as u32)),
+            usage: BufferUsages::INDEX,
+        });
+
+        SpriteInstancedPipeline {
+            view_layout: sprite_pipeline.view_layout.clone(),
+            material_layout: sprite_pipeline.material_layout.clone(),
+            quad_indices,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for SpriteInstancedPipeline {
+    type Key = SpritePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let instance_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Instance,
+            vec![
+                // transform
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                VertexFormat::Float32x4,
+                // uv_rect
+                VertexFormat::Float32x4,
+                // color
+                VertexFormat::Float32x4,
+                // flags
+                VertexFormat::Uint32,
+            ],
+        );
+
+        let mut shader_defs = Vec::new();
+        push_tonemapping_shader_defs(key, &mut shader_defs);
+
+        let format = match key.contains(SpritePipelineKey::HDR) {
+            true => ViewTarget::TEXTURE_FORMAT_HDR,
+            false => TextureFormat::bevy_default(),
+        };
+
+        RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: SPRITE_INSTANCED_SHADER_HANDLE.typed::<Shader>(),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![instance_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: SPRITE_INSTANCED_SHADER_HANDLE.typed::<Shader>(),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![self.view_layout.clone(), self.material_layout.clone()],
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: key.msaa_samples(),
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("sprite_instanced_pipeline".into()),
+            push_constant_ranges: Vec::new(),
+        }
+    }
+}
+
+// NOTE: These must match the constants in bevy_sprite/src/render/sprite_instanced.wgsl!
+const SPRITE_INSTANCE_FLIP_X: u32 = 1 << 0;
+const SPRITE_INSTANCE_FLIP_Y: u32 = 1 << 1;
+
+/// The per-instance data of a sprite drawn by the [`SpriteInstancingPlugin`].
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Pod, Zeroable)]
+struct SpriteInstance {
+    /// The columns of the sprite's transform, scaled to its size and offset by its anchor
+    transform: [[f32; 4]; 4],
+    /// The area of the texture to sample, as the UVs of its top left and bottom right corners
+    uv_rect: [f32; 4],
+    color: [f32; 4],
+    flags: u32,
+}
+
+impl SpriteInstance {
+    fn new(sprite: &ExtractedSprite, image_size: Vec2) -> Self {
+        // By default, the sprite covers the whole texture at its size
+        let mut uv_rect = Rect::new(0.0, 0.0, 1.0, 1.0);
+        let mut quad_size = image_size;
+        if let Some(rect) = sprite.rect {
+            uv_rect = Rect {
+                min: rect.min / image_size,
+                max: rect.max / image_size,
+            };
+            quad_size = rect.size();
+        }
+        if let Some(custom_size) = sprite.custom_size {
+            quad_size = custom_size;
+        }
+
+        let transform = sprite.transform.compute_matrix()
+            * Mat4::from_translation((-sprite.anchor * quad_size).extend(0.0))
+            * Mat4::from_scale(quad_size.extend(1.0));
+        let mut flags = 0;
+        if sprite.flip_x {
+            flags |= SPRITE_INSTANCE_FLIP_X;
+        }
+        if sprite.flip_y {
+            flags |= SPRITE_INSTANCE_FLIP_Y;
+        }
+        SpriteInstance {
+            transform: transform.to_cols_array_2d(),
+            uv_rect: [uv_rect.min.x, uv_rect.min.y, uv_rect.max.x, uv_rect.max.y],
+            color: sprite.color.as_linear_rgba_f32(),
+            flags,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct SpriteInstanceMeta {
+    instances: BufferVec<SpriteInstance>,
+}
+
+impl Default for SpriteInstanceMeta {
+    fn default() -> Self {
+        Self {
+            instances: BufferVec::new(BufferUsages::VERTEX),
+        }
+    }
+}
+
+/// Queues every extracted sprite for instanced drawing, leaving none for [`queue_sprites`].
+#[allow(clippy::too_many_arguments)]
+pub fn queue_instanced_sprites(
+    mut commands: Commands,
+    mut view_entities: Local<FixedBitSet>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut instance_meta: ResMut<SpriteInstanceMeta>,
+    sprite_pipeline: Res<SpritePipeline>,
+    instanced_pipeline: Res<SpriteInstancedPipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpriteInstancedPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    mut image_bind_groups: ResMut<ImageBindGroups>,
+    gpu_images: Res<RenderAssets<Image>>,
+    msaa: Res<Msaa>,
+    mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut views: Query<(
+        &mut RenderPhase<Transparent2d>,
+        &VisibleEntities,
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) {
+    instance_meta.instances.clear();
+
+    let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+    let draw_sprite_function = draw_functions.read().id::<DrawSpriteInstanced>();
+    let extracted_sprites = &mut extracted_sprites.sprites;
+    sort_extracted_sprites(extracted_sprites);
+    let image_bind_groups = &mut *image_bind_groups;
+
+    let mut index = 0;
+    // The sprites queued for a view with the size of their image, whose instances are computed
+    // in parallel once every phase item of the view is added
+    let mut sprites = Vec::new();
+    for (mut transparent_phase, visible_entities, view, tonemapping, dither) in &mut views {
+        let view_key = view_pipeline_key(view, tonemapping, dither) | msaa_key;
+        let pipeline = pipelines.specialize(&pipeline_cache, &instanced_pipeline, view_key);
+
+        view_entities.clear();
+        view_entities.extend(visible_entities.entities.iter().map(|e| e.index() as usize));
+        transparent_phase.items.reserve(extracted_sprites.len());
+
+        // Impossible starting values that will be replaced on the first iteration
+        let mut current_batch = SpriteBatch {
+            image_handle_id: HandleId::Id(Uuid::nil(), u64::MAX),
+            colored: false,
+        };
+        let mut current_batch_entity = Entity::PLACEHOLDER;
+        let mut current_image_size = Vec2::ZERO;
+        // Each sprite gets its own phase item with a range of a single instance. Successive items
+        // sharing a batch entity are merged by `batch_phase_system()`, into one draw call.
+        for extracted_sprite in extracted_sprites.iter() {
+            if !view_entities.contains(extracted_sprite.entity.index() as usize) {
+                continue;
+            }
+            // The color is part of each instance, so it doesn't split batches
+            let new_batch = SpriteBatch {
+                image_handle_id: extracted_sprite.image_handle_id,
+                colored: false,
+            };
+            if new_batch != current_batch {
+                // Skip this item if the texture is not ready
+                let Some(gpu_image) = gpu_images.get(&Handle::weak(new_batch.image_handle_id))
+                else {
+                    continue;
+                };
+                current_batch = new_batch;
+                current_image_size = gpu_image.size;
+                current_batch_entity = commands.spawn(current_batch).id();
+                queue_image_bind_group(
+                    image_bind_groups,
+                    &render_device,
+                    &sprite_pipeline,
+                    current_batch.image_handle_id,
+                    gpu_image,
+                );
+            }
+
+            sprites.push((extracted_sprite, current_image_size));
+            let item_start = index;
+            index += 1;
+
+            transparent_phase.add(Transparent2d {
+                draw_function: draw_sprite_function,
+                pipeline,
+                entity: current_batch_entity,
+                sort_key: FloatOrd(extracted_sprite.transform.translation().z),
+                batch_range: Some(item_start..index),
+            });
+        }
+
+        // The chunks keep the order of the sprites, so the instances match the batch ranges
+        let chunks = map_sprite_chunks(&sprites, |sprites| {
+            sprites
+                .iter()
+                .map(|&(sprite, image_size)| SpriteInstance::new(sprite, image_size))
+                .collect::<Vec<_>>()
+        });
+        for instance in chunks.into_iter().flatten() {
+            instance_meta.instances.push(instance);
+        }
+        sprites.clear();
+    }
+    instance_meta
+        .instances
+        .write_buffer(&render_device, &render_queue);
+
+    // Every sprite was queued, keep the allocation for the next frame
+    extracted_sprites.clear();
+}
+
+pub type DrawSpriteInstanced = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteTextureBindGroup<1>,
+    DrawSpriteInstanceBatch,
+);
+
+pub struct DrawSpriteInstanceBatch;
+impl<P: BatchedPhaseItem> RenderCommand<P> for DrawSpriteInstanceBatch {
+    type Param = (SRes<SpriteInstanceMeta>, SRes<SpriteInstancedPipeline>);
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = ();
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        _entity: (),
+        (instance_meta, instanced_pipeline): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(instances) = instance_meta.into_inner().instances.buffer() else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_vertex_buffer(0, instances.slice(..));
+        pass.set_index_buffer(
+            instanced_pipeline.into_inner().quad_indices.slice(..),
+            0,
+            IndexFormat::Uint32,
+        );
+        pass.draw_indexed(
+            0..QUAD_INDICES.len() as u32,
+            0,
+            item.batch_range().as_ref().unwrap().clone(),
+        );
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3;
+    use bevy_render::color::Color;
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn instance_from_atlas_sprite() {
+        let sprite = ExtractedSprite {
+            entity: Entity::PLACEHOLDER,
+            transform: GlobalTransform::from_translation(Vec3::new(10.0, 20.0, 1.0)),
+            color: Color::RED,
+            rect: Some(Rect::new(16.0, 0.0, 32.0, 8.0)),
+            custom_size: None,
+            image_handle_id: HandleId::default::<Image>(),
+            flip_x: true,
+            flip_y: false,
+            anchor: Vec2::new(-0.5, 0.5),
+        };
+        let instance = SpriteInstance::new(&sprite, Vec2::new(64.0, 32.0));
+        assert_eq!(instance.uv_rect, [0.25, 0.0, 0.5, 0.25]);
+        assert_eq!(instance.flags, SPRITE_INSTANCE_FLIP_X);
+        assert_eq!(instance.color, [1.0, 0.0, 0.0, 1.0]);
+
+        // The unit quad is scaled to the rect size, with its top left corner at the translation
+        let transform = Mat4::from_cols_array_2d(&instance.transform);
+        assert_eq!(
+            transform.transform_point3(Vec3::new(-0.5, 0.5, 0.0)),
+            Vec3::new(10.0, 20.0, 1.0)
+        );
+        assert_eq!(
+            transform.transform_point3(Vec3::new(0.5, -0.5, 0.0)),
+            Vec3::new(26.0, 12.0, 1.0)
+        );
+    }
+}
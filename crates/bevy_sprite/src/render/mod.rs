@@ -1,8 +1,14 @@
-use std::cmp::Ordering;
+mod instancing;
+mod static_batch;
+
+pub use instancing::*;
+pub use static_batch::*;
+
+use std::cell::Cell;
 
 use crate::{
     texture_atlas::{TextureAtlas, TextureAtlasSprite},
-    Sprite, SPRITE_SHADER_HANDLE,
+    NineSliceSprite, Sprite, StaticSpriteBatch, SPRITE_SHADER_HANDLE,
 };
 use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
 use bevy_core_pipeline::{
@@ -13,6 +19,7 @@ use bevy_ecs::{
     prelude::*,
     system::{lifetimeless::*, SystemParamItem, SystemState},
 };
+use bevy_hierarchy::Parent;
 use bevy_math::{Rect, Vec2};
 use bevy_reflect::Uuid;
 use bevy_render::{
@@ -25,7 +32,8 @@ use bevy_render::{
     render_resource::*,
     renderer::{RenderDevice, RenderQueue},
     texture::{
-        BevyDefault, DefaultImageSampler, GpuImage, Image, ImageSampler, TextureFormatPixelInfo,
+        BevyDefault, DefaultImageSampler, FailedImages, GpuImage, Image, ImageSampler,
+        TextureFormatPixelInfo,
     },
     view::{
         ComputedVisibility, ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniformOffset,
@@ -33,11 +41,13 @@ use bevy_render::{
     },
     Extract,
 };
-use bevy_transform::components::GlobalTransform;
+use bevy_tasks::ComputeTaskPool;
+use bevy_transform::components::{GlobalTransform, Transform};
 use bevy_utils::FloatOrd;
 use bevy_utils::HashMap;
 use bytemuck::{Pod, Zeroable};
 use fixedbitset::FixedBitSet;
+use thread_local::ThreadLocal;
 
 #[derive(Resource)]
 pub struct SpritePipeline {
@@ -55,41 +65,24 @@ impl FromWorld for SpritePipeline {
         )> = SystemState::new(world);
         let (render_device, default_sampler, render_queue) = system_state.get_mut(world);
 
-        let view_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            entries: &[BindGroupLayoutEntry {
-                binding: 0,
-                visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
-                ty: BindingType::Buffer {
-                    ty: BufferBindingType::Uniform,
-                    has_dynamic_offset: true,
-                    min_binding_size: Some(ViewUniform::min_size()),
-                },
-                count: None,
-            }],
-            label: Some("sprite_view_layout"),
-        });
-
-        let material_layout = render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
-            entries: &[
-                BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: ShaderStages::FRAGMENT,
-                    ty: BindingType::Texture {
-                        multisampled: false,
-                        sample_type: TextureSampleType::Float { filterable: true },
-                        view_dimension: TextureViewDimension::D2,
-                    },
-                    count: None,
-                },
-                BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: ShaderStages::FRAGMENT,
-                    ty: BindingType::Sampler(SamplerBindingType::Filtering),
-                    count: None,
-                },
-            ],
-            label: Some("sprite_material_layout"),
-        });
+        let view_layout = BindGroupLayoutBuilder::new()
+            .label("sprite_view_layout")
+            .uniform_buffer(
+                ShaderStages::VERTEX | ShaderStages::FRAGMENT,
+                true,
+                Some(ViewUniform::min_size()),
+            )
+            .build(&render_device);
+
+        let material_layout = BindGroupLayoutBuilder::new()
+            .label("sprite_material_layout")
+            .texture(
+                ShaderStages::FRAGMENT,
+                TextureSampleType::Float { filterable: true },
+                TextureViewDimension::D2,
+            )
+            .sampler(ShaderStages::FRAGMENT, SamplerBindingType::Filtering)
+            .build(&render_device);
         let dummy_white_gpu_image = {
             let image = Image::default();
             let texture = render_device.create_texture(&image.texture_descriptor);
@@ -222,35 +215,7 @@ impl SpecializedRenderPipeline for SpritePipeline {
             shader_defs.push("COLORED".into());
         }
 
-        if key.contains(SpritePipelineKey::TONEMAP_IN_SHADER) {
-            shader_defs.push("TONEMAP_IN_SHADER".into());
-
-            let method = key.intersection(SpritePipelineKey::TONEMAP_METHOD_RESERVED_BITS);
-
-            if method == SpritePipelineKey::TONEMAP_METHOD_NONE {
-                shader_defs.push("TONEMAP_METHOD_NONE".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_REINHARD {
-                shader_defs.push("TONEMAP_METHOD_REINHARD".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE {
-                shader_defs.push("TONEMAP_METHOD_REINHARD_LUMINANCE".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED {
-                shader_defs.push("TONEMAP_METHOD_ACES_FITTED".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_AGX {
-                shader_defs.push("TONEMAP_METHOD_AGX".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
-            {
-                shader_defs.push("TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC {
-                shader_defs.push("TONEMAP_METHOD_BLENDER_FILMIC".into());
-            } else if method == SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE {
-                shader_defs.push("TONEMAP_METHOD_TONY_MC_MAPFACE".into());
-            }
-
-            // Debanding is tied to tonemapping in the shader, cannot run without it.
-            if key.contains(SpritePipelineKey::DEBAND_DITHER) {
-                shader_defs.push("DEBAND_DITHER".into());
-            }
-        }
+        push_tonemapping_shader_defs(key, &mut shader_defs);
 
         let format = match key.contains(SpritePipelineKey::HDR) {
             true => ViewTarget::TEXTURE_FORMAT_HDR,
@@ -296,6 +261,38 @@ impl SpecializedRenderPipeline for SpritePipeline {
     }
 }
 
+/// Adds the shader defs for the tonemapping and debanding bits of `key`.
+fn push_tonemapping_shader_defs(key: SpritePipelineKey, shader_defs: &mut Vec<ShaderDefVal>) {
+    if key.contains(SpritePipelineKey::TONEMAP_IN_SHADER) {
+        shader_defs.push("TONEMAP_IN_SHADER".into());
+
+        let method = key.intersection(SpritePipelineKey::TONEMAP_METHOD_RESERVED_BITS);
+
+        if method == SpritePipelineKey::TONEMAP_METHOD_NONE {
+            shader_defs.push("TONEMAP_METHOD_NONE".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_REINHARD {
+            shader_defs.push("TONEMAP_METHOD_REINHARD".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE {
+            shader_defs.push("TONEMAP_METHOD_REINHARD_LUMINANCE".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED {
+            shader_defs.push("TONEMAP_METHOD_ACES_FITTED".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_AGX {
+            shader_defs.push("TONEMAP_METHOD_AGX".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM {
+            shader_defs.push("TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC {
+            shader_defs.push("TONEMAP_METHOD_BLENDER_FILMIC".into());
+        } else if method == SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE {
+            shader_defs.push("TONEMAP_METHOD_TONY_MC_MAPFACE".into());
+        }
+
+        // Debanding is tied to tonemapping in the shader, cannot run without it.
+        if key.contains(SpritePipelineKey::DEBAND_DITHER) {
+            shader_defs.push("DEBAND_DITHER".into());
+        }
+    }
+}
+
 #[derive(Component, Clone, Copy)]
 pub struct ExtractedSprite {
     pub entity: Entity,
@@ -342,13 +339,21 @@ pub fn extract_sprite_events(
             AssetEvent::Removed { handle } => AssetEvent::Removed {
                 handle: handle.clone_weak(),
             },
+            AssetEvent::Unused { handle } => AssetEvent::Unused {
+                handle: handle.clone_weak(),
+            },
         });
     }
 }
 
+/// Extracts the visible sprites, in parallel over the [`ComputeTaskPool`].
+///
+/// The sprites are extracted in no particular order, [`sort_extracted_sprites`] orders them.
 pub fn extract_sprites(
     mut extracted_sprites: ResMut<ExtractedSprites>,
+    mut thread_queues: Local<ThreadLocal<Cell<Vec<ExtractedSprite>>>>,
     texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    failed_images: Extract<Res<FailedImages>>,
     sprite_query: Extract<
         Query<(
             Entity,
@@ -356,6 +361,7 @@ pub fn extract_sprites(
             &Sprite,
             &GlobalTransform,
             &Handle<Image>,
+            Option<&Parent>,
         )>,
     >,
     atlas_query: Extract<
@@ -365,16 +371,31 @@ pub fn extract_sprites(
             &TextureAtlasSprite,
             &GlobalTransform,
             &Handle<TextureAtlas>,
+            Option<&Parent>,
         )>,
     >,
+    static_batch_query: Extract<Query<(), With<StaticSpriteBatch>>>,
 ) {
-    extracted_sprites.sprites.clear();
-    for (entity, visibility, sprite, transform, handle) in sprite_query.iter() {
-        if !visibility.is_visible() {
-            continue;
+    // Sprites in a static batch are drawn from its cached vertices instead
+    let is_static = |parent: Option<&Parent>| {
+        parent.is_some_and(|parent| static_batch_query.contains(parent.get()))
+    };
+
+    let extract_sprite = |(entity, visibility, sprite, transform, handle, parent): (
+        Entity,
+        &ComputedVisibility,
+        &Sprite,
+        &GlobalTransform,
+        &Handle<Image>,
+        Option<&Parent>,
+    )| {
+        if !visibility.is_visible() || is_static(parent) {
+            return None;
         }
         // PERF: we don't check in this function that the `Image` asset is ready, since it should be in most cases and hashing the handle is expensive
-        extracted_sprites.sprites.push(ExtractedSprite {
+        // Images that failed to load are replaced by the fallback image, if it's visible
+        let image_handle_id = failed_images.resolve(handle.id())?;
+        Some(ExtractedSprite {
             entity,
             color: sprite.color,
             transform: *transform,
@@ -383,15 +404,23 @@ pub fn extract_sprites(
             custom_size: sprite.custom_size,
             flip_x: sprite.flip_x,
             flip_y: sprite.flip_y,
-            image_handle_id: handle.id(),
+            image_handle_id,
             anchor: sprite.anchor.as_vec(),
-        });
-    }
-    for (entity, visibility, atlas_sprite, transform, texture_atlas_handle) in atlas_query.iter() {
-        if !visibility.is_visible() {
-            continue;
-        }
-        if let Some(texture_atlas) = texture_atlases.get(texture_atlas_handle) {
+        })
+    };
+    let extract_atlas_sprite =
+        |(entity, visibility, atlas_sprite, transform, texture_atlas_handle, parent): (
+            Entity,
+            &ComputedVisibility,
+            &TextureAtlasSprite,
+            &GlobalTransform,
+            &Handle<TextureAtlas>,
+            Option<&Parent>,
+        )| {
+            if !visibility.is_visible() || is_static(parent) {
+                return None;
+            }
+            let texture_atlas = texture_atlases.get(texture_atlas_handle)?;
             let rect = Some(
                 *texture_atlas
                     .textures
@@ -404,7 +433,7 @@ pub fn extract_sprites(
                         )
                     }),
             );
-            extracted_sprites.sprites.push(ExtractedSprite {
+            Some(ExtractedSprite {
                 entity,
                 color: atlas_sprite.color,
                 transform: *transform,
@@ -416,6 +445,71 @@ pub fn extract_sprites(
                 flip_y: atlas_sprite.flip_y,
                 image_handle_id: texture_atlas.texture.id(),
                 anchor: atlas_sprite.anchor.as_vec(),
+            })
+        };
+
+    extracted_sprites.sprites.clear();
+    // Iterating in parallel only adds overhead with a single thread
+    if ComputeTaskPool::get().thread_num() <= 1 {
+        let sprites = &mut extracted_sprites.sprites;
+        sprites.extend(sprite_query.iter().filter_map(extract_sprite));
+        sprites.extend(atlas_query.iter().filter_map(extract_atlas_sprite));
+        return;
+    }
+
+    // Each thread pushes to its own queue, so the sprites don't contend on a single Vec
+    let push = |sprite: Option<ExtractedSprite>| {
+        if let Some(sprite) = sprite {
+            let cell = thread_queues.get_or_default();
+            let mut queue = cell.take();
+            queue.push(sprite);
+            cell.set(queue);
+        }
+    };
+    sprite_query
+        .par_iter()
+        .for_each(|item| push(extract_sprite(item)));
+    atlas_query
+        .par_iter()
+        .for_each(|item| push(extract_atlas_sprite(item)));
+    for cell in thread_queues.iter_mut() {
+        extracted_sprites.sprites.append(cell.get_mut());
+    }
+}
+
+pub fn extract_nine_slice_sprites(
+    mut extracted_sprites: ResMut<ExtractedSprites>,
+    images: Extract<Res<Assets<Image>>>,
+    sprite_query: Extract<
+        Query<(
+            Entity,
+            &ComputedVisibility,
+            &NineSliceSprite,
+            &GlobalTransform,
+        )>,
+    >,
+) {
+    for (entity, visibility, sprite, transform) in sprite_query.iter() {
+        if !visibility.is_visible() {
+            continue;
+        }
+        // The texture size is needed to place the borders
+        let Some(image) = images.get(&sprite.texture) else {
+            continue;
+        };
+        let anchor_offset = -sprite.anchor.as_vec() * sprite.size;
+        for slice in sprite.compute_slices(image.size(), sprite.size) {
+            let offset = anchor_offset + slice.offset;
+            extracted_sprites.sprites.push(ExtractedSprite {
+                entity,
+                color: sprite.color,
+                transform: transform.mul_transform(Transform::from_translation(offset.extend(0.))),
+                rect: Some(slice.texture_rect),
+                custom_size: Some(slice.draw_size),
+                flip_x: false,
+                flip_y: false,
+                image_handle_id: sprite.texture.id(),
+                anchor: Vec2::ZERO,
             });
         }
     }
@@ -480,6 +574,181 @@ pub struct ImageBindGroups {
     values: HashMap<Handle<Image>, BindGroup>,
 }
 
+/// Computes the corner positions and UVs of the quad of `sprite`, in the order of
+/// `QUAD_VERTEX_POSITIONS`.
+fn sprite_quad(sprite: &ExtractedSprite, image_size: Vec2) -> ([[f32; 3]; 4], [Vec2; 4]) {
+    // Flipping only reorders the corners' UVs, so it never needs a new mesh
+    let mut uvs = QUAD_UVS;
+    if sprite.flip_x {
+        uvs = [uvs[1], uvs[0], uvs[3], uvs[2]];
+    }
+    if sprite.flip_y {
+        uvs = [uvs[3], uvs[2], uvs[1], uvs[0]];
+    }
+
+    // By default, the size of the quad is the size of the texture
+    let mut quad_size = image_size;
+
+    // If a rect is specified, adjust UVs and the size of the quad
+    if let Some(rect) = sprite.rect {
+        let rect_size = rect.size();
+        for uv in &mut uvs {
+            *uv = (rect.min + *uv * rect_size) / image_size;
+        }
+        quad_size = rect_size;
+    }
+
+    // Override the size if a custom one is specified
+    if let Some(custom_size) = sprite.custom_size {
+        quad_size = custom_size;
+    }
+
+    // Apply size and global transform
+    let positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
+        sprite
+            .transform
+            .transform_point(((quad_pos - sprite.anchor) * quad_size).extend(0.))
+            .into()
+    });
+    (positions, uvs)
+}
+
+/// Creates the key bits for rendering sprites to `view`, with its tonemapping and debanding.
+fn view_pipeline_key(
+    view: &ExtractedView,
+    tonemapping: Option<&Tonemapping>,
+    dither: Option<&DebandDither>,
+) -> SpritePipelineKey {
+    let mut view_key = SpritePipelineKey::from_hdr(view.hdr);
+
+    if !view.hdr {
+        if let Some(tonemapping) = tonemapping {
+            view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
+            view_key |= match tonemapping {
+                Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
+                Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
+                Tonemapping::ReinhardLuminance => {
+                    SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
+                }
+                Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
+                Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
+                Tonemapping::SomewhatBoringDisplayTransform => {
+                    SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
+                }
+                Tonemapping::TonyMcMapface => SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE,
+                Tonemapping::BlenderFilmic => SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC,
+            };
+        }
+        if let Some(DebandDither::Enabled) = dither {
+            view_key |= SpritePipelineKey::DEBAND_DITHER;
+        }
+    }
+    view_key
+}
+
+/// The number of sprites below which sorting them and computing their vertices is done on the
+/// current thread, since spreading it over the [`ComputeTaskPool`] would cost more than it saves.
+const PARALLEL_SPRITE_CHUNK_SIZE: usize = 4096;
+
+/// Splits `len` items in chunks of at least [`PARALLEL_SPRITE_CHUNK_SIZE`], one per thread of the
+/// [`ComputeTaskPool`], or returns `None` if there are too few items or threads to be worth it.
+fn parallel_chunk_size(len: usize) -> Option<usize> {
+    if len <= PARALLEL_SPRITE_CHUNK_SIZE {
+        return None;
+    }
+    let thread_num = ComputeTaskPool::get().thread_num();
+    (thread_num > 1).then(|| (len / thread_num + 1).max(PARALLEL_SPRITE_CHUNK_SIZE))
+}
+
+/// Maps `items` with `f` one chunk at a time, over the [`ComputeTaskPool`] when there are enough
+/// of them. The results are in the order of the chunks.
+fn map_sprite_chunks<T: Sync, U: Send + 'static>(
+    items: &[T],
+    f: impl Fn(&[T]) -> U + Sync,
+) -> Vec<U> {
+    let Some(chunk_size) = parallel_chunk_size(items.len()) else {
+        return vec![f(items)];
+    };
+    let f = &f;
+    ComputeTaskPool::get().scope(|scope| {
+        for chunk in items.chunks(chunk_size) {
+            scope.spawn(async move { f(chunk) });
+        }
+    })
+}
+
+/// Sorts sprites by z for correct transparency, then by handle to improve batching, and then by
+/// entity.
+///
+/// Sprites are extracted in parallel, so they arrive in a different order every frame. Sorting by
+/// entity makes the order of the sprites of different entities the same from one frame to the
+/// next, and the sort is stable, so the sprites of a single entity, like the glyphs of a text,
+/// keep the order they were extracted in.
+fn sort_extracted_sprites(extracted_sprites: &mut [ExtractedSprite]) {
+    // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
+    let key = |sprite: &ExtractedSprite| {
+        (
+            FloatOrd(sprite.transform.translation().z),
+            sprite.image_handle_id,
+            sprite.entity,
+        )
+    };
+    // The chunks are sorted in parallel, which leaves the final sort to merge them
+    if let Some(chunk_size) = parallel_chunk_size(extracted_sprites.len()) {
+        ComputeTaskPool::get().scope(|scope| {
+            for chunk in extracted_sprites.chunks_mut(chunk_size) {
+                scope.spawn(async move { chunk.sort_by_key(key) });
+            }
+        });
+    }
+    extracted_sprites.sort_by_key(key);
+}
+
+/// Creates the bind group of the image `image_handle_id`, if it doesn't already exist.
+fn queue_image_bind_group(
+    image_bind_groups: &mut ImageBindGroups,
+    render_device: &RenderDevice,
+    sprite_pipeline: &SpritePipeline,
+    image_handle_id: HandleId,
+    gpu_image: &GpuImage,
+) {
+    image_bind_groups
+        .values
+        .entry(Handle::weak(image_handle_id))
+        .or_insert_with(|| {
+            render_device.create_bind_group(&BindGroupDescriptor {
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(&gpu_image.texture_view),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&gpu_image.sampler),
+                    },
+                ],
+                label: Some("sprite_material_bind_group"),
+                layout: &sprite_pipeline.material_layout,
+            })
+        });
+}
+
+/// Removes the bind groups of images that were modified or removed, since their
+/// [`GpuImage`] has (probably) changed.
+pub fn remove_changed_image_bind_groups(
+    mut image_bind_groups: ResMut<ImageBindGroups>,
+    events: Res<SpriteAssetEvents>,
+) {
+    for event in &events.images {
+        match event {
+            AssetEvent::Created { .. } | AssetEvent::Unused { .. } => None,
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
+                image_bind_groups.values.remove(handle)
+            }
+        };
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn queue_sprites(
     mut commands: Commands,
@@ -503,18 +772,7 @@ pub fn queue_sprites(
         Option<&Tonemapping>,
         Option<&DebandDither>,
     )>,
-    events: Res<SpriteAssetEvents>,
 ) {
-    // If an image has changed, the GpuImage has (probably) changed
-    for event in &events.images {
-        match event {
-            AssetEvent::Created { .. } => None,
-            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
-                image_bind_groups.values.remove(handle)
-            }
-        };
-    }
-
     let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
 
     if let Some(view_binding) = view_uniforms.uniforms.binding() {
@@ -542,50 +800,16 @@ pub fn queue_sprites(
         // FIXME: VisibleEntities is ignored
 
         let extracted_sprites = &mut extracted_sprites.sprites;
-        // Sort sprites by z for correct transparency and then by handle to improve batching
-        // NOTE: This can be done independent of views by reasonably assuming that all 2D views look along the negative-z axis in world space
-        extracted_sprites.sort_unstable_by(|a, b| {
-            match a
-                .transform
-                .translation()
-                .z
-                .partial_cmp(&b.transform.translation().z)
-            {
-                Some(Ordering::Equal) | None => a.image_handle_id.cmp(&b.image_handle_id),
-                Some(other) => other,
-            }
-        });
+        sort_extracted_sprites(extracted_sprites);
         let image_bind_groups = &mut *image_bind_groups;
 
+        // The sprites queued for a view with the size of their image, whose vertices are
+        // computed in parallel once every phase item of the view is added
+        let mut quads = Vec::new();
+        let mut colored_quads = Vec::new();
+
         for (mut transparent_phase, visible_entities, view, tonemapping, dither) in &mut views {
-            let mut view_key = SpritePipelineKey::from_hdr(view.hdr) | msaa_key;
-
-            if !view.hdr {
-                if let Some(tonemapping) = tonemapping {
-                    view_key |= SpritePipelineKey::TONEMAP_IN_SHADER;
-                    view_key |= match tonemapping {
-                        Tonemapping::None => SpritePipelineKey::TONEMAP_METHOD_NONE,
-                        Tonemapping::Reinhard => SpritePipelineKey::TONEMAP_METHOD_REINHARD,
-                        Tonemapping::ReinhardLuminance => {
-                            SpritePipelineKey::TONEMAP_METHOD_REINHARD_LUMINANCE
-                        }
-                        Tonemapping::AcesFitted => SpritePipelineKey::TONEMAP_METHOD_ACES_FITTED,
-                        Tonemapping::AgX => SpritePipelineKey::TONEMAP_METHOD_AGX,
-                        Tonemapping::SomewhatBoringDisplayTransform => {
-                            SpritePipelineKey::TONEMAP_METHOD_SOMEWHAT_BORING_DISPLAY_TRANSFORM
-                        }
-                        Tonemapping::TonyMcMapface => {
-                            SpritePipelineKey::TONEMAP_METHOD_TONY_MC_MAPFACE
-                        }
-                        Tonemapping::BlenderFilmic => {
-                            SpritePipelineKey::TONEMAP_METHOD_BLENDER_FILMIC
-                        }
-                    };
-                }
-                if let Some(DebandDither::Enabled) = dither {
-                    view_key |= SpritePipelineKey::DEBAND_DITHER;
-                }
-            }
+            let view_key = view_pipeline_key(view, tonemapping, dither) | msaa_key;
 
             let pipeline = pipelines.specialize(
                 &pipeline_cache,
@@ -631,83 +855,25 @@ pub fn queue_sprites(
                         current_image_size = Vec2::new(gpu_image.size.x, gpu_image.size.y);
                         current_batch_entity = commands.spawn(current_batch).id();
 
-                        image_bind_groups
-                            .values
-                            .entry(Handle::weak(current_batch.image_handle_id))
-                            .or_insert_with(|| {
-                                render_device.create_bind_group(&BindGroupDescriptor {
-                                    entries: &[
-                                        BindGroupEntry {
-                                            binding: 0,
-                                            resource: BindingResource::TextureView(
-                                                &gpu_image.texture_view,
-                                            ),
-                                        },
-                                        BindGroupEntry {
-                                            binding: 1,
-                                            resource: BindingResource::Sampler(&gpu_image.sampler),
-                                        },
-                                    ],
-                                    label: Some("sprite_material_bind_group"),
-                                    layout: &sprite_pipeline.material_layout,
-                                })
-                            });
+                        queue_image_bind_group(
+                            image_bind_groups,
+                            &render_device,
+                            &sprite_pipeline,
+                            current_batch.image_handle_id,
+                            gpu_image,
+                        );
                     } else {
                         // Skip this item if the texture is not ready
                         continue;
                     }
                 }
 
-                // Calculate vertex data for this item
-
-                let mut uvs = QUAD_UVS;
-                if extracted_sprite.flip_x {
-                    uvs = [uvs[1], uvs[0], uvs[3], uvs[2]];
-                }
-                if extracted_sprite.flip_y {
-                    uvs = [uvs[3], uvs[2], uvs[1], uvs[0]];
-                }
-
-                // By default, the size of the quad is the size of the texture
-                let mut quad_size = current_image_size;
-
-                // If a rect is specified, adjust UVs and the size of the quad
-                if let Some(rect) = extracted_sprite.rect {
-                    let rect_size = rect.size();
-                    for uv in &mut uvs {
-                        *uv = (rect.min + *uv * rect_size) / current_image_size;
-                    }
-                    quad_size = rect_size;
-                }
-
-                // Override the size if a custom one is specified
-                if let Some(custom_size) = extracted_sprite.custom_size {
-                    quad_size = custom_size;
-                }
-
-                // Apply size and global transform
-                let positions = QUAD_VERTEX_POSITIONS.map(|quad_pos| {
-                    extracted_sprite
-                        .transform
-                        .transform_point(
-                            ((quad_pos - extracted_sprite.anchor) * quad_size).extend(0.),
-                        )
-                        .into()
-                });
-
                 // These items will be sorted by depth with other phase items
                 let sort_key = FloatOrd(extracted_sprite.transform.translation().z);
 
-                // Store the vertex data and add the item to the render phase
+                // Add the item to the render phase, its vertices are added below
                 if current_batch.colored {
-                    let vertex_color = extracted_sprite.color.as_linear_rgba_f32();
-                    for i in QUAD_INDICES {
-                        sprite_meta.colored_vertices.push(ColoredSpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                            color: vertex_color,
-                        });
-                    }
+                    colored_quads.push((extracted_sprite, current_image_size));
                     let item_start = colored_index;
                     colored_index += QUAD_INDICES.len() as u32;
                     let item_end = colored_index;
@@ -720,12 +886,7 @@ pub fn queue_sprites(
                         batch_range: Some(item_start..item_end),
                     });
                 } else {
-                    for i in QUAD_INDICES {
-                        sprite_meta.vertices.push(SpriteVertex {
-                            position: positions[i],
-                            uv: uvs[i].into(),
-                        });
-                    }
+                    quads.push((extracted_sprite, current_image_size));
                     let item_start = index;
                     index += QUAD_INDICES.len() as u32;
                     let item_end = index;
@@ -739,6 +900,40 @@ pub fn queue_sprites(
                     });
                 }
             }
+
+            // The chunks keep the order of the sprites, so the vertices match the batch ranges
+            let chunks = map_sprite_chunks(&quads, |quads| {
+                let mut vertices = Vec::with_capacity(quads.len() * QUAD_INDICES.len());
+                for &(sprite, image_size) in quads {
+                    let (positions, uvs) = sprite_quad(sprite, image_size);
+                    vertices.extend(QUAD_INDICES.map(|i| SpriteVertex {
+                        position: positions[i],
+                        uv: uvs[i].into(),
+                    }));
+                }
+                vertices
+            });
+            for vertex in chunks.into_iter().flatten() {
+                sprite_meta.vertices.push(vertex);
+            }
+            let chunks = map_sprite_chunks(&colored_quads, |quads| {
+                let mut vertices = Vec::with_capacity(quads.len() * QUAD_INDICES.len());
+                for &(sprite, image_size) in quads {
+                    let (positions, uvs) = sprite_quad(sprite, image_size);
+                    let color = sprite.color.as_linear_rgba_f32();
+                    vertices.extend(QUAD_INDICES.map(|i| ColoredSpriteVertex {
+                        position: positions[i],
+                        uv: uvs[i].into(),
+                        color,
+                    }));
+                }
+                vertices
+            });
+            for vertex in chunks.into_iter().flatten() {
+                sprite_meta.colored_vertices.push(vertex);
+            }
+            quads.clear();
+            colored_quads.clear();
         }
         sprite_meta
             .vertices
@@ -827,3 +1022,48 @@ impl<P: BatchedPhaseItem> RenderCommand<P> for DrawSpriteBatch {
         RenderCommandResult::Success
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::Vec3;
+    use bevy_tasks::TaskPoolBuilder;
+
+    #[test]
+    fn sort_is_independent_of_extraction_order() {
+        // Several threads, so that the sprites are sorted in parallel on any machine
+        ComputeTaskPool::init(|| TaskPoolBuilder::new().num_threads(4).build());
+
+        // Enough sprites to be sorted in parallel, with many equal depths and images
+        let sprites: Vec<_> = (0..3 * PARALLEL_SPRITE_CHUNK_SIZE as u32)
+            .map(|i| ExtractedSprite {
+                entity: Entity::from_raw(i),
+                transform: GlobalTransform::from_translation(Vec3::new(0.0, 0.0, (i % 5) as f32)),
+                color: Color::WHITE,
+                rect: None,
+                custom_size: None,
+                image_handle_id: HandleId::Id(Uuid::nil(), (i % 3) as u64),
+                flip_x: false,
+                flip_y: false,
+                anchor: Vec2::ZERO,
+            })
+            .collect();
+
+        let mut sorted = sprites.clone();
+        sort_extracted_sprites(&mut sorted);
+        let mut reversed: Vec<_> = sprites.iter().rev().copied().collect();
+        sort_extracted_sprites(&mut reversed);
+        let mut rotated = sprites;
+        rotated.rotate_left(1000);
+        sort_extracted_sprites(&mut rotated);
+
+        let entities = |sprites: &[ExtractedSprite]| -> Vec<_> {
+            sprites.iter().map(|sprite| sprite.entity).collect()
+        };
+        assert_eq!(entities(&sorted), entities(&reversed));
+        assert_eq!(entities(&sorted), entities(&rotated));
+        assert!(sorted.windows(2).all(|pair| {
+            pair[0].transform.translation().z <= pair[1].transform.translation().z
+        }));
+    }
+}
@@ -0,0 +1,66 @@
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+#import bevy_render::view
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+// NOTE: These must match the constants in bevy_sprite/src/render/instancing.rs!
+const SPRITE_INSTANCE_FLIP_X: u32 = 1u;
+const SPRITE_INSTANCE_FLIP_Y: u32 = 2u;
+
+struct VertexOutput {
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vertex(
+    // The corner of the quad, counter clockwise from the bottom left
+    @builtin(vertex_index) vertex_index: u32,
+    @location(0) i_transform_0: vec4<f32>,
+    @location(1) i_transform_1: vec4<f32>,
+    @location(2) i_transform_2: vec4<f32>,
+    @location(3) i_transform_3: vec4<f32>,
+    @location(4) i_uv_rect: vec4<f32>,
+    @location(5) i_color: vec4<f32>,
+    @location(6) i_flags: u32,
+) -> VertexOutput {
+    let corner = vec2<f32>(
+        f32(vertex_index == 1u || vertex_index == 2u),
+        f32(vertex_index >= 2u),
+    );
+    var uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    if ((i_flags & SPRITE_INSTANCE_FLIP_X) != 0u) {
+        uv.x = 1.0 - uv.x;
+    }
+    if ((i_flags & SPRITE_INSTANCE_FLIP_Y) != 0u) {
+        uv.y = 1.0 - uv.y;
+    }
+
+    let transform = mat4x4<f32>(i_transform_0, i_transform_1, i_transform_2, i_transform_3);
+    var out: VertexOutput;
+    out.uv = mix(i_uv_rect.xy, i_uv_rect.zw, uv);
+    out.color = i_color;
+    out.position = view.view_proj * transform * vec4<f32>(corner - 0.5, 0.0, 1.0);
+    return out;
+}
+
+@group(1) @binding(0)
+var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var sprite_sampler: sampler;
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = in.color * textureSample(sprite_texture, sprite_sampler, in.uv);
+
+#ifdef TONEMAP_IN_SHADER
+    color = tone_mapping(color);
+#endif
+
+    return color;
+}
@@ -0,0 +1,417 @@
+use std::ops::Range;
+
+use super::{
+    queue_image_bind_group, sort_extracted_sprites, sprite_quad, view_pipeline_key,
+    ColoredSpriteVertex, ExtractedSprite, ImageBindGroups, SetSpriteTextureBindGroup,
+    SetSpriteViewBindGroup, SpriteBatch, SpritePipeline, SpritePipelineKey, QUAD_INDICES,
+};
+use crate::{Sprite, StaticSpriteBatch, TextureAtlas, TextureAtlasSprite};
+use bevy_asset::{Assets, Handle, HandleId};
+use bevy_core_pipeline::{
+    core_2d::Transparent2d,
+    tonemapping::{DebandDither, Tonemapping},
+};
+use bevy_ecs::{
+    prelude::*,
+    system::{lifetimeless::*, SystemChangeTick, SystemParamItem},
+};
+use bevy_hierarchy::Children;
+use bevy_render::{
+    render_asset::RenderAssets,
+    render_phase::{
+        BatchedPhaseItem, DrawFunctions, RenderCommand, RenderCommandResult, RenderPhase,
+        SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{FailedImages, Image},
+    view::{ComputedVisibility, ExtractedView, Msaa, VisibleEntities},
+    Extract,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap, HashSet};
+use fixedbitset::FixedBitSet;
+
+/// The uploaded vertices of a [`StaticSpriteBatch`], kept across frames.
+#[derive(Default)]
+struct StaticSpriteBatchCache {
+    /// The [`StaticSpriteBatch::generation`] of the extracted sprites
+    generation: Option<u32>,
+    /// The texture atlases the extracted sprites were taken from
+    atlases: HashSet<HandleId>,
+    /// Sprites waiting to be uploaded, once all their images are ready
+    pending: Option<Vec<ExtractedSprite>>,
+    vertices: Option<Buffer>,
+    /// Runs of vertices sharing an image, in drawing order
+    groups: Vec<StaticSpriteGroup>,
+    visible: bool,
+}
+
+struct StaticSpriteGroup {
+    image_handle_id: HandleId,
+    range: Range<u32>,
+    sort_key: FloatOrd,
+}
+
+/// The uploaded vertices of every [`StaticSpriteBatch`], by batch entity.
+#[derive(Resource, Default)]
+pub struct StaticSpriteBatches {
+    batches: HashMap<Entity, StaticSpriteBatchCache>,
+}
+
+/// The batch entity of a run of static sprites queued for drawing.
+#[derive(Component)]
+pub struct StaticSpriteBatchEntity(Entity);
+
+/// Extracts the sprites of the [`StaticSpriteBatch`]es that are new, were marked dirty or use a
+/// texture atlas that changed.
+#[allow(clippy::too_many_arguments)]
+pub fn extract_static_sprite_batches(
+    mut static_batches: ResMut<StaticSpriteBatches>,
+    texture_atlases: Extract<Res<Assets<TextureAtlas>>>,
+    change_tick: Extract<SystemChangeTick>,
+    failed_images: Extract<Res<FailedImages>>,
+    batch_query: Extract<
+        Query<(
+            Entity,
+            &StaticSpriteBatch,
+            &ComputedVisibility,
+            Option<&Children>,
+        )>,
+    >,
+    sprite_query: Extract<Query<(Entity, &Sprite, &GlobalTransform, &Handle<Image>)>>,
+    atlas_query: Extract<
+        Query<(
+            Entity,
+            &TextureAtlasSprite,
+            &GlobalTransform,
+            &Handle<TextureAtlas>,
+        )>,
+    >,
+) {
+    let batches = &mut static_batches.batches;
+    batches.retain(|entity, _| batch_query.contains(*entity));
+    let changed_atlases: HashSet<HandleId> = texture_atlases
+        .iter_changed(change_tick.last_run(), change_tick.this_run())
+        .map(|(id, _)| id)
+        .collect();
+
+    for (entity, batch, visibility, children) in batch_query.iter() {
+        let cache = batches.entry(entity).or_default();
+        cache.visible = visibility.is_visible();
+        if cache.generation == Some(batch.generation())
+            && cache.atlases.is_disjoint(&changed_atlases)
+        {
+            continue;
+        }
+        cache.generation = Some(batch.generation());
+        cache.atlases.clear();
+
+        let mut sprites = Vec::new();
+        for &child in children.into_iter().flatten() {
+            if let Ok((entity, sprite, transform, handle)) = sprite_query.get(child) {
+                let Some(image_handle_id) = failed_images.resolve(handle.id()) else {
+                    continue;
+                };
+                sprites.push(ExtractedSprite {
+                    entity,
+                    transform: *transform,
+                    color: sprite.color,
+                    rect: sprite.rect,
+                    custom_size: sprite.custom_size,
+                    image_handle_id,
+                    flip_x: sprite.flip_x,
+                    flip_y: sprite.flip_y,
+                    anchor: sprite.anchor.as_vec(),
+                });
+            } else if let Ok((entity, atlas_sprite, transform, atlas_handle)) =
+                atlas_query.get(child)
+            {
+                cache.atlases.insert(atlas_handle.id());
+                let Some(texture_atlas) = texture_atlases.get(atlas_handle) else {
+                    continue;
+                };
+                let Some(rect) = texture_atlas.textures.get(atlas_sprite.index) else {
+                    continue;
+                };
+                sprites.push(ExtractedSprite {
+                    entity,
+                    transform: *transform,
+                    color: atlas_sprite.color,
+                    rect: Some(*rect),
+                    custom_size: atlas_sprite.custom_size,
+                    image_handle_id: texture_atlas.texture.id(),
+                    flip_x: atlas_sprite.flip_x,
+                    flip_y: atlas_sprite.flip_y,
+                    anchor: atlas_sprite.anchor.as_vec(),
+                });
+            }
+        }
+        cache.pending = Some(sprites);
+    }
+}
+
+/// Uploads the vertices of the dirty [`StaticSpriteBatch`]es, and queues every visible batch.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_static_sprite_batches(
+    mut commands: Commands,
+    mut view_entities: Local<FixedBitSet>,
+    draw_functions: Res<DrawFunctions<Transparent2d>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    sprite_pipeline: Res<SpritePipeline>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<SpritePipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    mut image_bind_groups: ResMut<ImageBindGroups>,
+    gpu_images: Res<RenderAssets<Image>>,
+    msaa: Res<Msaa>,
+    mut static_batches: ResMut<StaticSpriteBatches>,
+    mut views: Query<(
+        &mut RenderPhase<Transparent2d>,
+        &VisibleEntities,
+        &ExtractedView,
+        Option<&Tonemapping>,
+        Option<&DebandDither>,
+    )>,
+) {
+    for cache in static_batches.batches.values_mut() {
+        // Wait until every image is ready, since the sprite sizes can depend on them
+        let Some(sprites) = &mut cache.pending else {
+            continue;
+        };
+        if !sprites.iter().all(|sprite| {
+            gpu_images
+                .get(&Handle::weak(sprite.image_handle_id))
+                .is_some()
+        }) {
+            continue;
+        }
+        sort_extracted_sprites(sprites);
+
+        let mut vertices = Vec::with_capacity(sprites.len() * QUAD_INDICES.len());
+        for sprite in sprites.iter() {
+            let gpu_image = gpu_images
+                .get(&Handle::weak(sprite.image_handle_id))
+                .unwrap();
+            let (positions, uvs) = sprite_quad(sprite, gpu_image.size);
+            let color = sprite.color.as_linear_rgba_f32();
+            vertices.extend(QUAD_INDICES.map(|i| ColoredSpriteVertex {
+                position: positions[i],
+                uv: uvs[i].into(),
+                color,
+            }));
+        }
+        cache.groups = static_sprite_groups(sprites);
+
+        let contents = bytemuck::cast_slice(&vertices);
+        match &cache.vertices {
+            // Reuse the buffer when the new vertices fit in it
+            Some(buffer) if buffer.size() >= contents.len() as u64 => {
+                render_queue.write_buffer(buffer, 0, contents);
+            }
+            _ if vertices.is_empty() => cache.vertices = None,
+            _ => {
+                cache.vertices = Some(render_device.create_buffer_with_data(
+                    &BufferInitDescriptor {
+                        label: Some("static_sprite_batch_vertices"),
+                        contents,
+                        usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                    },
+                ));
+            }
+        }
+        cache.pending = None;
+    }
+
+    // Spawn an entity for each run of sprites sharing an image, shared by all views
+    let mut groups = Vec::new();
+    for (&batch_entity, cache) in &static_batches.batches {
+        if !cache.visible || cache.vertices.is_none() {
+            continue;
+        }
+        for group in &cache.groups {
+            let Some(gpu_image) = gpu_images.get(&Handle::weak(group.image_handle_id)) else {
+                continue;
+            };
+            queue_image_bind_group(
+                &mut image_bind_groups,
+                &render_device,
+                &sprite_pipeline,
+                group.image_handle_id,
+                gpu_image,
+            );
+            let group_entity = commands
+                .spawn((
+                    SpriteBatch {
+                        image_handle_id: group.image_handle_id,
+                        colored: true,
+                    },
+                    StaticSpriteBatchEntity(batch_entity),
+                ))
+                .id();
+            groups.push((batch_entity, group_entity, group));
+        }
+    }
+    if groups.is_empty() {
+        return;
+    }
+
+    let msaa_key = SpritePipelineKey::from_msaa_samples(msaa.samples());
+    let draw_function = draw_functions.read().id::<DrawStaticSprites>();
+    for (mut transparent_phase, visible_entities, view, tonemapping, dither) in &mut views {
+        let view_key = view_pipeline_key(view, tonemapping, dither) | msaa_key;
+        let pipeline = pipelines.specialize(
+            &pipeline_cache,
+            &sprite_pipeline,
+            view_key | SpritePipelineKey::COLORED,
+        );
+
+        view_entities.clear();
+        view_entities.extend(visible_entities.entities.iter().map(|e| e.index() as usize));
+        for (batch_entity, group_entity, group) in &groups {
+            if !view_entities.contains(batch_entity.index() as usize) {
+                continue;
+            }
+            transparent_phase.add(Transparent2d {
+                draw_function,
+                pipeline,
+                entity: *group_entity,
+                sort_key: group.sort_key,
+                batch_range: Some(group.range.clone()),
+            });
+        }
+    }
+}
+
+/// Splits the sorted `sprites` of a batch into runs sharing an image, with the range of their
+/// vertices.
+fn static_sprite_groups(sprites: &[ExtractedSprite]) -> Vec<StaticSpriteGroup> {
+    let quad_len = QUAD_INDICES.len() as u32;
+    let mut groups: Vec<StaticSpriteGroup> = Vec::new();
+    for (i, sprite) in sprites.iter().enumerate() {
+        let start = i as u32 * quad_len;
+        match groups.last_mut() {
+            Some(group) if group.image_handle_id == sprite.image_handle_id => {
+                group.range.end = start + quad_len;
+            }
+            _ => groups.push(StaticSpriteGroup {
+                image_handle_id: sprite.image_handle_id,
+                range: start..start + quad_len,
+                sort_key: FloatOrd(sprite.transform.translation().z),
+            }),
+        }
+    }
+    groups
+}
+
+pub type DrawStaticSprites = (
+    SetItemPipeline,
+    SetSpriteViewBindGroup<0>,
+    SetSpriteTextureBindGroup<1>,
+    DrawStaticSpriteBatch,
+);
+
+pub struct DrawStaticSpriteBatch;
+impl<P: BatchedPhaseItem> RenderCommand<P> for DrawStaticSpriteBatch {
+    type Param = SRes<StaticSpriteBatches>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<StaticSpriteBatchEntity>;
+
+    fn render<'w>(
+        item: &P,
+        _view: (),
+        batch_entity: &'_ StaticSpriteBatchEntity,
+        static_batches: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(vertices) = static_batches
+            .into_inner()
+            .batches
+            .get(&batch_entity.0)
+            .and_then(|cache| cache.vertices.as_ref())
+        else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_vertex_buffer(0, vertices.slice(..));
+        pass.draw(item.batch_range().as_ref().unwrap().clone(), 0..1);
+        RenderCommandResult::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_asset::AddAsset;
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::Vec3;
+    use bevy_render::MainWorld;
+
+    fn sprite(image: &Handle<Image>, z: f32) -> (Sprite, GlobalTransform, Handle<Image>) {
+        (
+            Sprite::default(),
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, z)),
+            image.clone_weak(),
+        )
+    }
+
+    /// The ranges of vertices of the groups the extracted sprites of `batch` are drawn in.
+    fn extract_groups(render_world: &mut World, batch: Entity) -> Option<Vec<Range<u32>>> {
+        let mut system = IntoSystem::into_system(extract_static_sprite_batches);
+        system.initialize(render_world);
+        system.run((), render_world);
+        let mut static_batches = render_world.resource_mut::<StaticSpriteBatches>();
+        // Taken like `queue_static_sprite_batches` does once the images are ready
+        let mut sprites = static_batches.batches.get_mut(&batch)?.pending.take()?;
+        sort_extracted_sprites(&mut sprites);
+        let groups = static_sprite_groups(&sprites);
+        Some(groups.into_iter().map(|group| group.range).collect())
+    }
+
+    #[test]
+    fn static_sprites_are_grouped_until_marked_dirty() {
+        let mut app = bevy_app::App::new();
+        app.add_plugin(bevy_asset::AssetPlugin::default())
+            .add_asset::<TextureAtlas>()
+            .init_resource::<FailedImages>();
+        let mut main_world = MainWorld::default();
+        *main_world = std::mem::take(&mut app.world);
+
+        let image_a = Handle::<Image>::weak(HandleId::random::<Image>());
+        let image_b = Handle::<Image>::weak(HandleId::random::<Image>());
+        let mut moved = Entity::PLACEHOLDER;
+        let batch = main_world
+            .spawn((StaticSpriteBatch::default(), ComputedVisibility::default()))
+            .with_children(|parent| {
+                parent.spawn(sprite(&image_a, 0.0));
+                parent.spawn(sprite(&image_b, 1.0));
+                parent.spawn(sprite(&image_a, 0.5));
+                moved = parent.spawn(sprite(&image_a, 2.0)).id();
+            })
+            .id();
+
+        let mut render_world = World::new();
+        render_world.insert_resource(main_world);
+        render_world.init_resource::<StaticSpriteBatches>();
+        // The sprites are sorted by z, and the image changes twice
+        assert_eq!(
+            extract_groups(&mut render_world, batch),
+            Some(vec![0..12, 12..18, 18..24])
+        );
+
+        // Moving a sprite is ignored until the batch is marked dirty
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        *main_world.get_mut::<GlobalTransform>(moved).unwrap() =
+            GlobalTransform::from_translation(Vec3::new(0.0, 0.0, -1.0));
+        assert_eq!(extract_groups(&mut render_world, batch), None);
+
+        let mut main_world = render_world.resource_mut::<MainWorld>();
+        main_world
+            .get_mut::<StaticSpriteBatch>(batch)
+            .unwrap()
+            .mark_dirty();
+        assert_eq!(
+            extract_groups(&mut render_world, batch),
+            Some(vec![0..18, 18..24])
+        );
+    }
+}
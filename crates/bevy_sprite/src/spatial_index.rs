@@ -0,0 +1,339 @@
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::prelude::*;
+use bevy_math::{IVec2, Rect, Vec2, Vec3};
+use bevy_render::{primitives::Aabb, view::VisibilitySystems};
+use bevy_transform::{components::GlobalTransform, TransformSystem};
+use bevy_utils::{HashMap, HashSet};
+
+use crate::SpriteSystem;
+
+/// Maintains a [`SpatialIndex2d`] of the entities with a [`GlobalTransform`] and an [`Aabb`],
+/// like the sprites and 2D meshes whose bounds are computed by
+/// [`calculate_bounds_2d`](crate::calculate_bounds_2d).
+///
+/// The index is updated in [`PostUpdate`], once the transforms are propagated, from the entities
+/// whose transform or bounds changed, and from the ones that were despawned or lost either
+/// component.
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialIndex2dPlugin {
+    /// The side of the square cells of the index grid, see [`SpatialIndex2d::new`].
+    pub cell_size: f32,
+}
+
+impl Default for SpatialIndex2dPlugin {
+    fn default() -> Self {
+        Self { cell_size: 128.0 }
+    }
+}
+
+impl Plugin for SpatialIndex2dPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(SpatialIndex2d::new(self.cell_size))
+            .add_systems(
+                PostUpdate,
+                update_spatial_index_2d
+                    .in_set(SpriteSystem::UpdateSpatialIndex)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(VisibilitySystems::CalculateBounds),
+            );
+    }
+}
+
+/// A grid of the world space bounds of 2D entities, to find the entities at a point, in an area
+/// or near a point without going through all of them.
+///
+/// Each entity is stored in every cell its bounds overlap, so queries only look at the entities
+/// of the cells they overlap. The cell size should be around the size of the common entities:
+/// smaller cells store large entities many times, larger cells hold more entities to check.
+///
+/// Maintained by the [`SpatialIndex2dPlugin`], the index can also be filled by hand with
+/// [`SpatialIndex2d::insert`] and [`SpatialIndex2d::remove`].
+#[derive(Resource, Debug, Clone)]
+pub struct SpatialIndex2d {
+    cell_size: f32,
+    cells: HashMap<IVec2, Vec<Entity>>,
+    bounds: HashMap<Entity, Rect>,
+}
+
+impl SpatialIndex2d {
+    /// Creates an empty index with square cells of `cell_size`.
+    ///
+    /// # Panics
+    /// Panics if `cell_size` isn't strictly positive.
+    pub fn new(cell_size: f32) -> Self {
+        assert!(
+            cell_size > 0.0,
+            "the cell size of a spatial index must be positive"
+        );
+        Self {
+            cell_size,
+            cells: HashMap::default(),
+            bounds: HashMap::default(),
+        }
+    }
+
+    /// The side of the cells of the index.
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// The number of entities in the index.
+    pub fn len(&self) -> usize {
+        self.bounds.len()
+    }
+
+    /// Returns `true` if there are no entities in the index.
+    pub fn is_empty(&self) -> bool {
+        self.bounds.is_empty()
+    }
+
+    /// The world space bounds of `entity`, if it is in the index.
+    pub fn get(&self, entity: Entity) -> Option<Rect> {
+        self.bounds.get(&entity).copied()
+    }
+
+    /// Adds `entity` to the index with the world space `bounds`, replacing its previous bounds if
+    /// it already was in it.
+    pub fn insert(&mut self, entity: Entity, bounds: Rect) {
+        self.remove(entity);
+        let (min, max) = self.cell_range(bounds);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                self.cells.entry(IVec2::new(x, y)).or_default().push(entity);
+            }
+        }
+        self.bounds.insert(entity, bounds);
+    }
+
+    /// Removes `entity` from the index, returning its bounds if it was in it.
+    pub fn remove(&mut self, entity: Entity) -> Option<Rect> {
+        let bounds = self.bounds.remove(&entity)?;
+        let (min, max) = self.cell_range(bounds);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let cell = IVec2::new(x, y);
+                let Some(entities) = self.cells.get_mut(&cell) else {
+                    continue;
+                };
+                if let Some(position) = entities.iter().position(|&e| e == entity) {
+                    entities.swap_remove(position);
+                }
+                if entities.is_empty() {
+                    self.cells.remove(&cell);
+                }
+            }
+        }
+        Some(bounds)
+    }
+
+    /// Removes every entity from the index.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.bounds.clear();
+    }
+
+    /// Returns the entities whose bounds contain `point`, in no particular order.
+    pub fn point(&self, point: Vec2) -> Vec<Entity> {
+        let Some(entities) = self.cells.get(&self.cell(point)) else {
+            return Vec::new();
+        };
+        entities
+            .iter()
+            .copied()
+            .filter(|entity| self.bounds[entity].contains(point))
+            .collect()
+    }
+
+    /// Returns the entities whose bounds overlap `area`, in no particular order.
+    ///
+    /// Bounds only touching the edge of `area` don't overlap it.
+    pub fn aabb(&self, area: Rect) -> Vec<Entity> {
+        let mut found: HashSet<Entity> = HashSet::default();
+        self.for_each_cell_in(area, |entities| {
+            found.extend(
+                entities
+                    .iter()
+                    .filter(|entity| !self.bounds[entity].intersect(area).is_empty()),
+            );
+        });
+        found.into_iter().collect()
+    }
+
+    /// Returns the entity whose bounds are the closest to `point`, if any is within `max_dist`.
+    ///
+    /// The distance is 0 for bounds containing `point`. Between entities at the same distance,
+    /// the lowest [`Entity`] is returned, so the result doesn't depend on the order they were
+    /// inserted in.
+    pub fn nearest(&self, point: Vec2, max_dist: f32) -> Option<Entity> {
+        let area = Rect::from_center_half_size(point, Vec2::splat(max_dist));
+        let mut nearest: Option<(f32, Entity)> = None;
+        self.for_each_cell_in(area, |entities| {
+            for &entity in entities {
+                let bounds = self.bounds[&entity];
+                let distance = (bounds.min - point)
+                    .max(point - bounds.max)
+                    .max(Vec2::ZERO)
+                    .length();
+                let closer = match nearest {
+                    Some((nearest_distance, nearest_entity)) => {
+                        (distance, entity) < (nearest_distance, nearest_entity)
+                    }
+                    None => true,
+                };
+                if distance <= max_dist && closer {
+                    nearest = Some((distance, entity));
+                }
+            }
+        });
+        nearest.map(|(_, entity)| entity)
+    }
+
+    /// The cell containing `point`.
+    fn cell(&self, point: Vec2) -> IVec2 {
+        (point / self.cell_size).floor().as_ivec2()
+    }
+
+    /// The first and last cells overlapped by `bounds`.
+    fn cell_range(&self, bounds: Rect) -> (IVec2, IVec2) {
+        (self.cell(bounds.min), self.cell(bounds.max))
+    }
+
+    /// Calls `f` with the entities of each cell overlapped by `area`.
+    ///
+    /// When `area` spans more cells than the index has, like with an infinite distance, only the
+    /// cells of the index are visited instead.
+    fn for_each_cell_in(&self, area: Rect, mut f: impl FnMut(&[Entity])) {
+        let (min, max) = self.cell_range(area);
+        let span = max.as_dvec2() - min.as_dvec2() + 1.0;
+        if span.x * span.y > self.cells.len() as f64 {
+            for (cell, entities) in &self.cells {
+                if (cell.cmpge(min) & cell.cmple(max)).all() {
+                    f(entities);
+                }
+            }
+            return;
+        }
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                if let Some(entities) = self.cells.get(&IVec2::new(x, y)) {
+                    f(entities);
+                }
+            }
+        }
+    }
+}
+
+/// The world space bounds of an entity with an [`Aabb`], ignoring the depth.
+fn world_bounds(transform: &GlobalTransform, aabb: &Aabb) -> Rect {
+    let center = Vec3::from(aabb.center);
+    let half_extents = Vec3::from(aabb.half_extents);
+    let corner = |x: f32, y: f32| {
+        transform
+            .transform_point(center + half_extents * Vec3::new(x, y, 0.0))
+            .truncate()
+    };
+    Rect::from_corners(corner(-1.0, -1.0), corner(1.0, 1.0))
+        .union_point(corner(-1.0, 1.0))
+        .union_point(corner(1.0, -1.0))
+}
+
+/// Updates the [`SpatialIndex2d`] from the entities whose [`GlobalTransform`] or [`Aabb`] changed,
+/// and removes the entities that lost either of them, including the despawned ones.
+pub fn update_spatial_index_2d(
+    mut index: ResMut<SpatialIndex2d>,
+    mut removed_transforms: RemovedComponents<GlobalTransform>,
+    mut removed_aabbs: RemovedComponents<Aabb>,
+    changed: Query<
+        (Entity, &GlobalTransform, &Aabb),
+        Or<(Changed<GlobalTransform>, Changed<Aabb>)>,
+    >,
+) {
+    // Removals first, so that an entity whose component was removed then inserted again is kept
+    for entity in removed_transforms.iter().chain(removed_aabbs.iter()) {
+        index.remove(entity);
+    }
+    for (entity, transform, aabb) in &changed {
+        index.insert(entity, world_bounds(transform, aabb));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_transform::components::Transform;
+
+    #[test]
+    fn queries_find_overlapping_entities() {
+        let mut index = SpatialIndex2d::new(10.0);
+        let [a, b, c] = [0, 1, 2].map(Entity::from_raw);
+        index.insert(a, Rect::new(0.0, 0.0, 4.0, 4.0));
+        index.insert(b, Rect::new(-15.0, -15.0, 25.0, 25.0));
+        index.insert(c, Rect::new(30.0, 30.0, 32.0, 32.0));
+
+        let mut found = index.point(Vec2::new(2.0, 2.0));
+        found.sort();
+        assert_eq!(found, [a, b]);
+        assert_eq!(index.point(Vec2::new(31.0, 31.0)), [c]);
+        assert!(index.point(Vec2::new(-20.0, 0.0)).is_empty());
+
+        let mut found = index.aabb(Rect::new(20.0, 20.0, 31.0, 31.0));
+        found.sort();
+        assert_eq!(found, [b, c]);
+
+        assert_eq!(index.nearest(Vec2::new(2.0, 2.0), 1.0), Some(a));
+        assert_eq!(index.nearest(Vec2::new(40.0, 31.0), 10.0), Some(c));
+        assert_eq!(index.nearest(Vec2::new(40.0, 31.0), 5.0), None);
+        assert_eq!(
+            index.nearest(Vec2::new(1000.0, 31.0), f32::INFINITY),
+            Some(c)
+        );
+
+        // Moving an entity removes it from its previous cells
+        index.insert(a, Rect::new(50.0, 50.0, 52.0, 52.0));
+        assert_eq!(index.point(Vec2::new(2.0, 2.0)), [b]);
+        assert_eq!(index.remove(b), Some(Rect::new(-15.0, -15.0, 25.0, 25.0)));
+        assert!(index.point(Vec2::new(2.0, 2.0)).is_empty());
+        assert_eq!(index.len(), 2);
+    }
+
+    #[test]
+    fn follows_changed_and_despawned_entities() {
+        let mut world = World::new();
+        world.insert_resource(SpatialIndex2d::new(10.0));
+        let mut schedule = Schedule::new();
+        schedule.add_systems(update_spatial_index_2d);
+
+        let aabb = Aabb {
+            center: Vec3::ZERO.into(),
+            half_extents: Vec3::new(2.0, 1.0, 0.0).into(),
+        };
+        let entity = world
+            .spawn((
+                GlobalTransform::from(Transform::from_xyz(5.0, 5.0, 1.0)),
+                aabb,
+            ))
+            .id();
+        schedule.run(&mut world);
+        assert_eq!(
+            world.resource::<SpatialIndex2d>().get(entity),
+            Some(Rect::new(3.0, 4.0, 7.0, 6.0))
+        );
+
+        *world.get_mut::<GlobalTransform>(entity).unwrap() =
+            GlobalTransform::from(Transform::from_xyz(20.0, 0.0, 0.0).with_scale(Vec3::splat(2.0)));
+        schedule.run(&mut world);
+        assert_eq!(
+            world
+                .resource::<SpatialIndex2d>()
+                .point(Vec2::new(23.0, 1.0)),
+            [entity]
+        );
+
+        // Spawned and despawned before the index is updated
+        let short_lived = world.spawn((GlobalTransform::default(), aabb)).id();
+        world.despawn(short_lived);
+        world.despawn(entity);
+        schedule.run(&mut world);
+        assert!(world.resource::<SpatialIndex2d>().is_empty());
+    }
+}
@@ -8,6 +8,9 @@ use bevy_render::color::Color;
 #[repr(C)]
 pub struct Sprite {
     /// The sprite's color tint
+    ///
+    /// The tint is multiplied with the texture in linear space without clamping, so with an HDR
+    /// camera, components above 1.0 stay bright enough to bloom.
     pub color: Color,
     /// Flip the sprite along the `X` axis
     pub flip_x: bool,
@@ -0,0 +1,46 @@
+use bevy_ecs::{bundle::Bundle, component::Component, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_render::view::{ComputedVisibility, Visibility};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+/// Marks the child sprites of this entity as static, so their vertices are uploaded to the GPU
+/// once and reused every frame, instead of being rebuilt every frame.
+///
+/// This is meant for sprites that rarely change, like tile maps and backgrounds. Only the direct
+/// children with a [`Sprite`](crate::Sprite) or a [`TextureAtlasSprite`](crate::TextureAtlasSprite)
+/// are part of the batch. Changes to them, including to their transforms, are ignored until
+/// [`StaticSpriteBatch::mark_dirty`] is called, which uploads them again. Changes to the texture
+/// atlases of the sprites upload them again automatically.
+///
+/// The whole batch is shown or hidden with the visibility of this entity. Its sprites are sorted
+/// by z among themselves, but each run of sprites sharing a texture is sorted against other items
+/// with the z of its first sprite.
+#[derive(Component, Debug, Default, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct StaticSpriteBatch {
+    generation: u32,
+}
+
+impl StaticSpriteBatch {
+    /// Uploads the sprites of the batch again on the next frame.
+    pub fn mark_dirty(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// A counter increased by each call to [`StaticSpriteBatch::mark_dirty`].
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// A Bundle of components for the parent entity of a [`StaticSpriteBatch`]
+#[derive(Bundle, Clone, Default)]
+pub struct StaticSpriteBatchBundle {
+    pub batch: StaticSpriteBatch,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
@@ -1,7 +1,7 @@
 use crate::Anchor;
 use bevy_asset::Handle;
 use bevy_ecs::{component::Component, reflect::ReflectComponent};
-use bevy_math::{Rect, Vec2};
+use bevy_math::{IVec2, Rect, URect, UVec2, Vec2};
 use bevy_reflect::{FromReflect, Reflect, TypeUuid};
 use bevy_render::{color::Color, texture::Image};
 use bevy_utils::HashMap;
@@ -9,6 +9,11 @@ use bevy_utils::HashMap;
 /// An atlas containing multiple textures (like a spritesheet or a tilemap).
 /// [Example usage animating sprite.](https://github.com/bevyengine/bevy/blob/latest/examples/2d/sprite_sheet.rs)
 /// [Example usage loading sprite sheet.](https://github.com/bevyengine/bevy/blob/latest/examples/2d/texture_atlas.rs)
+///
+/// Atlases created at runtime, like with [`TextureAtlas::from_grid`], can be stored in a scene
+/// next to the entities using them, with `DynamicSceneBuilder::extract_assets::<TextureAtlas>` and
+/// the `InlineAssetsPlugin::<TextureAtlas>` of `bevy_scene`. Their texture is only stored by its
+/// handle, so it should be loaded from a file.
 #[derive(Reflect, FromReflect, Debug, Clone, TypeUuid)]
 #[uuid = "7233c597-ccfa-411f-bd59-9af349432ada"]
 #[reflect(Debug)]
@@ -21,12 +26,54 @@ pub struct TextureAtlas {
     pub textures: Vec<Rect>,
     /// Mapping from texture handle to index
     pub texture_handles: Option<HashMap<Handle<Image>, usize>>,
+    /// Optional payload of each texture, like a glyph id or an index into a table of glyph
+    /// metrics, see [`TextureAtlas::add_texture_with_data`]
+    ///
+    /// It is indexed like [`textures`](TextureAtlas::textures), but can be shorter: textures
+    /// past its end have no payload.
+    pub user_data: Vec<Option<u64>>,
+    /// Whether adding textures is forbidden, see [`TextureAtlas::freeze`]
+    pub frozen: bool,
+}
+
+/// A 2D array texture containing multiple textures of the same size, one per layer.
+///
+/// Unlike a [`TextureAtlas`], textures are found by their layer index instead of a [`Rect`], so
+/// they can't bleed into each other. See [`TextureAtlasBuilder::finish_array`](crate::TextureAtlasBuilder::finish_array).
+#[derive(Debug, Clone)]
+pub struct TextureArrayAtlas {
+    /// The handle to the array texture in which the sprites are stored
+    pub texture: Handle<Image>,
+    /// The size of each layer
+    pub size: Vec2,
+    /// Mapping from texture handle to layer index
+    pub texture_handles: HashMap<Handle<Image>, u32>,
+}
+
+impl TextureArrayAtlas {
+    /// The number of textures in the [`TextureArrayAtlas`]
+    pub fn len(&self) -> usize {
+        self.texture_handles.len()
+    }
+
+    /// Returns `true` if there are no textures in the [`TextureArrayAtlas`]
+    pub fn is_empty(&self) -> bool {
+        self.texture_handles.is_empty()
+    }
+
+    /// Returns the layer of the texture corresponding to the given image handle
+    pub fn get_texture_layer(&self, texture: &Handle<Image>) -> Option<u32> {
+        self.texture_handles.get(texture).copied()
+    }
 }
 
 #[derive(Component, Debug, Clone, Reflect, FromReflect)]
 #[reflect(Component)]
 pub struct TextureAtlasSprite {
     /// The tint color used to draw the sprite, defaulting to [`Color::WHITE`]
+    ///
+    /// Like [`Sprite::color`](crate::Sprite::color), it isn't clamped, so it can be used to make
+    /// sprites bloom with an HDR camera.
     pub color: Color,
     /// Texture index in [`TextureAtlas`]
     pub index: usize,
@@ -74,6 +121,8 @@ impl TextureAtlas {
             size: dimensions,
             texture_handles: None,
             textures: Vec::new(),
+            user_data: Vec::new(),
+            frozen: false,
         }
     }
 
@@ -92,36 +141,44 @@ impl TextureAtlas {
     ) -> TextureAtlas {
         let padding = padding.unwrap_or_default();
         let offset = offset.unwrap_or_default();
-        let mut sprites = Vec::new();
-        let mut current_padding = Vec2::ZERO;
+        let grid_size = Vec2::new(columns as f32, rows as f32);
+        // The padding is only between the cells
+        let used_padding = Vec2::select(grid_size.cmpgt(Vec2::ONE), padding, Vec2::ZERO);
+        let size = ((tile_size + used_padding) * grid_size) - used_padding;
 
-        for y in 0..rows {
-            if y > 0 {
-                current_padding.y = padding.y;
-            }
-            for x in 0..columns {
-                if x > 0 {
-                    current_padding.x = padding.x;
+        let whole = |v: Vec2| v.cmpge(Vec2::ZERO).all() && v.fract() == Vec2::ZERO;
+        let sprites = if tile_size.cmpgt(Vec2::ZERO).all()
+            && whole(tile_size)
+            && whole(padding)
+            && whole(offset)
+        {
+            // Pixel aligned grids are tiled exactly in integer coordinates
+            URect::from_corners(UVec2::ZERO, (offset + size).as_uvec2())
+                .tiles(tile_size.as_uvec2(), padding.as_uvec2(), offset.as_uvec2())
+                .map(|tile| tile.as_rect())
+                .collect()
+        } else {
+            let mut sprites = Vec::new();
+            for y in 0..rows {
+                for x in 0..columns {
+                    let cell = Vec2::new(x as f32, y as f32);
+                    let rect_min = (tile_size + padding) * cell + offset;
+                    sprites.push(Rect {
+                        min: rect_min,
+                        max: rect_min + tile_size,
+                    });
                 }
-
-                let cell = Vec2::new(x as f32, y as f32);
-
-                let rect_min = (tile_size + current_padding) * cell + offset;
-
-                sprites.push(Rect {
-                    min: rect_min,
-                    max: rect_min + tile_size,
-                });
             }
-        }
-
-        let grid_size = Vec2::new(columns as f32, rows as f32);
+            sprites
+        };
 
         TextureAtlas {
-            size: ((tile_size + current_padding) * grid_size) - current_padding,
+            size,
             textures: sprites,
             texture,
             texture_handles: None,
+            user_data: Vec::new(),
+            frozen: false,
         }
     }
 
@@ -132,11 +189,84 @@ impl TextureAtlas {
     ///
     /// * `rect` - The section of the atlas that contains the texture to be added,
     /// from the top-left corner of the texture to the bottom-right corner
+    ///
+    /// # Panics
+    /// Panics if the atlas is [frozen](TextureAtlas::freeze).
     pub fn add_texture(&mut self, rect: Rect) -> usize {
+        assert!(
+            !self.frozen,
+            "Can't add a texture to a frozen texture atlas, its indices may already be in use"
+        );
         self.textures.push(rect);
         self.textures.len() - 1
     }
 
+    /// Add a sprite to the list of textures in the [`TextureAtlas`] along with a payload, which
+    /// can later be retrieved with [`TextureAtlas::data`] using the returned index.
+    ///
+    /// This lets callers associate e.g. glyph ids or metric indices with the packed texture,
+    /// keeping the atlas as the single source of truth keyed by index.
+    ///
+    /// # Panics
+    /// Panics if the atlas is [frozen](TextureAtlas::freeze).
+    pub fn add_texture_with_data(&mut self, rect: Rect, data: u64) -> usize {
+        let index = self.add_texture(rect);
+        self.set_data(index, Some(data));
+        index
+    }
+
+    /// Returns the payload of the texture at `index`, if it was added with
+    /// [`TextureAtlas::add_texture_with_data`].
+    pub fn data(&self, index: usize) -> Option<u64> {
+        self.user_data.get(index).copied().flatten()
+    }
+
+    /// Sets the payload of the texture at `index`, or removes it if `data` is `None`.
+    pub(crate) fn set_data(&mut self, index: usize, data: Option<u64>) {
+        if index >= self.user_data.len() {
+            if data.is_none() {
+                return;
+            }
+            self.user_data.resize(index + 1, None);
+        }
+        self.user_data[index] = data;
+    }
+
+    /// Marks the layout of the atlas as final, so that [`TextureAtlas::add_texture`] panics
+    /// instead of adding textures to it.
+    ///
+    /// This guards atlases shared by many sprites against being changed after the sprites
+    /// captured indices into them. The public fields can still be modified directly.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Returns the atlas, [frozen](TextureAtlas::freeze).
+    #[must_use]
+    pub fn frozen(mut self) -> Self {
+        self.freeze();
+        self
+    }
+
+    /// Returns `true` if the atlas is [frozen](TextureAtlas::freeze).
+    pub fn is_frozen(&self) -> bool {
+        self.frozen
+    }
+
+    /// Shifts the [`Rect`] of every texture in the atlas by `offset` pixels.
+    ///
+    /// This is used after the atlas texture is copied into a larger image at `offset`, to point
+    /// the layout at where the textures ended up. The [`size`](TextureAtlas::size) and
+    /// [`texture`](TextureAtlas::texture) of the atlas are left for the caller to update, and
+    /// since the indices don't change, frozen atlases can be translated too.
+    pub fn translate(&mut self, offset: IVec2) {
+        let offset = offset.as_vec2();
+        for rect in &mut self.textures {
+            rect.min += offset;
+            rect.max += offset;
+        }
+    }
+
     /// The number of textures in the [`TextureAtlas`]
     pub fn len(&self) -> usize {
         self.textures.len()
@@ -153,4 +283,127 @@ impl TextureAtlas {
             .as_ref()
             .and_then(|texture_handles| texture_handles.get(texture).cloned())
     }
+
+    /// Returns the index of the first texture containing the pixel at `coord` in the
+    /// [`TextureAtlas`], from its top-left corner.
+    ///
+    /// A pixel is contained in a texture when it is inside its [`Rect`], excluding the right and
+    /// bottom edges, so that it is only found in one of two textures next to each other. The
+    /// textures are scanned in index order, so this is linear in the number of textures.
+    pub fn index_at(&self, coord: UVec2) -> Option<usize> {
+        let coord = coord.as_vec2();
+        self.textures
+            .iter()
+            .position(|rect| (coord.cmpge(rect.min) & coord.cmplt(rect.max)).all())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "frozen texture atlas")]
+    fn frozen_atlas_rejects_textures() {
+        let mut atlas = TextureAtlas::new_empty(Handle::default(), Vec2::splat(32.0));
+        assert_eq!(atlas.add_texture(Rect::new(0.0, 0.0, 16.0, 16.0)), 0);
+        atlas.freeze();
+        assert!(atlas.is_frozen());
+        atlas.add_texture(Rect::new(16.0, 0.0, 32.0, 16.0));
+    }
+
+    #[test]
+    fn frozen_atlas_stays_frozen_through_reflection() {
+        let atlas = TextureAtlas::new_empty(Handle::default(), Vec2::splat(32.0)).frozen();
+        let reflected = atlas.clone_value();
+        assert!(TextureAtlas::from_reflect(&*reflected).unwrap().is_frozen());
+    }
+
+    #[test]
+    fn user_data_is_kept_per_texture() {
+        let mut atlas = TextureAtlas::new_empty(Handle::default(), Vec2::splat(32.0));
+        let plain = atlas.add_texture(Rect::new(0.0, 0.0, 16.0, 16.0));
+        let glyph = atlas.add_texture_with_data(Rect::new(16.0, 0.0, 32.0, 16.0), 42);
+        let last = atlas.add_texture(Rect::new(0.0, 16.0, 16.0, 32.0));
+        assert_eq!(atlas.data(plain), None);
+        assert_eq!(atlas.data(glyph), Some(42));
+        assert_eq!(atlas.data(last), None);
+        assert_eq!(atlas.data(99), None);
+    }
+
+    #[test]
+    fn from_grid_places_cells_row_major() {
+        let atlas = TextureAtlas::from_grid(
+            Handle::default(),
+            Vec2::new(8.0, 4.0),
+            2,
+            2,
+            Some(Vec2::new(2.0, 1.0)),
+            Some(Vec2::new(1.0, 3.0)),
+        );
+        assert_eq!(
+            atlas.textures,
+            [
+                Rect::new(1.0, 3.0, 9.0, 7.0),
+                Rect::new(11.0, 3.0, 19.0, 7.0),
+                Rect::new(1.0, 8.0, 9.0, 12.0),
+                Rect::new(11.0, 8.0, 19.0, 12.0),
+            ]
+        );
+        assert_eq!(atlas.size, Vec2::new(18.0, 9.0));
+
+        // Grids that aren't pixel aligned are laid out the same way
+        let atlas = TextureAtlas::from_grid(
+            Handle::default(),
+            Vec2::new(0.5, 4.0),
+            3,
+            1,
+            Some(Vec2::new(0.25, 1.0)),
+            None,
+        );
+        assert_eq!(
+            atlas.textures,
+            [
+                Rect::new(0.0, 0.0, 0.5, 4.0),
+                Rect::new(0.75, 0.0, 1.25, 4.0),
+                Rect::new(1.5, 0.0, 2.0, 4.0),
+            ]
+        );
+        assert_eq!(atlas.size, Vec2::new(2.0, 4.0));
+    }
+
+    #[test]
+    fn index_at_finds_the_texture_under_a_pixel() {
+        let atlas = TextureAtlas::from_grid(
+            Handle::default(),
+            Vec2::splat(16.0),
+            2,
+            2,
+            Some(Vec2::splat(2.0)),
+            None,
+        );
+        assert_eq!(atlas.index_at(UVec2::new(0, 0)), Some(0));
+        assert_eq!(atlas.index_at(UVec2::new(15, 15)), Some(0));
+        // The padding between the textures
+        assert_eq!(atlas.index_at(UVec2::new(16, 0)), None);
+        assert_eq!(atlas.index_at(UVec2::new(18, 0)), Some(1));
+        assert_eq!(atlas.index_at(UVec2::new(0, 18)), Some(2));
+        assert_eq!(atlas.index_at(UVec2::new(33, 33)), Some(3));
+        assert_eq!(atlas.index_at(UVec2::new(34, 34)), None);
+    }
+
+    #[test]
+    fn translate_shifts_every_texture() {
+        let mut atlas =
+            TextureAtlas::from_grid(Handle::default(), Vec2::splat(16.0), 2, 1, None, None);
+        atlas.translate(IVec2::new(64, -8));
+        assert_eq!(
+            atlas.textures,
+            [
+                Rect::new(64.0, -8.0, 80.0, 8.0),
+                Rect::new(80.0, -8.0, 96.0, 8.0)
+            ]
+        );
+        assert_eq!(atlas.size, Vec2::new(32.0, 16.0));
+    }
 }
@@ -1,18 +1,20 @@
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{Assets, DerivedAssets, Handle, HandleId};
+use bevy_ecs::world::{Mut, World};
 use bevy_log::{debug, error, warn};
-use bevy_math::{Rect, Vec2};
+use bevy_math::{Rect, UVec2, Vec2};
 use bevy_render::{
-    render_resource::{Extent3d, TextureDimension, TextureFormat},
-    texture::{Image, TextureFormatPixelInfo},
+    render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages},
+    texture::{Image, ImageMemoryTag, TextureError, TextureFormatPixelInfo},
 };
 use bevy_utils::HashMap;
 use rectangle_pack::{
     contains_smallest_box, pack_rects, volume_heuristic, GroupedRectsToPlace, PackedLocation,
-    RectToInsert, TargetBin,
+    RectToInsert, RectanglePackError, TargetBin,
 };
+use std::{cmp::Reverse, collections::BTreeMap};
 use thiserror::Error;
 
-use crate::texture_atlas::TextureAtlas;
+use crate::texture_atlas::{TextureArrayAtlas, TextureAtlas};
 
 #[derive(Debug, Error)]
 pub enum TextureAtlasBuilderError {
@@ -20,6 +22,54 @@ pub enum TextureAtlasBuilderError {
     NotEnoughSpace,
     #[error("added a texture with the wrong format in an atlas")]
     WrongFormat,
+    #[error("added a {actual} texture to an array atlas of {expected} textures")]
+    WrongSize { expected: Vec2, actual: Vec2 },
+    #[error("no textures were added to the array atlas")]
+    Empty,
+    #[error("could not copy a texture into the array atlas: {0}")]
+    Texture(#[from] TextureError),
+    #[error("the texture {0:?} of the atlas is not loaded")]
+    MissingTexture(HandleId),
+    #[error(
+        "the background pixel is {actual} bytes but the atlas format has {expected} byte pixels"
+    )]
+    WrongBackgroundSize { expected: usize, actual: usize },
+}
+
+/// How a [`TextureAtlasBuilder`] grows the atlas when the textures don't fit in it, see
+/// [`TextureAtlasBuilder::growth_policy`].
+///
+/// Each axis that grows is set to the next power of two, without exceeding the max size.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasGrowthPolicy {
+    /// Grows into a square with sides of the next power of two above the largest side.
+    Square,
+    /// Grows the height, then the width once the height reaches the max size, like for tile
+    /// sets laid out in columns.
+    GrowHeight,
+    /// Grows the width, then the height once the width reaches the max size.
+    GrowWidth,
+    /// Grows both sides, keeping the aspect ratio of the initial size.
+    #[default]
+    KeepAspect,
+}
+
+impl AtlasGrowthPolicy {
+    /// Returns the size to try after `size`, which is at most `max_size`.
+    pub fn grow(self, size: UVec2, max_size: UVec2) -> UVec2 {
+        let next = |side: u32| (side + 1).next_power_of_two();
+        let grown = match self {
+            AtlasGrowthPolicy::Square => UVec2::splat(next(size.max_element())),
+            AtlasGrowthPolicy::GrowHeight if size.y < max_size.y => {
+                UVec2::new(size.x, next(size.y))
+            }
+            AtlasGrowthPolicy::GrowHeight => UVec2::new(next(size.x), size.y),
+            AtlasGrowthPolicy::GrowWidth if size.x < max_size.x => UVec2::new(next(size.x), size.y),
+            AtlasGrowthPolicy::GrowWidth => UVec2::new(size.x, next(size.y)),
+            AtlasGrowthPolicy::KeepAspect => size * 2,
+        };
+        grown.min(max_size)
+    }
 }
 
 #[derive(Debug)]
@@ -34,10 +84,21 @@ pub struct TextureAtlasBuilder {
     initial_size: Vec2,
     /// The absolute maximum size of the texture atlas in pixels.
     max_size: Vec2,
-    /// The texture format for the textures that will be loaded in the atlas.
-    format: TextureFormat,
+    /// How the atlas grows when the textures don't fit in it.
+    growth_policy: AtlasGrowthPolicy,
+    /// The texture format for the textures that will be loaded in the atlas, see
+    /// [`TextureAtlasBuilder::format`].
+    format: Option<TextureFormat>,
     /// Enable automatic format conversion for textures if they are not in the atlas format.
     auto_format_conversion: bool,
+    /// The added textures in order, used when building an array atlas.
+    texture_handles: Vec<Handle<Image>>,
+    /// The pixel the atlas is filled with before the textures are copied in it, see
+    /// [`TextureAtlasBuilder::background`].
+    background: Option<Vec<u8>>,
+    /// Whether the layout only depends on the added textures, see
+    /// [`TextureAtlasBuilder::deterministic`].
+    deterministic: bool,
 }
 
 impl Default for TextureAtlasBuilder {
@@ -46,8 +107,12 @@ impl Default for TextureAtlasBuilder {
             rects_to_place: GroupedRectsToPlace::new(),
             initial_size: Vec2::new(256., 256.),
             max_size: Vec2::new(2048., 2048.),
-            format: TextureFormat::Rgba8UnormSrgb,
+            growth_policy: AtlasGrowthPolicy::default(),
+            format: None,
             auto_format_conversion: true,
+            texture_handles: Vec::new(),
+            background: None,
+            deterministic: false,
         }
     }
 }
@@ -67,9 +132,21 @@ impl TextureAtlasBuilder {
         self
     }
 
+    /// Sets how the atlas grows from its initial size when the textures don't fit in it.
+    ///
+    /// Defaults to [`AtlasGrowthPolicy::KeepAspect`], which doubles both sides.
+    pub fn growth_policy(mut self, growth_policy: AtlasGrowthPolicy) -> Self {
+        self.growth_policy = growth_policy;
+        self
+    }
+
     /// Sets the texture format for textures in the atlas.
+    ///
+    /// By default, the atlas keeps the format of its textures if they all share the same
+    /// uncompressed format, like `Rgba16Float` for HDR sprites, and uses `Rgba8UnormSrgb`
+    /// otherwise.
     pub fn format(mut self, format: TextureFormat) -> Self {
-        self.format = format;
+        self.format = Some(format);
         self
     }
 
@@ -79,8 +156,36 @@ impl TextureAtlasBuilder {
         self
     }
 
+    /// Sets the pixel the atlas is filled with before the textures are copied in it, which is
+    /// what the gaps between textures look like.
+    ///
+    /// The pixel is in the atlas format, so it is a single byte for an `R8Unorm` atlas, and it
+    /// must be premultiplied for a premultiplied atlas. Defaults to all zeroes, which is
+    /// transparent black for RGBA formats.
+    pub fn background(mut self, pixel: &[u8]) -> Self {
+        self.background = Some(pixel.to_vec());
+        self
+    }
+
+    /// Control whether the textures are packed in an order that only depends on the added
+    /// textures, so that the same textures always give the same atlas and the same indices,
+    /// whatever order they were added in.
+    ///
+    /// The textures are packed from the largest area to the smallest, then by their pixels and
+    /// by their handle. Textures that only differ by their handle are placed by the order of
+    /// their handle ids, which are stable for loaded textures but random for textures added to
+    /// [`Assets`] directly, so their placement may swap between runs.
+    ///
+    /// This is slower than the default packing, which can give different layouts and indices
+    /// between runs, even for the same textures added in the same order.
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
     /// Adds a texture to be copied to the texture atlas.
     pub fn add_texture(&mut self, texture_handle: Handle<Image>, texture: &Image) {
+        self.texture_handles.push(texture_handle.clone_weak());
         self.rects_to_place.push_rect(
             texture_handle,
             None,
@@ -115,27 +220,48 @@ impl TextureAtlasBuilder {
     }
 
     fn copy_converted_texture(
-        &self,
+        format: TextureFormat,
         atlas_texture: &mut Image,
         texture: &Image,
         packed_location: &PackedLocation,
     ) {
-        if self.format == texture.texture_descriptor.format {
+        if format == texture.texture_descriptor.format {
             Self::copy_texture_to_atlas(atlas_texture, texture, packed_location);
-        } else if let Some(converted_texture) = texture.convert(self.format) {
+        } else if let Some(converted_texture) = texture
+            .to_format(format)
+            .or_else(|| texture.convert(format))
+        {
             debug!(
                 "Converting texture from '{:?}' to '{:?}'",
-                texture.texture_descriptor.format, self.format
+                texture.texture_descriptor.format, format
             );
             Self::copy_texture_to_atlas(atlas_texture, &converted_texture, packed_location);
         } else {
             error!(
                 "Error converting texture from '{:?}' to '{:?}', ignoring",
-                texture.texture_descriptor.format, self.format
+                texture.texture_descriptor.format, format
             );
         }
     }
 
+    /// The format of the atlas texture: the configured format, or else the format shared by all
+    /// the added textures if it is uncompressed.
+    fn atlas_format<'t>(
+        &self,
+        get_texture: impl Fn(&Handle<Image>) -> Option<&'t Image>,
+    ) -> TextureFormat {
+        self.format
+            .or_else(|| {
+                shared_uncompressed_format(
+                    self.texture_handles
+                        .iter()
+                        .filter_map(get_texture)
+                        .map(|texture| texture.texture_descriptor.format),
+                )
+            })
+            .unwrap_or(TextureFormat::Rgba8UnormSrgb)
+    }
+
     /// Consumes the builder and returns a result with a new texture atlas.
     ///
     /// Internally it copies all rectangles from the textures and copies them
@@ -151,6 +277,129 @@ impl TextureAtlasBuilder {
         self,
         textures: &mut Assets<Image>,
     ) -> Result<TextureAtlas, TextureAtlasBuilderError> {
+        let (atlas_texture, texture_rects, texture_handles) =
+            self.pack(|handle| textures.get(handle))?;
+        let size = Vec2::new(
+            atlas_texture.texture_descriptor.size.width as f32,
+            atlas_texture.texture_descriptor.size.height as f32,
+        );
+        Ok(TextureAtlas {
+            textures: texture_rects,
+            texture_handles: Some(texture_handles),
+            ..TextureAtlas::new_empty(textures.add(atlas_texture), size)
+        })
+    }
+
+    /// Consumes the builder and adds a new texture atlas to `texture_atlases`, which is packed
+    /// again whenever one of its textures is modified, like when it is hot reloaded.
+    ///
+    /// The atlas and its texture are updated in place, so their handles stay valid and each
+    /// texture keeps its index. See [`DerivedAssets`].
+    ///
+    /// # Errors
+    ///
+    /// Fails like [`TextureAtlasBuilder::finish`]. Errors when packing the atlas again are logged.
+    pub fn finish_derived(
+        self,
+        textures: &mut Assets<Image>,
+        texture_atlases: &mut Assets<TextureAtlas>,
+        derived_assets: &mut DerivedAssets,
+    ) -> Result<Handle<TextureAtlas>, TextureAtlasBuilderError> {
+        let (initial_size, max_size, growth_policy, format, auto_format_conversion, deterministic) = (
+            self.initial_size,
+            self.max_size,
+            self.growth_policy,
+            self.format,
+            self.auto_format_conversion,
+            self.deterministic,
+        );
+        let sources: Vec<Handle<Image>> = self
+            .texture_handles
+            .iter()
+            .map(Handle::clone_weak)
+            .collect();
+        let texture_atlas = self.finish(textures)?;
+        let atlas_texture = texture_atlas.texture.clone_weak();
+        let texture_atlas = texture_atlases.add(texture_atlas);
+
+        let source_ids: Vec<HandleId> = sources.iter().map(Handle::id).collect();
+        let targets = [texture_atlas.id(), atlas_texture.id()];
+        let atlas = texture_atlas.clone_weak();
+        let rebuild = move |world: &mut World| {
+            world.resource_scope(|world, mut textures: Mut<Assets<Image>>| {
+                let mut builder = TextureAtlasBuilder {
+                    initial_size,
+                    max_size,
+                    growth_policy,
+                    format,
+                    auto_format_conversion,
+                    deterministic,
+                    ..Default::default()
+                };
+                for source in &sources {
+                    let texture = textures
+                        .get(source)
+                        .ok_or(TextureAtlasBuilderError::MissingTexture(source.id()))?;
+                    builder.add_texture(source.clone_weak(), texture);
+                }
+                let (packed_texture, packed_rects, packed_indices) =
+                    builder.pack(|handle| textures.get(handle))?;
+
+                let mut texture_atlases = world.resource_mut::<Assets<TextureAtlas>>();
+                let Some(texture_atlas) = texture_atlases.get_mut(&atlas) else {
+                    return Ok(());
+                };
+                texture_atlas.size = packed_texture.size();
+                // Keep the indices of the textures, which sprites already use
+                if let Some(texture_handles) = &texture_atlas.texture_handles {
+                    for (handle, &index) in texture_handles {
+                        if let Some(&packed_index) = packed_indices.get(handle) {
+                            texture_atlas.textures[index] = packed_rects[packed_index];
+                        }
+                    }
+                } else {
+                    // The indices change, so the payloads can't be matched to the textures
+                    texture_atlas.textures = packed_rects;
+                    texture_atlas.texture_handles = Some(packed_indices);
+                    texture_atlas.user_data.clear();
+                }
+                if let Some(texture) = textures.get_mut(&atlas_texture) {
+                    *texture = packed_texture;
+                }
+                Ok::<_, TextureAtlasBuilderError>(())
+            })?;
+            Ok(())
+        };
+        derived_assets
+            .register(source_ids, targets, rebuild)
+            .expect("a new texture atlas is not the source of other assets");
+        Ok(texture_atlas)
+    }
+
+    /// Packs the added textures, found with `get_texture`, into a new atlas texture.
+    ///
+    /// Returns the atlas texture, the area of each texture in it, and the index of the area of
+    /// each texture handle.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn pack<'t>(
+        self,
+        get_texture: impl Fn(&Handle<Image>) -> Option<&'t Image>,
+    ) -> Result<(Image, Vec<Rect>, HashMap<Handle<Image>, usize>), TextureAtlasBuilderError> {
+        let format = self.atlas_format(&get_texture);
+        if let Some(background) = &self.background {
+            if background.len() != format.pixel_size() {
+                return Err(TextureAtlasBuilderError::WrongBackgroundSize {
+                    expected: format.pixel_size(),
+                    actual: background.len(),
+                });
+            }
+        }
+        // The textures to pack one at a time, in a deterministic order
+        let sorted_textures = if self.deterministic {
+            Some(self.sorted_textures(&get_texture)?)
+        } else {
+            None
+        };
         let initial_width = self.initial_size.x as u32;
         let initial_height = self.initial_size.y as u32;
         let max_width = self.max_size.x as u32;
@@ -168,33 +417,61 @@ impl TextureAtlasBuilder {
 
             let last_attempt = current_height == max_height && current_width == max_width;
 
-            let mut target_bins = std::collections::BTreeMap::new();
-            target_bins.insert(0, TargetBin::new(current_width, current_height, 1));
-            rect_placements = match pack_rects(
-                &self.rects_to_place,
-                &mut target_bins,
-                &volume_heuristic,
-                &contains_smallest_box,
-            ) {
+            let packed = match &sorted_textures {
+                Some(sorted_textures) => {
+                    pack_in_order(sorted_textures, current_width, current_height)
+                }
+                None => {
+                    let mut target_bins = BTreeMap::new();
+                    target_bins.insert(0, TargetBin::new(current_width, current_height, 1));
+                    pack_rects(
+                        &self.rects_to_place,
+                        &mut target_bins,
+                        &volume_heuristic,
+                        &contains_smallest_box,
+                    )
+                    .map(|rect_placements| {
+                        rect_placements
+                            .packed_locations()
+                            .iter()
+                            .map(|(texture_handle, (_, packed_location))| {
+                                (texture_handle.clone_weak(), *packed_location)
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                }
+            };
+            rect_placements = match packed {
                 Ok(rect_placements) => {
-                    atlas_texture = Image::new(
-                        Extent3d {
-                            width: current_width,
-                            height: current_height,
-                            depth_or_array_layers: 1,
-                        },
-                        TextureDimension::D2,
-                        vec![
-                            0;
-                            self.format.pixel_size() * (current_width * current_height) as usize
-                        ],
-                        self.format,
-                    );
+                    let size = Extent3d {
+                        width: current_width,
+                        height: current_height,
+                        depth_or_array_layers: 1,
+                    };
+                    atlas_texture = match &self.background {
+                        Some(background) => {
+                            Image::new_fill(size, TextureDimension::D2, background, format)
+                        }
+                        None => Image::new(
+                            size,
+                            TextureDimension::D2,
+                            vec![
+                                0;
+                                format.pixel_size() * (current_width * current_height) as usize
+                            ],
+                            format,
+                        ),
+                    };
+                    atlas_texture.memory_tag = ImageMemoryTag::SpriteAtlas;
                     Some(rect_placements)
                 }
-                Err(rectangle_pack::RectanglePackError::NotEnoughBinSpace) => {
-                    current_height = (current_height * 2).clamp(0, max_height);
-                    current_width = (current_width * 2).clamp(0, max_width);
+                Err(RectanglePackError::NotEnoughBinSpace) => {
+                    let grown = self.growth_policy.grow(
+                        UVec2::new(current_width, current_height),
+                        UVec2::new(max_width, max_height),
+                    );
+                    current_width = grown.x;
+                    current_height = grown.y;
                     None
                 }
             };
@@ -206,10 +483,10 @@ impl TextureAtlasBuilder {
 
         let rect_placements = rect_placements.ok_or(TextureAtlasBuilderError::NotEnoughSpace)?;
 
-        let mut texture_rects = Vec::with_capacity(rect_placements.packed_locations().len());
+        let mut texture_rects = Vec::with_capacity(rect_placements.len());
         let mut texture_handles = HashMap::default();
-        for (texture_handle, (_, packed_location)) in rect_placements.packed_locations().iter() {
-            let texture = textures.get(texture_handle).unwrap();
+        for (texture_handle, packed_location) in &rect_placements {
+            let texture = get_texture(texture_handle).unwrap();
             let min = Vec2::new(packed_location.x() as f32, packed_location.y() as f32);
             let max = min
                 + Vec2::new(
@@ -218,23 +495,307 @@ impl TextureAtlasBuilder {
                 );
             texture_handles.insert(texture_handle.clone_weak(), texture_rects.len());
             texture_rects.push(Rect { min, max });
-            if texture.texture_descriptor.format != self.format && !self.auto_format_conversion {
+            if texture.texture_descriptor.format != format && !self.auto_format_conversion {
                 warn!(
                     "Loading a texture of format '{:?}' in an atlas with format '{:?}'",
-                    texture.texture_descriptor.format, self.format
+                    texture.texture_descriptor.format, format
                 );
                 return Err(TextureAtlasBuilderError::WrongFormat);
             }
-            self.copy_converted_texture(&mut atlas_texture, texture, packed_location);
+            Self::copy_converted_texture(format, &mut atlas_texture, texture, packed_location);
         }
-        Ok(TextureAtlas {
-            size: Vec2::new(
-                atlas_texture.texture_descriptor.size.width as f32,
-                atlas_texture.texture_descriptor.size.height as f32,
-            ),
-            texture: textures.add(atlas_texture),
-            textures: texture_rects,
-            texture_handles: Some(texture_handles),
+        Ok((atlas_texture, texture_rects, texture_handles))
+    }
+
+    /// Returns the added textures with their rect, sorted by decreasing area, then by their pixels
+    /// and by their handle.
+    fn sorted_textures<'t>(
+        &self,
+        get_texture: impl Fn(&Handle<Image>) -> Option<&'t Image>,
+    ) -> Result<Vec<(Handle<Image>, RectToInsert)>, TextureAtlasBuilderError> {
+        let mut textures = self
+            .texture_handles
+            .iter()
+            .map(|handle| {
+                get_texture(handle)
+                    .map(|texture| (handle, texture))
+                    .ok_or(TextureAtlasBuilderError::MissingTexture(handle.id()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        textures.sort_by_key(|(handle, texture)| {
+            let size = texture.texture_descriptor.size;
+            (
+                Reverse(size.width as u64 * size.height as u64),
+                Reverse(size.height),
+                &texture.data,
+                handle.id(),
+            )
+        });
+        textures.dedup_by_key(|(handle, _)| handle.id());
+        Ok(textures
+            .into_iter()
+            .map(|(handle, texture)| {
+                let size = texture.texture_descriptor.size;
+                (
+                    handle.clone_weak(),
+                    RectToInsert::new(size.width, size.height, 1),
+                )
+            })
+            .collect())
+    }
+
+    /// Consumes the builder and returns a 2D array texture with one added texture per layer.
+    ///
+    /// All textures must have the same size. Layers are assigned in the order the textures were
+    /// added.
+    ///
+    /// # Errors
+    ///
+    /// If the textures don't all have the same size, or can't be converted to the atlas format,
+    /// an error will be returned.
+    pub fn finish_array(
+        self,
+        textures: &mut Assets<Image>,
+    ) -> Result<TextureArrayAtlas, TextureAtlasBuilderError> {
+        let first = self
+            .texture_handles
+            .first()
+            .ok_or(TextureAtlasBuilderError::Empty)?;
+        let size = textures.get(first).unwrap().size();
+        let format = self.atlas_format(|handle| textures.get(handle));
+
+        let mut array_texture = Image::new_layered(
+            size.as_uvec2(),
+            self.texture_handles.len() as u32,
+            format,
+            TextureUsages::TEXTURE_BINDING | TextureUsages::COPY_DST,
+        );
+        array_texture.memory_tag = ImageMemoryTag::SpriteAtlas;
+
+        let mut texture_handles = HashMap::default();
+        for (layer, texture_handle) in self.texture_handles.iter().enumerate() {
+            let texture = textures.get(texture_handle).unwrap();
+            if texture.size() != size {
+                return Err(TextureAtlasBuilderError::WrongSize {
+                    expected: size,
+                    actual: texture.size(),
+                });
+            }
+            if texture.texture_descriptor.format == format {
+                array_texture.set_layer(layer as u32, texture)?;
+            } else if !self.auto_format_conversion {
+                warn!(
+                    "Loading a texture of format '{:?}' in an atlas with format '{:?}'",
+                    texture.texture_descriptor.format, format
+                );
+                return Err(TextureAtlasBuilderError::WrongFormat);
+            } else if let Some(converted_texture) = texture
+                .to_format(format)
+                .or_else(|| texture.convert(format))
+            {
+                array_texture.set_layer(layer as u32, &converted_texture)?;
+            } else {
+                error!(
+                    "Error converting texture from '{:?}' to '{:?}', ignoring",
+                    texture.texture_descriptor.format, format
+                );
+            }
+            texture_handles.insert(texture_handle.clone_weak(), layer as u32);
+        }
+        Ok(TextureArrayAtlas {
+            texture: textures.add(array_texture),
+            size,
+            texture_handles,
+        })
+    }
+}
+
+/// Returns the format shared by all `formats`, if there is one and it is uncompressed.
+fn shared_uncompressed_format(
+    mut formats: impl Iterator<Item = TextureFormat>,
+) -> Option<TextureFormat> {
+    let first = formats.next()?;
+    (first.block_dimensions() == (1, 1) && formats.all(|format| format == first)).then_some(first)
+}
+
+/// Packs the textures one after the other, in the given order, in an atlas of `width` by
+/// `height` pixels.
+fn pack_in_order(
+    textures: &[(Handle<Image>, RectToInsert)],
+    width: u32,
+    height: u32,
+) -> Result<Vec<(Handle<Image>, PackedLocation)>, RectanglePackError> {
+    // The free sections of the bin are kept between the textures
+    let mut target_bins = BTreeMap::new();
+    target_bins.insert(0, TargetBin::new(width, height, 1));
+    textures
+        .iter()
+        .map(|(texture_handle, rect)| {
+            let mut rects_to_place = GroupedRectsToPlace::<Handle<Image>>::new();
+            rects_to_place.push_rect(texture_handle.clone_weak(), None, *rect);
+            let rect_placements = pack_rects(
+                &rects_to_place,
+                &mut target_bins,
+                &volume_heuristic,
+                &contains_smallest_box,
+            )?;
+            let (_, packed_location) = rect_placements.packed_locations()[texture_handle];
+            Ok((texture_handle.clone_weak(), packed_location))
         })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_shared_uncompressed_format() {
+        let shared =
+            |formats: &[TextureFormat]| shared_uncompressed_format(formats.iter().copied());
+        assert_eq!(
+            shared(&[TextureFormat::Rgba16Float, TextureFormat::Rgba16Float]),
+            Some(TextureFormat::Rgba16Float)
+        );
+        assert_eq!(
+            shared(&[TextureFormat::Rgba16Float, TextureFormat::Rgba8UnormSrgb]),
+            None
+        );
+        assert_eq!(shared(&[TextureFormat::Bc1RgbaUnormSrgb]), None);
+        assert_eq!(shared(&[]), None);
+    }
+
+    #[test]
+    fn grows_along_the_preferred_axis() {
+        let texture = Image::new_fill(
+            Extent3d {
+                width: 32,
+                height: 32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let packed_size = |growth_policy| {
+            let mut builder = TextureAtlasBuilder::default()
+                .initial_size(Vec2::new(32., 32.))
+                .growth_policy(growth_policy);
+            for _ in 0..2 {
+                builder.add_texture(Handle::weak(HandleId::random::<Image>()), &texture);
+            }
+            let (atlas_texture, ..) = builder.pack(|_| Some(&texture)).unwrap();
+            atlas_texture.size()
+        };
+        assert_eq!(packed_size(AtlasGrowthPolicy::Square), Vec2::new(64., 64.));
+        assert_eq!(
+            packed_size(AtlasGrowthPolicy::GrowHeight),
+            Vec2::new(32., 64.)
+        );
+        assert_eq!(
+            packed_size(AtlasGrowthPolicy::GrowWidth),
+            Vec2::new(64., 32.)
+        );
+        assert_eq!(
+            packed_size(AtlasGrowthPolicy::KeepAspect),
+            Vec2::new(64., 64.)
+        );
+
+        // Once the preferred axis reaches the max size, the other one grows
+        let max_size = UVec2::new(64, 64);
+        let grow = |size| AtlasGrowthPolicy::GrowHeight.grow(size, max_size);
+        assert_eq!(grow(UVec2::new(32, 64)), UVec2::new(64, 64));
+        assert_eq!(
+            AtlasGrowthPolicy::Square.grow(UVec2::new(48, 16), max_size),
+            UVec2::new(64, 64)
+        );
+    }
+
+    #[test]
+    fn fills_gaps_with_the_background() {
+        let texture = Image::new_fill(
+            Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[255],
+            TextureFormat::R8Unorm,
+        );
+        let mut builder = TextureAtlasBuilder::default()
+            .initial_size(Vec2::new(16., 16.))
+            .format(TextureFormat::R8Unorm)
+            .background(&[128]);
+        builder.add_texture(Handle::weak(HandleId::random::<Image>()), &texture);
+        let (atlas_texture, rects, _) = builder.pack(|_| Some(&texture)).unwrap();
+
+        let covered = |x: usize, y: usize| rects[0].contains(Vec2::new(x as f32, y as f32) + 0.5);
+        for (i, pixel) in atlas_texture.data.iter().enumerate() {
+            let expected = if covered(i % 16, i / 16) { 255 } else { 128 };
+            assert_eq!(*pixel, expected);
+        }
+
+        let mut builder = TextureAtlasBuilder::default()
+            .format(TextureFormat::R8Unorm)
+            .background(&[0, 0, 0, 255]);
+        builder.add_texture(Handle::weak(HandleId::random::<Image>()), &texture);
+        assert!(matches!(
+            builder.pack(|_| Some(&texture)),
+            Err(TextureAtlasBuilderError::WrongBackgroundSize {
+                expected: 1,
+                actual: 4
+            })
+        ));
+    }
+
+    #[test]
+    fn deterministic_packing_ignores_the_insertion_order() {
+        let texture = |width, height, value| {
+            Image::new_fill(
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                TextureDimension::D2,
+                &[value],
+                TextureFormat::R8Unorm,
+            )
+        };
+        let textures: Vec<_> = [(8, 8, 1), (4, 8, 2), (8, 4, 3), (4, 4, 4), (4, 4, 5)]
+            .into_iter()
+            .map(|(width, height, value)| {
+                (
+                    Handle::<Image>::weak(HandleId::random::<Image>()),
+                    texture(width, height, value),
+                )
+            })
+            .collect();
+        let get_texture = |handle: &Handle<Image>| {
+            textures
+                .iter()
+                .find(|(texture_handle, _)| texture_handle == handle)
+                .map(|(_, texture)| texture)
+        };
+        let pack = |order: &[usize]| {
+            let mut builder = TextureAtlasBuilder::default()
+                .initial_size(Vec2::new(16., 16.))
+                .deterministic(true);
+            for &i in order {
+                builder.add_texture(textures[i].0.clone_weak(), &textures[i].1);
+            }
+            builder.pack(get_texture).unwrap()
+        };
+
+        let (atlas_texture, rects, indices) = pack(&[0, 1, 2, 3, 4]);
+        for order in [[4, 3, 2, 1, 0], [2, 4, 0, 3, 1]] {
+            let (other_texture, other_rects, other_indices) = pack(&order);
+            assert_eq!(atlas_texture.data, other_texture.data);
+            assert_eq!(rects, other_rects);
+            assert_eq!(indices, other_indices);
+        }
+        // The largest texture is packed first
+        assert_eq!(indices[&textures[0].0], 0);
     }
 }
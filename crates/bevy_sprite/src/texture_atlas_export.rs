@@ -0,0 +1,151 @@
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+use std::path::Path;
+use std::path::PathBuf;
+
+use bevy_asset::Handle;
+use bevy_math::{Rect, Vec2};
+use bevy_render::texture::{Image, TextureError};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::TextureAtlas;
+
+/// The serialized layout of a [`TextureAtlas`], written next to its texture by
+/// [`export_texture_atlas`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextureAtlasLayoutFile {
+    /// The texture file name, relative to the layout file
+    pub texture: String,
+    /// The size of the atlas texture
+    pub size: Vec2,
+    /// The specific areas of the atlas where each texture can be found
+    pub textures: Vec<Rect>,
+}
+
+impl TextureAtlasLayoutFile {
+    /// Creates the layout of `atlas`, whose texture is stored in the file named `texture`.
+    pub fn from_texture_atlas(atlas: &TextureAtlas, texture: impl Into<String>) -> Self {
+        Self {
+            texture: texture.into(),
+            size: atlas.size,
+            textures: atlas.textures.clone(),
+        }
+    }
+
+    /// Creates a [`TextureAtlas`] with this layout, using the already loaded `texture`.
+    pub fn into_texture_atlas(self, texture: Handle<Image>) -> TextureAtlas {
+        TextureAtlas {
+            textures: self.textures,
+            ..TextureAtlas::new_empty(texture, self.size)
+        }
+    }
+}
+
+/// The file format used by [`export_texture_atlas`] for the atlas texture.
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AtlasImageFormat {
+    /// KTX2, which keeps mip levels and array layers
+    #[cfg(feature = "ktx2")]
+    Ktx2,
+    /// DirectDraw Surface
+    #[cfg(feature = "dds")]
+    Dds,
+}
+
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+impl AtlasImageFormat {
+    /// The file extension for this format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "ktx2")]
+            AtlasImageFormat::Ktx2 => "ktx2",
+            #[cfg(feature = "dds")]
+            AtlasImageFormat::Dds => "dds",
+        }
+    }
+
+    fn encode(&self, image: &Image) -> Result<Vec<u8>, TextureError> {
+        match self {
+            #[cfg(feature = "ktx2")]
+            AtlasImageFormat::Ktx2 => bevy_render::texture::image_to_ktx2_buffer(image),
+            #[cfg(feature = "dds")]
+            AtlasImageFormat::Dds => bevy_render::texture::image_to_dds_buffer(image),
+        }
+    }
+}
+
+/// An error that occurs when exporting a [`TextureAtlas`].
+#[derive(Error, Debug)]
+pub enum TextureAtlasExportError {
+    #[error("the atlas texture could not be encoded: {0}")]
+    Texture(#[from] TextureError),
+    #[error("the atlas layout could not be serialized: {0}")]
+    Ron(#[from] ron::Error),
+    #[error("failed to write {path:?}: {error}")]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("{0:?} does not have a file name")]
+    InvalidPath(PathBuf),
+}
+
+/// Writes the texture of a runtime built atlas to `path` with the extension of `format`, and its
+/// layout next to it with an `atlas.ron` extension, so it can be shipped as a regular asset.
+///
+/// Returns the paths of the texture and layout files.
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+pub fn export_texture_atlas(
+    atlas: &TextureAtlas,
+    image: &Image,
+    path: impl AsRef<Path>,
+    format: AtlasImageFormat,
+) -> Result<(PathBuf, PathBuf), TextureAtlasExportError> {
+    let path = path.as_ref();
+    let texture_path = path.with_extension(format.extension());
+    let layout_path = path.with_extension("atlas.ron");
+    let Some(texture_name) = texture_path.file_name().and_then(|name| name.to_str()) else {
+        return Err(TextureAtlasExportError::InvalidPath(path.to_path_buf()));
+    };
+
+    let layout = TextureAtlasLayoutFile::from_texture_atlas(atlas, texture_name);
+    let layout = ron::ser::to_string_pretty(&layout, ron::ser::PrettyConfig::default())?;
+    let texture = format.encode(image)?;
+
+    write_file(&texture_path, texture)?;
+    write_file(&layout_path, layout)?;
+    Ok((texture_path, layout_path))
+}
+
+#[cfg(any(feature = "ktx2", feature = "dds"))]
+fn write_file(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), TextureAtlasExportError> {
+    std::fs::write(path, contents).map_err(|error| TextureAtlasExportError::Io {
+        path: path.to_path_buf(),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn layout_round_trip() {
+        let atlas = TextureAtlas {
+            textures: vec![
+                Rect::new(0.0, 0.0, 32.0, 32.0),
+                Rect::new(32.0, 0.0, 64.0, 16.0),
+            ],
+            ..TextureAtlas::new_empty(Handle::default(), Vec2::new(64.0, 32.0))
+        };
+        let layout = TextureAtlasLayoutFile::from_texture_atlas(&atlas, "atlas.ktx2");
+        let serialized = ron::to_string(&layout).unwrap();
+        let deserialized: TextureAtlasLayoutFile = ron::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, layout);
+
+        let loaded = deserialized.into_texture_atlas(Handle::default());
+        assert_eq!(loaded.size, atlas.size);
+        assert_eq!(loaded.textures, atlas.textures);
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use bevy_asset::{AssetIoError, AssetLoader, Handle, HandleId, LoadContext, LoadedAsset};
+use bevy_math::{Rect, Vec2};
+use bevy_reflect::{TypePath, TypeUuid};
+use bevy_render::texture::{CompressedImageFormats, Image, ImageType, TextureError};
+use bevy_utils::{BoxedFuture, HashMap};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{TextureAtlas, TextureAtlasBuilder, TextureAtlasBuilderError, TextureAtlasSprite};
+
+/// The label of the packed [`Image`] loaded by the [`TextureAtlasManifestLoader`].
+pub const ATLAS_IMAGE_LABEL: &str = "image";
+/// The label of the [`TextureAtlas`] loaded by the [`TextureAtlasManifestLoader`].
+pub const ATLAS_LAYOUT_LABEL: &str = "layout";
+
+/// A list of images to pack into a texture atlas, stored in a `.atlas` file.
+///
+/// ```ron
+/// (
+///     textures: {
+///         "goblin": "enemies/goblin.png",
+///         "orc": "enemies/orc.png",
+///     },
+/// )
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TextureAtlasManifest {
+    /// The images to pack by name, relative to the manifest file
+    pub textures: BTreeMap<String, String>,
+    /// The maximum size of the atlas texture, see [`TextureAtlasBuilder::max_size`]
+    #[serde(default)]
+    pub max_size: Option<Vec2>,
+}
+
+/// A texture of an atlas loaded from a [`TextureAtlasManifest`].
+#[derive(Debug, Clone, TypeUuid, TypePath)]
+#[uuid = "2f5a8f3e-6b0e-4f7a-b1d6-7c0a9d3e4b21"]
+pub struct AtlasSprite {
+    /// The atlas the texture was packed in
+    pub layout: Handle<TextureAtlas>,
+    /// The index of the texture in the atlas
+    pub index: usize,
+    /// The atlas texture
+    pub image: Handle<Image>,
+}
+
+impl AtlasSprite {
+    /// Creates a [`TextureAtlasSprite`] drawing this texture.
+    pub fn sprite(&self) -> TextureAtlasSprite {
+        TextureAtlasSprite::new(self.index)
+    }
+}
+
+/// Loads a [`TextureAtlasManifest`] from a `.atlas` file, and packs its images into an atlas.
+///
+/// The packed image is loaded as `sprites.atlas#image`, its [`TextureAtlas`] as
+/// `sprites.atlas#layout`, and each image as an [`AtlasSprite`] labeled with its name, like
+/// `sprites.atlas#goblin`. The atlas is packed again whenever one of its images changes, if
+/// assets are watched for changes.
+#[derive(Default)]
+pub struct TextureAtlasManifestLoader;
+
+impl AssetLoader for TextureAtlasManifestLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy_asset::Error>> {
+        Box::pin(async move {
+            let path = load_context.path().to_path_buf();
+            let manifest: TextureAtlasManifest = ron::de::from_bytes(bytes).map_err(|source| {
+                TextureAtlasManifestError::InvalidManifest {
+                    path: path.clone(),
+                    source,
+                }
+            })?;
+
+            let parent = path.parent().unwrap_or(Path::new(""));
+            let mut images = Vec::with_capacity(manifest.textures.len());
+            for (name, image_path) in &manifest.textures {
+                if name == ATLAS_IMAGE_LABEL || name == ATLAS_LAYOUT_LABEL {
+                    return Err(TextureAtlasManifestError::ReservedName(name.clone()).into());
+                }
+                let image_path = parent.join(image_path);
+                // Reading the image through the load context also reloads the atlas when it changes
+                let bytes = load_context.read_asset_bytes(&image_path).await?;
+                let extension = image_path
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or_default();
+                let image = Image::from_buffer(
+                    &bytes,
+                    ImageType::Extension(extension),
+                    CompressedImageFormats::NONE,
+                    true,
+                )
+                .map_err(|source| TextureAtlasManifestError::InvalidImage {
+                    path: image_path.clone(),
+                    source,
+                })?;
+                images.push((name.clone(), image));
+            }
+
+            let (image, rects, indices) = pack_named_textures(images, manifest.max_size)?;
+            let size = image.size();
+            let image = load_context.set_labeled_asset(ATLAS_IMAGE_LABEL, LoadedAsset::new(image));
+            let layout = load_context.set_labeled_asset(
+                ATLAS_LAYOUT_LABEL,
+                LoadedAsset::new(TextureAtlas {
+                    textures: rects,
+                    ..TextureAtlas::new_empty(image.clone(), size)
+                }),
+            );
+            for (name, index) in indices {
+                load_context.set_labeled_asset(
+                    &name,
+                    LoadedAsset::new(AtlasSprite {
+                        layout: layout.clone(),
+                        index,
+                        image: image.clone(),
+                    }),
+                );
+            }
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["atlas"]
+    }
+}
+
+/// An error that occurs when loading a [`TextureAtlasManifest`].
+#[derive(Error, Debug)]
+pub enum TextureAtlasManifestError {
+    #[error("invalid texture atlas manifest {path:?}: {source}")]
+    InvalidManifest {
+        path: PathBuf,
+        source: ron::error::SpannedError,
+    },
+    #[error("the texture name {0:?} is reserved for the atlas itself")]
+    ReservedName(String),
+    #[error("could not read an image of the texture atlas: {0}")]
+    Io(#[from] AssetIoError),
+    #[error("invalid texture atlas image {path:?}: {source}")]
+    InvalidImage { path: PathBuf, source: TextureError },
+    #[error("could not pack the texture atlas: {0}")]
+    Pack(#[from] TextureAtlasBuilderError),
+}
+
+/// Packs `images` into an atlas texture, returning the texture, the area of each image, and the
+/// index of the area of each image name.
+#[allow(clippy::type_complexity)]
+fn pack_named_textures(
+    images: Vec<(String, Image)>,
+    max_size: Option<Vec2>,
+) -> Result<(Image, Vec<Rect>, HashMap<String, usize>), TextureAtlasManifestError> {
+    let mut builder = TextureAtlasBuilder::default();
+    if let Some(max_size) = max_size {
+        builder = builder.max_size(max_size);
+    }
+    // The builder identifies textures by handle, so each image gets a placeholder one
+    let images: HashMap<Handle<Image>, (String, Image)> = images
+        .into_iter()
+        .map(|image| (Handle::weak(HandleId::random::<Image>()), image))
+        .collect();
+    for (handle, (_, image)) in &images {
+        builder.add_texture(handle.clone_weak(), image);
+    }
+    let (texture, rects, indices) =
+        builder.pack(|handle| images.get(handle).map(|(_, image)| image))?;
+    let indices = indices
+        .into_iter()
+        .map(|(handle, index)| (images[&handle].0.clone(), index))
+        .collect();
+    Ok((texture, rects, indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::UVec2;
+    use bevy_render::color::Color;
+
+    #[test]
+    fn pack_named_textures_by_name() {
+        let images = vec![
+            (
+                "small".to_string(),
+                Image::checkerboard(UVec2::splat(8), 1, Color::RED, Color::RED),
+            ),
+            (
+                "large".to_string(),
+                Image::checkerboard(UVec2::new(32, 16), 1, Color::BLUE, Color::BLUE),
+            ),
+        ];
+        let (texture, rects, indices) = pack_named_textures(images, None).unwrap();
+        assert_eq!(rects.len(), 2);
+        assert_eq!(rects[indices["small"]].size(), Vec2::splat(8.0));
+        assert_eq!(rects[indices["large"]].size(), Vec2::new(32.0, 16.0));
+
+        let min = rects[indices["large"]].min.as_uvec2();
+        assert_eq!(texture.get_pixel(min), Some(&Color::BLUE.as_rgba_u8()[..]));
+    }
+
+    #[test]
+    fn parse_manifest() {
+        let manifest: TextureAtlasManifest =
+            ron::from_str(r#"(textures: { "goblin": "enemies/goblin.png" })"#).unwrap();
+        assert_eq!(manifest.textures["goblin"], "enemies/goblin.png");
+        assert_eq!(manifest.max_size, None);
+    }
+}
@@ -3,7 +3,7 @@ use bevy_asset::{Assets, Handle};
 use bevy_math::Vec2;
 use bevy_render::{
     render_resource::{Extent3d, TextureDimension, TextureFormat},
-    texture::Image,
+    texture::{Image, ImageMemoryTag},
 };
 use bevy_sprite::{DynamicTextureAtlasBuilder, TextureAtlas};
 use bevy_utils::HashMap;
@@ -51,7 +51,7 @@ impl FontAtlas {
         texture_atlases: &mut Assets<TextureAtlas>,
         size: Vec2,
     ) -> FontAtlas {
-        let atlas_texture = textures.add(Image::new_fill(
+        let mut atlas_image = Image::new_fill(
             Extent3d {
                 width: size.x as u32,
                 height: size.y as u32,
@@ -60,7 +60,9 @@ impl FontAtlas {
             TextureDimension::D2,
             &[0, 0, 0, 0],
             TextureFormat::Rgba8UnormSrgb,
-        ));
+        );
+        atlas_image.memory_tag = ImageMemoryTag::FontAtlas;
+        let atlas_texture = textures.add(atlas_image);
         let texture_atlas = TextureAtlas::new_empty(atlas_texture, size);
         Self {
             texture_atlas: texture_atlases.add(texture_atlas),
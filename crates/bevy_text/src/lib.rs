@@ -73,6 +73,11 @@ pub enum YAxisOrientation {
 pub const DEFAULT_FONT_HANDLE: HandleUntyped =
     HandleUntyped::weak_from_u64(Font::TYPE_UUID, 1491772431825224042);
 
+/// The path the default font is registered under, to load it with the `AssetServer` like other
+/// fonts.
+#[cfg(feature = "default_font")]
+pub const DEFAULT_FONT_PATH: &str = "embedded://bevy_text/FiraMono-subset.ttf";
+
 impl Plugin for TextPlugin {
     fn build(&self, app: &mut App) {
         app.add_asset::<Font>()
@@ -113,5 +118,10 @@ impl Plugin for TextPlugin {
             "FiraMono-subset.ttf",
             |bytes: &[u8]| { Font::try_from_bytes(bytes.to_vec()).unwrap() }
         );
+        #[cfg(feature = "default_font")]
+        app.register_builtin_asset(
+            DEFAULT_FONT_PATH,
+            Font::try_from_bytes(include_bytes!("FiraMono-subset.ttf").to_vec()).unwrap(),
+        );
     }
 }
@@ -0,0 +1,269 @@
+use bevy_asset::{AssetEvent, Assets, Handle};
+use bevy_ecs::{change_detection::Ref, prelude::*};
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_render::{
+    camera::{Camera, OrthographicProjection},
+    color::Color,
+    mesh::{Indices, Mesh},
+    render_resource::PrimitiveTopology,
+    view::NoFrustumCulling,
+};
+use bevy_sprite::{ColorMaterial, Mesh2dHandle};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashSet;
+
+use crate::{TileLayer, TileMap, TileProjection, TileRange, TileSet};
+
+/// The mesh a [`TileMap`] is drawn with, added to the entity when its [`TileSet`] is loaded.
+///
+/// The mesh only has a quad for each tile in view of a camera, and is only rebuilt when the map
+/// or its tileset changes, or when the cameras move enough to show other tiles.
+#[derive(Component, Debug, Clone)]
+pub struct TileMapBuffer {
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+    visible: Option<TileRange>,
+    quad_count: usize,
+}
+
+impl TileMapBuffer {
+    /// The mesh of the map
+    pub fn mesh(&self) -> &Handle<Mesh> {
+        &self.mesh
+    }
+
+    /// The material of the map, which samples the texture of the [`TileSet`]
+    pub fn material(&self) -> &Handle<ColorMaterial> {
+        &self.material
+    }
+
+    /// The range of tiles in the mesh, or `None` if no tile is in view of a camera
+    pub fn visible_tiles(&self) -> Option<TileRange> {
+        self.visible
+    }
+
+    /// The number of non-empty tiles in the mesh
+    pub fn quad_count(&self) -> usize {
+        self.quad_count
+    }
+}
+
+/// Builds the mesh of the tiles of `tilemap` in `range`, in drawing order.
+///
+/// Returns the mesh and its number of quads.
+pub fn build_tilemap_mesh(
+    tilemap: &TileMap,
+    tileset: &TileSet,
+    range: Option<TileRange>,
+) -> (Mesh, usize) {
+    let mut coords: Vec<UVec2> = range
+        .into_iter()
+        .flat_map(|range| {
+            (range.min.y..range.max.y)
+                .rev()
+                .flat_map(move |y| (range.min.x..range.max.x).map(move |x| UVec2::new(x, y)))
+        })
+        .collect();
+    // Tiles in front are drawn last, so that tall tiles overlap the ones behind them
+    if tilemap.projection == TileProjection::Isometric {
+        coords.sort_by_key(|coord| std::cmp::Reverse(coord.x + coord.y));
+    }
+
+    let atlas = &tileset.atlas;
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+    let mut indices = Vec::new();
+    for coord in coords {
+        let Some(rect) = tilemap
+            .get(coord)
+            .and_then(|tile| atlas.textures.get(tile.0 as usize))
+        else {
+            continue;
+        };
+        let size = rect.size();
+        // The bottom of the tile sits on the bottom of its cell
+        let bottom = tilemap.tile_space_to_local(coord.as_vec2());
+        let min = match tilemap.projection {
+            TileProjection::Orthographic => bottom,
+            TileProjection::Isometric => bottom - Vec2::new(size.x / 2.0, 0.0),
+        };
+        let quad = Rect::from_corners(min, min + size);
+        let uv = Rect::from_corners(rect.min / atlas.size, rect.max / atlas.size);
+
+        let start = positions.len() as u32;
+        positions.extend([
+            [quad.min.x, quad.min.y, 0.0],
+            [quad.max.x, quad.min.y, 0.0],
+            [quad.max.x, quad.max.y, 0.0],
+            [quad.min.x, quad.max.y, 0.0],
+        ]);
+        uvs.extend([
+            [uv.min.x, uv.max.y],
+            [uv.max.x, uv.max.y],
+            [uv.max.x, uv.min.y],
+            [uv.min.x, uv.min.y],
+        ]);
+        indices.extend([0, 1, 2, 0, 2, 3].map(|i| start + i));
+    }
+
+    let quad_count = indices.len() / 6;
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    (mesh, quad_count)
+}
+
+/// Keeps the [`TileMapBuffer`] of every [`TileMap`] up to date with its tiles, its [`TileLayer`]
+/// and the cameras.
+#[allow(clippy::too_many_arguments)]
+pub fn update_tilemap_buffers(
+    mut commands: Commands,
+    mut tileset_events: EventReader<AssetEvent<TileSet>>,
+    tilesets: Res<Assets<TileSet>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    cameras: Query<(&Camera, &OrthographicProjection, &GlobalTransform)>,
+    mut tilemaps: Query<(
+        Entity,
+        Ref<TileMap>,
+        Option<Ref<TileLayer>>,
+        &GlobalTransform,
+        Option<&mut TileMapBuffer>,
+    )>,
+) {
+    let changed_tilesets: HashSet<Handle<TileSet>> = tileset_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                Some(handle.clone_weak())
+            }
+            AssetEvent::Removed { .. } | AssetEvent::Unused { .. } => None,
+        })
+        .collect();
+
+    // The world space areas seen by the cameras
+    let views: Vec<[Vec2; 4]> = cameras
+        .iter()
+        .filter(|(camera, ..)| camera.is_active)
+        .map(|(_, projection, transform)| {
+            let area = projection.area;
+            [
+                area.min,
+                Vec2::new(area.max.x, area.min.y),
+                Vec2::new(area.min.x, area.max.y),
+                area.max,
+            ]
+            .map(|corner| transform.transform_point(corner.extend(0.0)).truncate())
+        })
+        .collect();
+
+    for (entity, tilemap, layer, transform, buffer) in &mut tilemaps {
+        let Some(tileset) = tilesets.get(&tilemap.tileset) else {
+            continue;
+        };
+
+        let to_local = transform.affine().inverse();
+        let max_tile_size = tileset.max_tile_size();
+        let visible = views
+            .iter()
+            .filter_map(|corners| {
+                let corners =
+                    corners.map(|corner| to_local.transform_point3(corner.extend(0.0)).truncate());
+                let min = corners.iter().fold(Vec2::INFINITY, |min, c| min.min(*c));
+                let max = corners
+                    .iter()
+                    .fold(Vec2::NEG_INFINITY, |max, c| max.max(*c));
+                tilemap.tiles_in_area(Rect::from_corners(min, max), max_tile_size)
+            })
+            .reduce(|a, b| TileRange {
+                min: a.min.min(b.min),
+                max: a.max.max(b.max),
+            });
+        let color = layer.as_ref().map_or(Color::WHITE, |layer| layer.color);
+        let tileset_changed = changed_tilesets.contains(&tilemap.tileset);
+
+        match buffer {
+            Some(mut buffer) => {
+                let layer_changed = layer.as_ref().is_some_and(|layer| layer.is_changed());
+                if tileset_changed || layer_changed {
+                    if let Some(material) = materials.get_mut(&buffer.material) {
+                        material.color = color;
+                        material.texture = Some(tileset.atlas.texture.clone());
+                    }
+                }
+                if tileset_changed || tilemap.is_changed() || buffer.visible != visible {
+                    let (mesh, quad_count) = build_tilemap_mesh(&tilemap, tileset, visible);
+                    if let Some(buffer_mesh) = meshes.get_mut(&buffer.mesh) {
+                        *buffer_mesh = mesh;
+                    }
+                    buffer.visible = visible;
+                    buffer.quad_count = quad_count;
+                }
+            }
+            None => {
+                let (mesh, quad_count) = build_tilemap_mesh(&tilemap, tileset, visible);
+                let buffer = TileMapBuffer {
+                    mesh: meshes.add(mesh),
+                    material: materials.add(ColorMaterial {
+                        color,
+                        texture: Some(tileset.atlas.texture.clone()),
+                    }),
+                    visible,
+                    quad_count,
+                };
+                // The mesh is culled to the cameras when it is built, instead of by its bounds
+                commands.entity(entity).insert((
+                    Mesh2dHandle(buffer.mesh.clone()),
+                    buffer.material.clone(),
+                    buffer,
+                    NoFrustumCulling,
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TileId;
+    use bevy_render::mesh::VertexAttributeValues;
+    use bevy_sprite::TextureAtlas;
+
+    #[test]
+    fn mesh_skips_empty_tiles() {
+        let atlas =
+            TextureAtlas::from_grid(Handle::default(), Vec2::new(16.0, 16.0), 2, 1, None, None);
+        let tileset = TileSet::new(atlas);
+        let mut tilemap = TileMap::new(Handle::default(), 3, 2, UVec2::splat(16));
+        tilemap.set(UVec2::new(0, 0), TileId(0));
+        tilemap.set(UVec2::new(2, 1), TileId(1));
+        // Not in the tileset
+        tilemap.set(UVec2::new(1, 1), TileId(7));
+
+        let all = TileRange {
+            min: UVec2::ZERO,
+            max: tilemap.size(),
+        };
+        let (mesh, quad_count) = build_tilemap_mesh(&tilemap, &tileset, Some(all));
+        assert_eq!(quad_count, 2);
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        // The top row is drawn first
+        assert_eq!(positions[0], [32.0, 16.0, 0.0]);
+        assert_eq!(positions[4], [0.0, 0.0, 0.0]);
+
+        let bottom_left = TileRange {
+            min: UVec2::ZERO,
+            max: UVec2::ONE,
+        };
+        let (_, quad_count) = build_tilemap_mesh(&tilemap, &tileset, Some(bottom_left));
+        assert_eq!(quad_count, 1);
+        let (_, quad_count) = build_tilemap_mesh(&tilemap, &tileset, None);
+        assert_eq!(quad_count, 0);
+    }
+}
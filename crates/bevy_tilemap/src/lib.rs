@@ -0,0 +1,52 @@
+#![allow(clippy::type_complexity)]
+
+//! Tile maps drawn from the tiles of a [`TileSet`], with orthographic or isometric layouts.
+
+mod buffer;
+mod tilemap;
+mod tileset;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        TileId, TileLayer, TileMap, TileMapBundle, TileMapPlugin, TileProjection, TileSet,
+    };
+}
+
+pub use buffer::*;
+pub use tilemap::*;
+pub use tileset::*;
+
+use bevy_app::prelude::*;
+use bevy_asset::AddAsset;
+use bevy_ecs::prelude::*;
+use bevy_render::camera::CameraUpdateSystem;
+use bevy_transform::TransformSystem;
+
+/// Adds support for drawing [`TileMap`]s.
+#[derive(Default)]
+pub struct TileMapPlugin;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
+pub enum TileMapSystem {
+    /// Rebuilds the [`TileMapBuffer`]s of the tile maps that changed or came into view
+    UpdateBuffers,
+}
+
+impl Plugin for TileMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<TileSet>()
+            .register_asset_reflect::<TileSet>()
+            .register_type::<TileId>()
+            .register_type::<TileMap>()
+            .register_type::<TileLayer>()
+            .register_type::<TileProjection>()
+            .add_systems(
+                PostUpdate,
+                update_tilemap_buffers
+                    .in_set(TileMapSystem::UpdateBuffers)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(CameraUpdateSystem),
+            );
+    }
+}
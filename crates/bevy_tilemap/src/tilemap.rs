@@ -0,0 +1,311 @@
+use bevy_asset::Handle;
+use bevy_ecs::{bundle::Bundle, component::Component, reflect::ReflectComponent};
+use bevy_math::{Rect, UVec2, Vec2};
+use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect};
+use bevy_render::{
+    color::Color,
+    view::{ComputedVisibility, Visibility},
+};
+use bevy_transform::components::{GlobalTransform, Transform};
+
+use crate::{TileId, TileSet};
+
+/// How the tiles of a [`TileMap`] are laid out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Debug, Default, PartialEq)]
+pub enum TileProjection {
+    /// Tiles are laid out on a square grid, with columns going right and rows going up.
+    #[default]
+    Orthographic,
+    /// Tiles are laid out on a diamond grid, with columns going up and right and rows going up
+    /// and left, so the tile size is the size of the diamond.
+    Isometric,
+}
+
+/// A grid of tiles from a [`TileSet`], drawn as a single mesh.
+///
+/// The bottom left corner of the first tile is at the origin of the entity. Only the tiles in
+/// view of a camera with an [`OrthographicProjection`](bevy_render::camera::OrthographicProjection)
+/// are added to the mesh, see [`TileMapBuffer`](crate::TileMapBuffer).
+#[derive(Component, Debug, Default, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct TileMap {
+    /// The tiles to draw the map with
+    pub tileset: Handle<TileSet>,
+    /// The tiles of the map row by row, starting from the bottom row.
+    /// Its length must be `width * height`.
+    pub tiles: Vec<TileId>,
+    /// The number of columns
+    pub width: u32,
+    /// The number of rows
+    pub height: u32,
+    /// The size of a cell of the grid
+    pub tile_size: UVec2,
+    /// How the tiles are laid out
+    pub projection: TileProjection,
+}
+
+impl TileMap {
+    /// Creates an orthographic [`TileMap`] of `width` by `height` empty tiles.
+    pub fn new(tileset: Handle<TileSet>, width: u32, height: u32, tile_size: UVec2) -> Self {
+        Self {
+            tileset,
+            tiles: vec![TileId::EMPTY; width as usize * height as usize],
+            width,
+            height,
+            tile_size,
+            projection: TileProjection::Orthographic,
+        }
+    }
+
+    /// Returns the tile map with its tiles laid out with `projection`.
+    #[must_use]
+    pub fn with_projection(mut self, projection: TileProjection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    /// Returns the size of the map in tiles.
+    pub fn size(&self) -> UVec2 {
+        UVec2::new(self.width, self.height)
+    }
+
+    /// Returns the index in [`TileMap::tiles`] of the tile at `coord`, or `None` if it is outside
+    /// of the map.
+    pub fn index(&self, coord: UVec2) -> Option<usize> {
+        (coord.x < self.width && coord.y < self.height)
+            .then(|| coord.y as usize * self.width as usize + coord.x as usize)
+    }
+
+    /// Returns the tile at `coord`, or `None` if it is empty or outside of the map.
+    pub fn get(&self, coord: UVec2) -> Option<TileId> {
+        let tile = *self.tiles.get(self.index(coord)?)?;
+        (!tile.is_empty()).then_some(tile)
+    }
+
+    /// Sets the tile at `coord`.
+    ///
+    /// # Panics
+    /// Panics if `coord` is outside of the map.
+    pub fn set(&mut self, coord: UVec2, tile: TileId) {
+        let index = self.index(coord).unwrap_or_else(|| {
+            panic!(
+                "Tile {coord} is outside of a {}x{} tile map",
+                self.width, self.height
+            )
+        });
+        self.tiles[index] = tile;
+    }
+
+    /// Returns the center of the tile at `coord`, relative to the entity.
+    pub fn tile_center(&self, coord: UVec2) -> Vec2 {
+        self.tile_space_to_local(coord.as_vec2() + 0.5)
+    }
+
+    /// Returns the tile under the point `position` relative to the entity, or `None` if it is
+    /// outside of the map.
+    pub fn tile_at(&self, position: Vec2) -> Option<UVec2> {
+        let coord = self.local_to_tile_space(position).floor();
+        if coord.cmplt(Vec2::ZERO).any() || coord.cmpge(self.size().as_vec2()).any() {
+            return None;
+        }
+        Some(coord.as_uvec2())
+    }
+
+    /// Returns the range of tiles that can cover the `area`, relative to the entity, when drawn
+    /// with tiles of at most `max_tile_size`.
+    ///
+    /// Returns `None` if no tile can cover the area.
+    pub fn tiles_in_area(&self, area: Rect, max_tile_size: Vec2) -> Option<TileRange> {
+        // Tiles larger than a cell grow up and to the right in orthographic maps, and up and to
+        // both sides in isometric maps
+        let overhang = (max_tile_size - self.tile_size.as_vec2()).max(Vec2::ZERO);
+        let area = match self.projection {
+            TileProjection::Orthographic => Rect::from_corners(area.min - overhang, area.max),
+            TileProjection::Isometric => {
+                Rect::from_corners(area.min - overhang, area.max + Vec2::new(overhang.x, 0.0))
+            }
+        };
+
+        let corners = [
+            area.min,
+            Vec2::new(area.max.x, area.min.y),
+            Vec2::new(area.min.x, area.max.y),
+            area.max,
+        ]
+        .map(|corner| self.local_to_tile_space(corner));
+        let min = corners.iter().fold(Vec2::INFINITY, |min, c| min.min(*c));
+        let max = corners
+            .iter()
+            .fold(Vec2::NEG_INFINITY, |max, c| max.max(*c));
+
+        let min = min.floor().max(Vec2::ZERO);
+        let max = max.ceil().min(self.size().as_vec2());
+        if min.cmpge(max).any() {
+            return None;
+        }
+        Some(TileRange {
+            min: min.as_uvec2(),
+            max: max.as_uvec2(),
+        })
+    }
+
+    /// Converts a tile space position, where each tile is a unit square, to a position relative
+    /// to the entity.
+    pub(crate) fn tile_space_to_local(&self, tile: Vec2) -> Vec2 {
+        let size = self.tile_size.as_vec2();
+        match self.projection {
+            TileProjection::Orthographic => tile * size,
+            TileProjection::Isometric => Vec2::new(tile.x - tile.y, tile.x + tile.y) * size / 2.0,
+        }
+    }
+
+    fn local_to_tile_space(&self, position: Vec2) -> Vec2 {
+        let size = self.tile_size.as_vec2().max(Vec2::splat(f32::EPSILON));
+        match self.projection {
+            TileProjection::Orthographic => position / size,
+            TileProjection::Isometric => {
+                let diagonal = position / size * 2.0;
+                Vec2::new(diagonal.y + diagonal.x, diagonal.y - diagonal.x) / 2.0
+            }
+        }
+    }
+}
+
+/// A rectangular range of tiles of a [`TileMap`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileRange {
+    /// The first column and row of the range
+    pub min: UVec2,
+    /// The column and row after the last ones of the range
+    pub max: UVec2,
+}
+
+impl TileRange {
+    /// Returns the number of tiles in the range.
+    pub fn len(&self) -> usize {
+        let size = self.max.saturating_sub(self.min);
+        size.x as usize * size.y as usize
+    }
+
+    /// Returns `true` if the range has no tiles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Blends a [`TileMap`] over the maps behind it.
+///
+/// Maps are drawn back to front by the z of their [`Transform`], like sprites, so layers of a
+/// level can be stacked by spawning a [`TileMap`] for each layer with an increasing z.
+#[derive(Component, Debug, Clone, Reflect, FromReflect)]
+#[reflect(Component, Default)]
+pub struct TileLayer {
+    /// The color the tiles are multiplied with. Its alpha sets the opacity of the layer.
+    pub color: Color,
+}
+
+impl Default for TileLayer {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+        }
+    }
+}
+
+/// A Bundle of components for drawing a [`TileMap`]
+#[derive(Bundle, Clone, Default)]
+pub struct TileMapBundle {
+    pub tilemap: TileMap,
+    pub layer: TileLayer,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+    /// User indication of whether an entity is visible
+    pub visibility: Visibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub computed_visibility: ComputedVisibility,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tilemap(projection: TileProjection) -> TileMap {
+        TileMap::new(Handle::default(), 4, 3, UVec2::new(16, 8)).with_projection(projection)
+    }
+
+    #[test]
+    fn get_and_set_tiles() {
+        let mut map = tilemap(TileProjection::Orthographic);
+        assert_eq!(map.get(UVec2::new(3, 2)), None);
+        map.set(UVec2::new(3, 2), TileId(5));
+        assert_eq!(map.get(UVec2::new(3, 2)), Some(TileId(5)));
+        assert_eq!(map.tiles[11], TileId(5));
+        assert_eq!(map.index(UVec2::new(4, 0)), None);
+    }
+
+    #[test]
+    fn tile_positions_round_trip() {
+        for projection in [TileProjection::Orthographic, TileProjection::Isometric] {
+            let map = tilemap(projection);
+            for y in 0..map.height {
+                for x in 0..map.width {
+                    let coord = UVec2::new(x, y);
+                    assert_eq!(map.tile_at(map.tile_center(coord)), Some(coord));
+                }
+            }
+        }
+
+        let map = tilemap(TileProjection::Isometric);
+        assert_eq!(map.tile_center(UVec2::ZERO), Vec2::new(0.0, 4.0));
+        assert_eq!(map.tile_center(UVec2::new(1, 0)), Vec2::new(8.0, 8.0));
+        assert_eq!(map.tile_at(Vec2::new(0.0, -1.0)), None);
+    }
+
+    #[test]
+    fn tiles_in_area() {
+        let map = tilemap(TileProjection::Orthographic);
+        let area = Rect::new(20.0, 0.0, 40.0, 7.0);
+        assert_eq!(
+            map.tiles_in_area(area, Vec2::new(16.0, 8.0)),
+            Some(TileRange {
+                min: UVec2::new(1, 0),
+                max: UVec2::new(3, 1),
+            })
+        );
+        // Tall tiles from the row below can reach into the area
+        let area = Rect::new(20.0, 9.0, 40.0, 15.0);
+        assert_eq!(
+            map.tiles_in_area(area, Vec2::new(16.0, 16.0)).unwrap().min,
+            UVec2::new(1, 0)
+        );
+        assert_eq!(
+            map.tiles_in_area(Rect::new(-20.0, 0.0, -1.0, 8.0), Vec2::ZERO),
+            None
+        );
+
+        let map = tilemap(TileProjection::Isometric);
+        let range = map
+            .tiles_in_area(Rect::new(-1.0, 0.0, 1.0, 2.0), Vec2::new(16.0, 8.0))
+            .unwrap();
+        assert_eq!(range.min, UVec2::ZERO);
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn large_sizes_do_not_overflow() {
+        let range = TileRange {
+            min: UVec2::ZERO,
+            max: UVec2::splat(1 << 16),
+        };
+        assert_eq!(range.len(), 1 << 32);
+
+        let mut map = tilemap(TileProjection::Orthographic);
+        map.width = 1 << 16;
+        map.height = 1 << 16;
+        assert_eq!(
+            map.index(UVec2::new(1, (1 << 16) - 1)),
+            Some((1 << 32) - (1 << 16) + 1)
+        );
+    }
+}
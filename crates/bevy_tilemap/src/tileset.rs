@@ -0,0 +1,81 @@
+use bevy_math::Vec2;
+use bevy_reflect::{FromReflect, Reflect, TypeUuid};
+use bevy_sprite::TextureAtlas;
+use bevy_utils::HashMap;
+
+/// The index of a tile in a [`TileSet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, FromReflect)]
+#[reflect(Debug, Hash, PartialEq)]
+pub struct TileId(pub u32);
+
+impl TileId {
+    /// A tile that isn't drawn.
+    pub const EMPTY: TileId = TileId(u32::MAX);
+
+    /// Returns `true` if this is [`TileId::EMPTY`].
+    pub fn is_empty(self) -> bool {
+        self == TileId::EMPTY
+    }
+}
+
+impl Default for TileId {
+    fn default() -> Self {
+        TileId::EMPTY
+    }
+}
+
+/// The tiles a [`TileMap`](crate::TileMap) is drawn with.
+///
+/// Each tile is the texture of the [`TextureAtlas`] with the same index. Tiles larger than the
+/// [`TileMap::tile_size`](crate::TileMap::tile_size) overlap the tiles behind them, which is
+/// how walls and other tall tiles are usually drawn.
+#[derive(Reflect, FromReflect, Debug, Clone, TypeUuid)]
+#[uuid = "4f4a6e6c-93d3-4a8e-9a3c-2c4c4e8f5c31"]
+#[reflect(Debug)]
+pub struct TileSet {
+    /// The texture and layout of the tiles
+    pub atlas: TextureAtlas,
+    /// Custom properties of the tiles, like whether they can be walked on
+    pub properties: HashMap<TileId, HashMap<String, String>>,
+}
+
+impl TileSet {
+    /// Creates a [`TileSet`] with the tiles of `atlas` and no properties.
+    pub fn new(atlas: TextureAtlas) -> Self {
+        Self {
+            atlas,
+            properties: HashMap::default(),
+        }
+    }
+
+    /// The number of tiles in the [`TileSet`]
+    pub fn len(&self) -> usize {
+        self.atlas.len()
+    }
+
+    /// Returns `true` if there are no tiles in the [`TileSet`]
+    pub fn is_empty(&self) -> bool {
+        self.atlas.is_empty()
+    }
+
+    /// Returns the value of the property `key` of `tile`.
+    pub fn property(&self, tile: TileId, key: &str) -> Option<&str> {
+        self.properties.get(&tile)?.get(key).map(String::as_str)
+    }
+
+    /// Sets the property `key` of `tile` to `value`.
+    pub fn set_property(&mut self, tile: TileId, key: impl Into<String>, value: impl Into<String>) {
+        self.properties
+            .entry(tile)
+            .or_default()
+            .insert(key.into(), value.into());
+    }
+
+    /// Returns the size of the largest tile.
+    pub fn max_tile_size(&self) -> Vec2 {
+        self.atlas
+            .textures
+            .iter()
+            .fold(Vec2::ZERO, |size, rect| size.max(rect.size()))
+    }
+}
@@ -1,7 +1,7 @@
 use crate::components::{GlobalTransform, Transform};
 use bevy_ecs::{
     change_detection::Ref,
-    prelude::{Changed, DetectChanges, Entity, Query, With, Without},
+    prelude::{Changed, DetectChanges, DetectChangesMut, Entity, Query, With, Without},
     removal_detection::RemovedComponents,
     system::{Local, ParamSet},
 };
@@ -57,8 +57,10 @@ pub fn propagate_transforms(
     root_query.par_iter_mut().for_each_mut(
         |(entity, children, transform, mut global_transform)| {
             let changed = transform.is_changed() || orphaned_entities.binary_search(&entity).is_ok();
+            // Writing the same value doesn't trigger change detection, so that quiet hierarchies
+            // don't look changed to the systems filtering on `Changed<GlobalTransform>`
             if changed {
-                *global_transform = GlobalTransform::from(*transform);
+                global_transform.set_if_neq(GlobalTransform::from(*transform));
             }
 
             for (child, actual_parent) in parent_query.iter_many(children) {
@@ -145,7 +147,7 @@ unsafe fn propagate_recursive(
 
         changed |= transform.is_changed();
         if changed {
-            *global_transform = parent.mul_transform(*transform);
+            global_transform.set_if_neq(parent.mul_transform(*transform));
         }
         (*global_transform, children)
     };
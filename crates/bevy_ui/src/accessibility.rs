@@ -1,6 +1,6 @@
 use crate::{
     prelude::{Button, Label},
-    Node, UiImage,
+    AccessibilityHint, AriaRole, Node, UiImage,
 };
 use bevy_a11y::{
     accesskit::{NodeBuilder, Rect, Role},
@@ -142,6 +142,40 @@ fn label_changed(
     }
 }
 
+fn hint_changed(
+    mut commands: Commands,
+    mut query: Query<
+        (Entity, &AccessibilityHint, Option<&mut AccessibilityNode>),
+        Changed<AccessibilityHint>,
+    >,
+) {
+    for (entity, hint, accessible) in &mut query {
+        let role = match hint.role {
+            AriaRole::Generic => Role::GenericContainer,
+            AriaRole::Button => Role::Button,
+            AriaRole::Checkbox => Role::CheckBox,
+            AriaRole::ComboBox => Role::ComboBoxMenuButton,
+            AriaRole::ListBox => Role::ListBox,
+            AriaRole::ListBoxOption => Role::ListBoxOption,
+            AriaRole::ProgressBar => Role::ProgressIndicator,
+            AriaRole::Radio => Role::RadioButton,
+            AriaRole::RadioGroup => Role::RadioGroup,
+            AriaRole::Tooltip => Role::Tooltip,
+        };
+        let name = hint.label.clone().into_boxed_str();
+        if let Some(mut accessible) = accessible {
+            accessible.set_role(role);
+            accessible.set_name(name);
+        } else {
+            let mut node = NodeBuilder::new(role);
+            node.set_name(name);
+            commands
+                .entity(entity)
+                .insert(AccessibilityNode::from(node));
+        }
+    }
+}
+
 /// `AccessKit` integration for `bevy_ui`.
 pub(crate) struct AccessibilityPlugin;
 
@@ -154,6 +188,11 @@ impl Plugin for AccessibilityPlugin {
                 button_changed,
                 image_changed,
                 label_changed,
+                // Hints describe nodes more precisely than the roles guessed from their components
+                hint_changed
+                    .after(button_changed)
+                    .after(image_changed)
+                    .after(label_changed),
             ),
         );
     }
@@ -3,10 +3,11 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     change_detection::DetectChangesMut,
     entity::Entity,
+    event::{Event, EventWriter},
     prelude::{Component, With},
     query::WorldQuery,
     reflect::ReflectComponent,
-    system::{Local, Query, Res},
+    system::{Local, Query, Res, SystemParam},
 };
 use bevy_input::{mouse::MouseButton, touch::Touches, Input};
 use bevy_math::Vec2;
@@ -113,10 +114,69 @@ impl Default for FocusPolicy {
     }
 }
 
+/// Sent by [`ui_focus_system`] when a node with an [`Interaction`] is pressed, then released
+/// while the pointer is still over it.
+///
+/// The pointer is over a node when it is inside its visible rect, see [`CalculatedClip`], and no
+/// node above it blocks the interaction with [`FocusPolicy::Block`]. Touches send the same events
+/// as the left mouse button.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Click {
+    /// The clicked node
+    pub entity: Entity,
+}
+
+/// Sent by [`ui_focus_system`] when a node with an [`Interaction`] is pressed, see [`Click`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressStart {
+    /// The pressed node
+    pub entity: Entity,
+}
+
+/// Sent by [`ui_focus_system`] when a pressed node is released while the pointer isn't over it,
+/// so it isn't [clicked](Click).
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PressCancel {
+    /// The node that was pressed
+    pub entity: Entity,
+}
+
+/// Sent by [`ui_focus_system`] when the pointer starts being over a node with an
+/// [`Interaction`], see [`Click`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoverStart {
+    /// The hovered node
+    pub entity: Entity,
+}
+
+/// Sent by [`ui_focus_system`] when the pointer stops being over a node that sent a
+/// [`HoverStart`], including when the node is hidden.
+///
+/// Nothing is sent for nodes that were despawned, or that are no longer UI nodes.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HoverEnd {
+    /// The node that was hovered
+    pub entity: Entity,
+}
+
+/// The writers of the interaction events of [`ui_focus_system`].
+#[derive(SystemParam)]
+pub struct InteractionEvents<'w> {
+    click: EventWriter<'w, Click>,
+    press_start: EventWriter<'w, PressStart>,
+    press_cancel: EventWriter<'w, PressCancel>,
+    hover_start: EventWriter<'w, HoverStart>,
+    hover_end: EventWriter<'w, HoverEnd>,
+}
+
 /// Contains entities whose Interaction should be set to None
 #[derive(Default)]
 pub struct State {
     entities_to_reset: SmallVec<[Entity; 1]>,
+    /// The nodes that were pressed and not released yet
+    pressed: SmallVec<[Entity; 1]>,
+    /// The nodes the pointer was over in the last frame
+    hovered: SmallVec<[Entity; 1]>,
 }
 
 /// Main query for [`ui_focus_system`]
@@ -136,9 +196,14 @@ pub struct NodeQuery {
 /// The system that sets Interaction for all UI elements based on the mouse cursor activity
 ///
 /// Entities with a hidden [`ComputedVisibility`] are always treated as released.
+///
+/// It also sends the [`Click`], [`PressStart`], [`PressCancel`], [`HoverStart`] and [`HoverEnd`]
+/// events, which unlike changes of [`Interaction`] aren't missed when several of them happen in
+/// the same frame.
 #[allow(clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
+    mut interaction_events: InteractionEvents,
     camera: Query<(&Camera, Option<&UiCameraConfig>)>,
     windows: Query<&Window>,
     mouse_button_input: Res<Input<MouseButton>>,
@@ -156,6 +221,11 @@ pub fn ui_focus_system(
         }
     }
 
+    // the nodes that were despawned don't send any more events
+    let state = &mut *state;
+    state.pressed.retain(|entity| node_query.contains(*entity));
+    state.hovered.retain(|entity| node_query.contains(*entity));
+
     let mouse_released =
         mouse_button_input.just_released(MouseButton::Left) || touches_input.any_just_released();
     if mouse_released {
@@ -192,7 +262,15 @@ pub fn ui_focus_system(
                 .ok()
                 .and_then(|window| window.cursor_position())
         })
-        .or_else(|| touches_input.first_pressed_position());
+        .or_else(|| touches_input.first_pressed_position())
+        // a touch is no longer pressed in the frame it is released, but it is still where the
+        // node is clicked
+        .or_else(|| {
+            touches_input
+                .iter_just_released()
+                .next()
+                .map(|touch| touch.position())
+        });
 
     // prepare an iterator that contains all the nodes that have the cursor in their rect,
     // from the top node to the bottom one. this will also reset the interaction to `None`
@@ -264,10 +342,16 @@ pub fn ui_focus_system(
 
     // set Clicked or Hovered on top nodes. as soon as a node with a `Block` focus policy is detected,
     // the iteration will stop on it because it "captures" the interaction.
+    let mut hovered = SmallVec::<[Entity; 1]>::new();
     let mut iter = node_query.iter_many_mut(hovered_nodes.by_ref());
     while let Some(node) = iter.fetch_next() {
         if let Some(mut interaction) = node.interaction {
+            hovered.push(node.entity);
             if mouse_clicked {
+                interaction_events.press_start.send(PressStart {
+                    entity: node.entity,
+                });
+                state.pressed.push(node.entity);
                 // only consider nodes with Interaction "clickable"
                 if *interaction != Interaction::Clicked {
                     *interaction = Interaction::Clicked;
@@ -300,4 +384,178 @@ pub fn ui_focus_system(
             }
         }
     }
+
+    // the pressed nodes are clicked if they are released while the pointer is still over them
+    if mouse_released {
+        for entity in state.pressed.drain(..) {
+            if hovered.contains(&entity) {
+                interaction_events.click.send(Click { entity });
+            } else {
+                interaction_events.press_cancel.send(PressCancel { entity });
+            }
+        }
+    }
+
+    for &entity in &state.hovered {
+        if !hovered.contains(&entity) {
+            interaction_events.hover_end.send(HoverEnd { entity });
+        }
+    }
+    for &entity in &hovered {
+        if !state.hovered.contains(&entity) {
+            interaction_events.hover_start.send(HoverStart { entity });
+        }
+    }
+    state.hovered = hovered;
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+    use bevy_input::touch::{touch_screen_input_system, TouchInput, TouchPhase};
+    use bevy_math::Vec3;
+    use bevy_transform::components::Transform;
+
+    use super::*;
+
+    fn drain<E: Event>(world: &mut World) -> Vec<E> {
+        world.resource_mut::<Events<E>>().drain().collect()
+    }
+
+    fn setup() -> (World, Entity) {
+        let mut world = World::new();
+        world.init_resource::<Input<MouseButton>>();
+        world.init_resource::<Touches>();
+        world.init_resource::<Events<Click>>();
+        world.init_resource::<Events<PressStart>>();
+        world.init_resource::<Events<PressCancel>>();
+        world.init_resource::<Events<HoverStart>>();
+        world.init_resource::<Events<HoverEnd>>();
+        world.spawn((Window::default(), PrimaryWindow));
+        world.spawn(Camera::default());
+        let button = world
+            .spawn((
+                Node {
+                    calculated_size: Vec2::splat(20.0),
+                },
+                GlobalTransform::from(Transform::from_translation(Vec3::new(50.0, 50.0, 0.0))),
+                Interaction::default(),
+            ))
+            .id();
+        world.insert_resource(UiStack {
+            uinodes: vec![button],
+        });
+        (world, button)
+    }
+
+    fn set_cursor(world: &mut World, position: Vec2) {
+        let mut windows = world.query::<&mut Window>();
+        windows
+            .single_mut(world)
+            .set_cursor_position(Some(position));
+    }
+
+    #[test]
+    fn sends_interaction_events() {
+        let (mut world, button) = setup();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(ui_focus_system);
+        let mut run = |world: &mut World| {
+            schedule.run(world);
+            world.resource_mut::<Input<MouseButton>>().clear();
+        };
+
+        set_cursor(&mut world, Vec2::new(50.0, 50.0));
+        run(&mut world);
+        assert_eq!(
+            drain::<HoverStart>(&mut world),
+            [HoverStart { entity: button }]
+        );
+
+        // Pressed and released in the same frame
+        let mut mouse = world.resource_mut::<Input<MouseButton>>();
+        mouse.press(MouseButton::Left);
+        mouse.release(MouseButton::Left);
+        run(&mut world);
+        assert_eq!(
+            drain::<PressStart>(&mut world),
+            [PressStart { entity: button }]
+        );
+        assert_eq!(drain::<Click>(&mut world), [Click { entity: button }]);
+
+        // Released after the pointer left the node
+        world
+            .resource_mut::<Input<MouseButton>>()
+            .press(MouseButton::Left);
+        run(&mut world);
+        set_cursor(&mut world, Vec2::new(100.0, 100.0));
+        world
+            .resource_mut::<Input<MouseButton>>()
+            .release(MouseButton::Left);
+        run(&mut world);
+        assert_eq!(drain::<PressStart>(&mut world).len(), 1);
+        assert!(drain::<Click>(&mut world).is_empty());
+        assert_eq!(
+            drain::<PressCancel>(&mut world),
+            [PressCancel { entity: button }]
+        );
+        assert_eq!(drain::<HoverEnd>(&mut world), [HoverEnd { entity: button }]);
+    }
+
+    #[test]
+    fn touch_taps_click() {
+        let (mut world, button) = setup();
+        world.init_resource::<Events<TouchInput>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems((touch_screen_input_system, ui_focus_system).chain());
+        let mut touch = |world: &mut World, phase: TouchPhase| {
+            world.send_event(TouchInput {
+                phase,
+                position: Vec2::new(50.0, 50.0),
+                force: None,
+                id: 0,
+            });
+            schedule.run(world);
+        };
+
+        touch(&mut world, TouchPhase::Started);
+        assert_eq!(
+            drain::<PressStart>(&mut world),
+            [PressStart { entity: button }]
+        );
+        assert_eq!(
+            drain::<HoverStart>(&mut world),
+            [HoverStart { entity: button }]
+        );
+
+        touch(&mut world, TouchPhase::Ended);
+        assert_eq!(drain::<Click>(&mut world), [Click { entity: button }]);
+        assert!(drain::<PressCancel>(&mut world).is_empty());
+        assert!(drain::<HoverEnd>(&mut world).is_empty());
+    }
+
+    #[test]
+    fn despawned_nodes_send_no_events() {
+        let (mut world, button) = setup();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(ui_focus_system);
+
+        set_cursor(&mut world, Vec2::new(50.0, 50.0));
+        world
+            .resource_mut::<Input<MouseButton>>()
+            .press(MouseButton::Left);
+        schedule.run(&mut world);
+        assert_eq!(drain::<PressStart>(&mut world).len(), 1);
+        assert_eq!(drain::<HoverStart>(&mut world).len(), 1);
+
+        world.despawn(button);
+        world.resource_mut::<UiStack>().uinodes.clear();
+        let mut mouse = world.resource_mut::<Input<MouseButton>>();
+        mouse.clear();
+        mouse.release(MouseButton::Left);
+        schedule.run(&mut world);
+        assert!(drain::<Click>(&mut world).is_empty());
+        assert!(drain::<PressCancel>(&mut world).is_empty());
+        assert!(drain::<HoverEnd>(&mut world).is_empty());
+    }
 }
@@ -630,6 +630,51 @@ mod tests {
         assert_eq!(taffy_style.grid_row, sh::span(3));
     }
 
+    #[test]
+    fn test_auto_fill_columns() {
+        use taffy::style_helpers::TaffyMaxContent;
+        let context = LayoutContext::new(1.0, bevy_math::Vec2::new(800., 600.));
+        let style = Style {
+            display: Display::Grid,
+            width: Val::Px(350.),
+            grid_template_columns: GridTrack::auto_fill(
+                MinTrackSizingFunction::Px(50.),
+                MaxTrackSizingFunction::Px(100.),
+            ),
+            ..Default::default()
+        };
+        let taffy_style = from_style(&context, &style);
+        assert_eq!(
+            taffy_style.grid_template_columns,
+            vec![style_helpers::repeat(
+                taffy::style::GridTrackRepetition::AutoFill,
+                vec![style_helpers::minmax(
+                    style_helpers::points(50.),
+                    style_helpers::points(100.)
+                )],
+            )]
+        );
+
+        // Only three 100px columns fit in the container
+        let mut taffy = taffy::Taffy::new();
+        let items: Vec<_> = (0..4)
+            .map(|_| taffy.new_leaf(taffy::style::Style::default()).unwrap())
+            .collect();
+        let grid = taffy.new_with_children(taffy_style, &items).unwrap();
+        taffy
+            .compute_layout(grid, taffy::geometry::Size::MAX_CONTENT)
+            .unwrap();
+        let columns: Vec<_> = items
+            .iter()
+            .map(|item| taffy.layout(*item).unwrap().location.x)
+            .collect();
+        assert_eq!(
+            columns,
+            vec![0., 100., 200., 0.],
+            "the fourth item should wrap to a new row"
+        );
+    }
+
     #[test]
     fn test_into_length_percentage() {
         use taffy::style::LengthPercentage;
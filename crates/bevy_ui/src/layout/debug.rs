@@ -1,4 +1,4 @@
-use crate::UiSurface;
+use crate::{LayoutAudit, UiSurface};
 use bevy_ecs::prelude::Entity;
 use bevy_utils::HashMap;
 use std::fmt::Write;
@@ -7,16 +7,83 @@ use taffy::tree::LayoutTree;
 
 /// Prints a debug representation of the computed layout of the UI layout tree for each window.
 pub fn print_ui_layout_tree(ui_surface: &UiSurface) {
+    print_ui_layout_tree_with_options(ui_surface, &LayoutTreePrintOptions::default());
+}
+
+/// Options controlling the output of [`print_ui_layout_tree_with_options`].
+#[derive(Debug, Default, Clone)]
+pub struct LayoutTreePrintOptions {
+    /// Annotate each node with the size of its subtree, the number of nodes with a measure
+    /// function in it, and how many of those were recomputed during the last layout pass, which
+    /// runs their measure function again. Measured nodes that were recomputed are marked with
+    /// `[REMEASURED]`, and the branches containing them with a `!`, since nodes with expensive
+    /// measure functions (like text) under deep trees are the usual cause of slow layouts.
+    pub show_costs: bool,
+    /// The characters used to draw the branches of the tree
+    pub tree_style: LayoutTreeStyle,
+    /// Mark each node with `[DIRTY]` if its layout was recomputed during the last layout pass,
+    /// or `[CACHED]` if it was reused. A small change that dirties the whole tree makes every
+    /// layout slow.
+    pub show_dirty: bool,
+    /// Annotate the nodes flagged by [`UiSurface::audit`] with `[ZERO SIZE]`,
+    /// `[OUTSIDE VIEWPORT]` or `[OVERLAPPING ROOT]`, and list the orphaned nodes and dangling
+    /// roots, which aren't part of any printed tree, after the trees.
+    pub show_audit: bool,
+}
+
+/// The characters used to draw the branches of a printed layout tree, see
+/// [`LayoutTreePrintOptions::tree_style`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LayoutTreeStyle {
+    /// Box-drawing characters, like `├──`, `└──` and `│`
+    #[default]
+    Unicode,
+    /// ASCII characters, like `+--`, `\--` and `|`, for logs and fonts that mangle box-drawing
+    /// characters
+    Ascii,
+    /// Plain indentation
+    None,
+}
+
+impl LayoutTreeStyle {
+    /// The prefix of a node, depending on whether more siblings are printed after it.
+    fn fork(self, has_sibling: bool) -> &'static str {
+        match (self, has_sibling) {
+            (LayoutTreeStyle::Unicode, true) => "├── ",
+            (LayoutTreeStyle::Unicode, false) => "└── ",
+            (LayoutTreeStyle::Ascii, true) => "+-- ",
+            (LayoutTreeStyle::Ascii, false) => "\\-- ",
+            (LayoutTreeStyle::None, _) => "    ",
+        }
+    }
+
+    /// The indentation of the descendants of a node, depending on whether more siblings are
+    /// printed after it.
+    fn bar(self, has_sibling: bool) -> &'static str {
+        match (self, has_sibling) {
+            (LayoutTreeStyle::Unicode, true) => "│   ",
+            (LayoutTreeStyle::Ascii, true) => "|   ",
+            _ => "    ",
+        }
+    }
+}
+
+/// Prints a debug representation of the computed layout of the UI layout tree for each window,
+/// configured by the given [`LayoutTreePrintOptions`].
+pub fn print_ui_layout_tree_with_options(ui_surface: &UiSurface, options: &LayoutTreePrintOptions) {
     let taffy_to_entity: HashMap<Node, Entity> = ui_surface
         .entity_to_taffy
         .iter()
         .map(|(entity, node)| (*node, *entity))
         .collect();
+    let audit = options.show_audit.then(|| ui_surface.audit());
     for (&entity, &node) in ui_surface.window_nodes.iter() {
         let mut out = String::new();
         print_node(
             ui_surface,
             &taffy_to_entity,
+            options,
+            audit.as_ref(),
             entity,
             node,
             false,
@@ -25,39 +92,207 @@ pub fn print_ui_layout_tree(ui_surface: &UiSurface) {
         );
         bevy_log::info!("Layout tree for window entity: {entity:?}\n{out}");
     }
+    if let Some(audit) = audit {
+        if !audit.orphaned_nodes.is_empty() || !audit.dangling_roots.is_empty() {
+            bevy_log::info!(
+                "Layout audit [orphaned nodes: {orphaned:?} dangling roots: {dangling:?}]",
+                orphaned = audit.orphaned_nodes,
+                dangling = audit.dangling_roots,
+            );
+        }
+    }
+}
+
+/// Prints a single line summary of the UI layout tree for each window.
+///
+/// Each line contains the number of nodes in the tree, its maximum depth and the size of the
+/// window's root node. Useful for frame-by-frame monitoring where [`print_ui_layout_tree`] would
+/// flood the log.
+pub fn print_ui_layout_summary(ui_surface: &UiSurface) {
+    for (&entity, &node) in ui_surface.window_nodes.iter() {
+        let stats = collect_stats(ui_surface, node);
+        let root_size = ui_surface.taffy.layout(node).unwrap().size;
+        bevy_log::info!(
+            "Layout summary for window entity: {entity:?} [nodes: {nodes} max depth: {depth} width: {width} height: {height}]",
+            nodes = stats.node_count,
+            depth = stats.max_depth,
+            width = root_size.width,
+            height = root_size.height,
+        );
+    }
+}
+
+/// Aggregate statistics for a layout tree, see [`collect_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct LayoutStats {
+    /// Number of nodes in the tree, not counting the root node.
+    node_count: usize,
+    /// Depth of the deepest node in the tree, where the root's children have a depth of 1.
+    max_depth: usize,
+}
+
+/// Walks the layout tree below `root` gathering [`LayoutStats`] without formatting any nodes.
+fn collect_stats(ui_surface: &UiSurface, root: Node) -> LayoutStats {
+    let tree = &ui_surface.taffy;
+    let mut stats = LayoutStats::default();
+    let mut stack = vec![(root, 0)];
+    while let Some((node, depth)) = stack.pop() {
+        stats.max_depth = stats.max_depth.max(depth);
+        for child in tree.children(node).unwrap() {
+            stats.node_count += 1;
+            stack.push((child, depth + 1));
+        }
+    }
+    stats
+}
+
+/// Layout cost of a subtree, accumulated by [`print_node`].
+///
+/// Unlike [`LayoutStats`], which only has totals for the whole tree, this is needed for every
+/// printed node, so it is summed up from the children as they are printed rather than by walking
+/// each subtree again with [`collect_stats`].
+#[derive(Debug, Default, Clone, Copy)]
+struct SubtreeCost {
+    /// Number of nodes in the subtree, including its root.
+    nodes: usize,
+    /// Number of nodes in the subtree with a measure function.
+    measured: usize,
+    /// Number of nodes in the subtree with a measure function that were recomputed during the
+    /// last layout pass, see [`UiSurface::compute_window_layouts`].
+    remeasured: usize,
+}
+
+/// Formats an alignment property, which is `None` when left to its default.
+fn or_default(value: Option<impl std::fmt::Debug>) -> String {
+    value.map_or_else(|| "default".to_string(), |value| format!("{value:?}"))
+}
+
+/// Formats a size of a style the way it would be written in CSS.
+fn format_dimension(dimension: taffy::style::Dimension) -> String {
+    match dimension {
+        taffy::style::Dimension::Auto => "auto".to_string(),
+        taffy::style::Dimension::Points(points) => format!("{points}px"),
+        taffy::style::Dimension::Percent(fraction) => format!("{}%", fraction * 100.),
+    }
 }
 
 /// Recursively navigates the layout tree printing each node's information.
+#[allow(clippy::too_many_arguments)]
 fn print_node(
     ui_surface: &UiSurface,
     taffy_to_entity: &HashMap<Node, Entity>,
+    options: &LayoutTreePrintOptions,
+    audit: Option<&LayoutAudit>,
     entity: Entity,
     node: Node,
     has_sibling: bool,
     lines_string: String,
     acc: &mut String,
-) {
+) -> SubtreeCost {
     let tree = &ui_surface.taffy;
     let layout = tree.layout(node).unwrap();
     let style = tree.style(node).unwrap();
 
     let num_children = tree.child_count(node).unwrap();
+    let needs_measure = tree.needs_measure(node);
+    let remeasured = needs_measure && ui_surface.recomputed_nodes.contains(&node);
 
     let display_variant = match (num_children, style.display) {
-        (_, taffy::style::Display::None) => "NONE",
-        (0, _) => "LEAF",
-        (_, taffy::style::Display::Flex) => "FLEX",
-        (_, taffy::style::Display::Grid) => "GRID",
+        (_, taffy::style::Display::None) => "NONE".to_string(),
+        (0, _) => "LEAF".to_string(),
+        // The main axis and alignment explain where the children of a flex node end up
+        (_, taffy::style::Display::Flex) => format!(
+            "FLEX({:?}, justify: {}, align: {})",
+            style.flex_direction,
+            or_default(style.justify_content),
+            or_default(style.align_items),
+        ),
+        (_, taffy::style::Display::Grid) => "GRID".to_string(),
     };
 
-    let fork_string = if has_sibling {
-        "├── "
+    let fork_string = options.tree_style.fork(has_sibling);
+    let bar = options.tree_style.bar(has_sibling);
+    let new_string = lines_string.clone() + bar;
+
+    // Recurse into children first, so their costs are known when printing this node
+    let mut cost = SubtreeCost {
+        nodes: 1,
+        measured: usize::from(needs_measure),
+        remeasured: usize::from(remeasured),
+    };
+    // Dangling roots have no entity, they are reported by the audit instead
+    let children: Vec<(Node, Entity)> = tree
+        .children(node)
+        .unwrap()
+        .into_iter()
+        .filter_map(|child| Some((child, *taffy_to_entity.get(&child)?)))
+        .collect();
+    let mut children_acc = String::new();
+    for (index, &(child_node, child_entity)) in children.iter().enumerate() {
+        let has_sibling = index + 1 < children.len();
+        let child_cost = print_node(
+            ui_surface,
+            taffy_to_entity,
+            options,
+            audit,
+            child_entity,
+            child_node,
+            has_sibling,
+            new_string.clone(),
+            &mut children_acc,
+        );
+        cost.nodes += child_cost.nodes;
+        cost.measured += child_cost.measured;
+        cost.remeasured += child_cost.remeasured;
+    }
+
+    let cost_string = if options.show_costs {
+        format!(
+            " [subtree: {nodes} measured: {measured} remeasured: {remeasured}]{node_flag}{flag}",
+            nodes = cost.nodes,
+            measured = cost.measured,
+            remeasured = cost.remeasured,
+            node_flag = if remeasured { " [REMEASURED]" } else { "" },
+            flag = if cost.remeasured > 0 { " !" } else { "" },
+        )
+    } else {
+        String::new()
+    };
+    let dirty_string = match (
+        options.show_dirty,
+        ui_surface.recomputed_nodes.contains(&node),
+    ) {
+        (false, _) => "",
+        (true, true) => " [DIRTY]",
+        (true, false) => " [CACHED]",
+    };
+    let mut audit_string = String::new();
+    if let Some(audit) = audit {
+        for (entities, annotation) in [
+            (&audit.zero_sized_nodes, " [ZERO SIZE]"),
+            (&audit.out_of_viewport_nodes, " [OUTSIDE VIEWPORT]"),
+            (&audit.overlapping_roots, " [OVERLAPPING ROOT]"),
+        ] {
+            if entities.binary_search(&entity).is_ok() {
+                audit_string.push_str(annotation);
+            }
+        }
+    }
+    // The requested size is usually `auto` for measured nodes, the measure function resolves it
+    let measured_string = if needs_measure {
+        format!(
+            "measured [requested width: {} height: {}, resolved width: {} height: {}]",
+            format_dimension(style.size.width),
+            format_dimension(style.size.height),
+            layout.size.width,
+            layout.size.height,
+        )
     } else {
-        "└── "
+        String::new()
     };
     writeln!(
         acc,
-        "{lines}{fork} {display} [x: {x:<4} y: {y:<4} width: {width:<4} height: {height:<4}] ({entity:?}) {measured}",
+        "{lines}{fork} {display} [x: {x:<4} y: {y:<4} width: {width:<4} height: {height:<4}] ({entity:?}) {measured}{cost}{dirty}{audit}",
         lines = lines_string,
         fork = fork_string,
         display = display_variant,
@@ -65,23 +300,12 @@ fn print_node(
         y = layout.location.y,
         width = layout.size.width,
         height = layout.size.height,
-        measured = if tree.needs_measure(node) { "measured" } else { "" }
+        measured = measured_string,
+        cost = cost_string,
+        dirty = dirty_string,
+        audit = audit_string,
     ).ok();
-    let bar = if has_sibling { "│   " } else { "    " };
-    let new_string = lines_string + bar;
+    acc.push_str(&children_acc);
 
-    // Recurse into children
-    for (index, child_node) in tree.children(node).unwrap().iter().enumerate() {
-        let has_sibling = index < num_children - 1;
-        let child_entity = taffy_to_entity.get(child_node).unwrap();
-        print_node(
-            ui_surface,
-            taffy_to_entity,
-            *child_entity,
-            *child_node,
-            has_sibling,
-            new_string.clone(),
-            acc,
-        );
-    }
+    cost
 }
@@ -1,21 +1,21 @@
 mod convert;
 pub mod debug;
 
-use crate::{ContentSize, Node, Style, UiScale};
+use crate::{measurement::set_measure_world, ContentSize, Node, Style, UiScale};
 use bevy_ecs::{
-    change_detection::DetectChanges,
+    change_detection::{DetectChanges, DetectChangesMut},
     entity::Entity,
     event::EventReader,
-    query::{With, Without},
+    query::{Changed, With, Without},
     removal_detection::RemovedComponents,
-    system::{Query, Res, ResMut, Resource},
-    world::Ref,
+    system::{Query, Res, Resource, SystemParam, SystemState},
+    world::{Mut, Ref, World},
 };
 use bevy_hierarchy::{Children, Parent};
 use bevy_log::warn;
 use bevy_math::Vec2;
 use bevy_transform::components::Transform;
-use bevy_utils::HashMap;
+use bevy_utils::{HashMap, HashSet};
 use bevy_window::{PrimaryWindow, Window, WindowResolution, WindowScaleFactorChanged};
 use std::fmt;
 use taffy::{prelude::Size, style_helpers::TaffyMaxContent, Taffy};
@@ -39,10 +39,25 @@ impl LayoutContext {
     }
 }
 
+/// A UI root node, which is a UI node without a [`Parent`], and the layout node of the window it
+/// is laid out in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootNodePair {
+    /// The implicit layout node sized to the window, which is the parent of every root node in
+    /// the window
+    pub window_node: taffy::node::Node,
+    /// The root node entity
+    pub root_entity: Entity,
+}
+
 #[derive(Resource)]
 pub struct UiSurface {
     entity_to_taffy: HashMap<Entity, taffy::node::Node>,
     window_nodes: HashMap<Entity, taffy::node::Node>,
+    window_roots: HashMap<Entity, Vec<RootNodePair>>,
+    /// The nodes that were dirty when the layout was last computed, so their layout was
+    /// recomputed instead of reused from the cache
+    recomputed_nodes: HashSet<taffy::node::Node>,
     taffy: Taffy,
 }
 
@@ -58,6 +73,7 @@ impl fmt::Debug for UiSurface {
         f.debug_struct("UiSurface")
             .field("entity_to_taffy", &self.entity_to_taffy)
             .field("window_nodes", &self.window_nodes)
+            .field("window_roots", &self.window_roots)
             .finish()
     }
 }
@@ -67,6 +83,8 @@ impl Default for UiSurface {
         Self {
             entity_to_taffy: Default::default(),
             window_nodes: Default::default(),
+            window_roots: Default::default(),
+            recomputed_nodes: Default::default(),
             taffy: Taffy::new(),
         }
     }
@@ -97,6 +115,11 @@ impl UiSurface {
     }
 
     /// Update the children of the taffy node corresponding to the given [`Entity`].
+    ///
+    /// The taffy children are in the order of `children`, so nodes are laid out in [`Children`]
+    /// order, which [`HierarchyQueryExt::iter_descendants_depth_first`] also follows.
+    ///
+    /// [`HierarchyQueryExt::iter_descendants_depth_first`]: bevy_hierarchy::HierarchyQueryExt::iter_descendants_depth_first
     pub fn update_children(&mut self, entity: Entity, children: &Children) {
         let mut taffy_children = Vec::with_capacity(children.len());
         for child in children {
@@ -162,20 +185,58 @@ without UI components as a child of an entity with UI components, results may be
         parent_window: Entity,
         children: impl Iterator<Item = Entity>,
     ) {
-        let taffy_node = self.window_nodes.get(&parent_window).unwrap();
+        let window_node = *self.window_nodes.get(&parent_window).unwrap();
+        let roots = self.window_roots.entry(parent_window).or_default();
+        roots.clear();
         let child_nodes = children
-            .map(|e| *self.entity_to_taffy.get(&e).unwrap())
+            .map(|root_entity| {
+                roots.push(RootNodePair {
+                    window_node,
+                    root_entity,
+                });
+                *self.entity_to_taffy.get(&root_entity).unwrap()
+            })
             .collect::<Vec<taffy::node::Node>>();
-        self.taffy.set_children(*taffy_node, &child_nodes).unwrap();
+        self.taffy.set_children(window_node, &child_nodes).unwrap();
+    }
+
+    /// Iterates over each window entity with a layout and the UI root nodes laid out in it.
+    ///
+    /// The root nodes are updated by [`ui_layout_system`]. The rest of each tree can be traversed
+    /// through the [`Children`] of the root entities, with [`UiSurface::get_layout`].
+    pub fn window_roots_iter(&self) -> impl Iterator<Item = (Entity, &[RootNodePair])> {
+        self.window_nodes.keys().map(|window| {
+            let roots = self
+                .window_roots
+                .get(window)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            (*window, roots)
+        })
     }
 
     /// Compute the layout for each window entity's corresponding root node in the layout.
-    pub fn compute_window_layouts(&mut self) {
-        for window_node in self.window_nodes.values() {
-            self.taffy
-                .compute_layout(*window_node, Size::MAX_CONTENT)
-                .unwrap();
-        }
+    ///
+    /// The [`Measure`](crate::Measure)s of the nodes are given `world` and `scale_factor` in their
+    /// [`MeasureArgs`](crate::MeasureArgs).
+    pub fn compute_window_layouts(&mut self, world: &World, scale_factor: f64) {
+        self.recomputed_nodes.clear();
+        set_measure_world(world, scale_factor, || {
+            for window_node in self.window_nodes.values() {
+                // Taffy marks the ancestors of dirty nodes as dirty, so clean subtrees can be
+                // skipped
+                let mut stack = vec![*window_node];
+                while let Some(node) = stack.pop() {
+                    if self.taffy.dirty(node).unwrap() {
+                        self.recomputed_nodes.insert(node);
+                        stack.extend(self.taffy.children(node).unwrap());
+                    }
+                }
+                self.taffy
+                    .compute_layout(*window_node, Size::MAX_CONTENT)
+                    .unwrap();
+            }
+        });
     }
 
     /// Removes each entity from the internal map and then removes their associated node from taffy
@@ -183,10 +244,109 @@ without UI components as a child of an entity with UI components, results may be
         for entity in entities {
             if let Some(node) = self.entity_to_taffy.remove(&entity) {
                 self.taffy.remove(node).unwrap();
+                for roots in self.window_roots.values_mut() {
+                    roots.retain(|root| root.root_entity != entity);
+                }
             }
         }
     }
 
+    /// Returns the tracked entities that no longer exist in `world`, sorted.
+    ///
+    /// Their taffy nodes are leaked until they are removed with
+    /// [`remove_entities`](UiSurface::remove_entities), so this should always be empty after
+    /// [`ui_layout_system`] has run. It is meant for tests and tooling catching UI cleanup bugs.
+    pub fn dangling_entities(&self, world: &World) -> Vec<Entity> {
+        let mut entities: Vec<_> = self
+            .entity_to_taffy
+            .keys()
+            .copied()
+            .filter(|entity| world.get_entity(*entity).is_none())
+            .collect();
+        entities.sort();
+        entities
+    }
+
+    /// Checks the integrity of the layout trees, returning the issues found as a [`LayoutAudit`].
+    ///
+    /// Like [`dangling_entities`](UiSurface::dangling_entities), this is meant for tests and
+    /// tooling. The geometry checks use the layout computed by the last call to
+    /// [`compute_window_layouts`](UiSurface::compute_window_layouts).
+    pub fn audit(&self) -> LayoutAudit {
+        let taffy_to_entity: HashMap<taffy::node::Node, Entity> = self
+            .entity_to_taffy
+            .iter()
+            .map(|(entity, node)| (*node, *entity))
+            .collect();
+        let mut audit = LayoutAudit::default();
+
+        let mut root_window_count: HashMap<Entity, usize> = HashMap::new();
+        for root in self.window_roots.values().flatten() {
+            if self.entity_to_taffy.contains_key(&root.root_entity) {
+                *root_window_count.entry(root.root_entity).or_default() += 1;
+            } else {
+                audit.dangling_roots.push(root.root_entity);
+            }
+        }
+        audit.overlapping_roots.extend(
+            root_window_count
+                .into_iter()
+                .filter(|(_, count)| *count > 1)
+                .map(|(entity, _)| entity),
+        );
+
+        let mut reached = HashSet::new();
+        for window_node in self.window_nodes.values() {
+            let viewport = self.taffy.layout(*window_node).unwrap().size;
+            // Nodes below a `Display::None` node are hidden, so their geometry isn't checked
+            let mut stack = vec![(*window_node, Vec2::ZERO, false)];
+            while let Some((node, offset, hidden)) = stack.pop() {
+                for child in self.taffy.children(node).unwrap() {
+                    let Some(&entity) = taffy_to_entity.get(&child) else {
+                        continue;
+                    };
+                    reached.insert(entity);
+                    let layout = self.taffy.layout(child).unwrap();
+                    let position = offset + Vec2::new(layout.location.x, layout.location.y);
+                    let hidden = hidden
+                        || self.taffy.style(child).unwrap().display == taffy::style::Display::None;
+                    if !hidden {
+                        let size = Vec2::new(layout.size.width, layout.size.height);
+                        if size.x == 0. || size.y == 0. {
+                            audit.zero_sized_nodes.push(entity);
+                        }
+                        let end = position + size;
+                        if end.x < 0.
+                            || end.y < 0.
+                            || position.x > viewport.width
+                            || position.y > viewport.height
+                        {
+                            audit.out_of_viewport_nodes.push(entity);
+                        }
+                    }
+                    stack.push((child, position, hidden));
+                }
+            }
+        }
+        audit.orphaned_nodes.extend(
+            self.entity_to_taffy
+                .keys()
+                .filter(|entity| !reached.contains(*entity)),
+        );
+
+        for entities in [
+            &mut audit.orphaned_nodes,
+            &mut audit.dangling_roots,
+            &mut audit.overlapping_roots,
+            &mut audit.zero_sized_nodes,
+            &mut audit.out_of_viewport_nodes,
+        ] {
+            entities.sort();
+            entities.dedup();
+        }
+        audit
+    }
+
     /// Get the layout geometry for the taffy node corresponding to the ui node [`Entity`].
     /// Does not compute the layout geometry, `compute_window_layouts` should be run before using this function.
     pub fn get_layout(&self, entity: Entity) -> Result<&taffy::layout::Layout, LayoutError> {
@@ -204,30 +364,127 @@ with UI components as a child of an entity without UI components, results may be
     }
 }
 
+/// The integrity issues of the layout trees of a [`UiSurface`], returned by [`UiSurface::audit`].
+///
+/// Each list is sorted and holds each entity at most once.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LayoutAudit {
+    /// UI node entities whose layout node isn't reachable from any window, so they are never
+    /// laid out
+    pub orphaned_nodes: Vec<Entity>,
+    /// Entities still recorded as the root node of a window without having a layout node
+    pub dangling_roots: Vec<Entity>,
+    /// Root node entities laid out in more than one window
+    pub overlapping_roots: Vec<Entity>,
+    /// Visible nodes with a computed width or height of zero, whose `display` isn't
+    /// [`Display::None`](crate::Display::None)
+    pub zero_sized_nodes: Vec<Entity>,
+    /// Visible nodes laid out entirely outside of the viewport of their window
+    pub out_of_viewport_nodes: Vec<Entity>,
+}
+
+impl LayoutAudit {
+    /// Returns `true` if no issue was found.
+    pub fn is_empty(&self) -> bool {
+        self.orphaned_nodes.is_empty()
+            && self.dangling_roots.is_empty()
+            && self.overlapping_roots.is_empty()
+            && self.zero_sized_nodes.is_empty()
+            && self.out_of_viewport_nodes.is_empty()
+    }
+}
+
+/// A query filter for the UI nodes whose layout changed since the system last ran: their size,
+/// or their position relative to their parent, as computed by [`ui_layout_system`].
+///
+/// [`ui_layout_system`] only changes a [`Node`] when its layout is different, so this doesn't
+/// match nodes that were laid out again to the same rect. It also matches newly added nodes.
+/// Use it in systems in [`UiSystem::PostLayout`](crate::UiSystem::PostLayout) to react to the
+/// layout of the frame.
+pub type LayoutChanged = Changed<Node>;
+
 #[derive(Debug)]
 pub enum LayoutError {
     InvalidHierarchy,
     TaffyError(taffy::error::TaffyError),
 }
 
+/// Removes the layout node of a UI node right before it is despawned recursively, so that the
+/// [`UiSurface`] never tracks despawned entities, see [`DespawnHooks`](bevy_hierarchy::DespawnHooks).
+pub fn remove_despawned_ui_node(world: &mut World, entity: Entity) {
+    if let Some(mut ui_surface) = world.get_resource_mut::<UiSurface>() {
+        ui_surface.remove_entities([entity]);
+    }
+}
+
+/// The parameters of [`ui_layout_system`] used to update the [`UiSurface`] before the layout is
+/// computed.
+#[derive(SystemParam)]
+pub struct UiLayoutParams<'w, 's> {
+    primary_window: Query<'w, 's, (Entity, &'static Window), With<PrimaryWindow>>,
+    windows: Query<'w, 's, (Entity, &'static Window)>,
+    ui_scale: Res<'w, UiScale>,
+    scale_factor_events: EventReader<'w, 's, WindowScaleFactorChanged>,
+    resize_events: EventReader<'w, 's, bevy_window::WindowResized>,
+    root_node_query: Query<'w, 's, Entity, (With<Node>, Without<Parent>)>,
+    style_query: Query<'w, 's, (Entity, Ref<'static, Style>), With<Node>>,
+    measure_query: Query<'w, 's, (Entity, &'static mut ContentSize)>,
+    children_query: Query<'w, 's, (Entity, Ref<'static, Children>), With<Node>>,
+    removed_children: RemovedComponents<'w, 's, Children>,
+    removed_content_sizes: RemovedComponents<'w, 's, ContentSize>,
+    removed_nodes: RemovedComponents<'w, 's, Node>,
+}
+
+/// The queries of [`ui_layout_system`] used to update the UI nodes from their computed layout.
+type UpdateNodesQueries<'w, 's> = (
+    Query<'w, 's, (&'static mut Node, &'static mut Transform)>,
+    Query<'w, 's, &'static Children, With<Node>>,
+);
+
 /// Updates the UI's layout tree, computes the new layout geometry and then updates the sizes and transforms of all the UI nodes.
-#[allow(clippy::too_many_arguments)]
+///
+/// This is an exclusive system so that the [`Measure`](crate::Measure)s of the nodes can read the
+/// [`World`] while the layout is computed, see [`MeasureArgs`](crate::MeasureArgs).
 pub fn ui_layout_system(
-    primary_window: Query<(Entity, &Window), With<PrimaryWindow>>,
-    windows: Query<(Entity, &Window)>,
-    ui_scale: Res<UiScale>,
-    mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
-    mut resize_events: EventReader<bevy_window::WindowResized>,
-    mut ui_surface: ResMut<UiSurface>,
-    root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
-    style_query: Query<(Entity, Ref<Style>), With<Node>>,
-    mut measure_query: Query<(Entity, &mut ContentSize)>,
-    children_query: Query<(Entity, Ref<Children>), With<Node>>,
-    mut removed_children: RemovedComponents<Children>,
-    mut removed_content_sizes: RemovedComponents<ContentSize>,
-    mut node_transform_query: Query<(Entity, &mut Node, &mut Transform, Option<&Parent>)>,
-    mut removed_nodes: RemovedComponents<Node>,
+    world: &mut World,
+    params: &mut SystemState<UiLayoutParams>,
+    node_transform_query: &mut SystemState<UpdateNodesQueries>,
 ) {
+    world.resource_scope(|world, mut ui_surface: Mut<UiSurface>| {
+        let Some((logical_to_physical_factor, scale_factor)) =
+            update_ui_surface(&mut ui_surface, params.get_mut(world))
+        else {
+            return;
+        };
+
+        // compute layouts
+        ui_surface.compute_window_layouts(world, scale_factor);
+
+        update_nodes(
+            &ui_surface,
+            logical_to_physical_factor,
+            node_transform_query.get_mut(world),
+        );
+    });
+}
+
+/// Updates the layout tree from the UI nodes, returning the scale factor of the primary window and
+/// the scale factor of the UI, or `None` if there is no primary window to lay the UI out in.
+fn update_ui_surface(ui_surface: &mut UiSurface, params: UiLayoutParams) -> Option<(f64, f64)> {
+    let UiLayoutParams {
+        primary_window,
+        windows,
+        ui_scale,
+        mut scale_factor_events,
+        mut resize_events,
+        root_node_query,
+        style_query,
+        mut measure_query,
+        children_query,
+        mut removed_children,
+        mut removed_content_sizes,
+        mut removed_nodes,
+    } = params;
     // assume one window for time being...
     // TODO: Support window-independent scaling: https://github.com/bevyengine/bevy/issues/5621
     let (primary_window_entity, logical_to_physical_factor, physical_size) =
@@ -241,7 +498,7 @@ pub fn ui_layout_system(
                 ),
             )
         } else {
-            return;
+            return None;
         };
 
     let resized = resize_events
@@ -298,36 +555,455 @@ pub fn ui_layout_system(
         }
     }
 
-    // compute layouts
-    ui_surface.compute_window_layouts();
+    Some((logical_to_physical_factor, scale_factor))
+}
 
+/// Updates the [`Node`] and [`Transform`] of the UI nodes from their computed layout.
+///
+/// The trees are walked from their roots, and only the children of the nodes whose layout was
+/// recomputed or resized are visited: a node laid out from the cache to the same size has the same
+/// descendant layouts, so the subtrees of a static UI are skipped.
+fn update_nodes(
+    ui_surface: &UiSurface,
+    logical_to_physical_factor: f64,
+    (mut node_transform_query, children_query): UpdateNodesQueries,
+) {
     let physical_to_logical_factor = 1. / logical_to_physical_factor;
 
     let to_logical = |v| (physical_to_logical_factor * v as f64) as f32;
 
-    // PERF: try doing this incrementally
-    for (entity, mut node, mut transform, parent) in &mut node_transform_query {
-        let layout = ui_surface.get_layout(entity).unwrap();
+    // The nodes to update, with the physical size of their parent if it isn't a window
+    let mut stack: Vec<(Entity, Option<Size<f32>>)> = Vec::new();
+    for (_, roots) in ui_surface.window_roots_iter() {
+        stack.extend(
+            roots
+                .iter()
+                .filter(|root| ui_surface.recomputed_nodes.contains(&root.window_node))
+                .map(|root| (root.root_entity, None)),
+        );
+    }
+
+    while let Some((entity, parent_size)) = stack.pop() {
+        let Ok((mut node, mut transform)) = node_transform_query.get_mut(entity) else {
+            continue;
+        };
+        let Ok(layout) = ui_surface.get_layout(entity) else {
+            continue;
+        };
         let new_size = Vec2::new(
             to_logical(layout.size.width),
             to_logical(layout.size.height),
         );
-        // only trigger change detection when the new value is different
-        if node.calculated_size != new_size {
+        // only trigger change detection when the new value is different, `Node` changes are
+        // used to find the nodes whose layout changed
+        let resized = node.calculated_size != new_size;
+        if resized {
             node.calculated_size = new_size;
         }
         let mut new_position = transform.translation;
         new_position.x = to_logical(layout.location.x + layout.size.width / 2.0);
         new_position.y = to_logical(layout.location.y + layout.size.height / 2.0);
-        if let Some(parent) = parent {
-            if let Ok(parent_layout) = ui_surface.get_layout(**parent) {
-                new_position.x -= to_logical(parent_layout.size.width / 2.0);
-                new_position.y -= to_logical(parent_layout.size.height / 2.0);
-            }
+        if let Some(parent_size) = parent_size {
+            new_position.x -= to_logical(parent_size.width / 2.0);
+            new_position.y -= to_logical(parent_size.height / 2.0);
         }
         // only trigger change detection when the new value is different
         if transform.translation != new_position {
             transform.translation = new_position;
+            node.set_changed();
         }
+
+        let recomputed = ui_surface
+            .recomputed_nodes
+            .contains(&ui_surface.entity_to_taffy[&entity]);
+        if recomputed || resized {
+            if let Ok(children) = children_query.get(entity) {
+                stack.extend(children.iter().map(|child| (*child, Some(layout.size))));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AlignSelf, Val};
+    use bevy_ecs::system::{CommandQueue, Commands};
+    use bevy_hierarchy::{BuildWorldChildren, DespawnHooks, DespawnRecursiveExt};
+    use bevy_transform::{
+        components::GlobalTransform,
+        systems::{propagate_transforms, sync_simple_transforms},
+    };
+
+    #[test]
+    fn window_roots_iter() {
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let window = Entity::from_raw(0);
+        let roots = [Entity::from_raw(1), Entity::from_raw(2)];
+        for root in roots {
+            ui_surface.upsert_node(root, &Style::default(), &context);
+        }
+        ui_surface.update_window(window, &WindowResolution::new(800.0, 600.0));
+        assert_eq!(
+            ui_surface.window_roots_iter().collect::<Vec<_>>(),
+            vec![(window, &[][..])]
+        );
+
+        ui_surface.set_window_children(window, roots.into_iter());
+        let (window_entity, window_roots) = ui_surface.window_roots_iter().next().unwrap();
+        assert_eq!(window_entity, window);
+        let window_node = ui_surface.window_nodes[&window];
+        assert_eq!(
+            window_roots,
+            roots.map(|root_entity| RootNodePair {
+                window_node,
+                root_entity,
+            })
+        );
+    }
+
+    #[test]
+    fn compute_window_layouts_tracks_recomputed_nodes() {
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let window = Entity::from_raw(0);
+        let roots = [Entity::from_raw(1), Entity::from_raw(2)];
+        for root in roots {
+            ui_surface.upsert_node(root, &Style::default(), &context);
+        }
+        ui_surface.update_window(window, &WindowResolution::new(800.0, 600.0));
+        ui_surface.set_window_children(window, roots.into_iter());
+        ui_surface.compute_window_layouts(&World::new(), 1.0);
+        assert_eq!(ui_surface.recomputed_nodes.len(), 3);
+
+        ui_surface.compute_window_layouts(&World::new(), 1.0);
+        assert!(ui_surface.recomputed_nodes.is_empty());
+
+        let style = Style {
+            width: Val::Px(10.),
+            ..Default::default()
+        };
+        ui_surface.upsert_node(roots[0], &style, &context);
+        ui_surface.compute_window_layouts(&World::new(), 1.0);
+        let window_node = ui_surface.window_nodes[&window];
+        let root_node = ui_surface.entity_to_taffy[&roots[0]];
+        assert_eq!(
+            ui_surface.recomputed_nodes,
+            [window_node, root_node].into_iter().collect()
+        );
+    }
+
+    #[test]
+    fn update_children_keeps_children_order() {
+        let mut world = World::new();
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let [parent, a, b, c] = std::array::from_fn(|_| world.spawn_empty().id());
+        for entity in [parent, a, b, c] {
+            ui_surface.upsert_node(entity, &Style::default(), &context);
+        }
+        world.entity_mut(parent).push_children(&[c, a, b]);
+        ui_surface.update_children(parent, world.get::<Children>(parent).unwrap());
+
+        let taffy_children = ui_surface
+            .taffy
+            .children(ui_surface.entity_to_taffy[&parent])
+            .unwrap();
+        assert_eq!(
+            taffy_children,
+            [c, a, b].map(|entity| ui_surface.entity_to_taffy[&entity])
+        );
+    }
+
+    #[test]
+    fn dangling_entities() {
+        let mut world = World::new();
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let entities = [world.spawn_empty().id(), world.spawn_empty().id()];
+        for entity in entities {
+            ui_surface.upsert_node(entity, &Style::default(), &context);
+        }
+        assert!(ui_surface.dangling_entities(&world).is_empty());
+
+        world.despawn(entities[1]);
+        assert_eq!(ui_surface.dangling_entities(&world), vec![entities[1]]);
+
+        ui_surface.remove_entities([entities[1]]);
+        assert!(ui_surface.dangling_entities(&world).is_empty());
+    }
+
+    #[test]
+    fn audit_reports_tree_issues() {
+        let mut world = World::new();
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let [window_a, window_b, root, parent, empty, outside, hidden, orphan, shared] =
+            std::array::from_fn(|_| world.spawn_empty().id());
+        let sized = |left| Style {
+            left: Val::Px(left),
+            width: Val::Px(10.),
+            height: Val::Px(10.),
+            ..Default::default()
+        };
+        for entity in [root, parent, orphan, shared] {
+            ui_surface.upsert_node(entity, &sized(0.), &context);
+        }
+        ui_surface.upsert_node(empty, &Style::default(), &context);
+        ui_surface.upsert_node(outside, &sized(1000.), &context);
+        let hidden_style = Style {
+            display: crate::Display::None,
+            ..Default::default()
+        };
+        ui_surface.upsert_node(hidden, &hidden_style, &context);
+        world
+            .entity_mut(parent)
+            .push_children(&[empty, outside, hidden]);
+        ui_surface.update_children(parent, world.get::<Children>(parent).unwrap());
+        for window in [window_a, window_b] {
+            ui_surface.update_window(window, &WindowResolution::new(800.0, 600.0));
+        }
+        ui_surface.set_window_children(window_a, [root, parent, shared].into_iter());
+        ui_surface.set_window_children(window_b, [shared].into_iter());
+        ui_surface.compute_window_layouts(&world, 1.0);
+        assert!(ui_surface.audit().dangling_roots.is_empty());
+
+        // Forget the layout node of a root without removing it from its window
+        ui_surface.entity_to_taffy.remove(&root);
+        let audit = ui_surface.audit();
+        assert_eq!(
+            audit,
+            LayoutAudit {
+                orphaned_nodes: vec![orphan],
+                dangling_roots: vec![root],
+                overlapping_roots: vec![shared],
+                zero_sized_nodes: vec![empty],
+                out_of_viewport_nodes: vec![outside],
+            }
+        );
+        assert!(!audit.is_empty());
+    }
+
+    #[test]
+    fn despawn_recursive_removes_layout_nodes() {
+        let mut world = World::new();
+        world.init_resource::<UiSurface>();
+        world.init_resource::<DespawnHooks>();
+        world
+            .resource_mut::<DespawnHooks>()
+            .add::<Node>(remove_despawned_ui_node);
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let [parent, child] = std::array::from_fn(|_| world.spawn(Node::default()).id());
+        world.entity_mut(parent).push_children(&[child]);
+        let window = world.spawn_empty().id();
+        let mut ui_surface = world.resource_mut::<UiSurface>();
+        for entity in [parent, child] {
+            ui_surface.upsert_node(entity, &Style::default(), &context);
+        }
+        ui_surface.update_window(window, &WindowResolution::new(800.0, 600.0));
+        ui_surface.set_window_children(window, [parent].into_iter());
+
+        let mut queue = CommandQueue::default();
+        Commands::new(&mut queue, &world)
+            .entity(parent)
+            .despawn_recursive();
+        queue.apply(&mut world);
+
+        // The nodes are removed during the despawn, before the layout system runs again
+        let ui_surface = world.resource::<UiSurface>();
+        assert!(ui_surface.entity_to_taffy.is_empty());
+        assert!(ui_surface.dangling_entities(&world).is_empty());
+        assert_eq!(
+            ui_surface.window_roots_iter().collect::<Vec<_>>(),
+            vec![(window, &[][..])]
+        );
+    }
+
+    #[test]
+    fn aspect_ratio_sizes_auto_dimension() {
+        let mut ui_surface = UiSurface::default();
+        let context = LayoutContext::new(1.0, Vec2::new(800.0, 600.0));
+        let window = Entity::from_raw(0);
+        let roots = [Entity::from_raw(1), Entity::from_raw(2)];
+        let styles = [
+            Style {
+                width: Val::Px(160.),
+                aspect_ratio: Some(16. / 9.),
+                align_self: AlignSelf::Start,
+                ..Default::default()
+            },
+            Style {
+                height: Val::Px(50.),
+                aspect_ratio: Some(1.),
+                align_self: AlignSelf::Start,
+                ..Default::default()
+            },
+        ];
+        for (root, style) in roots.iter().zip(&styles) {
+            ui_surface.upsert_node(*root, style, &context);
+        }
+        ui_surface.update_window(window, &WindowResolution::new(800.0, 600.0));
+        ui_surface.set_window_children(window, roots.into_iter());
+        ui_surface.compute_window_layouts(&World::new(), 1.0);
+
+        let size = |entity| {
+            let layout = ui_surface.get_layout(entity).unwrap();
+            (layout.size.width, layout.size.height)
+        };
+        assert_eq!(size(roots[0]), (160., 90.));
+        assert_eq!(size(roots[1]), (50., 50.));
+    }
+
+    #[test]
+    fn layout_changed_only_matches_new_layouts() {
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<UiSurface>();
+        world.init_resource::<bevy_ecs::event::Events<WindowScaleFactorChanged>>();
+        world.init_resource::<bevy_ecs::event::Events<bevy_window::WindowResized>>();
+        world.spawn((Window::default(), PrimaryWindow));
+        let mut schedule = bevy_ecs::schedule::Schedule::new();
+        schedule.add_systems(ui_layout_system);
+
+        let style = |width| Style {
+            width: Val::Px(width),
+            height: Val::Px(10.0),
+            ..Default::default()
+        };
+        let first = world
+            .spawn((Node::default(), style(10.0), Transform::default()))
+            .id();
+        let second = world
+            .spawn((Node::default(), style(10.0), Transform::default()))
+            .id();
+        let mut changed = world.query_filtered::<Entity, LayoutChanged>();
+
+        schedule.run(&mut world);
+        assert_eq!(changed.iter(&world).count(), 2);
+        world.clear_trackers();
+
+        // Laying out the same nodes again doesn't change them
+        world.entity_mut(first).insert(style(10.0));
+        schedule.run(&mut world);
+        assert_eq!(changed.iter(&world).count(), 0);
+        world.clear_trackers();
+
+        // Resizing the first node resizes it and moves the second one
+        world.entity_mut(first).insert(style(20.0));
+        schedule.run(&mut world);
+        let mut changed_entities: Vec<_> = changed.iter(&world).collect();
+        changed_entities.sort();
+        assert_eq!(changed_entities, vec![first, second]);
+    }
+
+    #[test]
+    fn static_ui_tree_has_no_changes_on_quiet_frames() {
+        bevy_tasks::ComputeTaskPool::init(bevy_tasks::TaskPool::default);
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<UiSurface>();
+        world.init_resource::<bevy_ecs::event::Events<WindowScaleFactorChanged>>();
+        world.init_resource::<bevy_ecs::event::Events<bevy_window::WindowResized>>();
+        world.spawn((Window::default(), PrimaryWindow));
+        let mut schedule = bevy_ecs::schedule::Schedule::new();
+        schedule.add_systems(
+            (
+                ui_layout_system,
+                sync_simple_transforms,
+                propagate_transforms,
+            )
+                .chain(),
+        );
+
+        let style = Style {
+            width: Val::Px(10.0),
+            height: Val::Px(10.0),
+            ..Default::default()
+        };
+        let spawn_node = |world: &mut World| {
+            world
+                .spawn((
+                    Node::default(),
+                    style.clone(),
+                    Transform::default(),
+                    GlobalTransform::default(),
+                ))
+                .id()
+        };
+        let root = spawn_node(&mut world);
+        for _ in 0..3 {
+            let child = spawn_node(&mut world);
+            world.entity_mut(root).push_children(&[child]);
+            for _ in 0..3 {
+                let grandchild = spawn_node(&mut world);
+                world.entity_mut(child).push_children(&[grandchild]);
+            }
+        }
+        let mut changed_layouts = world.query_filtered::<Entity, LayoutChanged>();
+        let mut changed_transforms = world.query_filtered::<Entity, Changed<Transform>>();
+        let mut changed_global_transforms =
+            world.query_filtered::<Entity, Changed<GlobalTransform>>();
+
+        schedule.run(&mut world);
+        world.clear_trackers();
+
+        schedule.run(&mut world);
+        assert_eq!(changed_layouts.iter(&world).count(), 0);
+        assert_eq!(changed_transforms.iter(&world).count(), 0);
+        assert_eq!(changed_global_transforms.iter(&world).count(), 0);
+
+        // Only the window node is laid out again, the UI nodes reuse their cached layout
+        let ui_surface = world.resource::<UiSurface>();
+        assert!(ui_surface
+            .entity_to_taffy
+            .values()
+            .all(|node| !ui_surface.recomputed_nodes.contains(node)));
+    }
+
+    #[test]
+    fn measure_funcs_read_the_world() {
+        #[derive(Resource)]
+        struct ContentWidth(f32);
+
+        struct ResourceMeasure;
+
+        impl crate::Measure for ResourceMeasure {
+            fn measure(&self, args: crate::MeasureArgs) -> Vec2 {
+                let width = args.world.resource::<ContentWidth>().0;
+                Vec2::new(width, width / 2.0) * args.scale_factor as f32
+            }
+        }
+
+        let mut world = World::new();
+        world.insert_resource(UiScale { scale: 2.0 });
+        world.insert_resource(ContentWidth(30.0));
+        world.init_resource::<UiSurface>();
+        world.init_resource::<bevy_ecs::event::Events<WindowScaleFactorChanged>>();
+        world.init_resource::<bevy_ecs::event::Events<bevy_window::WindowResized>>();
+        world.spawn((Window::default(), PrimaryWindow));
+        let mut schedule = bevy_ecs::schedule::Schedule::new();
+        schedule.add_systems(ui_layout_system);
+
+        let mut content_size = ContentSize::default();
+        content_size.set(ResourceMeasure);
+        let node = world
+            .spawn((
+                Node::default(),
+                Style {
+                    align_self: AlignSelf::Start,
+                    ..Default::default()
+                },
+                Transform::default(),
+                content_size,
+            ))
+            .id();
+        schedule.run(&mut world);
+
+        // The window has a scale factor of 1, so the scale factor of the UI is the `UiScale`
+        assert_eq!(
+            world.get::<Node>(node).unwrap().calculated_size,
+            Vec2::new(60.0, 30.0)
+        );
     }
 }
@@ -9,6 +9,8 @@ mod geometry;
 mod layout;
 mod render;
 mod stack;
+mod transition;
+mod ui_material;
 mod ui_node;
 
 #[cfg(feature = "bevy_text")]
@@ -27,6 +29,8 @@ pub use geometry::*;
 pub use layout::*;
 pub use measurement::*;
 pub use render::*;
+pub use transition::*;
+pub use ui_material::*;
 pub use ui_node::*;
 use widget::UiImageSize;
 
@@ -34,14 +38,15 @@ use widget::UiImageSize;
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        camera_config::*, geometry::*, node_bundles::*, ui_node::*, widget::Button, widget::Label,
-        Interaction, UiScale,
+        camera_config::*, geometry::*, node_bundles::*, ui_material::*, ui_node::*, widget::Button,
+        widget::Label, Interaction, UiMaterialPlugin, UiScale,
     };
 }
 
 use crate::prelude::UiCameraConfig;
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
+use bevy_hierarchy::DespawnHooks;
 use bevy_input::InputSystem;
 use bevy_transform::TransformSystem;
 use stack::ui_stack_system;
@@ -57,6 +62,11 @@ pub struct UiPlugin;
 pub enum UiSystem {
     /// After this label, the ui layout state has been updated
     Layout,
+    /// Runs after [`UiSystem::Layout`] and before the transforms are propagated, for systems
+    /// that react to the new layout, like positioning a popup next to its anchor.
+    ///
+    /// Filter with [`LayoutChanged`] to only handle the nodes whose layout changed this frame.
+    PostLayout,
     /// After this label, input interactions with UI entities have been updated for this frame
     Focus,
     /// After this label, the [`UiStack`] resource has been updated
@@ -90,6 +100,7 @@ impl Plugin for UiPlugin {
             .register_type::<AlignSelf>()
             .register_type::<BackgroundColor>()
             .register_type::<CalculatedClip>()
+            .register_type::<widget::ColorGradient>()
             .register_type::<ContentSize>()
             .register_type::<Direction>()
             .register_type::<Display>()
@@ -99,7 +110,10 @@ impl Plugin for UiPlugin {
             .register_type::<GridAutoFlow>()
             .register_type::<GridPlacement>()
             .register_type::<GridTrack>()
+            .register_type::<ImageScaleMode>()
             .register_type::<Interaction>()
+            .register_type::<AccessibilityHint>()
+            .register_type::<AriaRole>()
             .register_type::<JustifyContent>()
             .register_type::<JustifyItems>()
             .register_type::<JustifySelf>()
@@ -109,6 +123,8 @@ impl Plugin for UiPlugin {
             .register_type::<Overflow>()
             .register_type::<OverflowAxis>()
             .register_type::<PositionType>()
+            .register_type::<widget::ProgressBar>()
+            .register_type::<widget::ProgressDirection>()
             .register_type::<RelativeCursorPosition>()
             .register_type::<RepeatedGridTrack>()
             .register_type::<Style>()
@@ -119,10 +135,19 @@ impl Plugin for UiPlugin {
             .register_type::<widget::Button>()
             .register_type::<widget::Label>()
             .register_type::<ZIndex>()
+            .init_resource::<DespawnHooks>()
+            .add_event::<Click>()
+            .add_event::<PressStart>()
+            .add_event::<PressCancel>()
+            .add_event::<HoverStart>()
+            .add_event::<HoverEnd>()
             .add_systems(
                 PreUpdate,
                 ui_focus_system.in_set(UiSystem::Focus).after(InputSystem),
             );
+        app.world
+            .resource_mut::<DespawnHooks>()
+            .add::<Node>(layout::remove_despawned_ui_node);
         // add these systems to front because these must run before transform update systems
         #[cfg(feature = "bevy_text")]
         app.add_systems(
@@ -143,7 +168,57 @@ impl Plugin for UiPlugin {
             ),
         );
         #[cfg(feature = "bevy_text")]
-        app.add_plugin(accessibility::AccessibilityPlugin);
+        app.add_plugin(accessibility::AccessibilityPlugin)
+            .init_resource::<widget::TooltipStyle>()
+            .init_resource::<widget::CheckboxStyle>()
+            .init_resource::<widget::DropdownStyle>()
+            .init_resource::<widget::RadioStyle>()
+            .init_resource::<widget::TextInputClipboard>()
+            .init_resource::<widget::TextInputFocus>()
+            .init_resource::<widget::TextInputStyle>()
+            .register_type::<widget::Checkbox>()
+            .register_type::<widget::Dropdown>()
+            .register_type::<widget::RadioGroup>()
+            .register_type::<widget::TextInput>()
+            // NOTE: used by TextInput::selection
+            .register_type::<Option<std::ops::Range<usize>>>()
+            .register_type::<widget::Tooltip>()
+            .add_event::<widget::CheckboxChanged>()
+            .add_event::<widget::DropdownChanged>()
+            .add_event::<widget::RadioGroupChanged>()
+            .add_systems(
+                PreUpdate,
+                (
+                    widget::tooltip_system,
+                    widget::checkbox_interaction_system,
+                    widget::radio_group_interaction_system,
+                    (
+                        widget::dropdown_interaction_system,
+                        widget::dropdown_keyboard_system,
+                    )
+                        .chain(),
+                    (
+                        widget::text_input_focus_system,
+                        widget::text_input_keyboard_system,
+                    )
+                        .chain(),
+                )
+                    .after(UiSystem::Focus),
+            )
+            .add_systems(
+                PostUpdate,
+                (
+                    widget::update_checkbox_system,
+                    widget::update_radio_group_system,
+                    widget::update_dropdown_system,
+                    widget::update_text_input_system,
+                )
+                    .before(UiSystem::Layout),
+            );
+        #[cfg(feature = "bevy_text")]
+        app.world
+            .resource_mut::<DespawnHooks>()
+            .add::<widget::TextInput>(widget::unfocus_despawned_text_input);
         app.add_systems(PostUpdate, {
             let system = widget::update_image_content_size_system.before(UiSystem::Layout);
             // Potential conflicts: `Assets<Image>`
@@ -157,6 +232,12 @@ impl Plugin for UiPlugin {
 
             system
         })
+        .configure_set(
+            PostUpdate,
+            UiSystem::PostLayout
+                .after(UiSystem::Layout)
+                .before(TransformSystem::TransformPropagate),
+        )
         .add_systems(
             PostUpdate,
             (
@@ -165,6 +246,14 @@ impl Plugin for UiPlugin {
                     .before(TransformSystem::TransformPropagate),
                 ui_stack_system.in_set(UiSystem::Stack),
                 update_clipping_system.after(TransformSystem::TransformPropagate),
+                widget::watch_ui_images.before(bevy_render::texture::update_failed_images),
+                widget::progress_bar_system.before(UiSystem::Layout),
+                (
+                    transition_system::<BackgroundColor>,
+                    transition_system::<StyleWidth>,
+                    transition_system::<StyleHeight>,
+                )
+                    .before(UiSystem::Layout),
             ),
         );
 
@@ -1,27 +1,83 @@
 use bevy_ecs::prelude::Component;
 use bevy_ecs::reflect::ReflectComponent;
+use bevy_ecs::world::World;
 use bevy_math::Vec2;
 use bevy_reflect::Reflect;
 use std::fmt::Formatter;
 pub use taffy::style::AvailableSpace;
 
+scoped_tls::scoped_thread_local!(static MEASURE_WORLD: World);
+scoped_tls::scoped_thread_local!(static MEASURE_SCALE_FACTOR: f64);
+
 impl std::fmt::Debug for ContentSize {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ContentSize").finish()
     }
 }
 
+/// The arguments of [`Measure::measure`].
+///
+/// The measure funcs are called while the layout is computed by
+/// [`ui_layout_system`](crate::ui_layout_system), which gives them read-only access to the
+/// [`World`], so nodes can be measured from assets or resources instead of data copied into the
+/// [`Measure`] beforehand. The [`UiSurface`](crate::UiSurface) is taken out of the world while the
+/// layout is computed, so it can't be read.
+pub struct MeasureArgs<'a> {
+    /// The width of the node, if it is already known
+    pub width: Option<f32>,
+    /// The height of the node, if it is already known
+    pub height: Option<f32>,
+    /// The width available to the node
+    pub available_width: AvailableSpace,
+    /// The height available to the node
+    pub available_height: AvailableSpace,
+    /// The number of physical pixels per logical pixel of the UI, the scale factor of the window
+    /// multiplied by the [`UiScale`](crate::UiScale)
+    pub scale_factor: f64,
+    /// The world the layout is computed in
+    pub world: &'a World,
+}
+
 /// A `Measure` is used to compute the size of a ui node
 /// when the size of that node is based on its content.
+///
+/// The sizes are in physical pixels. A `Measure` only needs to hold what identifies the content,
+/// like the handle of an asset, and can look the content up in the [`World`] of the
+/// [`MeasureArgs`]. Since [`ContentSize::set`] marks the node to be laid out again, it should be
+/// called again whenever the content changes its size:
+///
+/// ```
+/// # use bevy_asset::{Assets, Handle};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_math::Vec2;
+/// # use bevy_render::texture::Image;
+/// # use bevy_ui::{ContentSize, Measure, MeasureArgs};
+/// /// Sizes a node to twice the size of an image
+/// struct DoubleImageMeasure(Handle<Image>);
+///
+/// impl Measure for DoubleImageMeasure {
+///     fn measure(&self, args: MeasureArgs) -> Vec2 {
+///         args.world
+///             .resource::<Assets<Image>>()
+///             .get(&self.0)
+///             .map_or(Vec2::ZERO, |image| 2. * image.size())
+///     }
+/// }
+///
+/// #[derive(Component)]
+/// struct DoubleImage(Handle<Image>);
+///
+/// fn update_double_image_measures(
+///     mut query: Query<(&DoubleImage, &mut ContentSize), Changed<DoubleImage>>,
+/// ) {
+///     for (double_image, mut content_size) in &mut query {
+///         content_size.set(DoubleImageMeasure(double_image.0.clone_weak()));
+///     }
+/// }
+/// ```
 pub trait Measure: Send + Sync + 'static {
     /// Calculate the size of the node given the constraints.
-    fn measure(
-        &self,
-        width: Option<f32>,
-        height: Option<f32>,
-        available_width: AvailableSpace,
-        available_height: AvailableSpace,
-    ) -> Vec2;
+    fn measure(&self, args: MeasureArgs) -> Vec2;
 }
 
 /// A `FixedMeasure` is a `Measure` that ignores all constraints and
@@ -32,13 +88,7 @@ pub struct FixedMeasure {
 }
 
 impl Measure for FixedMeasure {
-    fn measure(
-        &self,
-        _: Option<f32>,
-        _: Option<f32>,
-        _: AvailableSpace,
-        _: AvailableSpace,
-    ) -> Vec2 {
+    fn measure(&self, _: MeasureArgs) -> Vec2 {
         self.size
     }
 }
@@ -55,12 +105,23 @@ pub struct ContentSize {
 
 impl ContentSize {
     /// Set a `Measure` for this function
+    ///
+    /// The measure is only called while the layout is computed with
+    /// [`UiSurface::compute_window_layouts`](crate::UiSurface::compute_window_layouts).
     pub fn set(&mut self, measure: impl Measure) {
         let measure_func =
             move |size: taffy::prelude::Size<Option<f32>>,
                   available: taffy::prelude::Size<AvailableSpace>| {
-                let size =
-                    measure.measure(size.width, size.height, available.width, available.height);
+                let size = with_measure_world(|world, scale_factor| {
+                    measure.measure(MeasureArgs {
+                        width: size.width,
+                        height: size.height,
+                        available_width: available.width,
+                        available_height: available.height,
+                        scale_factor,
+                        world,
+                    })
+                });
                 taffy::prelude::Size {
                     width: size.x,
                     height: size.y,
@@ -80,3 +141,21 @@ impl Default for ContentSize {
         }
     }
 }
+
+/// Runs `f`, giving `world` and `scale_factor` to the measure funcs called during it.
+pub(crate) fn set_measure_world<T>(world: &World, scale_factor: f64, f: impl FnOnce() -> T) -> T {
+    MEASURE_SCALE_FACTOR.set(&scale_factor, || MEASURE_WORLD.set(world, f))
+}
+
+/// Calls `f` with the world and scale factor of the layout being computed.
+///
+/// # Panics
+///
+/// Panics if no layout is being computed, when taffy is used to compute the layout directly.
+fn with_measure_world<T>(f: impl FnOnce(&World, f64) -> T) -> T {
+    assert!(
+        MEASURE_WORLD.is_set(),
+        "UI nodes can only be measured while `UiSurface::compute_window_layouts` is running"
+    );
+    MEASURE_WORLD.with(|world| MEASURE_SCALE_FACTOR.with(|&scale_factor| f(world, scale_factor)))
+}
@@ -1,5 +1,6 @@
 mod pipeline;
 mod render_pass;
+mod ui_material_pipeline;
 
 use bevy_core_pipeline::{core_2d::Camera2d, core_3d::Camera3d};
 use bevy_render::{ExtractSchedule, Render};
@@ -7,14 +8,18 @@ use bevy_render::{ExtractSchedule, Render};
 use bevy_window::{PrimaryWindow, Window};
 pub use pipeline::*;
 pub use render_pass::*;
+pub use ui_material_pipeline::*;
 
-use crate::{prelude::UiCameraConfig, BackgroundColor, CalculatedClip, Node, UiImage, UiStack};
+use crate::{
+    prelude::UiCameraConfig, BackgroundColor, CalculatedClip, ImageScaleMode, Node, UiImage,
+    UiStack,
+};
 use bevy_app::prelude::*;
 use bevy_asset::{load_internal_asset, AssetEvent, Assets, Handle, HandleUntyped};
 use bevy_ecs::prelude::*;
 use bevy_math::{Mat4, Rect, UVec4, Vec2, Vec3, Vec4Swizzles};
 use bevy_reflect::TypeUuid;
-use bevy_render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy_render::texture::{FailedImages, DEFAULT_IMAGE_HANDLE};
 use bevy_render::{
     camera::Camera,
     color::Color,
@@ -27,9 +32,9 @@ use bevy_render::{
     view::{ComputedVisibility, ExtractedView, ViewUniforms},
     Extract, RenderApp, RenderSet,
 };
-use bevy_sprite::SpriteAssetEvents;
 #[cfg(feature = "bevy_text")]
 use bevy_sprite::TextureAtlas;
+use bevy_sprite::{ExtractedSprites, NineSliceSprite, SpriteAssetEvents, SpriteSystem};
 #[cfg(feature = "bevy_text")]
 use bevy_text::{PositionedGlyph, Text, TextLayoutInfo};
 use bevy_transform::components::GlobalTransform;
@@ -78,6 +83,9 @@ pub fn build_ui_render(app: &mut App) {
                 extract_default_ui_camera_view::<Camera2d>,
                 extract_default_ui_camera_view::<Camera3d>,
                 extract_uinodes.in_set(RenderUiSystem::ExtractNode),
+                extract_nine_slice_uinodes
+                    .after(RenderUiSystem::ExtractNode)
+                    .after(SpriteSystem::ExtractSprites),
                 #[cfg(feature = "bevy_text")]
                 extract_text_uinodes.after(RenderUiSystem::ExtractNode),
             ),
@@ -159,11 +167,15 @@ pub struct ExtractedUiNode {
 #[derive(Resource, Default)]
 pub struct ExtractedUiNodes {
     pub uinodes: Vec<ExtractedUiNode>,
+    /// The stack indices of the nodes drawn by other pipelines, like [`UiMaterialPlugin`], which
+    /// the batches of `uinodes` are split around
+    pub(crate) batch_breaks: Vec<usize>,
 }
 
 pub fn extract_uinodes(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     images: Extract<Res<Assets<Image>>>,
+    failed_images: Extract<Res<FailedImages>>,
     ui_stack: Extract<Res<UiStack>>,
     uinode_query: Extract<
         Query<(
@@ -177,6 +189,7 @@ pub fn extract_uinodes(
     >,
 ) {
     extracted_uinodes.uinodes.clear();
+    extracted_uinodes.batch_breaks.clear();
     for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
         if let Ok((uinode, transform, color, maybe_image, visibility, clip)) =
             uinode_query.get(*entity)
@@ -187,11 +200,56 @@ pub fn extract_uinodes(
             }
 
             let (image, flip_x, flip_y) = if let Some(image) = maybe_image {
-                // Skip loading images
-                if !images.contains(&image.texture) {
+                let texture = if images.contains(&image.texture) {
+                    image.texture.clone_weak()
+                } else if failed_images.contains(image.texture.id()) {
+                    // Draw the fallback in place of images that failed to load, if it's visible
+                    let Some(fallback) = failed_images.resolve(image.texture.id()) else {
+                        continue;
+                    };
+                    Handle::weak(fallback)
+                } else {
+                    // Skip loading images
+                    continue;
+                };
+                if let (
+                    ImageScaleMode::Sliced {
+                        border,
+                        center_mode,
+                    },
+                    Some(texture_image),
+                ) = (image.scale_mode, images.get(&texture))
+                {
+                    let transform = transform.compute_matrix();
+                    let image_size = texture_image.size();
+                    for slice in border.compute_slices(center_mode, image_size, uinode.size()) {
+                        // Flipping mirrors the cells around the center of the node, and each cell
+                        // around its own center
+                        let mut offset = slice.offset;
+                        if image.flip_x {
+                            offset.x = -offset.x;
+                        }
+                        if image.flip_y {
+                            offset.y = -offset.y;
+                        }
+                        let scale = slice.draw_size / slice.texture_rect.size();
+                        extracted_uinodes.uinodes.push(ExtractedUiNode {
+                            stack_index,
+                            transform: transform
+                                * Mat4::from_translation(Vec3::new(offset.x, -offset.y, 0.))
+                                * Mat4::from_scale(scale.extend(1.)),
+                            color: color.0,
+                            rect: slice.texture_rect,
+                            image: texture.clone_weak(),
+                            atlas_size: Some(image_size),
+                            clip: clip.map(|clip| clip.clip),
+                            flip_x: image.flip_x,
+                            flip_y: image.flip_y,
+                        });
+                    }
                     continue;
                 }
-                (image.texture.clone_weak(), image.flip_x, image.flip_y)
+                (texture, image.flip_x, image.flip_y)
             } else {
                 (DEFAULT_IMAGE_HANDLE.typed().clone_weak(), false, false)
             };
@@ -214,6 +272,63 @@ pub fn extract_uinodes(
     }
 }
 
+/// Draws [`NineSliceSprite`] UI node backgrounds, sized to the node.
+pub fn extract_nine_slice_uinodes(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    extracted_sprites: Option<ResMut<ExtractedSprites>>,
+    images: Extract<Res<Assets<Image>>>,
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &NineSliceSprite,
+            &ComputedVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    if uinode_query.is_empty() {
+        return;
+    }
+    // UI nodes are visible to every camera, so don't also draw them in the world
+    if let Some(mut extracted_sprites) = extracted_sprites {
+        extracted_sprites
+            .sprites
+            .retain(|sprite| !uinode_query.contains(sprite.entity));
+    }
+
+    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
+        if let Ok((uinode, transform, sprite, visibility, clip)) = uinode_query.get(*entity) {
+            if !visibility.is_visible() || uinode.size().x == 0. || uinode.size().y == 0. {
+                continue;
+            }
+            let Some(image) = images.get(&sprite.texture) else {
+                continue;
+            };
+            let transform = transform.compute_matrix();
+            for slice in sprite.compute_slices(image.size(), uinode.size()) {
+                // Stretch the texture area over the cell. Like scaled nodes, stretched cells that
+                // are partially clipped don't adjust their UVs for the scale.
+                let scale = slice.draw_size / slice.texture_rect.size();
+                extracted_uinodes.uinodes.push(ExtractedUiNode {
+                    stack_index,
+                    transform: transform
+                        * Mat4::from_translation(Vec3::new(slice.offset.x, -slice.offset.y, 0.))
+                        * Mat4::from_scale(scale.extend(1.)),
+                    color: sprite.color,
+                    rect: slice.texture_rect,
+                    image: sprite.texture.clone_weak(),
+                    atlas_size: Some(image.size()),
+                    clip: clip.map(|clip| clip.clip),
+                    flip_x: false,
+                    flip_y: false,
+                });
+            }
+        }
+    }
+}
+
 /// The UI camera is "moved back" by this many units (plus the [`UI_CAMERA_TRANSFORM_OFFSET`]) and also has a view
 /// distance of this many units. This ensures that with a left-handed projection,
 /// as ui elements are "stacked on top of each other", they are within the camera's view
@@ -379,7 +494,8 @@ const QUAD_INDICES: [usize; 6] = [0, 2, 3, 0, 1, 2];
 pub struct UiBatch {
     pub range: Range<u32>,
     pub image: Handle<Image>,
-    pub z: f32,
+    /// The stack index of the first node of the batch, which orders it against other UI items
+    pub stack_index: usize,
 }
 
 pub fn prepare_uinodes(
@@ -392,25 +508,37 @@ pub fn prepare_uinodes(
     ui_meta.vertices.clear();
 
     // sort by ui stack index, starting from the deepest node
+    let extracted_uinodes = &mut *extracted_uinodes;
     extracted_uinodes
         .uinodes
         .sort_by_key(|node| node.stack_index);
+    extracted_uinodes.batch_breaks.sort_unstable();
 
     let mut start = 0;
     let mut end = 0;
     let mut current_batch_handle = Default::default();
-    let mut last_z = 0.0;
+    let mut batch_stack_index = 0;
+    let mut batch_breaks = extracted_uinodes.batch_breaks.iter().peekable();
     for extracted_uinode in &extracted_uinodes.uinodes {
-        if current_batch_handle != extracted_uinode.image {
+        // Nodes drawn by other pipelines in between end the batch, so they are drawn in order
+        let mut crosses_break = false;
+        while batch_breaks
+            .next_if(|&&index| index < extracted_uinode.stack_index)
+            .is_some()
+        {
+            crosses_break = true;
+        }
+        if current_batch_handle != extracted_uinode.image || crosses_break {
             if start != end {
                 commands.spawn(UiBatch {
                     range: start..end,
                     image: current_batch_handle,
-                    z: last_z,
+                    stack_index: batch_stack_index,
                 });
                 start = end;
             }
             current_batch_handle = extracted_uinode.image.clone_weak();
+            batch_stack_index = extracted_uinode.stack_index;
         }
 
         let mut uinode_rect = extracted_uinode.rect;
@@ -517,7 +645,6 @@ pub fn prepare_uinodes(
             });
         }
 
-        last_z = extracted_uinode.transform.w_axis[2];
         end += QUAD_INDICES.len() as u32;
     }
 
@@ -526,7 +653,7 @@ pub fn prepare_uinodes(
         commands.spawn(UiBatch {
             range: start..end,
             image: current_batch_handle,
-            z: last_z,
+            stack_index: batch_stack_index,
         });
     }
 
@@ -556,7 +683,7 @@ pub fn queue_uinodes(
     // If an image has changed, the GpuImage has (probably) changed
     for event in &events.images {
         match event {
-            AssetEvent::Created { .. } => None,
+            AssetEvent::Created { .. } | AssetEvent::Unused { .. } => None,
             AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => {
                 image_bind_groups.values.remove(handle)
             }
@@ -604,7 +731,7 @@ pub fn queue_uinodes(
                     draw_function: draw_ui_function,
                     pipeline,
                     entity,
-                    sort_key: FloatOrd(batch.z),
+                    sort_key: FloatOrd(batch.stack_index as f32),
                 });
             }
         }
@@ -0,0 +1,31 @@
+#import bevy_render::view
+
+@group(0) @binding(0)
+var<uniform> view: View;
+
+struct VertexOutput {
+    #import bevy_ui::ui_vertex_output
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vertex(
+    @location(0) vertex_position: vec3<f32>,
+    @location(1) vertex_uv: vec2<f32>,
+    @location(2) size: vec2<f32>,
+) -> VertexOutput {
+    var out: VertexOutput;
+    out.uv = vertex_uv;
+    out.size = size;
+    out.position = view.view_proj * vec4<f32>(vertex_position, 1.0);
+    return out;
+}
+
+struct FragmentInput {
+    #import bevy_ui::ui_vertex_output
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
@@ -0,0 +1,618 @@
+use std::{hash::Hash, marker::PhantomData, ops::Range};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{
+    load_internal_asset, AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleUntyped,
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::*,
+    system::{
+        lifetimeless::{Read, SRes},
+        SystemParamItem,
+    },
+};
+use bevy_math::{Mat4, Rect, Vec2, Vec4Swizzles};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    render_asset::{PrepareAssetSet, RenderAssets},
+    render_phase::{
+        AddRenderCommand, DrawFunctions, PhaseItem, RenderCommand, RenderCommandResult,
+        RenderPhase, SetItemPipeline, TrackedRenderPass,
+    },
+    render_resource::*,
+    renderer::{RenderDevice, RenderQueue},
+    texture::{BevyDefault, FallbackImage, Image},
+    view::{ComputedVisibility, ExtractedView, ViewTarget},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{FloatOrd, HashMap, HashSet};
+use bytemuck::{Pod, Zeroable};
+
+use super::{
+    ExtractedUiNodes, RenderUiSystem, SetUiViewBindGroup, TransparentUi, UiPipeline, QUAD_INDICES,
+    QUAD_VERTEX_POSITIONS,
+};
+use crate::{CalculatedClip, Node, UiMaterial, UiMaterialKey, UiMaterialNode, UiStack};
+
+pub const UI_MATERIAL_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10074188772096983955);
+
+pub const UI_VERTEX_OUTPUT_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 10123618247720234751);
+
+/// Adds the necessary ECS resources and render logic to draw UI nodes with a [`UiMaterialNode`]
+/// of the given [`UiMaterial`] type.
+pub struct UiMaterialPlugin<M: UiMaterial>(PhantomData<M>);
+
+impl<M: UiMaterial> Default for UiMaterialPlugin<M> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<M: UiMaterial> Plugin for UiMaterialPlugin<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            UI_VERTEX_OUTPUT_SHADER_HANDLE,
+            "ui_vertex_output.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            UI_MATERIAL_SHADER_HANDLE,
+            "ui_material.wgsl",
+            Shader::from_wgsl
+        );
+        app.add_asset::<M>();
+
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .add_render_command::<TransparentUi, DrawUiMaterial<M>>()
+                .init_resource::<ExtractedUiMaterials<M>>()
+                .init_resource::<ExtractedUiMaterialNodes<M>>()
+                .init_resource::<RenderUiMaterials<M>>()
+                .init_resource::<UiMaterialMeta<M>>()
+                .init_resource::<SpecializedRenderPipelines<UiMaterialPipeline<M>>>()
+                .add_systems(
+                    ExtractSchedule,
+                    (
+                        extract_ui_materials::<M>,
+                        extract_ui_material_nodes::<M>.after(RenderUiSystem::ExtractNode),
+                    ),
+                )
+                .add_systems(
+                    Render,
+                    (
+                        prepare_ui_materials::<M>
+                            .in_set(RenderSet::Prepare)
+                            .after(PrepareAssetSet::PreAssetPrepare),
+                        prepare_ui_material_nodes::<M>.in_set(RenderSet::Prepare),
+                        queue_ui_material_nodes::<M>.in_set(RenderSet::Queue),
+                    ),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<UiMaterialPipeline<M>>();
+        }
+    }
+}
+
+/// Render pipeline data for a given [`UiMaterial`]
+#[derive(Resource)]
+pub struct UiMaterialPipeline<M: UiMaterial> {
+    pub view_layout: BindGroupLayout,
+    pub ui_material_layout: BindGroupLayout,
+    pub vertex_shader: Option<Handle<Shader>>,
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<M>,
+}
+
+impl<M: UiMaterial> SpecializedRenderPipeline for UiMaterialPipeline<M>
+where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    type Key = UiMaterialKey<M>;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let vertex_layout = VertexBufferLayout::from_vertex_formats(
+            VertexStepMode::Vertex,
+            vec![
+                // position
+                VertexFormat::Float32x3,
+                // uv
+                VertexFormat::Float32x2,
+                // size
+                VertexFormat::Float32x2,
+            ],
+        );
+        let shader_defs = Vec::new();
+
+        let mut descriptor = RenderPipelineDescriptor {
+            vertex: VertexState {
+                shader: self
+                    .vertex_shader
+                    .clone()
+                    .unwrap_or_else(|| UI_MATERIAL_SHADER_HANDLE.typed()),
+                entry_point: "vertex".into(),
+                shader_defs: shader_defs.clone(),
+                buffers: vec![vertex_layout],
+            },
+            fragment: Some(FragmentState {
+                shader: self
+                    .fragment_shader
+                    .clone()
+                    .unwrap_or_else(|| UI_MATERIAL_SHADER_HANDLE.typed()),
+                shader_defs,
+                entry_point: "fragment".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: if key.hdr {
+                        ViewTarget::TEXTURE_FORMAT_HDR
+                    } else {
+                        TextureFormat::bevy_default()
+                    },
+                    blend: Some(BlendState::ALPHA_BLENDING),
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            layout: vec![self.view_layout.clone(), self.ui_material_layout.clone()],
+            push_constant_ranges: Vec::new(),
+            primitive: PrimitiveState {
+                front_face: FrontFace::Ccw,
+                cull_mode: None,
+                unclipped_depth: false,
+                polygon_mode: PolygonMode::Fill,
+                conservative: false,
+                topology: PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+            },
+            depth_stencil: None,
+            multisample: MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            label: Some("ui_material_pipeline".into()),
+        };
+
+        M::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+impl<M: UiMaterial> FromWorld for UiMaterialPipeline<M> {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.resource::<AssetServer>();
+        let render_device = world.resource::<RenderDevice>();
+        let ui_material_layout = M::bind_group_layout(render_device);
+
+        UiMaterialPipeline {
+            view_layout: world.resource::<UiPipeline>().view_layout.clone(),
+            ui_material_layout,
+            vertex_shader: match M::vertex_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            fragment_shader: match M::fragment_shader() {
+                ShaderRef::Default => None,
+                ShaderRef::Handle(handle) => Some(handle),
+                ShaderRef::Path(path) => Some(asset_server.load(path)),
+            },
+            marker: PhantomData,
+        }
+    }
+}
+
+pub type DrawUiMaterial<M> = (
+    SetItemPipeline,
+    SetUiViewBindGroup<0>,
+    SetUiMaterialBindGroup<M, 1>,
+    DrawUiMaterialNode<M>,
+);
+
+pub struct SetUiMaterialBindGroup<M: UiMaterial, const I: usize>(PhantomData<M>);
+impl<P: PhaseItem, M: UiMaterial, const I: usize> RenderCommand<P>
+    for SetUiMaterialBindGroup<M, I>
+{
+    type Param = SRes<RenderUiMaterials<M>>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<UiMaterialBatch<M>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        batch: &'w UiMaterialBatch<M>,
+        materials: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let Some(material) = materials.into_inner().get(&batch.material) else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(I, &material.bind_group, &[]);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct DrawUiMaterialNode<M: UiMaterial>(PhantomData<M>);
+impl<P: PhaseItem, M: UiMaterial> RenderCommand<P> for DrawUiMaterialNode<M> {
+    type Param = SRes<UiMaterialMeta<M>>;
+    type ViewWorldQuery = ();
+    type ItemWorldQuery = Read<UiMaterialBatch<M>>;
+
+    #[inline]
+    fn render<'w>(
+        _item: &P,
+        _view: (),
+        batch: &'w UiMaterialBatch<M>,
+        ui_meta: SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        pass.set_vertex_buffer(0, ui_meta.into_inner().vertices.buffer().unwrap().slice(..));
+        pass.draw(batch.range.clone(), 0..1);
+        RenderCommandResult::Success
+    }
+}
+
+pub struct ExtractedUiMaterialNode<M: UiMaterial> {
+    pub stack_index: usize,
+    pub transform: Mat4,
+    pub size: Vec2,
+    pub material: Handle<M>,
+    pub clip: Option<Rect>,
+}
+
+#[derive(Resource)]
+pub struct ExtractedUiMaterialNodes<M: UiMaterial> {
+    pub uinodes: Vec<ExtractedUiMaterialNode<M>>,
+}
+
+impl<M: UiMaterial> Default for ExtractedUiMaterialNodes<M> {
+    fn default() -> Self {
+        Self {
+            uinodes: Default::default(),
+        }
+    }
+}
+
+pub fn extract_ui_material_nodes<M: UiMaterial>(
+    mut extracted_uinodes: ResMut<ExtractedUiNodes>,
+    mut extracted_material_nodes: ResMut<ExtractedUiMaterialNodes<M>>,
+    materials: Extract<Res<Assets<M>>>,
+    ui_stack: Extract<Res<UiStack>>,
+    uinode_query: Extract<
+        Query<(
+            &Node,
+            &GlobalTransform,
+            &UiMaterialNode<M>,
+            &ComputedVisibility,
+            Option<&CalculatedClip>,
+        )>,
+    >,
+) {
+    extracted_material_nodes.uinodes.clear();
+    for (stack_index, entity) in ui_stack.uinodes.iter().enumerate() {
+        if let Ok((uinode, transform, material, visibility, clip)) = uinode_query.get(*entity) {
+            // Skip invisible and empty nodes, and materials that aren't loaded
+            if !visibility.is_visible()
+                || uinode.size().cmple(Vec2::ZERO).any()
+                || !materials.contains(&material.0)
+            {
+                continue;
+            }
+            extracted_material_nodes
+                .uinodes
+                .push(ExtractedUiMaterialNode {
+                    stack_index,
+                    transform: transform.compute_matrix(),
+                    size: uinode.size(),
+                    material: material.0.clone_weak(),
+                    clip: clip.map(|clip| clip.clip),
+                });
+            extracted_uinodes.batch_breaks.push(stack_index);
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Copy, Clone, Pod, Zeroable)]
+struct UiMaterialVertex {
+    pub position: [f32; 3],
+    pub uv: [f32; 2],
+    pub size: [f32; 2],
+}
+
+#[derive(Resource)]
+pub struct UiMaterialMeta<M: UiMaterial> {
+    vertices: BufferVec<UiMaterialVertex>,
+    marker: PhantomData<M>,
+}
+
+impl<M: UiMaterial> Default for UiMaterialMeta<M> {
+    fn default() -> Self {
+        Self {
+            vertices: BufferVec::new(BufferUsages::VERTEX),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// The vertices of a UI node drawn with a [`UiMaterial`].
+#[derive(Component)]
+pub struct UiMaterialBatch<M: UiMaterial> {
+    pub range: Range<u32>,
+    pub material: Handle<M>,
+    /// The stack index of the node, which orders it against other UI items
+    pub stack_index: usize,
+}
+
+pub fn prepare_ui_material_nodes<M: UiMaterial>(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut ui_meta: ResMut<UiMaterialMeta<M>>,
+    mut extracted_uinodes: ResMut<ExtractedUiMaterialNodes<M>>,
+) {
+    ui_meta.vertices.clear();
+
+    for extracted_uinode in extracted_uinodes.uinodes.drain(..) {
+        let rect_size = extracted_uinode.size.extend(1.0);
+
+        // Specify the corners of the node
+        let positions = QUAD_VERTEX_POSITIONS
+            .map(|pos| (extracted_uinode.transform * (pos * rect_size).extend(1.)).xyz());
+
+        // Calculate the effect of clipping, like for other UI nodes
+        // Note: this won't work with rotation/scaling, but that's much more complex (may need more that 2 quads)
+        let positions_diff = if let Some(clip) = extracted_uinode.clip {
+            [
+                Vec2::new(
+                    f32::max(clip.min.x - positions[0].x, 0.),
+                    f32::max(clip.min.y - positions[0].y, 0.),
+                ),
+                Vec2::new(
+                    f32::min(clip.max.x - positions[1].x, 0.),
+                    f32::max(clip.min.y - positions[1].y, 0.),
+                ),
+                Vec2::new(
+                    f32::min(clip.max.x - positions[2].x, 0.),
+                    f32::min(clip.max.y - positions[2].y, 0.),
+                ),
+                Vec2::new(
+                    f32::max(clip.min.x - positions[3].x, 0.),
+                    f32::min(clip.max.y - positions[3].y, 0.),
+                ),
+            ]
+        } else {
+            [Vec2::ZERO; 4]
+        };
+
+        let transformed_rect_size = extracted_uinode.transform.transform_vector3(rect_size);
+
+        // Don't try to cull nodes that have a rotation, see `prepare_uinodes`
+        if extracted_uinode.transform.x_axis[1] == 0.0
+            && (positions_diff[0].x - positions_diff[1].x >= transformed_rect_size.x
+                || positions_diff[1].y - positions_diff[2].y >= transformed_rect_size.y)
+        {
+            continue;
+        }
+
+        // The uvs of the clipped corners stay where they were on the whole node
+        let uvs = [Vec2::ZERO, Vec2::X, Vec2::ONE, Vec2::Y];
+        let start = ui_meta.vertices.len() as u32;
+        for i in QUAD_INDICES {
+            ui_meta.vertices.push(UiMaterialVertex {
+                position: (positions[i] + positions_diff[i].extend(0.)).into(),
+                uv: (uvs[i] + positions_diff[i] / transformed_rect_size.truncate()).into(),
+                size: extracted_uinode.size.into(),
+            });
+        }
+
+        commands.spawn(UiMaterialBatch {
+            range: start..ui_meta.vertices.len() as u32,
+            material: extracted_uinode.material,
+            stack_index: extracted_uinode.stack_index,
+        });
+    }
+
+    ui_meta.vertices.write_buffer(&render_device, &render_queue);
+}
+
+pub fn queue_ui_material_nodes<M: UiMaterial>(
+    draw_functions: Res<DrawFunctions<TransparentUi>>,
+    ui_material_pipeline: Res<UiMaterialPipeline<M>>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<UiMaterialPipeline<M>>>,
+    pipeline_cache: Res<PipelineCache>,
+    render_materials: Res<RenderUiMaterials<M>>,
+    ui_batches: Query<(Entity, &UiMaterialBatch<M>)>,
+    mut views: Query<(&ExtractedView, &mut RenderPhase<TransparentUi>)>,
+) where
+    M::Data: PartialEq + Eq + Hash + Clone,
+{
+    if ui_batches.is_empty() {
+        return;
+    }
+
+    let draw_function = draw_functions.read().id::<DrawUiMaterial<M>>();
+    for (view, mut transparent_phase) in &mut views {
+        for (entity, batch) in &ui_batches {
+            let Some(material) = render_materials.get(&batch.material) else {
+                continue;
+            };
+            let pipeline = pipelines.specialize(
+                &pipeline_cache,
+                &ui_material_pipeline,
+                UiMaterialKey {
+                    hdr: view.hdr,
+                    bind_group_data: material.key.clone(),
+                },
+            );
+            transparent_phase.add(TransparentUi {
+                draw_function,
+                pipeline,
+                entity,
+                sort_key: FloatOrd(batch.stack_index as f32),
+            });
+        }
+    }
+}
+
+/// Data prepared for a [`UiMaterial`] instance.
+pub struct PreparedUiMaterial<T: UiMaterial> {
+    pub bindings: Vec<OwnedBindingResource>,
+    pub bind_group: BindGroup,
+    pub key: T::Data,
+}
+
+#[derive(Resource)]
+pub struct ExtractedUiMaterials<M: UiMaterial> {
+    extracted: Vec<(Handle<M>, M)>,
+    removed: Vec<Handle<M>>,
+}
+
+impl<M: UiMaterial> Default for ExtractedUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            extracted: Default::default(),
+            removed: Default::default(),
+        }
+    }
+}
+
+/// Stores all prepared representations of [`UiMaterial`] assets for as long as they exist.
+#[derive(Resource, Deref, DerefMut)]
+pub struct RenderUiMaterials<T: UiMaterial>(HashMap<Handle<T>, PreparedUiMaterial<T>>);
+
+impl<T: UiMaterial> Default for RenderUiMaterials<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+/// This system extracts all created or modified assets of the corresponding [`UiMaterial`] type
+/// into the "render world".
+pub fn extract_ui_materials<M: UiMaterial>(
+    mut commands: Commands,
+    mut events: Extract<EventReader<AssetEvent<M>>>,
+    assets: Extract<Res<Assets<M>>>,
+) {
+    let mut changed_assets = HashSet::default();
+    let mut removed = Vec::new();
+    for event in events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                changed_assets.insert(handle.clone_weak());
+            }
+            AssetEvent::Removed { handle } => {
+                changed_assets.remove(handle);
+                removed.push(handle.clone_weak());
+            }
+            AssetEvent::Unused { .. } => {}
+        }
+    }
+
+    let mut extracted_assets = Vec::new();
+    for handle in changed_assets.drain() {
+        if let Some(asset) = assets.get(&handle) {
+            extracted_assets.push((handle, asset.clone()));
+        }
+    }
+
+    commands.insert_resource(ExtractedUiMaterials {
+        extracted: extracted_assets,
+        removed,
+    });
+}
+
+/// All [`UiMaterial`] values of a given type that should be prepared next frame.
+pub struct PrepareNextFrameUiMaterials<M: UiMaterial> {
+    assets: Vec<(Handle<M>, M)>,
+}
+
+impl<M: UiMaterial> Default for PrepareNextFrameUiMaterials<M> {
+    fn default() -> Self {
+        Self {
+            assets: Default::default(),
+        }
+    }
+}
+
+/// This system prepares all assets of the corresponding [`UiMaterial`] type
+/// which where extracted this frame for the GPU.
+pub fn prepare_ui_materials<M: UiMaterial>(
+    mut prepare_next_frame: Local<PrepareNextFrameUiMaterials<M>>,
+    mut extracted_assets: ResMut<ExtractedUiMaterials<M>>,
+    mut render_materials: ResMut<RenderUiMaterials<M>>,
+    render_device: Res<RenderDevice>,
+    images: Res<RenderAssets<Image>>,
+    fallback_image: Res<FallbackImage>,
+    pipeline: Res<UiMaterialPipeline<M>>,
+) {
+    let queued_assets = std::mem::take(&mut prepare_next_frame.assets);
+    for (handle, material) in queued_assets {
+        match prepare_ui_material(
+            &material,
+            &render_device,
+            &images,
+            &fallback_image,
+            &pipeline,
+        ) {
+            Ok(prepared_asset) => {
+                render_materials.insert(handle, prepared_asset);
+            }
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                prepare_next_frame.assets.push((handle, material));
+            }
+        }
+    }
+
+    for removed in std::mem::take(&mut extracted_assets.removed) {
+        render_materials.remove(&removed);
+    }
+
+    for (handle, material) in std::mem::take(&mut extracted_assets.extracted) {
+        match prepare_ui_material(
+            &material,
+            &render_device,
+            &images,
+            &fallback_image,
+            &pipeline,
+        ) {
+            Ok(prepared_asset) => {
+                render_materials.insert(handle, prepared_asset);
+            }
+            Err(AsBindGroupError::RetryNextUpdate) => {
+                prepare_next_frame.assets.push((handle, material));
+            }
+        }
+    }
+}
+
+fn prepare_ui_material<M: UiMaterial>(
+    material: &M,
+    render_device: &RenderDevice,
+    images: &RenderAssets<Image>,
+    fallback_image: &FallbackImage,
+    pipeline: &UiMaterialPipeline<M>,
+) -> Result<PreparedUiMaterial<M>, AsBindGroupError> {
+    let prepared = material.as_bind_group(
+        &pipeline.ui_material_layout,
+        render_device,
+        images,
+        fallback_image,
+    )?;
+    Ok(PreparedUiMaterial {
+        bindings: prepared.bindings,
+        bind_group: prepared.bind_group,
+        key: prepared.data,
+    })
+}
@@ -0,0 +1,6 @@
+#define_import_path bevy_ui::ui_vertex_output
+
+// The uv goes from the top left corner of the node at (0, 0) to its bottom right corner at (1, 1)
+@location(0) uv: vec2<f32>,
+// The size of the node in logical pixels
+@location(1) size: vec2<f32>,
@@ -63,14 +63,45 @@ fn insert_context_hierarchy(
     children_query: &Query<&Children>,
     entity: Entity,
     global_context: &mut StackingContext,
-    parent_context: Option<&mut StackingContext>,
+    mut parent_context: Option<&mut StackingContext>,
     total_entry_count: &mut usize,
 ) {
+    let z_index = zindex_query.get(entity).copied().unwrap_or_default();
+
+    // Nodes with `ZIndex::Auto` don't form a stacking context, so they are added to the context
+    // of their parent before their children, which are then sorted in the same context.
+    let (ZIndex::Local(value) | ZIndex::Global(value)) = z_index else {
+        *total_entry_count += 1;
+        parent_context
+            .as_deref_mut()
+            .unwrap_or(&mut *global_context)
+            .entries
+            .push(StackingContextEntry {
+                z_index: 0,
+                entity,
+                stack: StackingContext::default(),
+            });
+        if let Ok(children) = children_query.get(entity) {
+            for entity in children {
+                insert_context_hierarchy(
+                    zindex_query,
+                    children_query,
+                    *entity,
+                    global_context,
+                    parent_context.as_deref_mut(),
+                    total_entry_count,
+                );
+            }
+        }
+        return;
+    };
+
     let mut new_context = StackingContext::default();
 
     if let Ok(children) = children_query.get(entity) {
         // Reserve space for all children. In practice, some may not get pushed since
-        // nodes with `ZIndex::Global` are pushed to the global (root) context.
+        // nodes with `ZIndex::Global` are pushed to the global (root) context, and some more
+        // may be pushed for the descendants of children with `ZIndex::Auto`.
         new_context.entries.reserve_exact(children.len());
 
         for entity in children {
@@ -86,15 +117,14 @@ fn insert_context_hierarchy(
     }
 
     // The node will be added either to global/parent based on its z-index type: global/local.
-    let z_index = zindex_query.get(entity).unwrap_or(&ZIndex::Local(0));
-    let (entity_context, z_index) = match z_index {
-        ZIndex::Local(value) => (parent_context.unwrap_or(global_context), *value),
-        ZIndex::Global(value) => (global_context, *value),
+    let entity_context = match z_index {
+        ZIndex::Global(_) => global_context,
+        _ => parent_context.unwrap_or(global_context),
     };
 
     *total_entry_count += 1;
     entity_context.entries.push(StackingContextEntry {
-        z_index,
+        z_index: value,
         entity,
         stack: new_context,
     });
@@ -217,9 +247,9 @@ mod tests {
             (Label("2-0")),
             (Label("2-1")),
             (Label("2-1-0")),
-            (Label("1")), // ZIndex::Local(1)
+            (Label("1")),     // ZIndex::Local(1)
+            (Label("1-0-2")), // ZIndex::Local(-1), in the stacking context of "1"
             (Label("1-0")),
-            (Label("1-0-2")), // ZIndex::Local(-1)
             (Label("1-0-0")),
             (Label("1-0-1")),
             (Label("1-1")),
@@ -228,4 +258,59 @@ mod tests {
         ];
         assert_eq!(actual_result, expected_result);
     }
+
+    #[test]
+    fn test_ui_stack_stacking_contexts() {
+        fn stack_with_container_zindex(z_index: ZIndex) -> Vec<Label> {
+            let mut world = World::default();
+            world.init_resource::<UiStack>();
+
+            let mut queue = CommandQueue::default();
+            let mut commands = Commands::new(&mut queue, &world);
+            commands
+                .spawn(node_without_zindex("root"))
+                .with_children(|parent| {
+                    parent
+                        .spawn(node_with_zindex("container", z_index))
+                        .with_children(|parent| {
+                            parent.spawn(node_with_zindex("raised", ZIndex::Local(5)));
+                        });
+                    parent.spawn(node_without_zindex("sibling"));
+                });
+            queue.apply(&mut world);
+
+            let mut schedule = Schedule::default();
+            schedule.add_systems(ui_stack_system);
+            schedule.run(&mut world);
+
+            let mut query = world.query::<&Label>();
+            let ui_stack = world.resource::<UiStack>();
+            ui_stack
+                .uinodes
+                .iter()
+                .map(|entity| query.get(&world, *entity).unwrap().clone())
+                .collect()
+        }
+
+        // A local z-index forms a stacking context, which "raised" can't get out of
+        assert_eq!(
+            stack_with_container_zindex(ZIndex::Local(0)),
+            vec![
+                Label("root"),
+                Label("container"),
+                Label("raised"),
+                Label("sibling"),
+            ]
+        );
+        // With `ZIndex::Auto`, "raised" is sorted in the stacking context of the root
+        assert_eq!(
+            stack_with_container_zindex(ZIndex::Auto),
+            vec![
+                Label("root"),
+                Label("container"),
+                Label("sibling"),
+                Label("raised"),
+            ]
+        );
+    }
 }
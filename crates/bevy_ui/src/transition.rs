@@ -0,0 +1,385 @@
+use crate::{BackgroundColor, Style, UiSystem, Val};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res},
+};
+use bevy_render::color::Color;
+use bevy_time::Time;
+use std::{marker::PhantomData, time::Duration};
+
+/// Adds the [`transition_system`] animating the property `P` of the nodes with a
+/// [`Transition<P>`].
+///
+/// The [`BackgroundColor`], [`StyleWidth`] and [`StyleHeight`] properties are already animated by
+/// the [`UiPlugin`](crate::UiPlugin), this is only needed for other properties.
+pub struct TransitionPlugin<P>(PhantomData<fn() -> P>);
+
+impl<P> Default for TransitionPlugin<P> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<P: UiProperty> Plugin for TransitionPlugin<P> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, transition_system::<P>.before(UiSystem::Layout));
+    }
+}
+
+/// A value of a component of UI nodes that can be animated by a [`Transition`].
+pub trait UiProperty: Send + Sync + 'static {
+    /// The component holding the value
+    type Component: Component;
+    /// The type of the value
+    type Value: Clone + PartialEq + Send + Sync + 'static;
+
+    /// Reads the value of the property from `component`.
+    fn get(component: &Self::Component) -> Self::Value;
+
+    /// Writes `value` to the property of `component`.
+    fn set(component: &mut Self::Component, value: Self::Value);
+
+    /// The value a fraction `t` of the way from `from` to `to`, where `t` may be a little outside
+    /// of `0.0..=1.0` for easings that overshoot.
+    fn interpolate(from: &Self::Value, to: &Self::Value, t: f32) -> Self::Value;
+}
+
+impl UiProperty for BackgroundColor {
+    type Component = BackgroundColor;
+    type Value = Color;
+
+    fn get(component: &Self::Component) -> Self::Value {
+        component.0
+    }
+
+    fn set(component: &mut Self::Component, value: Self::Value) {
+        component.0 = value;
+    }
+
+    /// Interpolates the sRGB components, like CSS.
+    fn interpolate(from: &Self::Value, to: &Self::Value, t: f32) -> Self::Value {
+        let [r, g, b, a] = from.as_rgba_f32();
+        let [to_r, to_g, to_b, to_a] = to.as_rgba_f32();
+        let lerp = |from: f32, to: f32| from + (to - from) * t;
+        Color::rgba(
+            lerp(r, to_r),
+            lerp(g, to_g),
+            lerp(b, to_b),
+            lerp(a, to_a).clamp(0., 1.),
+        )
+    }
+}
+
+/// The [`Style::width`] of a node, as a [`UiProperty`]
+pub struct StyleWidth;
+
+impl UiProperty for StyleWidth {
+    type Component = Style;
+    type Value = Val;
+
+    fn get(component: &Self::Component) -> Self::Value {
+        component.width
+    }
+
+    fn set(component: &mut Self::Component, value: Self::Value) {
+        component.width = value;
+    }
+
+    fn interpolate(from: &Self::Value, to: &Self::Value, t: f32) -> Self::Value {
+        interpolate_val(*from, *to, t)
+    }
+}
+
+/// The [`Style::height`] of a node, as a [`UiProperty`]
+pub struct StyleHeight;
+
+impl UiProperty for StyleHeight {
+    type Component = Style;
+    type Value = Val;
+
+    fn get(component: &Self::Component) -> Self::Value {
+        component.height
+    }
+
+    fn set(component: &mut Self::Component, value: Self::Value) {
+        component.height = value;
+    }
+
+    fn interpolate(from: &Self::Value, to: &Self::Value, t: f32) -> Self::Value {
+        interpolate_val(*from, *to, t)
+    }
+}
+
+/// Interpolates two [`Val`]s of the same unit, or switches from one to the other halfway like
+/// CSS does for values that can't be interpolated.
+fn interpolate_val(from: Val, to: Val, t: f32) -> Val {
+    let lerp = |from: f32, to: f32| from + (to - from) * t;
+    match (from, to) {
+        (Val::Px(from), Val::Px(to)) => Val::Px(lerp(from, to)),
+        (Val::Percent(from), Val::Percent(to)) => Val::Percent(lerp(from, to)),
+        (Val::Vw(from), Val::Vw(to)) => Val::Vw(lerp(from, to)),
+        (Val::Vh(from), Val::Vh(to)) => Val::Vh(lerp(from, to)),
+        (Val::VMin(from), Val::VMin(to)) => Val::VMin(lerp(from, to)),
+        (Val::VMax(from), Val::VMax(to)) => Val::VMax(lerp(from, to)),
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// How the progress of a [`Transition`] is distributed over its duration, like the CSS
+/// `transition-timing-function`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Easing {
+    /// Constant speed
+    Linear,
+    /// Speeds up quickly and slows down gradually, the CSS default
+    #[default]
+    Ease,
+    /// Starts slowly
+    EaseIn,
+    /// Ends slowly
+    EaseOut,
+    /// Starts and ends slowly
+    EaseInOut,
+    /// A cubic Bézier curve from `(0, 0)` to `(1, 1)` with the control points `(x1, y1)` and
+    /// `(x2, y2)`, like the CSS `cubic-bezier()`. The `x` coordinates are clamped to `0.0..=1.0`.
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// The progress of the transition at the fraction `t` of its duration, between `0.0` and
+    /// `1.0` unless the curve overshoots.
+    pub fn sample(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        let (x1, y1, x2, y2) = match *self {
+            Easing::Linear => return t,
+            Easing::Ease => (0.25, 0.1, 0.25, 1.),
+            Easing::EaseIn => (0.42, 0., 1., 1.),
+            Easing::EaseOut => (0., 0., 0.58, 1.),
+            Easing::EaseInOut => (0.42, 0., 0.58, 1.),
+            Easing::CubicBezier(x1, y1, x2, y2) => (x1.clamp(0., 1.), y1, x2.clamp(0., 1.), y2),
+        };
+        let bezier = |p1: f32, p2: f32, s: f32| {
+            let inverse = 1. - s;
+            3. * inverse * inverse * s * p1 + 3. * inverse * s * s * p2 + s * s * s
+        };
+        // `x` is monotonic with the control points in `0.0..=1.0`, so the curve parameter for
+        // `t` is found by bisection
+        let (mut low, mut high) = (0., 1.);
+        let mut s = t;
+        for _ in 0..32 {
+            let x = bezier(x1, x2, s);
+            if (x - t).abs() < 1e-6 {
+                break;
+            }
+            if x < t {
+                low = s;
+            } else {
+                high = s;
+            }
+            s = (low + high) / 2.;
+        }
+        bezier(y1, y2, s)
+    }
+}
+
+/// Animates changes to the property `P` of a node, like a CSS `transition`.
+///
+/// When the value of the property is changed, the [`transition_system`] puts the previous value
+/// back and moves it to the new one over `duration`, after waiting for `delay`. Changing the
+/// value again while it is moving starts a new transition from the value currently shown.
+///
+/// The built-in properties are [`BackgroundColor`], [`StyleWidth`] and [`StyleHeight`], others
+/// need a [`TransitionPlugin`].
+#[derive(Component)]
+pub struct Transition<P: UiProperty> {
+    /// How long the property takes to reach its new value
+    pub duration: Duration,
+    /// How the progress is distributed over the duration
+    pub easing: Easing,
+    /// How long the property keeps its previous value before it starts moving
+    pub delay: Duration,
+    marker: PhantomData<fn() -> P>,
+}
+
+impl<P: UiProperty> Transition<P> {
+    /// Creates a [`Transition`] with the default [`Easing`] and no delay.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            easing: Easing::default(),
+            delay: Duration::ZERO,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns this [`Transition`] with `easing`.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Returns this [`Transition`] starting after `delay`.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// The state of the [`Transition<P>`] of a node, added by [`transition_system`]: the value it
+/// started from, the value it moves to, when it started and the value currently shown.
+#[derive(Component)]
+pub struct ActiveTransition<P: UiProperty> {
+    from: P::Value,
+    to: P::Value,
+    start: Duration,
+    current: P::Value,
+    finished: bool,
+}
+
+impl<P: UiProperty> ActiveTransition<P> {
+    /// The value the property moves to
+    pub fn target(&self) -> &P::Value {
+        &self.to
+    }
+
+    /// Returns `true` if the property reached its target.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Starts a transition when the property `P` of a node with a [`Transition<P>`] changes, and
+/// moves the property of the nodes in transition towards their target.
+pub fn transition_system<P: UiProperty>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut nodes: Query<(
+        Entity,
+        &Transition<P>,
+        &mut P::Component,
+        Option<&mut ActiveTransition<P>>,
+    )>,
+) {
+    let now = time.elapsed();
+    for (entity, transition, mut component, active) in &mut nodes {
+        let value = P::get(&component);
+        let Some(mut active) = active else {
+            commands.entity(entity).insert(ActiveTransition::<P> {
+                from: value.clone(),
+                to: value.clone(),
+                start: now,
+                current: value,
+                finished: true,
+            });
+            continue;
+        };
+
+        if value != active.current {
+            // Changed since the last frame, start over from what is shown
+            active.from = active.current.clone();
+            active.to = value.clone();
+            active.start = now;
+            active.finished = false;
+        }
+        if active.finished {
+            continue;
+        }
+
+        let elapsed = now
+            .saturating_sub(active.start)
+            .saturating_sub(transition.delay);
+        let t = if transition.duration.is_zero() {
+            1.
+        } else {
+            elapsed.as_secs_f32() / transition.duration.as_secs_f32()
+        };
+        let current = if t >= 1. {
+            active.finished = true;
+            active.to.clone()
+        } else {
+            P::interpolate(&active.from, &active.to, transition.easing.sample(t))
+        };
+        if current != value {
+            P::set(&mut component, current.clone());
+        }
+        active.current = current;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_utils::Instant;
+
+    #[test]
+    fn easing_endpoints() {
+        for easing in [
+            Easing::Linear,
+            Easing::Ease,
+            Easing::EaseIn,
+            Easing::EaseOut,
+            Easing::EaseInOut,
+            Easing::CubicBezier(0.3, -0.5, 0.7, 1.5),
+        ] {
+            assert!(easing.sample(0.).abs() < 1e-4);
+            assert!((easing.sample(1.) - 1.).abs() < 1e-4);
+        }
+        assert!((Easing::EaseInOut.sample(0.5) - 0.5).abs() < 1e-4);
+        assert!(Easing::EaseIn.sample(0.25) < 0.25);
+        assert!(Easing::EaseOut.sample(0.25) > 0.25);
+    }
+
+    #[test]
+    fn transition_restarts_from_current_value() {
+        let mut world = World::new();
+        let start = Instant::now();
+        let mut time = Time::new(start);
+        time.update_with_instant(start);
+        world.insert_resource(time);
+        let mut schedule = Schedule::new();
+        schedule.add_systems(transition_system::<StyleWidth>);
+        let mut advance = |world: &mut World, millis: u64| {
+            world
+                .resource_mut::<Time>()
+                .update_with_instant(start + Duration::from_millis(millis));
+            schedule.run(world);
+        };
+
+        let node = world
+            .spawn((
+                Style {
+                    width: Val::Px(0.),
+                    ..Default::default()
+                },
+                Transition::<StyleWidth>::new(Duration::from_millis(100))
+                    .with_easing(Easing::Linear)
+                    .with_delay(Duration::from_millis(10)),
+            ))
+            .id();
+        advance(&mut world, 0);
+        assert!(world.get::<ActiveTransition<StyleWidth>>(node).is_some());
+
+        world.get_mut::<Style>(node).unwrap().width = Val::Px(100.);
+        // The previous value is kept during the delay
+        advance(&mut world, 0);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(0.));
+        advance(&mut world, 60);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(50.));
+
+        // Retargeting mid-flight starts from the value shown
+        world.get_mut::<Style>(node).unwrap().width = Val::Px(0.);
+        advance(&mut world, 60);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(50.));
+        advance(&mut world, 120);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(25.));
+        advance(&mut world, 200);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(0.));
+        let active = world.get::<ActiveTransition<StyleWidth>>(node).unwrap();
+        assert!(active.is_finished());
+        assert_eq!(active.target(), &Val::Px(0.));
+    }
+}
@@ -0,0 +1,164 @@
+use std::hash::Hash;
+
+use bevy_asset::Handle;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::component::Component;
+use bevy_reflect::{TypePath, TypeUuid};
+use bevy_render::render_resource::{AsBindGroup, RenderPipelineDescriptor, ShaderRef};
+
+/// Materials are used alongside [`UiMaterialPlugin`](crate::UiMaterialPlugin) and
+/// [`MaterialNodeBundle`](crate::node_bundles::MaterialNodeBundle) to draw UI nodes with custom
+/// shader logic, without having to write a render pipeline.
+///
+/// UiMaterials must implement [`AsBindGroup`] to define how data will be transferred to the GPU and bound in shaders.
+/// [`AsBindGroup`] can be derived, which makes generating bindings straightforward. See the [`AsBindGroup`] docs for details.
+///
+/// Materials must also implement [`TypeUuid`] so they can be treated as an [`Asset`](bevy_asset::Asset).
+///
+/// The node is drawn as a quad covering its whole area, trimmed to its
+/// [`CalculatedClip`](crate::CalculatedClip). Its background color and image are not drawn.
+///
+/// # Example
+///
+/// Here is a simple UiMaterial implementation. The [`AsBindGroup`] derive has many features. To see what else is available,
+/// check out the [`AsBindGroup`] documentation.
+/// ```
+/// # use bevy_ui::{node_bundles::MaterialNodeBundle, Style, UiMaterial, UiMaterialNode, Val};
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_reflect::{TypeUuid, TypePath};
+/// # use bevy_render::{render_resource::{AsBindGroup, ShaderRef}, color::Color};
+/// # use bevy_asset::Assets;
+///
+/// #[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
+/// #[uuid = "9c5a0ddf-1eaf-41b4-9832-ed736fd26af3"]
+/// pub struct CustomMaterial {
+///     #[uniform(0)]
+///     color: Color,
+/// }
+///
+/// // All functions on `UiMaterial` have default impls. You only need to implement the
+/// // functions that are relevant for your material.
+/// impl UiMaterial for CustomMaterial {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/custom_ui_material.wgsl".into()
+///     }
+/// }
+///
+/// // Spawn a UI node using `CustomMaterial`.
+/// fn setup(mut commands: Commands, mut materials: ResMut<Assets<CustomMaterial>>) {
+///     commands.spawn(MaterialNodeBundle {
+///         style: Style {
+///             width: Val::Px(200.0),
+///             height: Val::Px(200.0),
+///             ..Default::default()
+///         },
+///         material: UiMaterialNode(materials.add(CustomMaterial { color: Color::RED })),
+///         ..Default::default()
+///     });
+/// }
+/// ```
+/// In WGSL shaders, the material's binding and the inputs of the fragment shader would look like
+/// this:
+///
+/// ```wgsl
+/// struct CustomMaterial {
+///     color: vec4<f32>,
+/// }
+///
+/// @group(1) @binding(0)
+/// var<uniform> material: CustomMaterial;
+///
+/// struct FragmentInput {
+///     #import bevy_ui::ui_vertex_output
+/// };
+///
+/// @fragment
+/// fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+///     // `in.uv` goes from the top left to the bottom right corner of the node, and `in.size`
+///     // is the size of the node in logical pixels
+///     return material.color * in.uv.x;
+/// }
+/// ```
+pub trait UiMaterial: AsBindGroup + Send + Sync + Clone + TypeUuid + TypePath + Sized {
+    /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default UI
+    /// vertex shader will be used.
+    fn vertex_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the default
+    /// UI fragment shader, which draws the node white, will be used.
+    fn fragment_shader() -> ShaderRef {
+        ShaderRef::Default
+    }
+
+    /// Customizes the default [`RenderPipelineDescriptor`].
+    #[allow(unused_variables)]
+    #[inline]
+    fn specialize(descriptor: &mut RenderPipelineDescriptor, key: UiMaterialKey<Self>) {}
+}
+
+/// The key a [`UiMaterial`] pipeline is specialized with.
+pub struct UiMaterialKey<M: UiMaterial> {
+    /// Whether the pipeline draws to an HDR target
+    pub hdr: bool,
+    /// The data of the material, from [`AsBindGroup::Data`]
+    pub bind_group_data: M::Data,
+}
+
+impl<M: UiMaterial> Eq for UiMaterialKey<M> where M::Data: PartialEq {}
+
+impl<M: UiMaterial> PartialEq for UiMaterialKey<M>
+where
+    M::Data: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.hdr == other.hdr && self.bind_group_data == other.bind_group_data
+    }
+}
+
+impl<M: UiMaterial> Clone for UiMaterialKey<M>
+where
+    M::Data: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            hdr: self.hdr,
+            bind_group_data: self.bind_group_data.clone(),
+        }
+    }
+}
+
+impl<M: UiMaterial> Hash for UiMaterialKey<M>
+where
+    M::Data: Hash,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hdr.hash(state);
+        self.bind_group_data.hash(state);
+    }
+}
+
+/// The [`UiMaterial`] a UI node is drawn with, in place of its background color and image.
+///
+/// The material type must be added with [`UiMaterialPlugin`](crate::UiMaterialPlugin).
+#[derive(Component, Debug, Deref, DerefMut)]
+pub struct UiMaterialNode<M: UiMaterial>(pub Handle<M>);
+
+impl<M: UiMaterial> Default for UiMaterialNode<M> {
+    fn default() -> Self {
+        Self(Handle::default())
+    }
+}
+
+impl<M: UiMaterial> Clone for UiMaterialNode<M> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<M: UiMaterial> From<Handle<M>> for UiMaterialNode<M> {
+    fn from(handle: Handle<M>) -> Self {
+        Self(handle)
+    }
+}
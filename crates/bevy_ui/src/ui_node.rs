@@ -8,6 +8,7 @@ use bevy_render::{
     color::Color,
     texture::{Image, DEFAULT_IMAGE_HANDLE},
 };
+use bevy_sprite::{BorderRect, NineSliceCenter};
 use bevy_transform::prelude::GlobalTransform;
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
@@ -383,6 +384,9 @@ pub struct Style {
 
     /// The aspect ratio of the node (defined as `width / height`)
     ///
+    /// When one of `width` and `height` is [`Val::Auto`], it is computed from the other one so
+    /// the node keeps this ratio, like for square thumbnails or widescreen video placeholders.
+    ///
     /// <https://developer.mozilla.org/en-US/docs/Web/CSS/aspect-ratio>
     pub aspect_ratio: Option<f32>,
 
@@ -1212,6 +1216,32 @@ impl GridTrack {
         }
         .into()
     }
+
+    /// Create a `repeat(auto-fill, minmax())` grid track, which repeats a minmax() track as many
+    /// times as fits the size of the grid container.
+    ///
+    /// `max` must be a fixed pixel or percentage size, as the number of tracks is computed from it.
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/repeat#auto-fill>
+    pub fn auto_fill<T: From<RepeatedGridTrack>>(
+        min: MinTrackSizingFunction,
+        max: MaxTrackSizingFunction,
+    ) -> T {
+        RepeatedGridTrack::minmax(GridTrackRepetition::AutoFill, min, max)
+    }
+
+    /// Create a `repeat(auto-fit, minmax())` grid track, which repeats a minmax() track as many
+    /// times as fits the size of the grid container, then collapses the tracks that have no items.
+    ///
+    /// `max` must be a fixed pixel or percentage size, as the number of tracks is computed from it.
+    ///
+    /// <https://developer.mozilla.org/en-US/docs/Web/CSS/repeat#auto-fit>
+    pub fn auto_fit<T: From<RepeatedGridTrack>>(
+        min: MinTrackSizingFunction,
+        max: MaxTrackSizingFunction,
+    ) -> T {
+        RepeatedGridTrack::minmax(GridTrackRepetition::AutoFit, min, max)
+    }
 }
 
 impl Default for GridTrack {
@@ -1573,6 +1603,8 @@ pub struct UiImage {
     pub flip_x: bool,
     /// Whether the image should be flipped along its y-axis
     pub flip_y: bool,
+    /// How the image is scaled to the size of the node
+    pub scale_mode: ImageScaleMode,
 }
 
 impl Default for UiImage {
@@ -1581,6 +1613,7 @@ impl Default for UiImage {
             texture: DEFAULT_IMAGE_HANDLE.typed(),
             flip_x: false,
             flip_y: false,
+            scale_mode: ImageScaleMode::default(),
         }
     }
 }
@@ -1606,6 +1639,35 @@ impl UiImage {
         self.flip_y = true;
         self
     }
+
+    /// draw the image as a nine-slice with the given borders, see [`ImageScaleMode::Sliced`]
+    #[must_use]
+    pub const fn with_slices(mut self, border: BorderRect, center_mode: NineSliceCenter) -> Self {
+        self.scale_mode = ImageScaleMode::Sliced {
+            border,
+            center_mode,
+        };
+        self
+    }
+}
+
+/// How a [`UiImage`] is scaled to the size of its node.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+#[reflect(Default, PartialEq)]
+pub enum ImageScaleMode {
+    /// Stretch the whole image over the node
+    #[default]
+    Stretch,
+    /// Split the image into a 3×3 grid, where the corners keep their size and the edges stretch
+    /// along the node, like a [`NineSliceSprite`](bevy_sprite::NineSliceSprite).
+    ///
+    /// This lets buttons and panels scale without distorting their borders.
+    Sliced {
+        /// The size of the borders in the image, in pixels
+        border: BorderRect,
+        /// How the center of the image fills the space between the borders
+        center_mode: NineSliceCenter,
+    },
 }
 
 impl From<Handle<Image>> for UiImage {
@@ -1615,6 +1677,10 @@ impl From<Handle<Image>> for UiImage {
 }
 
 /// The calculated clip of the node
+///
+/// This is the intersection of the rects of every ancestor with [`Overflow`] clipping, computed
+/// by [`update_clipping_system`](crate::update::update_clipping_system). The renderer trims the
+/// vertices and UVs of the node to this rect, so clipped nodes batch and blend like any other.
 #[derive(Component, Default, Copy, Clone, Debug, Reflect, FromReflect)]
 #[reflect(FromReflect, Component)]
 pub struct CalculatedClip {
@@ -1626,31 +1692,89 @@ pub struct CalculatedClip {
 /// by its location in the UI hierarchy. A node with a higher z-index will appear on top
 /// of other nodes with a lower z-index.
 ///
+/// Like in CSS, nodes are sorted within stacking contexts. A node with a [`ZIndex::Local`] or
+/// [`ZIndex::Global`] z-index forms a new stacking context, in which its descendants are sorted
+/// and which is sorted as a whole with the node. A node with [`ZIndex::Auto`], the default,
+/// doesn't, so it and its descendants are sorted in the stacking context of their closest
+/// ancestor that forms one.
+///
 /// UI nodes that have the same z-index will appear according to the order in which they
 /// appear in the UI hierarchy. In such a case, the last node to be added to its parent
-/// will appear in front of this parent's other children.
+/// will appear in front of this parent's other children, and descendants appear in front of
+/// their ancestors.
 ///
 /// Internally, nodes with a global z-index share the stacking context of root UI nodes
 /// (nodes that have no parent). Because of this, there is no difference between using
 /// [`ZIndex::Local(n)`] and [`ZIndex::Global(n)`] for root nodes.
 ///
-/// Nodes without this component will be treated as if they had a value of [`ZIndex::Local(0)`].
-#[derive(Component, Copy, Clone, Debug, Reflect, FromReflect)]
+/// Nodes without this component will be treated as if they had a value of [`ZIndex::Auto`].
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq, Reflect, FromReflect)]
 #[reflect(Component, FromReflect)]
 pub enum ZIndex {
-    /// Indicates the order in which this node should be rendered relative to its siblings.
+    /// Renders this node and its descendants in the stacking context of its parent, like a
+    /// z-index of 0 that doesn't form a stacking context.
+    #[default]
+    Auto,
+    /// Indicates the order in which this node should be rendered within the stacking context of
+    /// its parent.
     Local(i32),
     /// Indicates the order in which this node should be rendered relative to root nodes and
     /// all other nodes that have a global z-index.
     Global(i32),
 }
 
-impl Default for ZIndex {
-    fn default() -> Self {
-        Self::Local(0)
+/// Describes what a UI node is for, so accessibility integrations like screen readers can announce
+/// the control and its state.
+///
+/// Widgets like `Dropdown` keep the label of their hint in sync with their state. With the
+/// `bevy_text` feature, the hint is also passed on to `AccessKit`.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default, PartialEq)]
+pub struct AccessibilityHint {
+    /// The kind of control the node is
+    pub role: AriaRole,
+    /// The text announced for the node, like the selected option of a dropdown
+    pub label: String,
+}
+
+impl AccessibilityHint {
+    /// Creates an [`AccessibilityHint`] with the given role and label.
+    pub fn new(role: AriaRole, label: impl Into<String>) -> Self {
+        Self {
+            role,
+            label: label.into(),
+        }
     }
 }
 
+/// The kind of control described by an [`AccessibilityHint`], named after the matching
+/// [WAI-ARIA roles](https://www.w3.org/TR/wai-aria/#role_definitions).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect, FromReflect)]
+#[reflect(FromReflect, Default, PartialEq, Hash)]
+pub enum AriaRole {
+    /// A node with no particular meaning
+    #[default]
+    Generic,
+    /// A button that does something when clicked
+    Button,
+    /// A box that is checked or not
+    Checkbox,
+    /// A button that opens a list of options, of which one is selected
+    ComboBox,
+    /// A list of options
+    ListBox,
+    /// An option of a [`AriaRole::ListBox`]
+    ListBoxOption,
+    /// A bar showing the progress of a task
+    ProgressBar,
+    /// An option of a [`AriaRole::RadioGroup`]
+    Radio,
+    /// A group of options of which one is selected
+    RadioGroup,
+    /// A popup describing another node
+    Tooltip,
+}
+
 #[cfg(test)]
 mod tests {
     use crate::ValArithmeticError;
@@ -90,3 +90,65 @@ fn update_clipping(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_hierarchy::BuildWorldChildren;
+    use bevy_math::{Rect, Vec2};
+    use bevy_transform::components::GlobalTransform;
+
+    use super::update_clipping_system;
+    use crate::{CalculatedClip, Node, Overflow, Style};
+
+    fn node(center: Vec2, size: f32, overflow: Overflow) -> (Node, GlobalTransform, Style) {
+        (
+            Node {
+                calculated_size: Vec2::splat(size),
+            },
+            GlobalTransform::from_translation(center.extend(0.)),
+            Style {
+                overflow,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn nested_clips_intersect() {
+        let mut world = World::default();
+        let mut clipped = None;
+        let mut clipped_y = None;
+        world
+            .spawn(node(Vec2::splat(50.), 100., Overflow::clip()))
+            .with_children(|parent| {
+                parent
+                    .spawn(node(Vec2::splat(100.), 100., Overflow::clip()))
+                    .with_children(|parent| {
+                        clipped =
+                            Some(parent.spawn(node(Vec2::ZERO, 10., Overflow::clip_y())).id());
+                    });
+                parent
+                    .spawn(node(Vec2::splat(50.), 20., Overflow::clip_y()))
+                    .with_children(|parent| {
+                        clipped_y = Some(
+                            parent
+                                .spawn(node(Vec2::ZERO, 10., Overflow::visible()))
+                                .id(),
+                        );
+                    });
+            });
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_clipping_system);
+        schedule.run(&mut world);
+
+        let clip = |entity| world.get::<CalculatedClip>(entity).unwrap().clip;
+        assert_eq!(
+            clip(clipped.unwrap()),
+            Rect::from_corners(Vec2::splat(50.), Vec2::splat(100.))
+        );
+        // Only the clipped axis of the parent narrows the clip
+        assert_eq!(clip(clipped_y.unwrap()), Rect::new(0., 40., 100., 60.));
+    }
+}
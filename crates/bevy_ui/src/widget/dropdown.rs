@@ -0,0 +1,499 @@
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    AccessibilityHint, AlignItems, AriaRole, BackgroundColor, Display, FlexDirection, FocusPolicy,
+    Interaction, PositionType, Style, UiRect, Val, ZIndex,
+};
+use bevy_ecs::{
+    prelude::{Component, Entity, Event, EventReader, EventWriter},
+    query::{Changed, Or},
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, Resource},
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    ButtonState,
+};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextStyle};
+
+/// A button showing the selected option, which opens a list of the options to select from.
+///
+/// The dropdown node gets a trigger button and a list of options as children, which are kept in
+/// sync with this component and drawn with the [`DropdownStyle`] resource. The trigger and the
+/// options are clicked through their [`Interaction`]. While the list is open, the arrow keys
+/// move through the options, `Enter` selects the highlighted option and `Escape` closes the list.
+///
+/// A [`DropdownChanged`] event is sent when another option is selected. The dropdown, its list and
+/// its options get an [`AccessibilityHint`] describing them.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct Dropdown {
+    /// The labels of the options
+    pub options: Vec<String>,
+    /// The index of the selected option
+    pub selected: Option<usize>,
+    /// The text shown in the trigger button while no option is selected
+    pub placeholder: String,
+    /// Whether the list of options is open
+    pub open: bool,
+}
+
+impl Dropdown {
+    /// Creates a closed [`Dropdown`] with the given options and none selected.
+    pub fn new(options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`Dropdown`] showing `placeholder` while no option is selected.
+    pub fn with_placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Returns this [`Dropdown`] with the option at `selected` selected.
+    pub fn with_selected(mut self, selected: usize) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    /// The label of the selected option, if any
+    pub fn selected_option(&self) -> Option<&str> {
+        self.selected
+            .and_then(|selected| self.options.get(selected))
+            .map(String::as_str)
+    }
+
+    /// The text shown in the trigger button
+    fn trigger_text(&self) -> &str {
+        self.selected_option().unwrap_or(&self.placeholder)
+    }
+}
+
+/// How [`Dropdown`]s are drawn, used when their nodes are spawned or changed.
+#[derive(Resource, Debug, Clone)]
+pub struct DropdownStyle {
+    /// The style of the text of the trigger button and the options
+    pub text_style: TextStyle,
+    /// The background color of the trigger button
+    pub trigger_color: Color,
+    /// The background color of the options
+    pub option_color: Color,
+    /// The background color of the highlighted option
+    pub highlighted_color: Color,
+    /// The space between the text of the trigger button or of an option and its edges
+    pub padding: UiRect,
+}
+
+impl Default for DropdownStyle {
+    fn default() -> Self {
+        Self {
+            text_style: TextStyle {
+                font_size: 18.,
+                ..Default::default()
+            },
+            trigger_color: Color::rgb(0.2, 0.2, 0.2),
+            option_color: Color::rgb(0.15, 0.15, 0.15),
+            highlighted_color: Color::rgb(0.3, 0.3, 0.45),
+            padding: UiRect::axes(Val::Px(8.), Val::Px(4.)),
+        }
+    }
+}
+
+/// An event sent when another option of a [`Dropdown`] is selected.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DropdownChanged {
+    /// The entity of the [`Dropdown`]
+    pub entity: Entity,
+    /// The index of the newly selected option
+    pub selected: usize,
+}
+
+/// The nodes of a [`Dropdown`] and the option highlighted in its list, added by
+/// [`update_dropdown_system`]
+#[derive(Component, Debug, Clone)]
+pub struct DropdownNodes {
+    trigger_label: Entity,
+    list: Entity,
+    /// The options the option rows were spawned for
+    options: Vec<String>,
+    rows: Vec<Entity>,
+    /// The option highlighted with the arrow keys or the cursor, cleared when the list closes
+    highlighted: Option<usize>,
+}
+
+impl DropdownNodes {
+    /// The highlighted option, or the selected one if no option was highlighted since the list
+    /// opened
+    pub fn highlighted(&self, dropdown: &Dropdown) -> Option<usize> {
+        self.highlighted.or(dropdown.selected)
+    }
+}
+
+/// The trigger button of a [`Dropdown`], opening and closing its list when clicked
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DropdownTrigger {
+    /// The entity of the [`Dropdown`]
+    pub dropdown: Entity,
+}
+
+/// An option of a [`Dropdown`], selecting the option when clicked
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DropdownOption {
+    /// The entity of the [`Dropdown`]
+    pub dropdown: Entity,
+    /// The index of the option in the dropdown
+    pub index: usize,
+}
+
+/// Selects `index` in `dropdown` and closes its list, sending a [`DropdownChanged`] event if the
+/// selection changed.
+fn select_option(
+    entity: Entity,
+    dropdown: &mut Dropdown,
+    nodes: &mut DropdownNodes,
+    index: usize,
+    events: &mut EventWriter<DropdownChanged>,
+) {
+    dropdown.open = false;
+    nodes.highlighted = None;
+    if dropdown.selected != Some(index) {
+        dropdown.selected = Some(index);
+        events.send(DropdownChanged {
+            entity,
+            selected: index,
+        });
+    }
+}
+
+/// Spawns a row under `list` for each option of `dropdown`, returning their entities.
+fn spawn_option_rows(
+    commands: &mut Commands,
+    dropdown_style: &DropdownStyle,
+    entity: Entity,
+    dropdown: &Dropdown,
+    list: Entity,
+) -> Vec<Entity> {
+    let mut rows = Vec::with_capacity(dropdown.options.len());
+    commands.entity(list).with_children(|parent| {
+        for (index, option) in dropdown.options.iter().enumerate() {
+            let row = parent
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            padding: dropdown_style.padding,
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor(dropdown_style.option_color),
+                        focus_policy: FocusPolicy::Block,
+                        ..Default::default()
+                    },
+                    Interaction::default(),
+                    DropdownOption {
+                        dropdown: entity,
+                        index,
+                    },
+                    AccessibilityHint::new(AriaRole::ListBoxOption, option.clone()),
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        option.clone(),
+                        dropdown_style.text_style.clone(),
+                    ));
+                })
+                .id();
+            rows.push(row);
+        }
+    });
+    rows
+}
+
+/// Opens and closes the lists of [`Dropdown`]s when their trigger is clicked, and selects the
+/// options that are clicked or hovered.
+pub fn dropdown_interaction_system(
+    triggers: Query<(&DropdownTrigger, &Interaction), Changed<Interaction>>,
+    options: Query<(&DropdownOption, &Interaction), Changed<Interaction>>,
+    mut dropdowns: Query<(&mut Dropdown, &mut DropdownNodes)>,
+    mut events: EventWriter<DropdownChanged>,
+) {
+    for (trigger, interaction) in &triggers {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        if let Ok((mut dropdown, mut nodes)) = dropdowns.get_mut(trigger.dropdown) {
+            dropdown.open = !dropdown.open;
+            nodes.highlighted = None;
+        }
+    }
+
+    for (option, interaction) in &options {
+        let Ok((mut dropdown, mut nodes)) = dropdowns.get_mut(option.dropdown) else {
+            continue;
+        };
+        match interaction {
+            Interaction::Clicked => select_option(
+                option.dropdown,
+                &mut dropdown,
+                &mut nodes,
+                option.index,
+                &mut events,
+            ),
+            Interaction::Hovered => nodes.highlighted = Some(option.index),
+            Interaction::None => {}
+        }
+    }
+}
+
+/// Moves through the options of open [`Dropdown`]s with the arrow keys, selects the highlighted
+/// option with `Enter` and closes the list with `Escape`.
+///
+/// There is no keyboard focus for UI nodes, so the keys go to every open dropdown.
+pub fn dropdown_keyboard_system(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut dropdowns: Query<(Entity, &mut Dropdown, &mut DropdownNodes)>,
+    mut events: EventWriter<DropdownChanged>,
+) {
+    for input in keyboard_input.iter() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        let Some(key_code) = input.key_code else {
+            continue;
+        };
+        for (entity, mut dropdown, mut nodes) in &mut dropdowns {
+            if !dropdown.open {
+                continue;
+            }
+            let last = dropdown.options.len().checked_sub(1);
+            let highlighted = nodes.highlighted(&dropdown);
+            match (key_code, last) {
+                (KeyCode::Down, Some(last)) => {
+                    nodes.highlighted = Some(highlighted.map_or(0, |index| (index + 1).min(last)));
+                }
+                (KeyCode::Up, Some(_)) => {
+                    nodes.highlighted =
+                        Some(highlighted.map_or(0, |index| index.saturating_sub(1)));
+                }
+                (KeyCode::Return | KeyCode::NumpadEnter, Some(last)) => {
+                    if let Some(index) = highlighted {
+                        let index = index.min(last);
+                        select_option(entity, &mut dropdown, &mut nodes, index, &mut events);
+                    }
+                }
+                (KeyCode::Escape, _) => {
+                    dropdown.open = false;
+                    nodes.highlighted = None;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Spawns the nodes of new [`Dropdown`]s, and updates them when the dropdown or its highlighted
+/// option changes.
+///
+/// The option rows are spawned again when the options change.
+pub fn update_dropdown_system(
+    mut commands: Commands,
+    dropdown_style: Res<DropdownStyle>,
+    mut dropdowns: Query<
+        (Entity, &Dropdown, Option<&mut DropdownNodes>),
+        Or<(Changed<Dropdown>, Changed<DropdownNodes>)>,
+    >,
+    mut styles: Query<&mut Style>,
+    mut colors: Query<&mut BackgroundColor>,
+    mut texts: Query<&mut Text>,
+    mut hints: Query<&mut AccessibilityHint>,
+) {
+    let display = |open: bool| if open { Display::Flex } else { Display::None };
+
+    for (entity, dropdown, nodes) in &mut dropdowns {
+        let Some(mut nodes) = nodes else {
+            let mut trigger_label = Entity::PLACEHOLDER;
+            let mut list = Entity::PLACEHOLDER;
+            commands
+                .entity(entity)
+                .insert(AccessibilityHint::new(
+                    AriaRole::ComboBox,
+                    dropdown.trigger_text(),
+                ))
+                .with_children(|parent| {
+                    parent
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    padding: dropdown_style.padding,
+                                    align_items: AlignItems::Center,
+                                    ..Default::default()
+                                },
+                                background_color: BackgroundColor(dropdown_style.trigger_color),
+                                focus_policy: FocusPolicy::Block,
+                                ..Default::default()
+                            },
+                            Interaction::default(),
+                            DropdownTrigger { dropdown: entity },
+                        ))
+                        .with_children(|parent| {
+                            trigger_label = parent
+                                .spawn(TextBundle::from_section(
+                                    dropdown.trigger_text(),
+                                    dropdown_style.text_style.clone(),
+                                ))
+                                .id();
+                        });
+                    list = parent
+                        .spawn((
+                            NodeBundle {
+                                style: Style {
+                                    display: display(dropdown.open),
+                                    position_type: PositionType::Absolute,
+                                    top: Val::Percent(100.),
+                                    left: Val::Px(0.),
+                                    min_width: Val::Percent(100.),
+                                    flex_direction: FlexDirection::Column,
+                                    ..Default::default()
+                                },
+                                // Drawn above the other UI, but below tooltips
+                                z_index: ZIndex::Global(i32::MAX - 1),
+                                ..Default::default()
+                            },
+                            AccessibilityHint::new(AriaRole::ListBox, dropdown.placeholder.clone()),
+                        ))
+                        .id();
+                });
+            let rows = spawn_option_rows(&mut commands, &dropdown_style, entity, dropdown, list);
+            commands.entity(entity).insert(DropdownNodes {
+                trigger_label,
+                list,
+                options: dropdown.options.clone(),
+                rows,
+                highlighted: None,
+            });
+            continue;
+        };
+
+        if nodes.options != dropdown.options {
+            for row in nodes.rows.drain(..) {
+                commands.entity(row).despawn_recursive();
+            }
+            nodes.rows =
+                spawn_option_rows(&mut commands, &dropdown_style, entity, dropdown, nodes.list);
+            nodes.options = dropdown.options.clone();
+        }
+
+        if let Ok(mut text) = texts.get_mut(nodes.trigger_label) {
+            if text.sections[0].value != dropdown.trigger_text() {
+                text.sections[0].value = dropdown.trigger_text().to_string();
+            }
+        }
+        if let Ok(mut hint) = hints.get_mut(entity) {
+            if hint.label != dropdown.trigger_text() {
+                hint.label = dropdown.trigger_text().to_string();
+            }
+        }
+        if let Ok(mut style) = styles.get_mut(nodes.list) {
+            style.display = display(dropdown.open);
+        }
+        let highlighted = nodes.highlighted(dropdown);
+        for (index, row) in nodes.rows.iter().enumerate() {
+            if let Ok(mut color) = colors.get_mut(*row) {
+                color.0 = if highlighted == Some(index) {
+                    dropdown_style.highlighted_color
+                } else {
+                    dropdown_style.option_color
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        event::Events,
+        schedule::{IntoSystemConfigs, Schedule},
+        world::World,
+    };
+
+    fn press(world: &mut World, key_code: KeyCode) {
+        world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state: ButtonState::Pressed,
+        });
+    }
+
+    #[test]
+    fn dropdown_keyboard_navigation() {
+        let mut world = World::new();
+        world.init_resource::<DropdownStyle>();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Events<DropdownChanged>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(
+            (
+                dropdown_interaction_system,
+                dropdown_keyboard_system,
+                update_dropdown_system,
+            )
+                .chain(),
+        );
+
+        let dropdown = world
+            .spawn(Dropdown::new(["easy", "normal", "hard"]).with_placeholder("difficulty"))
+            .id();
+        schedule.run(&mut world);
+        let nodes = world.get::<DropdownNodes>(dropdown).unwrap().clone();
+        assert_eq!(nodes.rows.len(), 3);
+        assert_eq!(
+            world.get::<AccessibilityHint>(dropdown),
+            Some(&AccessibilityHint::new(AriaRole::ComboBox, "difficulty"))
+        );
+
+        // Keys are ignored while the list is closed
+        press(&mut world, KeyCode::Down);
+        schedule.run(&mut world);
+        assert_eq!(
+            world.get::<DropdownNodes>(dropdown).unwrap().highlighted,
+            None
+        );
+
+        world.get_mut::<Dropdown>(dropdown).unwrap().open = true;
+        press(&mut world, KeyCode::Down);
+        press(&mut world, KeyCode::Down);
+        press(&mut world, KeyCode::Return);
+        schedule.run(&mut world);
+        let state = world.get::<Dropdown>(dropdown).unwrap();
+        assert_eq!((state.selected, state.open), (Some(1), false));
+        let events = world.resource::<Events<DropdownChanged>>();
+        assert_eq!(
+            events.iter_current_update_events().collect::<Vec<_>>(),
+            [&DropdownChanged {
+                entity: dropdown,
+                selected: 1
+            }]
+        );
+        assert_eq!(
+            world.get::<Text>(nodes.trigger_label).unwrap().sections[0].value,
+            "normal"
+        );
+        assert_eq!(
+            world.get::<Style>(nodes.list).unwrap().display,
+            Display::None
+        );
+
+        world.get_mut::<Dropdown>(dropdown).unwrap().open = true;
+        press(&mut world, KeyCode::Up);
+        press(&mut world, KeyCode::Escape);
+        schedule.run(&mut world);
+        let state = world.get::<Dropdown>(dropdown).unwrap();
+        assert_eq!((state.selected, state.open), (Some(1), false));
+    }
+}
@@ -1,16 +1,16 @@
-use crate::{measurement::AvailableSpace, ContentSize, Measure, Node, UiImage};
-use bevy_asset::Assets;
+use crate::{ContentSize, Measure, MeasureArgs, Node, UiImage};
+use bevy_asset::{Assets, Handle};
 #[cfg(feature = "bevy_text")]
 use bevy_ecs::query::Without;
 use bevy_ecs::{
     prelude::Component,
-    query::With,
+    query::{Changed, With},
     reflect::ReflectComponent,
-    system::{Query, Res},
+    system::{Query, Res, ResMut},
 };
 use bevy_math::Vec2;
 use bevy_reflect::{std_traits::ReflectDefault, FromReflect, Reflect, ReflectFromReflect};
-use bevy_render::texture::Image;
+use bevy_render::texture::{FailedImages, Image};
 #[cfg(feature = "bevy_text")]
 use bevy_text::Text;
 
@@ -29,22 +29,25 @@ impl UiImageSize {
     }
 }
 
+/// The [`Measure`] of image nodes, which keeps the aspect ratio of the image.
+///
+/// The size of the image is read from the [`Assets<Image>`] of the layout's world.
 #[derive(Clone)]
 pub struct ImageMeasure {
-    // target size of the image
-    size: Vec2,
+    texture: Handle<Image>,
 }
 
 impl Measure for ImageMeasure {
-    fn measure(
-        &self,
-        width: Option<f32>,
-        height: Option<f32>,
-        _: AvailableSpace,
-        _: AvailableSpace,
-    ) -> Vec2 {
-        let mut size = self.size;
-        match (width, height) {
+    fn measure(&self, args: MeasureArgs) -> Vec2 {
+        let Some(mut size) = args
+            .world
+            .get_resource::<Assets<Image>>()
+            .and_then(|textures| textures.get(&self.texture))
+            .map(Image::size)
+        else {
+            return Vec2::ZERO;
+        };
+        match (args.width, args.height) {
             (None, None) => {}
             (Some(width), None) => {
                 size.y = width * size.y / size.x;
@@ -84,8 +87,21 @@ pub fn update_image_content_size_system(
             // Update only if size has changed to avoid needless layout calculations
             if size != image_size.size {
                 image_size.size = size;
-                content_size.set(ImageMeasure { size });
+                content_size.set(ImageMeasure {
+                    texture: image.texture.clone_weak(),
+                });
             }
         }
     }
 }
+
+/// Watches the images of [`UiImage`]s, so they can be replaced by a fallback if they fail to load
+pub fn watch_ui_images(
+    textures: Res<Assets<Image>>,
+    mut failed_images: ResMut<FailedImages>,
+    query: Query<&UiImage, Changed<UiImage>>,
+) {
+    for image in &query {
+        failed_images.watch(&textures, &image.texture);
+    }
+}
@@ -1,13 +1,33 @@
 //! This module contains the basic building blocks of Bevy's UI
 
 mod button;
+#[cfg(feature = "bevy_text")]
+mod dropdown;
 mod image;
 mod label;
+mod progress_bar;
 #[cfg(feature = "bevy_text")]
 mod text;
+#[cfg(feature = "bevy_text")]
+mod text_input;
+#[cfg(feature = "bevy_text")]
+mod toggle;
+#[cfg(feature = "bevy_text")]
+mod tooltip;
+mod virtual_list;
 
 pub use button::*;
+#[cfg(feature = "bevy_text")]
+pub use dropdown::*;
 pub use image::*;
 pub use label::*;
+pub use progress_bar::*;
 #[cfg(feature = "bevy_text")]
 pub use text::*;
+#[cfg(feature = "bevy_text")]
+pub use text_input::*;
+#[cfg(feature = "bevy_text")]
+pub use toggle::*;
+#[cfg(feature = "bevy_text")]
+pub use tooltip::*;
+pub use virtual_list::*;
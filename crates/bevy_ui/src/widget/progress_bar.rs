@@ -0,0 +1,268 @@
+use crate::{node_bundles::NodeBundle, BackgroundColor, FocusPolicy, PositionType, Style, Val};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    query::Changed,
+    reflect::ReflectComponent,
+    system::{Commands, Query},
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use bevy_render::color::Color;
+
+/// A bar filled in proportion to `value / max`, like a health or loading bar.
+///
+/// The bar node gets two child nodes, drawing its background and its fill, which are kept in sync
+/// with this component. Its own [`Style`] sets the size of the bar.
+#[derive(Component, Debug, Clone, PartialEq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct ProgressBar {
+    /// The current progress, between `0.` and `max`
+    pub value: f32,
+    /// The progress of a full bar
+    pub max: f32,
+    /// The direction the bar fills in
+    pub direction: ProgressDirection,
+    /// The color of the filled part of the bar, if there is no `gradient`
+    pub fill_color: Color,
+    /// The color of the empty part of the bar
+    pub background_color: Color,
+    /// Colors the fill by the fraction of the bar it covers instead of `fill_color`, like red for
+    /// an almost empty health bar and green for a full one
+    pub gradient: Option<ColorGradient>,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self {
+            value: 0.,
+            max: 1.,
+            direction: ProgressDirection::default(),
+            fill_color: Color::rgb(0.3, 0.7, 0.3),
+            background_color: Color::DARK_GRAY,
+            gradient: None,
+        }
+    }
+}
+
+impl ProgressBar {
+    /// Creates a [`ProgressBar`] at `value` out of `max`.
+    pub fn new(value: f32, max: f32) -> Self {
+        Self {
+            value,
+            max,
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`ProgressBar`] filling in the given direction.
+    pub const fn with_direction(mut self, direction: ProgressDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Returns this [`ProgressBar`] with its fill colored by the gradient.
+    pub fn with_gradient(mut self, gradient: ColorGradient) -> Self {
+        self.gradient = Some(gradient);
+        self
+    }
+
+    /// The filled fraction of the bar, between `0.` and `1.`
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0. {
+            (self.value / self.max).clamp(0., 1.)
+        } else {
+            0.
+        }
+    }
+
+    /// The color of the fill at the current progress.
+    pub fn current_fill_color(&self) -> Color {
+        match &self.gradient {
+            Some(gradient) => gradient.sample(self.fraction()),
+            None => self.fill_color,
+        }
+    }
+
+    /// The style of the fill node at the current progress.
+    fn fill_style(&self) -> Style {
+        let fraction = Val::Percent(self.fraction() * 100.);
+        let (width, height) = if self.direction.is_horizontal() {
+            (fraction, Val::Percent(100.))
+        } else {
+            (Val::Percent(100.), fraction)
+        };
+        let mut style = Style {
+            position_type: PositionType::Absolute,
+            width,
+            height,
+            ..Default::default()
+        };
+        if self.direction == ProgressDirection::RightToLeft {
+            style.right = Val::Px(0.);
+        } else {
+            style.left = Val::Px(0.);
+        }
+        if self.direction == ProgressDirection::BottomToTop {
+            style.bottom = Val::Px(0.);
+        } else {
+            style.top = Val::Px(0.);
+        }
+        style
+    }
+}
+
+/// The direction a [`ProgressBar`] fills in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(FromReflect, Default, PartialEq)]
+pub enum ProgressDirection {
+    #[default]
+    LeftToRight,
+    RightToLeft,
+    BottomToTop,
+    TopToBottom,
+}
+
+impl ProgressDirection {
+    /// Returns `true` if the bar fills along the horizontal axis
+    pub const fn is_horizontal(self) -> bool {
+        matches!(
+            self,
+            ProgressDirection::LeftToRight | ProgressDirection::RightToLeft
+        )
+    }
+}
+
+/// Colors interpolated between stops, each with a position between `0.` and `1.`
+#[derive(Debug, Default, Clone, PartialEq, Reflect, FromReflect)]
+#[reflect(FromReflect, Default, PartialEq)]
+pub struct ColorGradient {
+    /// The positions and colors of the stops, sorted by position
+    stops: Vec<(f32, Color)>,
+}
+
+impl ColorGradient {
+    /// Creates a [`ColorGradient`] from `(position, color)` stops, in any order.
+    pub fn new(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let mut stops: Vec<(f32, Color)> = stops.into_iter().collect();
+        stops.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        Self { stops }
+    }
+
+    /// The stops of the gradient, sorted by position
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// The color at `position`, interpolated in linear space between the nearest stops.
+    ///
+    /// Positions before the first stop or after the last one get the color of that stop. An
+    /// empty gradient is [`Color::NONE`].
+    pub fn sample(&self, position: f32) -> Color {
+        let Some(&(first_position, first_color)) = self.stops.first() else {
+            return Color::NONE;
+        };
+        if position <= first_position {
+            return first_color;
+        }
+        for window in self.stops.windows(2) {
+            let [(start, start_color), (end, end_color)] = [window[0], window[1]];
+            if position <= end {
+                let t = if end > start {
+                    (position - start) / (end - start)
+                } else {
+                    1.
+                };
+                let [r0, g0, b0, a0] = start_color.as_linear_rgba_f32();
+                let [r1, g1, b1, a1] = end_color.as_linear_rgba_f32();
+                return Color::rgba_linear(
+                    r0 + (r1 - r0) * t,
+                    g0 + (g1 - g0) * t,
+                    b0 + (b1 - b0) * t,
+                    a0 + (a1 - a0) * t,
+                );
+            }
+        }
+        self.stops[self.stops.len() - 1].1
+    }
+}
+
+/// The background and fill nodes of a [`ProgressBar`], added by [`progress_bar_system`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ProgressBarNodes {
+    background: Entity,
+    fill: Entity,
+}
+
+/// Spawns the background and fill nodes of new [`ProgressBar`]s, and updates them when the
+/// progress bar changes.
+pub fn progress_bar_system(
+    mut commands: Commands,
+    progress_bars: Query<(Entity, &ProgressBar, Option<&ProgressBarNodes>), Changed<ProgressBar>>,
+    mut nodes: Query<(&mut Style, &mut BackgroundColor)>,
+) {
+    for (entity, progress_bar, progress_bar_nodes) in &progress_bars {
+        let background_style = Style {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..Default::default()
+        };
+        let fill_style = progress_bar.fill_style();
+        let fill_color = progress_bar.current_fill_color();
+
+        let Some(progress_bar_nodes) = progress_bar_nodes else {
+            let part = |style: Style, color: Color| NodeBundle {
+                style,
+                background_color: BackgroundColor(color),
+                focus_policy: FocusPolicy::Pass,
+                ..Default::default()
+            };
+            let background = commands
+                .spawn(part(background_style, progress_bar.background_color))
+                .id();
+            let fill = commands.spawn(part(fill_style, fill_color)).id();
+            commands
+                .entity(entity)
+                .push_children(&[background, fill])
+                .insert(ProgressBarNodes { background, fill });
+            continue;
+        };
+
+        if let Ok((_, mut background_color)) = nodes.get_mut(progress_bar_nodes.background) {
+            background_color.0 = progress_bar.background_color;
+        }
+        if let Ok((mut style, mut background_color)) = nodes.get_mut(progress_bar_nodes.fill) {
+            *style = fill_style;
+            background_color.0 = fill_color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sample_gradient_between_stops() {
+        let gradient =
+            ColorGradient::new([(1., Color::GREEN), (0., Color::RED), (0.5, Color::YELLOW)]);
+        assert_eq!(gradient.sample(-1.), Color::RED);
+        assert_eq!(
+            gradient.sample(0.5).as_linear_rgba_f32(),
+            Color::YELLOW.as_linear_rgba_f32()
+        );
+        assert_eq!(gradient.sample(2.), Color::GREEN);
+
+        let [r, g, b, _] = gradient.sample(0.75).as_linear_rgba_f32();
+        assert_eq!([r, g, b], [0.5, 1., 0.]);
+        assert_eq!(ColorGradient::default().sample(0.5), Color::NONE);
+    }
+
+    #[test]
+    fn fraction_is_clamped() {
+        assert_eq!(ProgressBar::new(5., 10.).fraction(), 0.5);
+        assert_eq!(ProgressBar::new(15., 10.).fraction(), 1.);
+        assert_eq!(ProgressBar::new(5., 0.).fraction(), 0.);
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{ContentSize, Measure, Node, UiScale};
+use crate::{ContentSize, Measure, MeasureArgs, Node, UiScale};
 use bevy_asset::Assets;
 use bevy_ecs::{
     prelude::{Component, DetectChanges},
@@ -39,19 +39,23 @@ impl Default for TextFlags {
     }
 }
 
+/// The [`Measure`] of text nodes.
+///
+/// The fonts of the text are looked up and the min-content and max-content sizes computed once in
+/// [`measure_text_system`], since the text is measured many times per layout.
 #[derive(Clone)]
 pub struct TextMeasure {
     pub info: TextMeasureInfo,
 }
 
 impl Measure for TextMeasure {
-    fn measure(
-        &self,
-        width: Option<f32>,
-        height: Option<f32>,
-        available_width: AvailableSpace,
-        _available_height: AvailableSpace,
-    ) -> Vec2 {
+    fn measure(&self, args: MeasureArgs) -> Vec2 {
+        let MeasureArgs {
+            width,
+            height,
+            available_width,
+            ..
+        } = args;
         let x = width.unwrap_or_else(|| match available_width {
             AvailableSpace::Definite(x) => x.clamp(
                 self.info.min_width_content_size.x,
@@ -0,0 +1,581 @@
+use crate::{
+    node_bundles::TextBundle, BackgroundColor, FocusPolicy, Interaction, Style, UiRect, Val,
+};
+use bevy_ecs::{
+    change_detection::{DetectChanges, Ref},
+    prelude::{Component, Entity, EventReader},
+    query::With,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, ResMut, Resource},
+    world::World,
+};
+use bevy_hierarchy::BuildChildren;
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::MouseButton,
+    ButtonState, Input,
+};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextSection, TextStyle};
+use bevy_window::ReceivedCharacter;
+use std::ops::Range;
+
+/// An editable single line of text.
+///
+/// The node gets an [`Interaction`] and a child text node, which is kept in sync with this
+/// component and drawn with the [`TextInputStyle`] resource. Clicking the node gives it the
+/// [`TextInputFocus`], and while it is focused the typed characters are inserted at the cursor.
+///
+/// The cursor and the selection are in characters, not bytes. They are drawn in the text itself:
+/// the cursor as [`TextInputStyle::cursor`] and the selected text in
+/// [`TextInputStyle::selection_color`].
+///
+/// The supported keys are the arrow keys, `Home` and `End`, with `Shift` to select, `Backspace`,
+/// `Delete`, `Escape` to lose the focus, and `Ctrl+A`, `Ctrl+C`, `Ctrl+X` and `Ctrl+V` to select
+/// everything and to copy, cut and paste with the [`TextInputClipboard`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct TextInput {
+    /// The text in the input
+    pub value: String,
+    /// The position of the cursor, in characters
+    pub cursor: usize,
+    /// The selected characters, one end of which is the cursor
+    pub selection: Option<Range<usize>>,
+    /// The text shown while the input is empty
+    pub placeholder: String,
+    /// The maximum number of characters of the value
+    pub max_length: Option<usize>,
+}
+
+impl TextInput {
+    /// Creates an empty [`TextInput`] showing `placeholder`.
+    pub fn new(placeholder: impl Into<String>) -> Self {
+        Self {
+            placeholder: placeholder.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`TextInput`] with `value` in it and the cursor at its end.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = value.into();
+        self.cursor = self.len();
+        self
+    }
+
+    /// Returns this [`TextInput`] accepting at most `max_length` characters.
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// The number of characters of the value
+    pub fn len(&self) -> usize {
+        self.value.chars().count()
+    }
+
+    /// Returns `true` if the value is empty
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// The selected text, if any
+    pub fn selected_text(&self) -> Option<&str> {
+        let selection = self.clamped_selection()?;
+        Some(&self.value[self.byte_range(selection)])
+    }
+
+    /// Selects the whole value, with the cursor at its end.
+    pub fn select_all(&mut self) {
+        self.cursor = self.len();
+        self.selection = (self.cursor > 0).then_some(0..self.cursor);
+    }
+
+    /// Moves the cursor to the character at `position`, extending the selection from the other
+    /// end of the selection or from the cursor if `select` is `true`, or clearing it otherwise.
+    pub fn move_cursor(&mut self, position: usize, select: bool) {
+        self.clamp();
+        let position = position.min(self.len());
+        if select {
+            let anchor = match &self.selection {
+                Some(selection) if selection.start == self.cursor => selection.end,
+                Some(selection) => selection.start,
+                None => self.cursor,
+            };
+            self.selection =
+                (anchor != position).then(|| anchor.min(position)..anchor.max(position));
+        } else {
+            self.selection = None;
+        }
+        self.cursor = position;
+    }
+
+    /// Replaces the selection, or inserts at the cursor if nothing is selected, with `text`.
+    ///
+    /// The text is cut short to keep the value within `max_length` characters.
+    pub fn insert(&mut self, text: &str) {
+        self.clamp();
+        self.delete_selection();
+        let available = self.max_length.map_or(usize::MAX, |max_length| {
+            max_length.saturating_sub(self.len())
+        });
+        let end = text
+            .char_indices()
+            .nth(available)
+            .map_or(text.len(), |(index, _)| index);
+        let text = &text[..end];
+        let at = self.byte_index(self.cursor);
+        self.value.insert_str(at, text);
+        self.cursor += text.chars().count();
+    }
+
+    /// Deletes the selection, or the character before the cursor if nothing is selected.
+    pub fn delete_backward(&mut self) {
+        self.clamp();
+        if !self.delete_selection() && self.cursor > 0 {
+            self.cursor -= 1;
+            let at = self.byte_index(self.cursor);
+            self.value.remove(at);
+        }
+    }
+
+    /// Deletes the selection, or the character after the cursor if nothing is selected.
+    pub fn delete_forward(&mut self) {
+        self.clamp();
+        if !self.delete_selection() && self.cursor < self.len() {
+            let at = self.byte_index(self.cursor);
+            self.value.remove(at);
+        }
+    }
+
+    /// The selection with its ends in order and within the value, unless it is empty.
+    fn clamped_selection(&self) -> Option<Range<usize>> {
+        let selection = self.selection.as_ref()?;
+        let len = self.len();
+        let (start, end) = (selection.start.min(len), selection.end.min(len));
+        (start != end).then(|| start.min(end)..start.max(end))
+    }
+
+    /// Brings the cursor and the selection back within the value, which may have been changed
+    /// since they were set.
+    fn clamp(&mut self) {
+        self.cursor = self.cursor.min(self.len());
+        self.selection = self.clamped_selection();
+    }
+
+    /// Deletes the selected text and puts the cursor where it was, returning `true` if there
+    /// was a selection.
+    fn delete_selection(&mut self) -> bool {
+        let Some(selection) = self.selection.take() else {
+            return false;
+        };
+        let range = self.byte_range(selection.clone());
+        self.value.replace_range(range, "");
+        self.cursor = selection.start;
+        true
+    }
+
+    /// The byte index of the character at `position`, or the length of the value past its end
+    fn byte_index(&self, position: usize) -> usize {
+        self.value
+            .char_indices()
+            .nth(position)
+            .map_or(self.value.len(), |(index, _)| index)
+    }
+
+    /// The byte range of the characters in `range`, whose ends can be in either order
+    fn byte_range(&self, range: Range<usize>) -> Range<usize> {
+        let (start, end) = (range.start.min(range.end), range.start.max(range.end));
+        self.byte_index(start)..self.byte_index(end)
+    }
+}
+
+/// How [`TextInput`]s are drawn.
+#[derive(Resource, Debug, Clone)]
+pub struct TextInputStyle {
+    /// The style of the text
+    pub text_style: TextStyle,
+    /// The color of the placeholder
+    pub placeholder_color: Color,
+    /// The color of the selected text
+    pub selection_color: Color,
+    /// The text drawn at the cursor of the focused input
+    pub cursor: String,
+    /// The color of the cursor
+    pub cursor_color: Color,
+    /// The background color of the input
+    pub background_color: Color,
+    /// The background color of the focused input
+    pub focused_background_color: Color,
+    /// The space between the text and the edges of the input
+    pub padding: UiRect,
+}
+
+impl Default for TextInputStyle {
+    fn default() -> Self {
+        Self {
+            text_style: TextStyle {
+                font_size: 18.,
+                ..Default::default()
+            },
+            placeholder_color: Color::GRAY,
+            selection_color: Color::rgb(0.4, 0.6, 1.),
+            cursor: "|".to_string(),
+            cursor_color: Color::WHITE,
+            background_color: Color::rgb(0.15, 0.15, 0.15),
+            focused_background_color: Color::rgb(0.2, 0.2, 0.25),
+            padding: UiRect::axes(Val::Px(6.), Val::Px(4.)),
+        }
+    }
+}
+
+/// The [`TextInput`] receiving the keyboard input, if any
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TextInputFocus(pub Option<Entity>);
+
+/// Unfocuses a [`TextInput`] right before it is despawned recursively, see
+/// [`DespawnHooks`](bevy_hierarchy::DespawnHooks).
+pub fn unfocus_despawned_text_input(world: &mut World, entity: Entity) {
+    if let Some(mut focus) = world.get_resource_mut::<TextInputFocus>() {
+        if focus.0 == Some(entity) {
+            focus.0 = None;
+        }
+    }
+}
+
+/// The text copied or cut from [`TextInput`]s, and pasted into them.
+///
+/// This clipboard only lives in the app. To share text with other apps, a platform clipboard
+/// integration can keep it in sync with the system clipboard.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct TextInputClipboard(pub String);
+
+/// The text node of a [`TextInput`], added by [`update_text_input_system`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TextInputNodes {
+    text: Entity,
+}
+
+/// Gives the [`TextInputFocus`] to the [`TextInput`] that is clicked, or takes it away when the
+/// mouse is pressed anywhere else.
+pub fn text_input_focus_system(
+    mouse_button_input: Res<Input<MouseButton>>,
+    text_inputs: Query<(Entity, &Interaction), With<TextInput>>,
+    mut focus: ResMut<TextInputFocus>,
+) {
+    if !mouse_button_input.just_pressed(MouseButton::Left) {
+        return;
+    }
+    let clicked = text_inputs
+        .iter()
+        .find(|(_, interaction)| **interaction == Interaction::Clicked)
+        .map(|(entity, _)| entity);
+    if focus.0 != clicked {
+        focus.0 = clicked;
+    }
+}
+
+/// Edits the focused [`TextInput`] with the [`KeyboardInput`] and [`ReceivedCharacter`] events.
+pub fn text_input_keyboard_system(
+    mut keyboard_input: EventReader<KeyboardInput>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+    keys: Res<Input<KeyCode>>,
+    mut focus: ResMut<TextInputFocus>,
+    mut clipboard: ResMut<TextInputClipboard>,
+    mut text_inputs: Query<&mut TextInput>,
+) {
+    let Some(mut text_input) = focus.0.and_then(|entity| text_inputs.get_mut(entity).ok()) else {
+        keyboard_input.clear();
+        received_characters.clear();
+        return;
+    };
+
+    let shift = keys.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    // `Ctrl` shortcuts use `Command` on macOS
+    let control = keys.any_pressed([
+        KeyCode::LControl,
+        KeyCode::RControl,
+        KeyCode::LWin,
+        KeyCode::RWin,
+    ]);
+    for input in keyboard_input.iter() {
+        if input.state != ButtonState::Pressed {
+            continue;
+        }
+        let Some(key_code) = input.key_code else {
+            continue;
+        };
+        let cursor = text_input.cursor;
+        match key_code {
+            KeyCode::Left => match (&text_input.selection, shift) {
+                (Some(selection), false) => {
+                    let start = selection.start;
+                    text_input.move_cursor(start, false);
+                }
+                _ => text_input.move_cursor(cursor.saturating_sub(1), shift),
+            },
+            KeyCode::Right => match (&text_input.selection, shift) {
+                (Some(selection), false) => {
+                    let end = selection.end;
+                    text_input.move_cursor(end, false);
+                }
+                _ => text_input.move_cursor(cursor + 1, shift),
+            },
+            KeyCode::Home => text_input.move_cursor(0, shift),
+            KeyCode::End => {
+                let len = text_input.len();
+                text_input.move_cursor(len, shift);
+            }
+            KeyCode::Back => text_input.delete_backward(),
+            KeyCode::Delete => text_input.delete_forward(),
+            KeyCode::Escape => focus.0 = None,
+            KeyCode::A if control => text_input.select_all(),
+            KeyCode::C | KeyCode::X if control => {
+                if let Some(selected) = text_input.selected_text() {
+                    clipboard.0 = selected.to_string();
+                    if key_code == KeyCode::X {
+                        text_input.delete_backward();
+                    }
+                }
+            }
+            KeyCode::V if control => text_input.insert(&clipboard.0.clone()),
+            _ => {}
+        }
+    }
+
+    for received_character in received_characters.iter() {
+        // Shortcuts and keys like `Backspace` also send control characters
+        if !control && !received_character.char.is_control() {
+            text_input.insert(received_character.char.encode_utf8(&mut [0; 4]));
+        }
+    }
+}
+
+/// Spawns the text nodes of new [`TextInput`]s, and updates them when the input or the
+/// [`TextInputFocus`] changes.
+pub fn update_text_input_system(
+    mut commands: Commands,
+    text_input_style: Res<TextInputStyle>,
+    focus: Res<TextInputFocus>,
+    mut text_inputs: Query<(
+        Entity,
+        Ref<TextInput>,
+        Option<&TextInputNodes>,
+        Option<&mut BackgroundColor>,
+    )>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, text_input, nodes, background) in &mut text_inputs {
+        if !focus.is_changed() && !text_input.is_changed() {
+            continue;
+        }
+        let focused = focus.0 == Some(entity);
+        let sections = text_input_sections(&text_input, &text_input_style, focused);
+        let background_color = if focused {
+            text_input_style.focused_background_color
+        } else {
+            text_input_style.background_color
+        };
+
+        let Some(nodes) = nodes else {
+            let mut text = Entity::PLACEHOLDER;
+            commands
+                .entity(entity)
+                .insert((
+                    Interaction::default(),
+                    FocusPolicy::Block,
+                    BackgroundColor(background_color),
+                ))
+                .with_children(|parent| {
+                    text = parent
+                        .spawn(TextBundle::from_sections(sections).with_style(Style {
+                            margin: text_input_style.padding,
+                            ..Default::default()
+                        }))
+                        .id();
+                })
+                .insert(TextInputNodes { text });
+            continue;
+        };
+
+        if let Some(mut background) = background {
+            background.0 = background_color;
+        }
+        if let Ok(mut text) = texts.get_mut(nodes.text) {
+            text.sections = sections;
+        }
+    }
+}
+
+/// The sections of the text of a [`TextInput`]: the text before the selection, the selection and
+/// the text after it, with the cursor inserted if the input is focused.
+fn text_input_sections(
+    text_input: &TextInput,
+    text_input_style: &TextInputStyle,
+    focused: bool,
+) -> Vec<TextSection> {
+    let section = |value: &str, color: Color| TextSection {
+        value: value.to_string(),
+        style: TextStyle {
+            color,
+            ..text_input_style.text_style.clone()
+        },
+    };
+    let text_color = text_input_style.text_style.color;
+
+    if text_input.is_empty() {
+        let mut sections = Vec::new();
+        if focused {
+            sections.push(section(
+                &text_input_style.cursor,
+                text_input_style.cursor_color,
+            ));
+        }
+        sections.push(section(
+            &text_input.placeholder,
+            text_input_style.placeholder_color,
+        ));
+        return sections;
+    }
+
+    let len = text_input.len();
+    let cursor = text_input.cursor.min(len);
+    let selection = text_input.clamped_selection().unwrap_or(cursor..cursor);
+    let mut boundaries = vec![0, selection.start, selection.end, cursor, len];
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    let mut sections = Vec::new();
+    for (index, &start) in boundaries.iter().enumerate() {
+        if focused && start == cursor {
+            sections.push(section(
+                &text_input_style.cursor,
+                text_input_style.cursor_color,
+            ));
+        }
+        let Some(&end) = boundaries.get(index + 1) else {
+            break;
+        };
+        let color = if selection.contains(&start) {
+            text_input_style.selection_color
+        } else {
+            text_color
+        };
+        sections.push(section(
+            &text_input.value[text_input.byte_range(start..end)],
+            color,
+        ));
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{event::Events, schedule::Schedule, world::World};
+
+    fn press(world: &mut World, key_code: KeyCode) {
+        world.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state: ButtonState::Pressed,
+        });
+    }
+
+    #[test]
+    fn text_input_editing() {
+        let mut text_input = TextInput::new("name")
+            .with_value("héllo")
+            .with_max_length(8);
+        assert_eq!(text_input.cursor, 5);
+
+        text_input.move_cursor(1, false);
+        text_input.move_cursor(3, true);
+        assert_eq!(text_input.selected_text(), Some("él"));
+        text_input.move_cursor(0, true);
+        assert_eq!(text_input.selection, Some(0..1));
+
+        // Cut short to the maximum length
+        text_input.insert("Oh, h");
+        assert_eq!(text_input.value, "Oh, éllo");
+        assert_eq!(text_input.cursor, 4);
+
+        text_input.delete_backward();
+        text_input.delete_forward();
+        assert_eq!(text_input.value, "Oh,llo");
+
+        text_input.select_all();
+        text_input.delete_forward();
+        assert!(text_input.is_empty());
+        assert_eq!(text_input.cursor, 0);
+    }
+
+    #[test]
+    fn text_input_recovers_from_stale_cursor_and_selection() {
+        let mut text_input = TextInput::default().with_value("héllo");
+        // The value is shortened from code, leaving the cursor past its end
+        text_input.value.truncate(3);
+        text_input.delete_backward();
+        assert_eq!(text_input.value, "h");
+        assert_eq!(text_input.cursor, 1);
+
+        text_input.value = "héllo".to_string();
+        text_input.selection = Some(3..1);
+        assert_eq!(text_input.selected_text(), Some("él"));
+        text_input.selection = Some(4..9);
+        assert_eq!(text_input.selected_text(), Some("o"));
+        text_input.insert("!");
+        assert_eq!(text_input.value, "héll!");
+        assert_eq!(text_input.selection, None);
+    }
+
+    #[test]
+    fn text_input_keyboard_shortcuts() {
+        let mut world = World::new();
+        world.init_resource::<Events<KeyboardInput>>();
+        world.init_resource::<Events<ReceivedCharacter>>();
+        world.init_resource::<Input<KeyCode>>();
+        world.init_resource::<TextInputClipboard>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(text_input_keyboard_system);
+
+        let text_input = world.spawn(TextInput::default().with_value("copy")).id();
+        world.insert_resource(TextInputFocus(Some(text_input)));
+        let window = Entity::PLACEHOLDER;
+        for char in " me".chars() {
+            world.send_event(ReceivedCharacter { window, char });
+        }
+        schedule.run(&mut world);
+        assert_eq!(world.get::<TextInput>(text_input).unwrap().value, "copy me");
+
+        world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::LControl);
+        press(&mut world, KeyCode::A);
+        press(&mut world, KeyCode::X);
+        world.send_event(ReceivedCharacter { window, char: 'x' });
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<TextInputClipboard>().0, "copy me");
+        assert!(world.get::<TextInput>(text_input).unwrap().is_empty());
+
+        press(&mut world, KeyCode::V);
+        press(&mut world, KeyCode::V);
+        schedule.run(&mut world);
+
+        world
+            .resource_mut::<Input<KeyCode>>()
+            .release(KeyCode::LControl);
+        world
+            .resource_mut::<Input<KeyCode>>()
+            .press(KeyCode::LShift);
+        press(&mut world, KeyCode::Home);
+        schedule.run(&mut world);
+        let text_input = world.get::<TextInput>(text_input).unwrap();
+        assert_eq!(text_input.value, "copy mecopy me");
+        assert_eq!(text_input.selection, Some(0..14));
+        assert_eq!(text_input.cursor, 0);
+    }
+}
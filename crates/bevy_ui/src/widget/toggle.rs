@@ -0,0 +1,531 @@
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    AlignItems, BackgroundColor, FlexDirection, FocusPolicy, Interaction, Style, UiRect, Val,
+};
+use bevy_ecs::{
+    prelude::{Component, Entity, Event, EventWriter},
+    query::Changed,
+    reflect::ReflectComponent,
+    system::{Commands, Query, Res, Resource},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use bevy_render::color::Color;
+use bevy_text::{Text, TextStyle};
+
+/// A box that is checked and unchecked by clicking it, followed by a label.
+///
+/// The checkbox node gets an [`Interaction`] and child nodes drawing its box and label, which are
+/// kept in sync with this component and drawn with the [`CheckboxStyle`] resource. A
+/// [`CheckboxChanged`] event is sent when the checkbox is clicked.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct Checkbox {
+    /// Whether the box is checked
+    pub checked: bool,
+    /// The text shown next to the box
+    pub label: String,
+}
+
+impl Checkbox {
+    /// Creates an unchecked [`Checkbox`] with the given label.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            checked: false,
+            label: label.into(),
+        }
+    }
+
+    /// Returns this [`Checkbox`] checked or not.
+    pub fn with_checked(mut self, checked: bool) -> Self {
+        self.checked = checked;
+        self
+    }
+}
+
+/// A list of options of which exactly one is selected by clicking it.
+///
+/// The radio group node gets a child row per option, with an [`Interaction`], a mark and a label,
+/// which are kept in sync with this component and drawn with the [`RadioStyle`] resource. A
+/// [`RadioGroupChanged`] event is sent when another option is clicked.
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct RadioGroup {
+    /// The labels of the options
+    pub options: Vec<String>,
+    /// The index of the selected option
+    pub selected: usize,
+}
+
+impl RadioGroup {
+    /// Creates a [`RadioGroup`] with the given options and the first one selected.
+    pub fn new(options: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            selected: 0,
+        }
+    }
+
+    /// Returns this [`RadioGroup`] with the option at `selected` selected.
+    pub fn with_selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+}
+
+/// How [`Checkbox`]es are drawn, used when their nodes are spawned.
+#[derive(Resource, Debug, Clone)]
+pub struct CheckboxStyle {
+    /// The width and height of the box in logical pixels
+    pub size: f32,
+    /// The width of the edge of the box in logical pixels
+    pub border: f32,
+    /// The color of the edge of the box
+    pub border_color: Color,
+    /// The color inside the box
+    pub background_color: Color,
+    /// The color of the mark of a checked box
+    pub mark_color: Color,
+    /// The space between the edge of the box and its mark in logical pixels
+    pub mark_inset: f32,
+    /// The space between the box and the label in logical pixels
+    pub gap: f32,
+    /// The style of the label
+    pub label_style: TextStyle,
+}
+
+impl Default for CheckboxStyle {
+    fn default() -> Self {
+        Self {
+            size: 18.,
+            border: 2.,
+            border_color: Color::GRAY,
+            background_color: Color::DARK_GRAY,
+            mark_color: Color::WHITE,
+            mark_inset: 3.,
+            gap: 8.,
+            label_style: TextStyle {
+                font_size: 18.,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// How [`RadioGroup`]s are drawn, used when their nodes are spawned.
+///
+/// UI nodes are drawn as rectangles, so the marks of the options are square.
+#[derive(Resource, Debug, Clone)]
+pub struct RadioStyle {
+    /// The width and height of the mark of an option in logical pixels
+    pub size: f32,
+    /// The width of the edge of the mark in logical pixels
+    pub border: f32,
+    /// The color of the edge of the mark
+    pub border_color: Color,
+    /// The color inside the mark
+    pub background_color: Color,
+    /// The color of the dot of the selected option
+    pub dot_color: Color,
+    /// The space between the edge of the mark and its dot in logical pixels
+    pub dot_inset: f32,
+    /// The space between the mark and the label in logical pixels
+    pub gap: f32,
+    /// The space between the options in logical pixels
+    pub spacing: f32,
+    /// The style of the labels
+    pub label_style: TextStyle,
+}
+
+impl Default for RadioStyle {
+    fn default() -> Self {
+        Self {
+            size: 18.,
+            border: 2.,
+            border_color: Color::GRAY,
+            background_color: Color::DARK_GRAY,
+            dot_color: Color::WHITE,
+            dot_inset: 4.,
+            gap: 8.,
+            spacing: 4.,
+            label_style: TextStyle {
+                font_size: 18.,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// An event sent when a [`Checkbox`] is clicked.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckboxChanged {
+    /// The entity of the [`Checkbox`]
+    pub entity: Entity,
+    /// Whether the box is now checked
+    pub checked: bool,
+}
+
+/// An event sent when another option of a [`RadioGroup`] is selected by clicking it.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RadioGroupChanged {
+    /// The entity of the [`RadioGroup`]
+    pub entity: Entity,
+    /// The index of the newly selected option
+    pub selected: usize,
+}
+
+/// The mark and label nodes of a [`Checkbox`], added by [`update_checkbox_system`]
+#[derive(Component, Debug, Clone, Copy)]
+pub struct CheckboxNodes {
+    mark: Entity,
+    label: Entity,
+}
+
+/// The option rows of a [`RadioGroup`], added by [`update_radio_group_system`]
+#[derive(Component, Debug, Clone)]
+pub struct RadioGroupNodes {
+    /// The options the rows were spawned for
+    options: Vec<String>,
+    rows: Vec<Entity>,
+    dots: Vec<Entity>,
+}
+
+/// An option row of a [`RadioGroup`], selecting the option when clicked
+#[derive(Component, Debug, Clone, Copy)]
+pub struct RadioOption {
+    /// The entity of the [`RadioGroup`]
+    pub group: Entity,
+    /// The index of the option in the group
+    pub index: usize,
+}
+
+/// The colors and sizes shared by the boxes of checkboxes and the marks of radio options
+struct ToggleBox {
+    size: f32,
+    border: f32,
+    border_color: Color,
+    background_color: Color,
+    inset: f32,
+}
+
+impl ToggleBox {
+    /// Spawns the box under `parent` and returns the entity of its mark.
+    fn spawn(&self, parent: &mut ChildBuilder, mark_color: Color) -> Entity {
+        let mut mark = Entity::PLACEHOLDER;
+        parent
+            .spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(self.size),
+                    height: Val::Px(self.size),
+                    padding: UiRect::all(Val::Px(self.border)),
+                    ..Default::default()
+                },
+                background_color: BackgroundColor(self.border_color),
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            width: Val::Percent(100.),
+                            height: Val::Percent(100.),
+                            padding: UiRect::all(Val::Px(self.inset)),
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor(self.background_color),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        mark = parent
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    width: Val::Percent(100.),
+                                    height: Val::Percent(100.),
+                                    ..Default::default()
+                                },
+                                background_color: BackgroundColor(mark_color),
+                                ..Default::default()
+                            })
+                            .id();
+                    });
+            });
+        mark
+    }
+}
+
+fn row_style(gap: f32) -> Style {
+    Style {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(gap),
+        ..Default::default()
+    }
+}
+
+/// Toggles the [`Checkbox`]es that are clicked and sends a [`CheckboxChanged`] event for them.
+pub fn checkbox_interaction_system(
+    mut checkboxes: Query<(Entity, &mut Checkbox, &Interaction), Changed<Interaction>>,
+    mut events: EventWriter<CheckboxChanged>,
+) {
+    for (entity, mut checkbox, interaction) in &mut checkboxes {
+        if *interaction == Interaction::Clicked {
+            checkbox.checked = !checkbox.checked;
+            events.send(CheckboxChanged {
+                entity,
+                checked: checkbox.checked,
+            });
+        }
+    }
+}
+
+/// Spawns the nodes of new [`Checkbox`]es, and updates them when the checkbox changes.
+pub fn update_checkbox_system(
+    mut commands: Commands,
+    checkbox_style: Res<CheckboxStyle>,
+    checkboxes: Query<(Entity, &Checkbox, Option<&CheckboxNodes>), Changed<Checkbox>>,
+    mut colors: Query<&mut BackgroundColor>,
+    mut texts: Query<&mut Text>,
+) {
+    for (entity, checkbox, checkbox_nodes) in &checkboxes {
+        let mark_color = if checkbox.checked {
+            checkbox_style.mark_color
+        } else {
+            Color::NONE
+        };
+
+        let Some(checkbox_nodes) = checkbox_nodes else {
+            let toggle_box = ToggleBox {
+                size: checkbox_style.size,
+                border: checkbox_style.border,
+                border_color: checkbox_style.border_color,
+                background_color: checkbox_style.background_color,
+                inset: checkbox_style.mark_inset,
+            };
+            let mut mark = Entity::PLACEHOLDER;
+            let mut label = Entity::PLACEHOLDER;
+            commands
+                .entity(entity)
+                .insert((row_style(checkbox_style.gap), Interaction::default()))
+                .insert((FocusPolicy::Block, BackgroundColor(Color::NONE)))
+                .with_children(|parent| {
+                    mark = toggle_box.spawn(parent, mark_color);
+                    label = parent
+                        .spawn(TextBundle::from_section(
+                            checkbox.label.clone(),
+                            checkbox_style.label_style.clone(),
+                        ))
+                        .id();
+                })
+                .insert(CheckboxNodes { mark, label });
+            continue;
+        };
+
+        if let Ok(mut color) = colors.get_mut(checkbox_nodes.mark) {
+            color.0 = mark_color;
+        }
+        if let Ok(mut text) = texts.get_mut(checkbox_nodes.label) {
+            if text.sections[0].value != checkbox.label {
+                text.sections[0].value = checkbox.label.clone();
+            }
+        }
+    }
+}
+
+/// Selects the options of [`RadioGroup`]s that are clicked and sends a [`RadioGroupChanged`]
+/// event when the selected option changes.
+pub fn radio_group_interaction_system(
+    options: Query<(&RadioOption, &Interaction), Changed<Interaction>>,
+    mut radio_groups: Query<&mut RadioGroup>,
+    mut events: EventWriter<RadioGroupChanged>,
+) {
+    for (option, interaction) in &options {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let Ok(mut radio_group) = radio_groups.get_mut(option.group) else {
+            continue;
+        };
+        if radio_group.selected != option.index {
+            radio_group.selected = option.index;
+            events.send(RadioGroupChanged {
+                entity: option.group,
+                selected: option.index,
+            });
+        }
+    }
+}
+
+/// Spawns the option rows of new [`RadioGroup`]s, and updates them when the radio group changes.
+///
+/// The rows are spawned again when the options change.
+pub fn update_radio_group_system(
+    mut commands: Commands,
+    radio_style: Res<RadioStyle>,
+    mut radio_groups: Query<
+        (Entity, &RadioGroup, Option<&mut RadioGroupNodes>),
+        Changed<RadioGroup>,
+    >,
+    mut colors: Query<&mut BackgroundColor>,
+) {
+    for (entity, radio_group, radio_group_nodes) in &mut radio_groups {
+        let dot_color = |index: usize| {
+            if index == radio_group.selected {
+                radio_style.dot_color
+            } else {
+                Color::NONE
+            }
+        };
+
+        match radio_group_nodes {
+            Some(radio_group_nodes) if radio_group_nodes.options == radio_group.options => {
+                for (index, dot) in radio_group_nodes.dots.iter().enumerate() {
+                    if let Ok(mut color) = colors.get_mut(*dot) {
+                        color.0 = dot_color(index);
+                    }
+                }
+            }
+            radio_group_nodes => {
+                if let Some(radio_group_nodes) = radio_group_nodes {
+                    for row in &radio_group_nodes.rows {
+                        commands.entity(*row).despawn_recursive();
+                    }
+                }
+
+                let toggle_box = ToggleBox {
+                    size: radio_style.size,
+                    border: radio_style.border,
+                    border_color: radio_style.border_color,
+                    background_color: radio_style.background_color,
+                    inset: radio_style.dot_inset,
+                };
+                let mut rows = Vec::with_capacity(radio_group.options.len());
+                let mut dots = Vec::with_capacity(radio_group.options.len());
+                commands
+                    .entity(entity)
+                    .insert(Style {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(radio_style.spacing),
+                        ..Default::default()
+                    })
+                    .with_children(|parent| {
+                        for (index, label) in radio_group.options.iter().enumerate() {
+                            let mut dot = Entity::PLACEHOLDER;
+                            let row = parent
+                                .spawn((
+                                    NodeBundle {
+                                        style: row_style(radio_style.gap),
+                                        focus_policy: FocusPolicy::Block,
+                                        ..Default::default()
+                                    },
+                                    Interaction::default(),
+                                    RadioOption {
+                                        group: entity,
+                                        index,
+                                    },
+                                ))
+                                .with_children(|parent| {
+                                    dot = toggle_box.spawn(parent, dot_color(index));
+                                    parent.spawn(TextBundle::from_section(
+                                        label.clone(),
+                                        radio_style.label_style.clone(),
+                                    ));
+                                })
+                                .id();
+                            rows.push(row);
+                            dots.push(dot);
+                        }
+                    })
+                    .insert(RadioGroupNodes {
+                        options: radio_group.options.clone(),
+                        rows,
+                        dots,
+                    });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{
+        event::Events,
+        schedule::{IntoSystemConfigs, Schedule},
+        world::World,
+    };
+
+    fn world() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<CheckboxStyle>();
+        world.init_resource::<RadioStyle>();
+        world.init_resource::<Events<CheckboxChanged>>();
+        world.init_resource::<Events<RadioGroupChanged>>();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(
+            (
+                checkbox_interaction_system,
+                radio_group_interaction_system,
+                update_checkbox_system,
+                update_radio_group_system,
+            )
+                .chain(),
+        );
+        (world, schedule)
+    }
+
+    #[test]
+    fn checkbox_toggles_when_clicked() {
+        let (mut world, mut schedule) = world();
+        let checkbox = world.spawn(Checkbox::new("sound")).id();
+        schedule.run(&mut world);
+        let mark = world.get::<CheckboxNodes>(checkbox).unwrap().mark;
+        assert_eq!(world.get::<BackgroundColor>(mark).unwrap().0, Color::NONE);
+
+        *world.get_mut::<Interaction>(checkbox).unwrap() = Interaction::Clicked;
+        schedule.run(&mut world);
+        assert!(world.get::<Checkbox>(checkbox).unwrap().checked);
+        assert_eq!(
+            world.get::<BackgroundColor>(mark).unwrap().0,
+            CheckboxStyle::default().mark_color
+        );
+        let events = world.resource::<Events<CheckboxChanged>>();
+        assert_eq!(
+            events.iter_current_update_events().collect::<Vec<_>>(),
+            [&CheckboxChanged {
+                entity: checkbox,
+                checked: true
+            }]
+        );
+    }
+
+    #[test]
+    fn radio_group_selects_clicked_option() {
+        let (mut world, mut schedule) = world();
+        let group = world
+            .spawn(RadioGroup::new(["easy", "normal", "hard"]))
+            .id();
+        schedule.run(&mut world);
+        let nodes = world.get::<RadioGroupNodes>(group).unwrap().clone();
+        assert_eq!(nodes.rows.len(), 3);
+
+        *world.get_mut::<Interaction>(nodes.rows[2]).unwrap() = Interaction::Clicked;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<RadioGroup>(group).unwrap().selected, 2);
+        let dot_colors: Vec<Color> = nodes
+            .dots
+            .iter()
+            .map(|dot| world.get::<BackgroundColor>(*dot).unwrap().0)
+            .collect();
+        let dot_color = RadioStyle::default().dot_color;
+        assert_eq!(dot_colors, [Color::NONE, Color::NONE, dot_color]);
+        let events = world.resource::<Events<RadioGroupChanged>>();
+        assert_eq!(
+            events.iter_current_update_events().collect::<Vec<_>>(),
+            [&RadioGroupChanged {
+                entity: group,
+                selected: 2
+            }]
+        );
+    }
+}
@@ -0,0 +1,242 @@
+use crate::{
+    node_bundles::{NodeBundle, TextBundle},
+    BackgroundColor, Interaction, PositionType, Style, UiRect, UiScale, Val, ZIndex,
+};
+use bevy_ecs::{
+    prelude::{Component, Entity},
+    query::With,
+    reflect::ReflectComponent,
+    system::{Commands, Local, Query, Res, Resource},
+};
+use bevy_hierarchy::{BuildChildren, DespawnRecursiveExt};
+use bevy_math::Vec2;
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use bevy_render::color::Color;
+use bevy_text::TextStyle;
+use bevy_time::Time;
+use bevy_utils::{Duration, HashMap};
+use bevy_window::{PrimaryWindow, Window};
+
+/// Shows a popup with `text` near the cursor once the node has been hovered for `delay`.
+///
+/// The node needs an [`Interaction`] to detect when it is hovered, like a
+/// [`ButtonBundle`](crate::node_bundles::ButtonBundle) has. The popup is drawn above all the
+/// other UI, with the [`TooltipStyle`] resource.
+#[derive(Component, Debug, Clone, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct Tooltip {
+    /// The text shown in the popup
+    pub text: String,
+    /// How long the node must be hovered before the popup is shown
+    pub delay: Duration,
+    /// The maximum width of the popup in logical pixels, the text wraps past it
+    pub max_width: f32,
+}
+
+impl Default for Tooltip {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            delay: Duration::from_millis(500),
+            max_width: 300.,
+        }
+    }
+}
+
+impl Tooltip {
+    /// Creates a [`Tooltip`] showing `text` with the default delay and width.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`Tooltip`] shown after being hovered for `delay`.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Returns this [`Tooltip`] with its text wrapping past `max_width` logical pixels.
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = max_width;
+        self
+    }
+}
+
+/// How the popups of the [`Tooltip`]s are drawn.
+#[derive(Resource, Debug, Clone)]
+pub struct TooltipStyle {
+    /// The style of the popup text
+    pub text_style: TextStyle,
+    /// The background color of the popup
+    pub background_color: Color,
+    /// The space between the popup text and its edges
+    pub padding: UiRect,
+    /// The offset of the top left corner of the popup from the cursor, in logical pixels
+    pub offset: Vec2,
+}
+
+impl Default for TooltipStyle {
+    fn default() -> Self {
+        Self {
+            text_style: TextStyle {
+                font_size: 16.,
+                ..Default::default()
+            },
+            background_color: Color::rgba(0.1, 0.1, 0.1, 0.9),
+            padding: UiRect::all(Val::Px(6.)),
+            offset: Vec2::new(12., 16.),
+        }
+    }
+}
+
+/// Marker for the popup entity of a [`Tooltip`]
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct TooltipPopup;
+
+/// The hover state of a node with a [`Tooltip`]
+#[derive(Default)]
+pub struct TooltipHover {
+    hovered: Duration,
+    popup: Option<Entity>,
+}
+
+/// Spawns the popups of the hovered [`Tooltip`]s once their delay has passed, moves them with the
+/// cursor, and despawns them when the cursor leaves their node.
+#[allow(clippy::too_many_arguments)]
+pub fn tooltip_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    ui_scale: Res<UiScale>,
+    tooltip_style: Res<TooltipStyle>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    tooltips: Query<(Entity, &Tooltip, &Interaction)>,
+    mut popups: Query<&mut Style, With<TooltipPopup>>,
+    mut hovers: Local<HashMap<Entity, TooltipHover>>,
+) {
+    // Popups are positioned with `Val::Px`, which is scaled by the `UiScale`
+    let cursor_position = windows
+        .get_single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .map(|position| position / ui_scale.scale as f32 + tooltip_style.offset);
+
+    for (entity, tooltip, interaction) in &tooltips {
+        if *interaction == Interaction::None {
+            if let Some(popup) = hovers.remove(&entity).and_then(|hover| hover.popup) {
+                commands.entity(popup).despawn_recursive();
+            }
+            continue;
+        }
+
+        let hover = hovers.entry(entity).or_default();
+        hover.hovered += time.delta();
+        let Some(position) = cursor_position else {
+            continue;
+        };
+        if let Some(popup) = hover.popup {
+            if let Ok(mut style) = popups.get_mut(popup) {
+                style.left = Val::Px(position.x);
+                style.top = Val::Px(position.y);
+            }
+        } else if hover.hovered >= tooltip.delay {
+            let popup = commands
+                .spawn((
+                    NodeBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            left: Val::Px(position.x),
+                            top: Val::Px(position.y),
+                            max_width: Val::Px(tooltip.max_width),
+                            padding: tooltip_style.padding,
+                            ..Default::default()
+                        },
+                        background_color: BackgroundColor(tooltip_style.background_color),
+                        // Drawn above all the other UI
+                        z_index: ZIndex::Global(i32::MAX),
+                        ..Default::default()
+                    },
+                    TooltipPopup,
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        tooltip.text.clone(),
+                        tooltip_style.text_style.clone(),
+                    ));
+                })
+                .id();
+            hover.popup = Some(popup);
+        }
+    }
+
+    // Despawn the popups of nodes that lost their `Tooltip` or were despawned while hovered
+    hovers.retain(|entity, hover| {
+        let keep = tooltips.contains(*entity);
+        if let (false, Some(popup)) = (keep, hover.popup) {
+            commands.entity(popup).despawn_recursive();
+        }
+        keep
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_utils::Instant;
+
+    #[test]
+    fn tooltip_shows_after_delay() {
+        let mut world = World::new();
+        world.init_resource::<UiScale>();
+        world.init_resource::<TooltipStyle>();
+        let mut window = Window::default();
+        window.set_cursor_position(Some(Vec2::new(100., 50.)));
+        world.spawn((window, PrimaryWindow));
+        let node = world
+            .spawn((
+                Tooltip::new("hello").with_delay(Duration::from_secs(1)),
+                Interaction::Hovered,
+            ))
+            .id();
+
+        let start = Instant::now();
+        let mut time = Time::new(start);
+        time.update_with_instant(start);
+        world.insert_resource(time);
+
+        let mut schedule = Schedule::new();
+        schedule.add_systems(tooltip_system);
+        let popups = |world: &mut World| {
+            world
+                .query_filtered::<&Style, With<TooltipPopup>>()
+                .iter(world)
+                .map(|style| (style.left, style.top))
+                .collect::<Vec<_>>()
+        };
+        let advance = |world: &mut World, secs: f32| {
+            let mut time = world.resource_mut::<Time>();
+            let last = time.last_update().unwrap();
+            time.update_with_instant(last + Duration::from_secs_f32(secs));
+        };
+
+        advance(&mut world, 0.5);
+        schedule.run(&mut world);
+        assert!(popups(&mut world).is_empty());
+
+        advance(&mut world, 0.6);
+        schedule.run(&mut world);
+        let offset = TooltipStyle::default().offset;
+        assert_eq!(
+            popups(&mut world),
+            [(Val::Px(100. + offset.x), Val::Px(50. + offset.y))]
+        );
+
+        *world.get_mut::<Interaction>(node).unwrap() = Interaction::None;
+        schedule.run(&mut world);
+        assert!(popups(&mut world).is_empty());
+    }
+}
@@ -0,0 +1,382 @@
+use crate::{
+    node_bundles::NodeBundle, FlexDirection, Node, Overflow, RelativeCursorPosition, Style,
+    UiSystem, Val,
+};
+use bevy_app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy_ecs::{
+    change_detection::{DetectChanges, Ref},
+    prelude::{Component, Entity, EventReader},
+    reflect::ReflectComponent,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query},
+};
+use bevy_hierarchy::{BuildChildren, ChildBuilder, DespawnRecursiveExt};
+use bevy_input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy_reflect::std_traits::ReflectDefault;
+use bevy_reflect::{FromReflect, Reflect, ReflectFromReflect};
+use std::{marker::PhantomData, ops::Range};
+
+/// Adds the systems of [`VirtualList<T>`]s.
+pub struct VirtualListPlugin<T>(PhantomData<fn() -> T>);
+
+impl<T> Default for VirtualListPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Send + Sync + 'static> Plugin for VirtualListPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.register_type::<VirtualListScroll>()
+            .register_type::<VirtualListRow>()
+            .add_systems(
+                PreUpdate,
+                virtual_list_scroll_system::<T>.after(UiSystem::Focus),
+            )
+            .add_systems(
+                PostUpdate,
+                update_virtual_list_system::<T>.before(UiSystem::Layout),
+            );
+    }
+}
+
+/// A vertical list of `items` that only spawns the nodes of the rows that are visible, to keep
+/// lists of thousands of items cheap to lay out and draw.
+///
+/// The rows have a fixed `item_height`, and are drawn by `render_item` as children of a row node
+/// with a [`VirtualListRow`]. Rows scrolled out of view are recycled for the rows scrolled into
+/// view, their children are despawned and `render_item` is called again. Spacer nodes above and
+/// below the rows take the height of the items that aren't spawned.
+///
+/// The list is scrolled with the mouse wheel while it is hovered, or by changing its
+/// [`VirtualListScroll`]. Its own [`Style`] sets the size of the visible part of the list, and
+/// its overflow is clipped.
+///
+/// Needs the [`VirtualListPlugin<T>`].
+#[derive(Component)]
+pub struct VirtualList<T: Send + Sync + 'static> {
+    /// The items of the list
+    pub items: Vec<T>,
+    /// The height of each row in logical pixels
+    pub item_height: f32,
+    /// The number of rows spawned above and below the visible rows, so they are ready before
+    /// they are scrolled into view
+    pub overscan: usize,
+    /// Spawns the nodes of a row for an item
+    pub render_item: fn(&T, &mut ChildBuilder),
+}
+
+impl<T: Send + Sync + 'static> VirtualList<T> {
+    /// Creates a [`VirtualList`] with rows of `item_height` drawn by `render_item`.
+    pub fn new(items: Vec<T>, item_height: f32, render_item: fn(&T, &mut ChildBuilder)) -> Self {
+        Self {
+            items,
+            item_height,
+            overscan: 2,
+            render_item,
+        }
+    }
+
+    /// Returns this [`VirtualList`] spawning `overscan` rows past the visible ones.
+    pub fn with_overscan(mut self, overscan: usize) -> Self {
+        self.overscan = overscan;
+        self
+    }
+
+    /// The height of all the rows of the list
+    pub fn content_height(&self) -> f32 {
+        self.items.len() as f32 * self.item_height
+    }
+
+    /// The indices of the items spawned when the list is scrolled by `offset` and shows
+    /// `viewport_height`, including the [`VirtualList::overscan`].
+    pub fn visible_range(&self, offset: f32, viewport_height: f32) -> Range<usize> {
+        if self.item_height <= 0. {
+            return 0..0;
+        }
+        let len = self.items.len();
+        let first = (offset / self.item_height).floor().max(0.) as usize;
+        let last = ((offset + viewport_height) / self.item_height)
+            .ceil()
+            .max(0.) as usize;
+        first.saturating_sub(self.overscan).min(len)..last.saturating_add(self.overscan).min(len)
+    }
+}
+
+/// How far a [`VirtualList`] is scrolled down, in logical pixels
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct VirtualListScroll {
+    pub offset: f32,
+}
+
+/// The node of a row of a [`VirtualList`], showing the item at `index`
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Reflect, FromReflect)]
+#[reflect(Component, FromReflect, Default)]
+pub struct VirtualListRow {
+    pub index: usize,
+}
+
+/// The nodes of a [`VirtualList`], added by [`update_virtual_list_system`]
+#[derive(Component, Debug, Clone)]
+pub struct VirtualListNodes {
+    content: Entity,
+    top_spacer: Entity,
+    bottom_spacer: Entity,
+    /// The spawned rows, by index
+    rows: Vec<(usize, Entity)>,
+    offset: f32,
+}
+
+/// Scrolls the hovered [`VirtualList<T>`]s with the mouse wheel.
+pub fn virtual_list_scroll_system<T: Send + Sync + 'static>(
+    mut mouse_wheel: EventReader<MouseWheel>,
+    mut lists: Query<(
+        &VirtualList<T>,
+        &RelativeCursorPosition,
+        &mut VirtualListScroll,
+    )>,
+) {
+    for event in mouse_wheel.iter() {
+        for (list, cursor_position, mut scroll) in &mut lists {
+            if !cursor_position.mouse_over() {
+                continue;
+            }
+            let delta = match event.unit {
+                MouseScrollUnit::Line => event.y * list.item_height,
+                MouseScrollUnit::Pixel => event.y,
+            };
+            scroll.offset = (scroll.offset - delta).max(0.);
+        }
+    }
+}
+
+/// Spawns the rows of [`VirtualList<T>`]s that are scrolled into view, recycling the ones
+/// scrolled out of view, and re-renders all the rows when the list changes.
+///
+/// The list is laid out with the size of its node from the previous frame.
+pub fn update_virtual_list_system<T: Send + Sync + 'static>(
+    mut commands: Commands,
+    mut lists: Query<(
+        Entity,
+        Ref<VirtualList<T>>,
+        &Node,
+        Option<&mut VirtualListScroll>,
+        Option<&mut VirtualListNodes>,
+    )>,
+    mut styles: Query<&mut Style>,
+) {
+    for (entity, list, node, scroll, nodes) in &mut lists {
+        let (Some(mut scroll), Some(mut nodes)) = (scroll, nodes) else {
+            spawn_virtual_list_nodes(&mut commands, entity, &mut styles);
+            continue;
+        };
+
+        let viewport_height = node.size().y;
+        let max_offset = (list.content_height() - viewport_height).max(0.);
+        if !(0.0..=max_offset).contains(&scroll.offset) {
+            scroll.offset = scroll.offset.clamp(0., max_offset);
+        }
+        let offset = scroll.offset;
+        let range = list.visible_range(offset, viewport_height);
+        let spawned = nodes
+            .rows
+            .first()
+            .zip(nodes.rows.last())
+            .map_or(0..0, |((first, _), (last, _))| *first..last + 1);
+        if !list.is_changed() && spawned == range && nodes.offset == offset {
+            continue;
+        }
+        nodes.offset = offset;
+
+        let mut set_height = |entity: Entity, height: f32| {
+            if let Ok(mut style) = styles.get_mut(entity) {
+                style.height = Val::Px(height);
+            }
+        };
+        set_height(nodes.top_spacer, range.start as f32 * list.item_height);
+        set_height(
+            nodes.bottom_spacer,
+            (list.items.len() - range.end) as f32 * list.item_height,
+        );
+        if let Ok(mut style) = styles.get_mut(nodes.content) {
+            style.top = Val::Px(-offset);
+        }
+
+        // Keep the rows still in view, unless the items changed, and recycle the others
+        let mut recycled = Vec::new();
+        let mut rows = Vec::with_capacity(range.len());
+        for (index, row) in nodes.rows.drain(..) {
+            if range.contains(&index) && !list.is_changed() {
+                rows.push((index, row));
+            } else {
+                recycled.push(row);
+            }
+        }
+        for index in range {
+            if rows.iter().any(|(row_index, _)| *row_index == index) {
+                continue;
+            }
+            let row = match recycled.pop() {
+                Some(row) => {
+                    commands.entity(row).despawn_descendants();
+                    row
+                }
+                None => commands.spawn(NodeBundle::default()).id(),
+            };
+            commands
+                .entity(row)
+                .insert((
+                    VirtualListRow { index },
+                    Style {
+                        height: Val::Px(list.item_height),
+                        flex_shrink: 0.,
+                        ..Default::default()
+                    },
+                ))
+                .with_children(|parent| (list.render_item)(&list.items[index], parent));
+            rows.push((index, row));
+        }
+        for row in recycled {
+            commands.entity(row).despawn_recursive();
+        }
+        rows.sort_by_key(|(index, _)| *index);
+
+        let children: Vec<_> = std::iter::once(nodes.top_spacer)
+            .chain(rows.iter().map(|(_, row)| *row))
+            .chain(std::iter::once(nodes.bottom_spacer))
+            .collect();
+        commands.entity(nodes.content).replace_children(&children);
+        nodes.rows = rows;
+    }
+}
+
+/// Spawns the content node of a new [`VirtualList`], and clips the list around it.
+fn spawn_virtual_list_nodes(
+    commands: &mut Commands,
+    entity: Entity,
+    styles: &mut Query<&mut Style>,
+) {
+    if let Ok(mut style) = styles.get_mut(entity) {
+        style.overflow = Overflow::clip_y();
+    }
+    let spacer = || NodeBundle {
+        style: Style {
+            flex_shrink: 0.,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut commands = commands.entity(entity);
+    let (mut content, mut top_spacer, mut bottom_spacer) = (
+        Entity::PLACEHOLDER,
+        Entity::PLACEHOLDER,
+        Entity::PLACEHOLDER,
+    );
+    commands.with_children(|parent| {
+        content = parent
+            .spawn(NodeBundle {
+                style: Style {
+                    flex_direction: FlexDirection::Column,
+                    flex_shrink: 0.,
+                    width: Val::Percent(100.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .with_children(|parent| {
+                top_spacer = parent.spawn(spacer()).id();
+                bottom_spacer = parent.spawn(spacer()).id();
+            })
+            .id();
+    });
+    commands.insert((
+        VirtualListScroll::default(),
+        RelativeCursorPosition::default(),
+        VirtualListNodes {
+            content,
+            top_spacer,
+            bottom_spacer,
+            rows: Vec::new(),
+            offset: f32::NAN,
+        },
+    ));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_ecs::{schedule::Schedule, world::World};
+    use bevy_hierarchy::Children;
+    use bevy_math::Vec2;
+
+    #[derive(Component)]
+    struct Item(usize);
+
+    #[test]
+    fn virtual_list_recycles_rows() {
+        let mut world = World::new();
+        let mut schedule = Schedule::new();
+        schedule.add_systems(update_virtual_list_system::<usize>);
+
+        let list = VirtualList::new((0..100).collect(), 10., |item, parent| {
+            parent.spawn(Item(*item));
+        });
+        assert_eq!(list.visible_range(0., 50.), 0..7);
+        assert_eq!(list.visible_range(105., 50.), 8..18);
+        assert_eq!(list.visible_range(990., 50.), 97..100);
+
+        let list = world
+            .spawn((
+                list,
+                Node {
+                    calculated_size: Vec2::new(100., 50.),
+                },
+                Style::default(),
+            ))
+            .id();
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+        let rows = |world: &mut World| {
+            let nodes = world.get::<VirtualListNodes>(list).unwrap().clone();
+            let children = world.get::<Children>(nodes.content).unwrap().to_vec();
+            assert_eq!(children.first(), Some(&nodes.top_spacer));
+            assert_eq!(children.last(), Some(&nodes.bottom_spacer));
+            children[1..children.len() - 1]
+                .iter()
+                .map(|&row| {
+                    let index = world.get::<VirtualListRow>(row).unwrap().index;
+                    let item = world.get::<Children>(row).unwrap()[0];
+                    assert_eq!(world.get::<Item>(item).unwrap().0, index);
+                    (index, row)
+                })
+                .collect::<Vec<_>>()
+        };
+        let first_rows = rows(&mut world);
+        assert_eq!(
+            first_rows
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            (0..7).collect::<Vec<_>>()
+        );
+
+        // Scrolled past the end, clamped to the last rows
+        world.get_mut::<VirtualListScroll>(list).unwrap().offset = 2000.;
+        schedule.run(&mut world);
+        assert_eq!(world.get::<VirtualListScroll>(list).unwrap().offset, 950.);
+        let last_rows = rows(&mut world);
+        assert_eq!(
+            last_rows
+                .iter()
+                .map(|(index, _)| *index)
+                .collect::<Vec<_>>(),
+            (93..100).collect::<Vec<_>>()
+        );
+        // The same row nodes are reused
+        for (_, row) in &last_rows {
+            assert!(first_rows.iter().any(|(_, first_row)| first_row == row));
+        }
+        assert_eq!(world.query::<&Item>().iter(&world).count(), 7);
+    }
+}
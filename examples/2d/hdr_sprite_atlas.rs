@@ -0,0 +1,121 @@
+//! Packs procedurally generated HDR sprites into a float texture atlas, and makes them glow with
+//! 2d bloom.
+//!
+//! The light sprites are `Rgba16Float` images brighter than 1.0 at their center. The
+//! [`TextureAtlasBuilder`] keeps their format since they all share it, and the sprite tints are
+//! applied without clamping, so the bright values reach the bloom pass.
+
+use bevy::{
+    core_pipeline::{bloom::BloomSettings, tonemapping::Tonemapping},
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+fn main() {
+    App::new()
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins(DefaultPlugins)
+        .add_systems(Startup, setup)
+        .add_systems(Update, pulse)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera {
+                hdr: true,
+                ..default()
+            },
+            tonemapping: Tonemapping::TonyMcMapface,
+            ..default()
+        },
+        BloomSettings::default(),
+    ));
+
+    // Light cookies of different sizes and falloffs, with peaks above 1.0
+    let mut builder = TextureAtlasBuilder::default();
+    for (size, peak, falloff) in [(32, 4.0, 2.0), (48, 8.0, 1.0), (64, 3.0, 4.0)] {
+        let image = light_cookie(size, peak, falloff);
+        builder.add_texture(images.add(image.clone()), &image);
+    }
+    let texture_atlas = texture_atlases.add(builder.finish(&mut images).unwrap());
+
+    let colors = [
+        Color::rgb_linear(1.0, 0.4, 0.1),
+        Color::rgb_linear(0.2, 0.6, 1.0),
+        Color::rgb_linear(0.5, 1.0, 0.3),
+    ];
+    for i in 0..9 {
+        commands.spawn((
+            SpriteSheetBundle {
+                texture_atlas: texture_atlas.clone(),
+                sprite: TextureAtlasSprite {
+                    index: i % 3,
+                    color: colors[i % 3],
+                    custom_size: Some(Vec2::splat(96.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz((i as f32 - 4.0) * 120.0, 0.0, 0.0),
+                ..default()
+            },
+            Pulse(i as f32),
+        ));
+    }
+}
+
+#[derive(Component)]
+struct Pulse(f32);
+
+/// Scales each sprite's tint above 1.0 over time, to show that it isn't clamped.
+fn pulse(time: Res<Time>, mut sprites: Query<(&Pulse, &mut TextureAtlasSprite)>) {
+    for (Pulse(phase), mut sprite) in &mut sprites {
+        let [red, green, blue, _] = sprite.color.as_linear_rgba_f32();
+        let max = red.max(green).max(blue);
+        let brightness = 1.5 + (time.elapsed_seconds() * 2.0 + phase).sin();
+        sprite.color = Color::rgb_linear(red, green, blue) * (brightness / max);
+    }
+}
+
+/// Creates a round `Rgba16Float` light, reaching `peak` at its center.
+fn light_cookie(size: u32, peak: f32, falloff: f32) -> Image {
+    let center = size as f32 / 2.0;
+    let mut data = Vec::with_capacity((size * size * 8) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let distance = Vec2::new(x as f32 + 0.5, y as f32 + 0.5).distance(Vec2::splat(center));
+            let intensity = (1.0 - distance / center).max(0.0).powf(falloff);
+            let value = peak * intensity;
+            for channel in [value, value, value, intensity] {
+                data.extend_from_slice(&f32_to_f16_bits(channel).to_le_bytes());
+            }
+        }
+    }
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba16Float,
+    )
+}
+
+/// Converts a positive `f32` to the bits of a half precision float, flushing tiny values to zero.
+fn f32_to_f16_bits(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let exponent = ((bits >> 23) & 0xff) as i32 - 127 + 15;
+    if exponent <= 0 {
+        0
+    } else if exponent >= 31 {
+        0x7c00
+    } else {
+        ((exponent as u16) << 10) | ((bits >> 13) & 0x3ff) as u16
+    }
+}
@@ -1,7 +1,10 @@
 //! In this example we generate a new texture atlas (sprite sheet) from a folder containing
 //! individual sprites.
 
-use bevy::{asset::LoadState, prelude::*};
+use bevy::{
+    asset::{LoadState, LoadedFolder},
+    prelude::*,
+};
 
 fn main() {
     App::new()
@@ -23,12 +26,16 @@ enum AppState {
 
 #[derive(Resource, Default)]
 struct RpgSpriteHandles {
-    handles: Vec<HandleUntyped>,
+    folder: LoadedFolder,
 }
 
 fn load_textures(mut rpg_sprite_handles: ResMut<RpgSpriteHandles>, asset_server: Res<AssetServer>) {
     // load multiple, individual sprites from a folder
-    rpg_sprite_handles.handles = asset_server.load_folder("textures/rpg").unwrap();
+    rpg_sprite_handles.folder = asset_server
+        .load_folder_filtered("textures/rpg", |path| {
+            path.extension().is_some_and(|extension| extension == "png")
+        })
+        .unwrap();
 }
 
 fn check_textures(
@@ -37,9 +44,7 @@ fn check_textures(
     asset_server: Res<AssetServer>,
 ) {
     // Advance the `AppState` once all sprite handles have been loaded by the `AssetServer`
-    if let LoadState::Loaded = asset_server
-        .get_group_load_state(rpg_sprite_handles.handles.iter().map(|handle| handle.id()))
-    {
+    if let LoadState::Loaded = rpg_sprite_handles.folder.load_state(&asset_server) {
         next_state.set(AppState::Finished);
     }
 }
@@ -53,14 +58,13 @@ fn setup(
 ) {
     // Build a `TextureAtlas` using the individual sprites
     let mut texture_atlas_builder = TextureAtlasBuilder::default();
-    for handle in &rpg_sprite_handles.handles {
-        let handle = handle.typed_weak();
+    for (path, handle) in rpg_sprite_handles.folder.iter_typed::<Image>(&asset_server) {
         let Some(texture) = textures.get(&handle) else {
-            warn!("{:?} did not resolve to an `Image` asset.", asset_server.get_handle_path(handle));
+            warn!("{:?} did not resolve to an `Image` asset.", path);
             continue;
         };
 
-        texture_atlas_builder.add_texture(handle, texture);
+        texture_atlas_builder.add_texture(handle.clone_weak(), texture);
     }
 
     let texture_atlas = texture_atlas_builder.finish(&mut textures).unwrap();
@@ -0,0 +1,136 @@
+//! Draws two layers of tiles with a generated tileset.
+//!
+//! Press space to switch between orthographic and isometric layouts, and the arrow keys to move
+//! the camera.
+
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+const TILE_SIZE: u32 = 16;
+const MAP_SIZE: u32 = 64;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins.set(ImagePlugin::default_nearest()))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (toggle_projection, move_camera))
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut tilesets: ResMut<Assets<TileSet>>,
+) {
+    commands.spawn(Camera2dBundle {
+        projection: OrthographicProjection {
+            scale: 0.5,
+            ..default()
+        },
+        ..default()
+    });
+
+    // A row of solid color tiles
+    let colors = [
+        Color::rgb(0.3, 0.6, 0.2),
+        Color::rgb(0.2, 0.4, 0.8),
+        Color::rgb(0.6, 0.6, 0.6),
+        Color::rgb(0.9, 0.8, 0.3),
+    ];
+    let size = Extent3d {
+        width: TILE_SIZE * colors.len() as u32,
+        height: TILE_SIZE,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image::new_fill(
+        size,
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    for y in 0..size.height {
+        for x in 0..size.width {
+            let color = colors[(x / TILE_SIZE) as usize].as_rgba_u8();
+            let edge = x % TILE_SIZE == 0 || y % TILE_SIZE == 0;
+            let pixel = if edge { [0, 0, 0, 255] } else { color };
+            image.set_pixel(UVec2::new(x, y), &pixel).unwrap();
+        }
+    }
+    let atlas = TextureAtlas::from_grid(
+        images.add(image),
+        Vec2::splat(TILE_SIZE as f32),
+        colors.len(),
+        1,
+        None,
+        None,
+    );
+    let tileset = tilesets.add(TileSet::new(atlas));
+
+    // Grass and water on the ground, with a translucent layer of paths on top
+    let mut ground = TileMap::new(tileset.clone(), MAP_SIZE, MAP_SIZE, UVec2::splat(TILE_SIZE));
+    let mut paths = ground.clone();
+    for y in 0..MAP_SIZE {
+        for x in 0..MAP_SIZE {
+            let coord = UVec2::new(x, y);
+            let water = (x as f32 * 0.2).sin() + (y as f32 * 0.15).cos() > 1.2;
+            ground.set(coord, TileId(if water { 1 } else { 0 }));
+            if x % 8 == 0 || y % 12 == 0 {
+                paths.set(coord, TileId(if water { 2 } else { 3 }));
+            }
+        }
+    }
+
+    let offset = -((MAP_SIZE * TILE_SIZE / 2) as f32);
+    commands.spawn(TileMapBundle {
+        tilemap: ground,
+        transform: Transform::from_xyz(offset, offset, 0.0),
+        ..default()
+    });
+    commands.spawn(TileMapBundle {
+        tilemap: paths,
+        layer: TileLayer {
+            color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+        },
+        transform: Transform::from_xyz(offset, offset, 1.0),
+        ..default()
+    });
+}
+
+fn toggle_projection(keyboard: Res<Input<KeyCode>>, mut tilemaps: Query<&mut TileMap>) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut tilemap in &mut tilemaps {
+        // Isometric cells are half as tall as they are wide, so the square tiles look like blocks
+        (tilemap.projection, tilemap.tile_size) = match tilemap.projection {
+            TileProjection::Orthographic => (
+                TileProjection::Isometric,
+                UVec2::new(TILE_SIZE, TILE_SIZE / 2),
+            ),
+            TileProjection::Isometric => (TileProjection::Orthographic, UVec2::splat(TILE_SIZE)),
+        };
+    }
+}
+
+fn move_camera(
+    time: Res<Time>,
+    keyboard: Res<Input<KeyCode>>,
+    mut cameras: Query<&mut Transform, With<Camera>>,
+) {
+    let mut direction = Vec2::ZERO;
+    for (key, key_direction) in [
+        (KeyCode::Left, Vec2::NEG_X),
+        (KeyCode::Right, Vec2::X),
+        (KeyCode::Down, Vec2::NEG_Y),
+        (KeyCode::Up, Vec2::Y),
+    ] {
+        if keyboard.pressed(key) {
+            direction += key_direction;
+        }
+    }
+    for mut transform in &mut cameras {
+        transform.translation += (direction * 200.0 * time.delta_seconds()).extend(0.0);
+    }
+}
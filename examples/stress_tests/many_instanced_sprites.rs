@@ -0,0 +1,97 @@
+//! Renders a lot of color tinted sprites sharing a texture atlas, to compare instanced sprite
+//! rendering with the default sprite rendering.
+//!
+//! This example spawns 5000 animated sprites with random colors and flips, which are all drawn
+//! using the [`SpriteInstancingPlugin`](bevy::sprite::SpriteInstancingPlugin).
+//!
+//! Add the `--no-instancing` arg to draw them with the default sprite rendering instead, where
+//! the color tints split the sprites into many batches.
+
+use bevy::{
+    diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin},
+    prelude::*,
+    sprite::SpriteInstancingPlugin,
+    window::PresentMode,
+};
+
+use rand::Rng;
+
+const SPRITE_COUNT: (i32, i32) = (100, 50);
+
+fn main() {
+    let mut app = App::new();
+    // Since this is also used as a benchmark, we want it to display performance data.
+    app.add_plugin(LogDiagnosticsPlugin::default())
+        .add_plugin(FrameTimeDiagnosticsPlugin)
+        .add_plugins(DefaultPlugins.set(WindowPlugin {
+            primary_window: Some(Window {
+                present_mode: PresentMode::AutoNoVsync,
+                ..default()
+            }),
+            ..default()
+        }));
+    if std::env::args().nth(1).unwrap_or_default() != "--no-instancing" {
+        app.add_plugin(SpriteInstancingPlugin);
+    }
+    app.add_systems(Startup, setup)
+        .add_systems(Update, animate_sprites)
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    assets: Res<AssetServer>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    warn!(include_str!("warning_string.txt"));
+
+    let mut rng = rand::thread_rng();
+
+    let texture_handle = assets.load("textures/rpg/chars/gabe/gabe-idle-run.png");
+    let texture_atlas =
+        TextureAtlas::from_grid(texture_handle, Vec2::new(24.0, 24.0), 7, 1, None, None);
+    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+    commands.spawn(Camera2dBundle::default());
+
+    let tile_size = Vec2::splat(12.0);
+    let (columns, rows) = SPRITE_COUNT;
+    for y in -rows / 2..rows / 2 {
+        for x in -columns / 2..columns / 2 {
+            let position = Vec2::new(x as f32, y as f32) * tile_size;
+            commands.spawn((
+                SpriteSheetBundle {
+                    texture_atlas: texture_atlas_handle.clone(),
+                    transform: Transform::from_translation(position.extend(rng.gen::<f32>())),
+                    sprite: TextureAtlasSprite {
+                        index: rng.gen_range(0..7),
+                        color: Color::hsl(rng.gen_range(0.0..360.0), 0.8, 0.7),
+                        flip_x: rng.gen(),
+                        flip_y: rng.gen(),
+                        custom_size: Some(tile_size),
+                        ..default()
+                    },
+                    ..default()
+                },
+                AnimationTimer(Timer::from_seconds(
+                    rng.gen_range(0.05..0.2),
+                    TimerMode::Repeating,
+                )),
+            ));
+        }
+    }
+}
+
+#[derive(Component, Deref, DerefMut)]
+struct AnimationTimer(Timer);
+
+fn animate_sprites(
+    time: Res<Time>,
+    mut query: Query<(&mut AnimationTimer, &mut TextureAtlasSprite)>,
+) {
+    for (mut timer, mut sprite) in &mut query {
+        if timer.tick(time.delta()).just_finished() {
+            sprite.index = (sprite.index + 1) % 7;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! This example illustrates how to make a group of radio buttons, by iterating over the buttons
+//! of the group in layout order.
+
+use bevy::{prelude::*, winit::WinitSettings};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Only run the app when there is user input. This will significantly reduce CPU/GPU use.
+        .insert_resource(WinitSettings::desktop_app())
+        .add_systems(Startup, setup)
+        .add_systems(Update, radio_group_system)
+        .run();
+}
+
+const UNSELECTED_BUTTON: Color = Color::rgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::rgb(0.25, 0.25, 0.25);
+const SELECTED_BUTTON: Color = Color::rgb(0.35, 0.75, 0.35);
+
+/// A group of radio buttons, of which only one is selected at a time.
+#[derive(Component)]
+struct RadioGroup {
+    /// The index of the selected button, in layout order
+    selected: usize,
+}
+
+#[derive(Component)]
+struct RadioButton;
+
+fn radio_group_system(
+    mut groups: Query<(Entity, &mut RadioGroup)>,
+    children: Query<&Children>,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor), With<RadioButton>>,
+) {
+    for (group, mut radio_group) in &mut groups {
+        // The descendants are visited in the order of `Children`, which is the order they are
+        // laid out in, so the index of a button is its position in the group. Entities that
+        // aren't buttons, like the text of the buttons, are skipped.
+        let mut group_buttons = buttons.iter_many_mut(children.iter_descendants_depth_first(group));
+        let mut index = 0;
+        while let Some((interaction, _)) = group_buttons.fetch_next() {
+            if *interaction == Interaction::Clicked && radio_group.selected != index {
+                radio_group.selected = index;
+            }
+            index += 1;
+        }
+
+        let mut group_buttons = buttons.iter_many_mut(children.iter_descendants_depth_first(group));
+        let mut index = 0;
+        while let Some((interaction, mut color)) = group_buttons.fetch_next() {
+            *color = if index == radio_group.selected {
+                SELECTED_BUTTON.into()
+            } else if *interaction == Interaction::Hovered {
+                HOVERED_BUTTON.into()
+            } else {
+                UNSELECTED_BUTTON.into()
+            };
+            index += 1;
+        }
+    }
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    // ui camera
+    commands.spawn(Camera2dBundle::default());
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    column_gap: Val::Px(10.0),
+                    ..default()
+                },
+                ..default()
+            },
+            RadioGroup { selected: 0 },
+        ))
+        .with_children(|parent| {
+            for label in ["Small", "Medium", "Large"] {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: Style {
+                                width: Val::Px(150.0),
+                                height: Val::Px(65.0),
+                                // horizontally center child text
+                                justify_content: JustifyContent::Center,
+                                // vertically center child text
+                                align_items: AlignItems::Center,
+                                ..default()
+                            },
+                            background_color: UNSELECTED_BUTTON.into(),
+                            ..default()
+                        },
+                        RadioButton,
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            label,
+                            TextStyle {
+                                font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                                font_size: 40.0,
+                                color: Color::rgb(0.9, 0.9, 0.9),
+                            },
+                        ));
+                    });
+            }
+        });
+}
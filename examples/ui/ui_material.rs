@@ -0,0 +1,72 @@
+//! Demonstrates how to draw UI nodes with a custom shader using [`UiMaterial`].
+
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, TypeUuid},
+    render::render_resource::{AsBindGroup, ShaderRef},
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(UiMaterialPlugin::<ScanlineMaterial>::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, animate)
+        .run();
+}
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ScanlineMaterial>>) {
+    commands.spawn(Camera2dBundle::default());
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|parent| {
+            parent.spawn(MaterialNodeBundle {
+                style: Style {
+                    width: Val::Px(300.0),
+                    height: Val::Px(200.0),
+                    ..default()
+                },
+                material: UiMaterialNode(materials.add(ScanlineMaterial {
+                    color: Color::rgb(0.2, 0.9, 0.4),
+                    offset: 0.0,
+                })),
+                ..default()
+            });
+        });
+}
+
+/// Scrolls the scanlines of every material
+fn animate(time: Res<Time>, mut materials: ResMut<Assets<ScanlineMaterial>>) {
+    for (_, material) in materials.iter_mut() {
+        material.offset = time.elapsed_seconds() * 20.0;
+    }
+}
+
+/// The [`UiMaterial`] trait comes with sensible defaults for all methods, so only the shader
+/// that draws the scanlines needs to be set.
+impl UiMaterial for ScanlineMaterial {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/custom_ui_material.wgsl".into()
+    }
+}
+
+// This is the struct that will be passed to your shader
+#[derive(AsBindGroup, TypeUuid, TypePath, Debug, Clone)]
+#[uuid = "4a3b2f6e-8d1c-4e5a-9b7f-0c6d2e1a3f58"]
+pub struct ScanlineMaterial {
+    #[uniform(0)]
+    color: Color,
+    /// How far the scanlines scrolled, in logical pixels
+    #[uniform(0)]
+    offset: f32,
+}
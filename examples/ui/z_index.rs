@@ -17,8 +17,9 @@ fn setup(mut commands: Commands) {
     commands.spawn(Camera2dBundle::default());
 
     // spawn the container with default z-index.
-    // the default z-index value is `ZIndex::Local(0)`.
-    // because this is a root UI node, using local or global values will do the same thing.
+    // the default z-index value is `ZIndex::Auto`, which doesn't form a stacking context:
+    // the z-index of descendants is relative to the closest ancestor with a local or global
+    // z-index, or to the root nodes if there is none.
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -30,8 +31,11 @@ fn setup(mut commands: Commands) {
             ..default()
         })
         .with_children(|parent| {
+            // spawn the gray container with a local z-index of 0, so it forms a stacking context.
+            // the local z-indices of its children are relative to it.
             parent
                 .spawn(NodeBundle {
+                    z_index: ZIndex::Local(0),
                     background_color: Color::GRAY.into(),
                     style: Style {
                         width: Val::Px(180.0),